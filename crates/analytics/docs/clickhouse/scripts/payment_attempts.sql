@@ -43,6 +43,17 @@ CREATE TABLE payment_attempt_queue (
     `organization_id` String,
     `profile_id` String,
     `card_network` Nullable(String),
+    `device_fingerprint` Nullable(String),
+    `three_ds_version` Nullable(String),
+    `three_ds_message_version` Nullable(String),
+    `request_challenge` Nullable(Bool),
+    `tokenization_latency` Nullable(Int64),
+    `connector_fee_amount` Nullable(UInt64),
+    `connector_fee_currency` LowCardinality(Nullable(String)),
+    `card_funding_type` LowCardinality(Nullable(String)),
+    `card_bin` Nullable(String),
+    `liability_shift` Nullable(Bool),
+    `connector_response_received_at` Nullable(DateTime64(3)),
     `sign_flag` Int8
 ) ENGINE = Kafka SETTINGS kafka_broker_list = 'kafka0:29092',
 kafka_topic_list = 'hyperswitch-payment-attempt-events',
@@ -96,6 +107,17 @@ CREATE TABLE payment_attempts (
     `organization_id` String,
     `profile_id` String,
     `card_network` Nullable(String),
+    `device_fingerprint` Nullable(String),
+    `three_ds_version` Nullable(String),
+    `three_ds_message_version` Nullable(String),
+    `request_challenge` Nullable(Bool),
+    `tokenization_latency` Nullable(Int64),
+    `connector_fee_amount` Nullable(UInt64),
+    `connector_fee_currency` LowCardinality(Nullable(String)),
+    `card_funding_type` LowCardinality(Nullable(String)),
+    `card_bin` Nullable(String),
+    `liability_shift` Nullable(Bool),
+    `connector_response_received_at` Nullable(DateTime64(3)),
     `sign_flag` Int8,
     INDEX connectorIndex connector TYPE bloom_filter GRANULARITY 1,
     INDEX paymentMethodIndex payment_method TYPE bloom_filter GRANULARITY 1,
@@ -152,6 +174,17 @@ CREATE MATERIALIZED VIEW payment_attempt_mv TO payment_attempts (
     `organization_id` String,
     `profile_id` String,
     `card_network` Nullable(String),
+    `device_fingerprint` Nullable(String),
+    `three_ds_version` Nullable(String),
+    `three_ds_message_version` Nullable(String),
+    `request_challenge` Nullable(Bool),
+    `tokenization_latency` Nullable(Int64),
+    `connector_fee_amount` Nullable(UInt64),
+    `connector_fee_currency` LowCardinality(Nullable(String)),
+    `card_funding_type` LowCardinality(Nullable(String)),
+    `card_bin` Nullable(String),
+    `liability_shift` Nullable(Bool),
+    `connector_response_received_at` Nullable(DateTime64(3)),
     `sign_flag` Int8
 ) AS
 SELECT
@@ -200,6 +233,17 @@ SELECT
     organization_id,
     profile_id,
     card_network,
+    device_fingerprint,
+    three_ds_version,
+    three_ds_message_version,
+    request_challenge,
+    tokenization_latency,
+    connector_fee_amount,
+    connector_fee_currency,
+    card_funding_type,
+    card_bin,
+    liability_shift,
+    connector_response_received_at,
     sign_flag
 FROM
     payment_attempt_queue
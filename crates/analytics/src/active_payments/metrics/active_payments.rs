@@ -76,7 +76,7 @@ where
         query_builder
             .execute_query::<ActivePaymentsMetricRow, _>(pool)
             .await
-            .change_context(MetricsError::QueryBuildingError)?
+            .switch()?
             .change_context(MetricsError::QueryExecutionFailure)?
             .into_iter()
             .map(|i| Ok((ActivePaymentsMetricsBucketIdentifier::new(None), i)))
@@ -41,7 +41,7 @@ where
     query_builder
         .execute_query::<ApiEventFilter, _>(pool)
         .await
-        .change_context(FiltersError::QueryBuildingError)?
+        .switch()?
         .change_context(FiltersError::QueryExecutionFailure)
 }
 
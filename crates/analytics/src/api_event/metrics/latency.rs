@@ -93,7 +93,7 @@ where
         query_builder
             .execute_query::<LatencyAvg, _>(pool)
             .await
-            .change_context(MetricsError::QueryBuildingError)?
+            .switch()?
             .change_context(MetricsError::QueryExecutionFailure)?
             .into_iter()
             .map(|i| {
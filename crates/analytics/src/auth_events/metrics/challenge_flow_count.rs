@@ -88,7 +88,7 @@ where
         query_builder
             .execute_query::<AuthEventMetricRow, _>(pool)
             .await
-            .change_context(MetricsError::QueryBuildingError)?
+            .switch()?
             .change_context(MetricsError::QueryExecutionFailure)?
             .into_iter()
             .map(|i| {
@@ -3,6 +3,11 @@ use std::sync::Arc;
 use actix_web::http::StatusCode;
 use common_utils::errors::ParsingError;
 use error_stack::{report, Report, ResultExt};
+use futures::{
+    future::BoxFuture,
+    stream::{BoxStream, TryChunksError},
+    FutureExt, StreamExt, TryStreamExt,
+};
 use router_env::logger;
 use time::PrimitiveDateTime;
 
@@ -15,7 +20,7 @@ use super::{
     payments::{
         distribution::PaymentDistributionRow, filters::PaymentFilterRow, metrics::PaymentMetricRow,
     },
-    query::{Aggregate, ToSql, Window},
+    query::{percentile_fraction, Aggregate, DurationInMillis, ToSql, Window},
     refunds::{filters::RefundFilterRow, metrics::RefundMetricRow},
     sdk_events::{filters::SdkEventFilter, metrics::SdkEventMetricRow},
     types::{AnalyticsCollection, AnalyticsDataSource, LoadRow, QueryExecutionError},
@@ -59,7 +64,7 @@ impl Default for ClickhouseConfig {
 }
 
 impl ClickhouseClient {
-    async fn execute_query(&self, query: &str) -> ClickhouseResult<Vec<serde_json::Value>> {
+    async fn send_query(&self, query: &str, format: &str) -> ClickhouseResult<reqwest::Response> {
         logger::debug!("Executing query: {query}");
         let client = reqwest::Client::new();
         let params = CkhQuery {
@@ -71,7 +76,7 @@ impl ClickhouseClient {
             .post(&self.config.host)
             .query(&params)
             .basic_auth(self.config.username.clone(), self.config.password.clone())
-            .body(format!("{query}\nFORMAT JSON"))
+            .body(format!("{query}\nFORMAT {format}"))
             .send()
             .await
             .change_context(ClickhouseError::ConnectionError)?;
@@ -86,11 +91,87 @@ impl ClickhouseClient {
                 |t| Err(report!(ClickhouseError::ResponseNotOK(t))),
             )
         } else {
-            Ok(response
-                .json::<CkhOutput<serde_json::Value>>()
-                .await
-                .change_context(ClickhouseError::ResponseError)?
-                .data)
+            Ok(response)
+        }
+    }
+
+    async fn execute_query(&self, query: &str) -> ClickhouseResult<Vec<serde_json::Value>> {
+        Ok(self
+            .send_query(query, "JSON")
+            .await?
+            .json::<CkhOutput<serde_json::Value>>()
+            .await
+            .change_context(ClickhouseError::ResponseError)?
+            .data)
+    }
+
+    /// Run the query with the rows returned one per line, so that they are read from the response
+    /// body as it is received instead of once the whole body is received
+    fn execute_query_streamed<'a>(
+        &'a self,
+        query: &'a str,
+    ) -> BoxStream<'a, ClickhouseResult<serde_json::Value>> {
+        let response: BoxFuture<'a, _> = self.send_query(query, "JSONEachRow").boxed();
+        response
+            .into_stream()
+            .map_ok(|response| {
+                futures::stream::try_unfold(
+                    (response, JsonEachRowReader::default(), false),
+                    |(mut response, mut reader, mut body_received)| async move {
+                        loop {
+                            if let Some(row) = reader.next_row(body_received) {
+                                return row
+                                    .map(|row| Some((row, (response, reader, body_received))));
+                            }
+                            if body_received {
+                                return Ok(None);
+                            }
+                            match response.chunk().await {
+                                Ok(Some(chunk)) => reader.push_chunk(&chunk),
+                                Ok(None) => body_received = true,
+                                Err(error) => {
+                                    return Err(error)
+                                        .change_context(ClickhouseError::ResponseError)
+                                }
+                            }
+                        }
+                    },
+                )
+            })
+            .try_flatten()
+            .boxed()
+    }
+}
+
+/// Rows of a `JSONEachRow` response, which has a JSON object per line. The body is received in
+/// chunks that are not aligned to lines, so the part of a row received so far is kept until the
+/// rest of it is received.
+#[derive(Debug, Default)]
+struct JsonEachRowReader {
+    buffer: Vec<u8>,
+}
+
+impl JsonEachRowReader {
+    fn push_chunk(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Take the next complete row, if any. The last row may not end with a new line, so it is
+    /// only complete once the whole body is received.
+    fn next_row(&mut self, body_received: bool) -> Option<ClickhouseResult<serde_json::Value>> {
+        loop {
+            let line = match self.buffer.iter().position(|byte| *byte == b'\n') {
+                Some(line_end) => self.buffer.drain(..=line_end).collect::<Vec<_>>(),
+                None if body_received && !self.buffer.is_empty() => {
+                    std::mem::take(&mut self.buffer)
+                }
+                None => return None,
+            };
+            if !line.iter().all(u8::is_ascii_whitespace) {
+                return Some(
+                    serde_json::from_slice(&line).change_context(ClickhouseError::ResponseError),
+                );
+            }
         }
     }
 }
@@ -130,9 +211,14 @@ impl AnalyticsDataSource for ClickhouseClient {
     fn get_table_engine(table: AnalyticsCollection) -> TableEngine {
         match table {
             AnalyticsCollection::Payment
+            | AnalyticsCollection::PaymentWithIntentCreatedAt
+            | AnalyticsCollection::PaymentWithFirstAttempt
+            | AnalyticsCollection::RefundWithPayment
+            | AnalyticsCollection::DisputeWithPayment
             | AnalyticsCollection::Refund
             | AnalyticsCollection::FraudCheck
             | AnalyticsCollection::PaymentIntent
+            | AnalyticsCollection::PaymentIntentWithRepeatCustomer
             | AnalyticsCollection::Dispute => {
                 TableEngine::CollapsingMergeTree { sign: "sign_flag" }
             }
@@ -142,9 +228,33 @@ impl AnalyticsDataSource for ClickhouseClient {
             | AnalyticsCollection::ConnectorEvents
             | AnalyticsCollection::ApiEventsAnalytics
             | AnalyticsCollection::OutgoingWebhookEvent
-            | AnalyticsCollection::ActivePaymentsAnalytics => TableEngine::BasicTree,
+            | AnalyticsCollection::ActivePaymentsAnalytics
+            | AnalyticsCollection::CustomerPaymentMethods => TableEngine::BasicTree,
         }
     }
+
+    fn escape_string_literal(value: &str) -> String {
+        value.replace('\\', "\\\\").replace('\'', "\\'")
+    }
+
+    fn load_results_in_batches<'a, T>(
+        &'a self,
+        query: &'a str,
+        batch_size: usize,
+    ) -> BoxStream<'a, common_utils::errors::CustomResult<Vec<T>, QueryExecutionError>>
+    where
+        Self: LoadRow<T>,
+        T: Send + 'a,
+    {
+        self.execute_query_streamed(query)
+            .map(|row| {
+                row.change_context(QueryExecutionError::DatabaseError)
+                    .and_then(Self::load_row)
+            })
+            .try_chunks(batch_size.max(1))
+            .map_err(|TryChunksError(_, error)| error)
+            .boxed()
+    }
 }
 
 impl<T, E> LoadRow<T> for ClickhouseClient
@@ -423,6 +533,65 @@ impl ToSql<ClickhouseClient> for AnalyticsCollection {
     fn to_sql(&self, _table_engine: &TableEngine) -> error_stack::Result<String, ParsingError> {
         match self {
             Self::Payment => Ok("payment_attempts".to_string()),
+            // Intents have a row per version, any of them has the creation time
+            Self::PaymentWithIntentCreatedAt => Ok(
+                "(SELECT payment_attempts.*, payment_intents.created_at AS intent_created_at \
+                FROM payment_attempts ANY INNER JOIN payment_intents \
+                ON payment_intents.merchant_id = payment_attempts.merchant_id \
+                AND payment_intents.payment_id = payment_attempts.payment_id) payment_attempts"
+                    .to_string(),
+            ),
+            Self::PaymentWithFirstAttempt => Ok(
+                "(SELECT payment_attempts.*, \
+                toBool(payment_attempts.created_at = first_attempt.first_attempt_at) AS first_attempt \
+                FROM payment_attempts ANY INNER JOIN \
+                (SELECT merchant_id, payment_id, min(created_at) AS first_attempt_at \
+                FROM payment_attempts GROUP BY merchant_id, payment_id) first_attempt \
+                ON first_attempt.merchant_id = payment_attempts.merchant_id \
+                AND first_attempt.payment_id = payment_attempts.payment_id) payment_attempts"
+                    .to_string(),
+            ),
+            // Refunds keep their own sign so that their versions collapse. Attempts have a row per
+            // version, they are collapsed to their latest version before the join so that each
+            // refund is joined to a single row with the current columns of its attempt.
+            Self::RefundWithPayment => Ok(
+                "(SELECT payment_attempts.*, refunds.refund_amount AS refund_amount, \
+                refunds.refund_status AS refund_status, refunds.sign_flag AS sign_flag \
+                FROM refunds ANY INNER JOIN \
+                (SELECT * EXCEPT (sign_flag) FROM payment_attempts FINAL WHERE sign_flag = 1) \
+                payment_attempts \
+                ON payment_attempts.merchant_id = refunds.merchant_id \
+                AND payment_attempts.attempt_id = refunds.attempt_id) payment_attempts"
+                    .to_string(),
+            ),
+            // Attempts have a row per version, the payment method of their latest version is
+            // taken so that each dispute is joined to a single row
+            Self::DisputeWithPayment => Ok(
+                "(SELECT dispute.*, payment_attempts.payment_method AS payment_method, \
+                payment_attempts.payment_method_type AS payment_method_type \
+                FROM dispute ANY INNER JOIN \
+                (SELECT merchant_id, attempt_id, \
+                argMax(payment_method, modified_at) AS payment_method, \
+                argMax(payment_method_type, modified_at) AS payment_method_type \
+                FROM payment_attempts GROUP BY merchant_id, attempt_id) payment_attempts \
+                ON payment_attempts.merchant_id = dispute.merchant_id \
+                AND payment_attempts.attempt_id = dispute.attempt_id) dispute"
+                    .to_string(),
+            ),
+            // Customers without a successful payment have no row in the joined table, so
+            // `has_success` is left at its default of zero for them
+            Self::PaymentIntentWithRepeatCustomer => Ok(
+                "(SELECT payment_intents.*, toBool(first_success.has_success = 1 \
+                AND first_success.first_success_at < payment_intents.created_at) AS is_repeat_customer \
+                FROM payment_intents LEFT ANY JOIN \
+                (SELECT merchant_id, customer_id, min(created_at) AS first_success_at, \
+                toUInt8(1) AS has_success FROM payment_intents \
+                WHERE status = 'succeeded' AND customer_id IS NOT NULL \
+                GROUP BY merchant_id, customer_id) first_success \
+                ON first_success.merchant_id = payment_intents.merchant_id \
+                AND first_success.customer_id = payment_intents.customer_id) payment_intents"
+                    .to_string(),
+            ),
             Self::Refund => Ok("refunds".to_string()),
             Self::FraudCheck => Ok("fraud_check".to_string()),
             Self::SdkEvents => Ok("sdk_events_audit".to_string()),
@@ -434,6 +603,10 @@ impl ToSql<ClickhouseClient> for AnalyticsCollection {
             Self::OutgoingWebhookEvent => Ok("outgoing_webhook_events_audit".to_string()),
             Self::Dispute => Ok("dispute".to_string()),
             Self::ActivePaymentsAnalytics => Ok("active_payments".to_string()),
+            Self::CustomerPaymentMethods => Err(error_stack::report!(ParsingError::UnknownError)
+                .attach_printable(
+                    "CustomerPaymentMethods table is not implemented for Clickhouse",
+                ))?,
         }
     }
 }
@@ -498,8 +671,8 @@ where
                 percentile,
             } => {
                 format!(
-                    "quantilesExact(0.{})({})[1]{}",
-                    percentile.map_or_else(|| "50".to_owned(), |percentile| percentile.to_string()),
+                    "quantileExact({})({}){}",
+                    percentile_fraction(*percentile),
                     field
                         .to_sql(table_engine)
                         .attach_printable("Failed to percentile aggregate")?,
@@ -515,10 +688,45 @@ where
                     alias.map_or_else(|| "".to_owned(), |alias| format!(" as {}", alias))
                 )
             }
+            Self::AvgDuration {
+                start_field,
+                end_field,
+                alias,
+            } => {
+                format!(
+                    "avg(dateDiff('second', {}, {})){}",
+                    start_field
+                        .to_sql(table_engine)
+                        .attach_printable("Failed to average duration aggregate")?,
+                    end_field
+                        .to_sql(table_engine)
+                        .attach_printable("Failed to average duration aggregate")?,
+                    alias.map_or_else(|| "".to_owned(), |alias| format!(" as {}", alias))
+                )
+            }
         })
     }
 }
 
+impl<T> ToSql<ClickhouseClient> for DurationInMillis<T>
+where
+    T: ToSql<ClickhouseClient>,
+{
+    fn to_sql(&self, table_engine: &TableEngine) -> error_stack::Result<String, ParsingError> {
+        Ok(format!(
+            "dateDiff('millisecond', {}, {}){}",
+            self.start_field
+                .to_sql(table_engine)
+                .attach_printable("Failed to build millisecond duration")?,
+            self.end_field
+                .to_sql(table_engine)
+                .attach_printable("Failed to build millisecond duration")?,
+            self.alias
+                .map_or_else(|| "".to_owned(), |alias| format!(" as {}", alias))
+        ))
+    }
+}
+
 impl<T> ToSql<ClickhouseClient> for Window<T>
 where
     T: ToSql<ClickhouseClient>,
@@ -587,3 +795,49 @@ pub enum ClickhouseError {
     #[error("Clickhouse response error")]
     ResponseError,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_split_across_chunks_are_read_once_complete() {
+        let mut reader = JsonEachRowReader::default();
+
+        reader.push_chunk(br#"{"connector":"stripe","count":3}"#);
+        reader.push_chunk(b"\n{\"connector\":\"ad");
+        assert_eq!(
+            reader.next_row(false).unwrap().unwrap(),
+            serde_json::json!({"connector": "stripe", "count": 3})
+        );
+        assert!(reader.next_row(false).is_none());
+
+        reader.push_chunk(br#"yen","count":1}"#);
+        assert!(reader.next_row(false).is_none());
+        assert_eq!(
+            reader.next_row(true).unwrap().unwrap(),
+            serde_json::json!({"connector": "adyen", "count": 1})
+        );
+        assert!(reader.next_row(true).is_none());
+    }
+
+    #[test]
+    fn test_blank_lines_are_skipped_and_malformed_rows_rejected() {
+        let mut reader = JsonEachRowReader::default();
+        reader.push_chunk(b"\n{\"count\":1}\n\n{\"count\":\n");
+
+        assert_eq!(
+            reader.next_row(true).unwrap().unwrap(),
+            serde_json::json!({"count": 1})
+        );
+        assert!(matches!(
+            reader
+                .next_row(true)
+                .unwrap()
+                .unwrap_err()
+                .current_context(),
+            ClickhouseError::ResponseError
+        ));
+        assert!(reader.next_row(true).is_none());
+    }
+}
@@ -50,7 +50,7 @@ where
     query_builder
         .execute_query::<ConnectorEventsResult, _>(pool)
         .await
-        .change_context(FiltersError::QueryBuildingError)?
+        .switch()?
         .change_context(FiltersError::QueryExecutionFailure)
 }
 
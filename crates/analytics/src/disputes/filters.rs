@@ -39,7 +39,7 @@ where
     query_builder
         .execute_query::<DisputeFilterRow, _>(pool)
         .await
-        .change_context(FiltersError::QueryBuildingError)?
+        .switch()?
         .change_context(FiltersError::QueryExecutionFailure)
 }
 #[derive(Debug, serde::Serialize, Eq, PartialEq, serde::Deserialize)]
@@ -12,6 +12,10 @@ pub enum AnalyticsError {
     UnknownError,
     #[error("Access Forbidden Analytics Error")]
     AccessForbiddenError,
+    #[error("Dimension combination not allowed: {0}")]
+    DimensionCombinationNotAllowed(String),
+    #[error("Invalid rollup granularity: {0}")]
+    InvalidRollupGranularity(&'static str),
 }
 
 impl ErrorSwitch<ApiErrorResponse> for AnalyticsError {
@@ -32,6 +36,20 @@ impl ErrorSwitch<ApiErrorResponse> for AnalyticsError {
             Self::AccessForbiddenError => {
                 ApiErrorResponse::Unauthorized(ApiError::new("IR", 0, "Access Forbidden", None))
             }
+            Self::DimensionCombinationNotAllowed(dimensions) => {
+                ApiErrorResponse::BadRequest(ApiError::new(
+                    "IR",
+                    1,
+                    format!("Grouping by the dimensions [{dimensions}] together is not allowed"),
+                    None,
+                ))
+            }
+            Self::InvalidRollupGranularity(reason) => ApiErrorResponse::BadRequest(ApiError::new(
+                "IR",
+                2,
+                format!("Invalid rollup granularity, {reason}"),
+                None,
+            )),
         }
     }
 }
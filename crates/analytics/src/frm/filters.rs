@@ -47,7 +47,7 @@ where
     query_builder
         .execute_query::<FrmFilterRow, _>(pool)
         .await
-        .change_context(FiltersError::QueryBuildingError)?
+        .switch()?
         .change_context(FiltersError::QueryExecutionFailure)
 }
 
@@ -56,12 +56,14 @@ use api_models::analytics::{
 };
 use clickhouse::ClickhouseClient;
 pub use clickhouse::ClickhouseConfig;
+use currency_conversion::types::ExchangeRates;
 use error_stack::report;
 use router_env::{
     logger,
     tracing::{self, instrument},
     types::FlowMetric,
 };
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use storage_impl::config::Database;
 use strum::Display;
 
@@ -964,6 +966,85 @@ pub struct ReportConfig {
     pub region: String,
 }
 
+/// Allow-list of dimension combinations accepted in metrics queries.
+///
+/// Queries grouped by a high cardinality dimension are accepted only if all their dimensions are
+/// part of one of the allowed combinations, queries without such dimensions are not restricted.
+#[derive(Clone, Debug, serde::Deserialize, Default)]
+#[serde(default)]
+pub struct DimensionAllowListConfig {
+    pub high_cardinality_dimensions: HashSet<String>,
+    pub allowed_combinations: Vec<HashSet<String>>,
+}
+
+impl DimensionAllowListConfig {
+    pub fn validate<T: AsRef<str>>(&self, dimensions: &[T]) -> errors::AnalyticsResult<()> {
+        let has_high_cardinality_dimension = dimensions.iter().any(|dimension| {
+            self.high_cardinality_dimensions
+                .contains(dimension.as_ref())
+        });
+        let is_allowed_combination = self.allowed_combinations.iter().any(|combination| {
+            dimensions
+                .iter()
+                .all(|dimension| combination.contains(dimension.as_ref()))
+        });
+
+        if has_high_cardinality_dimension && !is_allowed_combination {
+            return Err(report!(
+                errors::AnalyticsError::DimensionCombinationNotAllowed(
+                    dimensions
+                        .iter()
+                        .map(|dimension| dimension.as_ref())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Currency that captured volume is reported in.
+#[derive(Clone, Debug, serde::Deserialize, Default)]
+#[serde(default)]
+pub struct ReportingCurrencyConfig {
+    pub currency: common_enums::Currency,
+}
+
+/// Reporting currency along with the FX rates of the forex service used to convert payment
+/// amounts into it. The rates are `None` when they could not be fetched, amounts of other
+/// currencies are not converted then.
+#[derive(Clone, Debug, Default)]
+pub struct ReportingCurrency {
+    pub currency: common_enums::Currency,
+    pub exchange_rates: Option<Arc<ExchangeRates>>,
+}
+
+impl ReportingCurrency {
+    /// Converts an amount in the minor unit of `currency` to the minor unit of the reporting
+    /// currency, `None` if no FX rate is available for `currency`.
+    pub fn convert_amount(&self, amount: i64, currency: common_enums::Currency) -> Option<f64> {
+        if currency == self.currency {
+            return amount.to_f64();
+        }
+        let converted_amount = currency_conversion::conversion::convert(
+            self.exchange_rates.as_deref()?,
+            currency,
+            self.currency,
+            amount,
+        )
+        .ok()?;
+        let minor_unit_factor = 10_i64.checked_pow(u32::from(
+            self.currency.number_of_digits_after_decimal_point(),
+        ))?;
+
+        converted_amount
+            .checked_mul(Decimal::from(minor_unit_factor))?
+            .to_f64()
+    }
+}
+
 /// Analytics Flow routes Enums
 /// Info - Dimensions and filters available for the domain
 /// Filters - Set of values present for the dimension
@@ -999,3 +1080,64 @@ pub enum AnalyticsFlow {
 }
 
 impl FlowMetric for AnalyticsFlow {}
+
+#[cfg(test)]
+mod tests {
+    use api_models::analytics::payments::PaymentDimensions;
+
+    use super::*;
+
+    fn dimension_allow_list() -> DimensionAllowListConfig {
+        DimensionAllowListConfig {
+            high_cardinality_dimensions: HashSet::from(["client_version".to_string()]),
+            allowed_combinations: vec![HashSet::from([
+                "client_source".to_string(),
+                "client_version".to_string(),
+            ])],
+        }
+    }
+
+    #[test]
+    fn test_allowed_dimension_combination_is_accepted() {
+        let allow_list = dimension_allow_list();
+
+        assert!(allow_list
+            .validate(&[
+                PaymentDimensions::ClientSource,
+                PaymentDimensions::ClientVersion
+            ])
+            .is_ok());
+        assert!(allow_list
+            .validate(&[PaymentDimensions::ClientVersion])
+            .is_ok());
+        // Dimensions which are not high cardinality are not restricted
+        assert!(allow_list
+            .validate(&[PaymentDimensions::Connector, PaymentDimensions::Currency])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_high_cardinality_dimension_outside_allowed_combination_is_rejected() {
+        match dimension_allow_list().validate(&[
+            PaymentDimensions::ClientVersion,
+            PaymentDimensions::Connector,
+        ]) {
+            Err(error) => assert!(matches!(
+                error.current_context(),
+                errors::AnalyticsError::DimensionCombinationNotAllowed(dimensions)
+                    if dimensions == "client_version, connector"
+            )),
+            Ok(()) => panic!("high cardinality combination must be rejected"),
+        }
+    }
+
+    #[test]
+    fn test_empty_allow_list_accepts_any_combination() {
+        assert!(DimensionAllowListConfig::default()
+            .validate(&[
+                PaymentDimensions::ClientVersion,
+                PaymentDimensions::Connector
+            ])
+            .is_ok());
+    }
+}
@@ -1,5 +1,6 @@
 use api_models::analytics::payment_intents::PaymentIntentMetricsBucketValue;
 use bigdecimal::ToPrimitive;
+use diesel_models::enums as storage_enums;
 
 use super::metrics::PaymentIntentMetricRow;
 
@@ -9,6 +10,7 @@ pub struct PaymentIntentMetricsAccumulator {
     pub total_smart_retries: CountAccumulator,
     pub smart_retried_amount: SumAccumulator,
     pub payment_intent_count: CountAccumulator,
+    pub new_vs_repeat_success_rate: NewVsRepeatSuccessRateAccumulator,
 }
 
 #[derive(Debug, Default)]
@@ -23,6 +25,19 @@ pub struct ErrorDistributionAccumulator {
     pub error_vec: Vec<ErrorDistributionRow>,
 }
 
+#[derive(Debug, Default)]
+pub struct SuccessRateAccumulator {
+    pub success: i64,
+    pub total: i64,
+}
+
+/// Success rates of payments of new and of repeat customers, accumulated separately
+#[derive(Debug, Default)]
+pub struct NewVsRepeatSuccessRateAccumulator {
+    pub new_customers: SuccessRateAccumulator,
+    pub repeat_customers: SuccessRateAccumulator,
+}
+
 #[derive(Debug, Default)]
 #[repr(transparent)]
 pub struct CountAccumulator {
@@ -78,13 +93,60 @@ impl PaymentIntentMetricAccumulator for SumAccumulator {
     }
 }
 
+impl PaymentIntentMetricAccumulator for SuccessRateAccumulator {
+    type MetricOutput = Option<f64>;
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentIntentMetricRow) {
+        if let Some(ref status) = metrics.status {
+            if status.as_ref() == &storage_enums::IntentStatus::Succeeded {
+                self.success += metrics.count.unwrap_or_default();
+            }
+        };
+        self.total += metrics.count.unwrap_or_default();
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        if self.total <= 0 {
+            None
+        } else {
+            Some(
+                f64::from(u32::try_from(self.success).ok()?) * 100.0
+                    / f64::from(u32::try_from(self.total).ok()?),
+            )
+        }
+    }
+}
+
+impl PaymentIntentMetricAccumulator for NewVsRepeatSuccessRateAccumulator {
+    type MetricOutput = (Option<f64>, Option<f64>);
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentIntentMetricRow) {
+        if metrics.is_repeat_customer.unwrap_or(false) {
+            self.repeat_customers.add_metrics_bucket(metrics);
+        } else {
+            self.new_customers.add_metrics_bucket(metrics);
+        }
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        (
+            self.new_customers.collect(),
+            self.repeat_customers.collect(),
+        )
+    }
+}
+
 impl PaymentIntentMetricsAccumulator {
     pub fn collect(self) -> PaymentIntentMetricsBucketValue {
+        let (new_customer_success_rate, repeat_customer_success_rate) =
+            self.new_vs_repeat_success_rate.collect();
         PaymentIntentMetricsBucketValue {
             successful_smart_retries: self.successful_smart_retries.collect(),
             total_smart_retries: self.total_smart_retries.collect(),
             smart_retried_amount: self.smart_retried_amount.collect(),
             payment_intent_count: self.payment_intent_count.collect(),
+            new_customer_success_rate,
+            repeat_customer_success_rate,
         }
     }
 }
@@ -119,6 +119,9 @@ pub async fn get_metrics(
                         PaymentIntentMetrics::PaymentIntentCount => metrics_builder
                             .payment_intent_count
                             .add_metrics_bucket(&value),
+                        PaymentIntentMetrics::NewVsRepeatSuccessRate => metrics_builder
+                            .new_vs_repeat_success_rate
+                            .add_metrics_bucket(&value),
                     }
                 }
 
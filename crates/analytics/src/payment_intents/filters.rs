@@ -45,7 +45,7 @@ where
     query_builder
         .execute_query::<PaymentIntentFilterRow, _>(pool)
         .await
-        .change_context(FiltersError::QueryBuildingError)?
+        .switch()?
         .change_context(FiltersError::QueryExecutionFailure)
 }
 
@@ -16,11 +16,13 @@ use crate::{
     types::{AnalyticsCollection, AnalyticsDataSource, DBEnumWrapper, LoadRow, MetricsResult},
 };
 
+mod new_vs_repeat_success_rate;
 mod payment_intent_count;
 mod smart_retried_amount;
 mod successful_smart_retries;
 mod total_smart_retries;
 
+use new_vs_repeat_success_rate::NewVsRepeatSuccessRate;
 use payment_intent_count::PaymentIntentCount;
 use smart_retried_amount::SmartRetriedAmount;
 use successful_smart_retries::SuccessfulSmartRetries;
@@ -37,6 +39,8 @@ pub struct PaymentIntentMetricRow {
     pub start_bucket: Option<PrimitiveDateTime>,
     #[serde(with = "common_utils::custom_serde::iso8601::option")]
     pub end_bucket: Option<PrimitiveDateTime>,
+    /// Whether the customer had a successful payment created before the payments of the row
+    pub is_repeat_customer: Option<bool>,
 }
 
 pub trait PaymentIntentMetricAnalytics: LoadRow<PaymentIntentMetricRow> {}
@@ -98,6 +102,11 @@ where
                     .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
                     .await
             }
+            Self::NewVsRepeatSuccessRate => {
+                NewVsRepeatSuccessRate
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
         }
     }
 }
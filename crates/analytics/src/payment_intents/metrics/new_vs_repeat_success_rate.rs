@@ -0,0 +1,269 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payment_intents::{
+        PaymentIntentDimensions, PaymentIntentFilters, PaymentIntentMetricsBucketIdentifier,
+    },
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::PaymentIntentMetricRow;
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, GroupByClause, PostProcessingError, QueryBuilder, QueryFilter, SeriesBucket,
+        ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Success rate of payments split by whether the customer already had a successful payment
+/// before the payment was created.
+///
+/// Each payment in the time range is looked up against the earlier successful payments of its
+/// customer, so only the payments of the time range are scanned. Guest checkouts have no
+/// customer and are always counted as new customers.
+#[derive(Default)]
+pub(super) struct NewVsRepeatSuccessRate;
+
+impl NewVsRepeatSuccessRate {
+    /// Query counting the payments per requested dimension, status and whether their customer is
+    /// a repeat customer
+    fn build_query<T>(
+        dimensions: &[PaymentIntentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentIntentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        let is_status_dimension =
+            dimensions.contains(&PaymentIntentDimensions::PaymentIntentStatus);
+        let mut query_builder: QueryBuilder<T> =
+            QueryBuilder::new(AnalyticsCollection::PaymentIntentWithRepeatCustomer);
+
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+        if !is_status_dimension {
+            query_builder
+                .add_select_column(PaymentIntentDimensions::PaymentIntentStatus)
+                .switch()?;
+        }
+        query_builder
+            .add_select_column("is_repeat_customer")
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        filters.set_filter_clause(&mut query_builder).switch()?;
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+        if !is_status_dimension {
+            query_builder
+                .add_group_by_clause(PaymentIntentDimensions::PaymentIntentStatus)
+                .attach_printable("Error grouping by status")
+                .switch()?;
+        }
+        query_builder
+            .add_group_by_clause("is_repeat_customer")
+            .attach_printable("Error grouping by is_repeat_customer")
+            .switch()?;
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        Ok(query_builder)
+    }
+
+    /// Bucket of a row by its dimensions, the status is only part of the bucket when it was
+    /// requested as a dimension so that payments of all statuses are counted together otherwise
+    fn get_bucket(
+        i: PaymentIntentMetricRow,
+        is_status_dimension: bool,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> error_stack::Result<
+        (PaymentIntentMetricsBucketIdentifier, PaymentIntentMetricRow),
+        PostProcessingError,
+    > {
+        Ok((
+            PaymentIntentMetricsBucketIdentifier::new(
+                i.status
+                    .as_ref()
+                    .filter(|_| is_status_dimension)
+                    .map(|i| i.0),
+                i.currency.as_ref().map(|i| i.0),
+                i.profile_id.clone(),
+                TimeRange {
+                    start_time: match (granularity, i.start_bucket) {
+                        (Some(g), Some(st)) => g.clip_to_start(st)?,
+                        _ => time_range.start_time,
+                    },
+                    end_time: granularity.as_ref().map_or_else(
+                        || Ok(time_range.end_time),
+                        |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                    )?,
+                },
+            ),
+            i,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> super::PaymentIntentMetric<T> for NewVsRepeatSuccessRate
+where
+    T: AnalyticsDataSource + super::PaymentIntentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentIntentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentIntentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentIntentMetricsBucketIdentifier, PaymentIntentMetricRow)>>
+    {
+        let is_status_dimension =
+            dimensions.contains(&PaymentIntentDimensions::PaymentIntentStatus);
+
+        Self::build_query::<T>(dimensions, auth, filters, granularity, time_range)?
+            .execute_query::<PaymentIntentMetricRow, _>(pool)
+            .await
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)?
+            .into_iter()
+            .map(|row| Self::get_bucket(row, is_status_dimension, granularity, time_range))
+            .collect::<error_stack::Result<
+                HashSet<(PaymentIntentMetricsBucketIdentifier, PaymentIntentMetricRow)>,
+                PostProcessingError,
+            >>()
+            .change_context(MetricsError::PostProcessingFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common_enums::{Currency, IntentStatus};
+
+    use super::*;
+    use crate::{
+        payment_intents::accumulator::{
+            NewVsRepeatSuccessRateAccumulator, PaymentIntentMetricAccumulator,
+        },
+        payments::test_utils,
+        types::DBEnumWrapper,
+    };
+
+    fn payments(
+        status: IntentStatus,
+        is_repeat_customer: bool,
+        count: i64,
+    ) -> PaymentIntentMetricRow {
+        PaymentIntentMetricRow {
+            status: Some(DBEnumWrapper(status)),
+            currency: Some(DBEnumWrapper(Currency::USD)),
+            profile_id: None,
+            total: None,
+            count: Some(count),
+            start_bucket: None,
+            end_bucket: None,
+            is_repeat_customer: Some(is_repeat_customer),
+        }
+    }
+
+    fn success_rates(
+        is_status_dimension: bool,
+    ) -> HashMap<Option<IntentStatus>, (Option<f64>, Option<f64>)> {
+        let rows = [
+            payments(IntentStatus::Succeeded, false, 3),
+            payments(IntentStatus::Failed, false, 1),
+            payments(IntentStatus::Succeeded, true, 1),
+            payments(IntentStatus::Failed, true, 1),
+        ];
+
+        let mut accumulators = HashMap::<_, NewVsRepeatSuccessRateAccumulator>::new();
+        for row in rows {
+            let (bucket, row) = NewVsRepeatSuccessRate::get_bucket(
+                row,
+                is_status_dimension,
+                &None,
+                &test_utils::time_range(),
+            )
+            .unwrap();
+            accumulators
+                .entry(bucket.status)
+                .or_default()
+                .add_metrics_bucket(&row);
+        }
+        accumulators
+            .into_iter()
+            .map(|(status, accumulator)| (status, accumulator.collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_payments_of_all_statuses_share_a_bucket_unless_split_by_status() {
+        assert_eq!(
+            success_rates(false),
+            HashMap::from([(None, (Some(75.0), Some(50.0)))])
+        );
+        assert_eq!(
+            success_rates(true),
+            HashMap::from([
+                (Some(IntentStatus::Succeeded), (Some(100.0), Some(100.0))),
+                (Some(IntentStatus::Failed), (Some(0.0), Some(0.0))),
+            ])
+        );
+    }
+}
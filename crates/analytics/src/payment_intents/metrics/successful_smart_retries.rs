@@ -102,7 +102,7 @@ where
         query_builder
             .execute_query::<PaymentIntentMetricRow, _>(pool)
             .await
-            .change_context(MetricsError::QueryBuildingError)?
+            .switch()?
             .change_context(MetricsError::QueryExecutionFailure)?
             .into_iter()
             .map(|i| {
@@ -1,8 +1,11 @@
 pub mod accumulator;
 mod core;
 pub mod distribution;
+pub mod export;
 pub mod filters;
 pub mod metrics;
+#[cfg(test)]
+pub(crate) mod test_utils;
 pub mod types;
 pub use accumulator::{
     PaymentDistributionAccumulator, PaymentMetricAccumulator, PaymentMetricsAccumulator,
@@ -1,9 +1,18 @@
-use api_models::analytics::payments::{ErrorResult, PaymentMetricsBucketValue};
+use std::collections::{BTreeSet, HashMap};
+
+use api_models::analytics::payments::{
+    nearest_rank_percentile, CurrencyAmount, ErrorResult, FailureClassCounts, LatencyPercentiles,
+    PaymentMethodsPerCustomer, PaymentMetricsBucketValue, RetriesPerPayment,
+};
 use bigdecimal::ToPrimitive;
 use diesel_models::enums as storage_enums;
 use router_env::logger;
 
-use super::{distribution::PaymentDistributionRow, metrics::PaymentMetricRow};
+use super::{
+    distribution::PaymentDistributionRow,
+    metrics::{classify_failure, FailureClass, PaymentMetricRow},
+};
+use crate::ReportingCurrency;
 
 #[derive(Debug, Default)]
 pub struct PaymentMetricsAccumulator {
@@ -16,6 +25,27 @@ pub struct PaymentMetricsAccumulator {
     pub retries_count: CountAccumulator,
     pub retries_amount_processed: SumAccumulator,
     pub connector_success_rate: SuccessRateAccumulator,
+    pub chargeback_ratio: ChargebackRatioAccumulator,
+    pub surcharge_collected: SumAccumulator,
+    pub avg_surcharge_collected: AverageAccumulator,
+    pub payment_success_rate_interval: SuccessRateIntervalAccumulator,
+    pub captured_amount: SumAccumulator,
+    pub captured_amount_in_reporting_currency: ReportingCurrencyAmountAccumulator,
+    pub decline_rate: DeclineRateAccumulator,
+    pub auth_type_success_rate: SuccessRateAccumulator,
+    pub auth_type_payment_count: CountAccumulator,
+    pub tokenization_latency: LatencyPercentileAccumulator,
+    pub client_source_success_rate: SuccessRateAccumulator,
+    pub client_source_payment_count: CountAccumulator,
+    pub net_revenue: NetRevenueAccumulator,
+    pub payment_methods_per_customer: PaymentMethodsPerCustomerAccumulator,
+    pub avg_time_to_success: AverageDurationAccumulator,
+    pub retries_per_payment: RetriesPerPaymentAccumulator,
+    pub failure_classification: FailureClassificationAccumulator,
+    pub processed_amount_in_reporting_currency: ReportingCurrencyNormalizedAmountAccumulator,
+    pub dispute_rate: DisputeRateAccumulator,
+    pub first_attempt_success_rate: SuccessRateAccumulator,
+    pub connector_latency: ConnectorLatencyAccumulator,
 }
 
 #[derive(Debug, Default)]
@@ -36,6 +66,47 @@ pub struct SuccessRateAccumulator {
     pub total: i64,
 }
 
+/// Wilson score interval of the success rate, so that buckets with few payments are reported
+/// with correspondingly wide bounds
+#[derive(Debug, Default)]
+pub struct SuccessRateIntervalAccumulator {
+    pub success_rate: SuccessRateAccumulator,
+}
+
+/// Captured amount converted to the reporting currency with the FX rates of the forex service.
+/// Days with an amount that has no rate are collected instead of being dropped silently.
+#[derive(Debug, Default)]
+pub struct ReportingCurrencyAmountAccumulator {
+    pub total: Option<f64>,
+    pub missing_rate_dates: BTreeSet<time::Date>,
+}
+
+#[derive(Debug, Default)]
+pub struct DeclineRateAccumulator {
+    pub declined: i64,
+    pub total: i64,
+}
+
+/// Number of payments per latency, the percentiles are computed once all rows of the bucket
+/// are added
+#[derive(Debug, Default)]
+pub struct LatencyPercentileAccumulator {
+    pub timings: Vec<(u64, u64)>,
+}
+
+#[derive(Debug, Default)]
+pub struct ChargebackRatioAccumulator {
+    pub disputes: i64,
+    pub transactions: i64,
+}
+
+/// Disputes per successful payment, as a ratio between 0 and 1
+#[derive(Debug, Default)]
+pub struct DisputeRateAccumulator {
+    pub disputes: i64,
+    pub successful_payments: i64,
+}
+
 #[derive(Debug, Default)]
 #[repr(transparent)]
 pub struct CountAccumulator {
@@ -48,6 +119,58 @@ pub struct SumAccumulator {
     pub total: Option<i64>,
 }
 
+/// Sum of amounts that can be negative, such as net revenue where refunds and fees exceed the
+/// captured amount
+#[derive(Debug, Default)]
+#[repr(transparent)]
+pub struct NetRevenueAccumulator {
+    pub total: Option<i64>,
+}
+
+/// Number of customers per number of saved payment methods, the average is computed once all
+/// rows of the bucket are collected
+#[derive(Debug, Default)]
+pub struct PaymentMethodsPerCustomerAccumulator {
+    pub counts: Vec<(u64, u64)>,
+}
+
+/// Average duration of the payments of all rows, each row holding the average duration and the
+/// number of payments it was computed from
+#[derive(Debug, Default)]
+pub struct AverageDurationAccumulator {
+    pub total_seconds: f64,
+    pub count: i64,
+}
+
+/// Connector latencies in milliseconds along with the number of attempts that took that long,
+/// the average and 95th percentile are computed once all rows of the bucket are collected
+#[derive(Debug, Default)]
+pub struct ConnectorLatencyAccumulator {
+    pub timings: Vec<(u64, u64)>,
+}
+
+/// Number of payments per number of attempts and outcome, bucketed once all rows of the bucket
+/// are collected
+#[derive(Debug, Default)]
+pub struct RetriesPerPaymentAccumulator {
+    pub counts: Vec<(u64, bool, u64)>,
+}
+
+/// Number of failed payments per failure class, classified by connector and error code
+#[derive(Debug, Default)]
+pub struct FailureClassificationAccumulator {
+    pub failures: FailureClassCounts,
+}
+
+/// Amount of all currencies converted to the reporting currency with the FX rates of the forex
+/// service. Amounts without a rate are kept apart per payment currency instead of being added to the
+/// total.
+#[derive(Debug, Default)]
+pub struct ReportingCurrencyNormalizedAmountAccumulator {
+    pub total: Option<f64>,
+    pub without_rate: HashMap<storage_enums::Currency, i64>,
+}
+
 #[derive(Debug, Default)]
 pub struct AverageAccumulator {
     pub total: u32,
@@ -131,6 +254,164 @@ impl PaymentMetricAccumulator for SuccessRateAccumulator {
     }
 }
 
+impl SuccessRateIntervalAccumulator {
+    /// z-score for a 95% confidence level
+    const Z_SCORE: f64 = 1.96;
+
+    fn wilson_score_interval(&self) -> Option<(f64, f64)> {
+        if self.success_rate.total <= 0 {
+            return None;
+        }
+        let success = f64::from(u32::try_from(self.success_rate.success).ok()?);
+        let total = f64::from(u32::try_from(self.success_rate.total).ok()?);
+
+        let z_squared = Self::Z_SCORE * Self::Z_SCORE;
+        let proportion = success / total;
+        let denominator = 1.0 + z_squared / total;
+        let center = (proportion + z_squared / (2.0 * total)) / denominator;
+        let margin = Self::Z_SCORE / denominator
+            * (proportion * (1.0 - proportion) / total + z_squared / (4.0 * total * total)).sqrt();
+
+        Some((
+            (center - margin).max(0.0) * 100.0,
+            (center + margin).min(1.0) * 100.0,
+        ))
+    }
+}
+
+impl PaymentMetricAccumulator for SuccessRateIntervalAccumulator {
+    type MetricOutput = (Option<f64>, Option<f64>);
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        self.success_rate.add_metrics_bucket(metrics)
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        self.wilson_score_interval()
+            .map_or((None, None), |(lower, upper)| (Some(lower), Some(upper)))
+    }
+}
+
+impl ReportingCurrencyAmountAccumulator {
+    pub fn add_metrics_bucket(
+        &mut self,
+        metrics: &PaymentMetricRow,
+        reporting_currency: &ReportingCurrency,
+    ) {
+        let (Some(amount), Some(currency), Some(start_bucket)) = (
+            metrics.total.as_ref().and_then(ToPrimitive::to_i64),
+            metrics.currency.as_ref(),
+            metrics.start_bucket,
+        ) else {
+            logger::error!(message="Dropping metrics for reporting currency accumulator", metric=?metrics);
+            return;
+        };
+
+        match reporting_currency.convert_amount(amount, currency.0) {
+            Some(converted_amount) => {
+                self.total = Some(self.total.unwrap_or_default() + converted_amount)
+            }
+            None => {
+                self.missing_rate_dates.insert(start_bucket.date());
+            }
+        }
+    }
+
+    pub fn collect(self) -> (Option<u64>, Option<Vec<String>>) {
+        #[allow(clippy::as_conversions)]
+        let total = self.total.map(|total| total.round() as u64);
+        let missing_rate_dates = (!self.missing_rate_dates.is_empty()).then(|| {
+            self.missing_rate_dates
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        });
+
+        (total, missing_rate_dates)
+    }
+}
+
+impl ReportingCurrencyNormalizedAmountAccumulator {
+    pub fn add_metrics_bucket(
+        &mut self,
+        metrics: &PaymentMetricRow,
+        reporting_currency: &ReportingCurrency,
+    ) {
+        let (Some(amount), Some(currency)) = (
+            metrics.total.as_ref().and_then(ToPrimitive::to_i64),
+            metrics.currency.as_ref(),
+        ) else {
+            logger::error!(message="Dropping metrics for normalized amount accumulator", metric=?metrics);
+            return;
+        };
+
+        match reporting_currency.convert_amount(amount, currency.0) {
+            Some(converted_amount) => {
+                self.total = Some(self.total.unwrap_or_default() + converted_amount)
+            }
+            None => *self.without_rate.entry(currency.0).or_default() += amount,
+        }
+    }
+
+    pub fn collect(self) -> (Option<u64>, Option<Vec<CurrencyAmount>>) {
+        #[allow(clippy::as_conversions)]
+        let total = self.total.map(|total| total.round() as u64);
+        let without_rate = (!self.without_rate.is_empty()).then(|| {
+            let mut without_rate = self
+                .without_rate
+                .into_iter()
+                .map(|(currency, amount)| CurrencyAmount {
+                    currency,
+                    amount: u64::try_from(amount).unwrap_or_default(),
+                })
+                .collect::<Vec<_>>();
+            without_rate.sort_by_key(|amount| amount.currency.to_string());
+            without_rate
+        });
+        (total, without_rate)
+    }
+}
+
+impl PaymentMetricAccumulator for ChargebackRatioAccumulator {
+    type MetricOutput = Option<f64>;
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        self.disputes += metrics.dispute_count.unwrap_or_default();
+        self.transactions += metrics.count.unwrap_or_default();
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        if self.transactions <= 0 {
+            None
+        } else {
+            Some(
+                f64::from(u32::try_from(self.disputes).ok()?) * 100.0
+                    / f64::from(u32::try_from(self.transactions).ok()?),
+            )
+        }
+    }
+}
+
+impl PaymentMetricAccumulator for DisputeRateAccumulator {
+    type MetricOutput = Option<f64>;
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        self.disputes += metrics.dispute_count.unwrap_or_default();
+        self.successful_payments += metrics.count.unwrap_or_default();
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        if self.successful_payments <= 0 {
+            None
+        } else {
+            let ratio = f64::from(u32::try_from(self.disputes).ok()?)
+                / f64::from(u32::try_from(self.successful_payments).ok()?);
+            // Disputes of a bucket can be raised against payments of earlier buckets
+            Some(ratio.min(1.0))
+        }
+    }
+}
+
 impl PaymentMetricAccumulator for CountAccumulator {
     type MetricOutput = Option<u64>;
     #[inline]
@@ -166,6 +447,76 @@ impl PaymentMetricAccumulator for SumAccumulator {
     }
 }
 
+impl PaymentMetricAccumulator for NetRevenueAccumulator {
+    type MetricOutput = Option<i64>;
+    #[inline]
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        self.total = match (
+            self.total,
+            metrics.total.as_ref().and_then(ToPrimitive::to_i64),
+        ) {
+            (None, None) => None,
+            (None, i @ Some(_)) | (i @ Some(_), None) => i,
+            (Some(a), Some(b)) => Some(a + b),
+        }
+    }
+    #[inline]
+    fn collect(self) -> Self::MetricOutput {
+        self.total
+    }
+}
+
+impl PaymentMetricAccumulator for DeclineRateAccumulator {
+    type MetricOutput = Option<f64>;
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        if let Some(ref status) = metrics.status {
+            if matches!(
+                status.as_ref(),
+                storage_enums::AttemptStatus::Failure
+                    | storage_enums::AttemptStatus::AuthorizationFailed
+                    | storage_enums::AttemptStatus::AuthenticationFailed
+            ) {
+                self.declined += metrics.count.unwrap_or_default();
+            }
+        };
+        self.total += metrics.count.unwrap_or_default();
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        if self.total <= 0 {
+            None
+        } else {
+            Some(
+                f64::from(u32::try_from(self.declined).ok()?) * 100.0
+                    / f64::from(u32::try_from(self.total).ok()?),
+            )
+        }
+    }
+}
+
+impl PaymentMetricAccumulator for LatencyPercentileAccumulator {
+    type MetricOutput = Option<LatencyPercentiles>;
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        let latency = metrics
+            .tokenization_latency
+            .and_then(|latency| u64::try_from(latency).ok());
+        let count = metrics.count.and_then(|count| u64::try_from(count).ok());
+
+        match (latency, count) {
+            (Some(latency), Some(count)) => self.timings.push((latency, count)),
+            _ => {
+                logger::error!(message="Dropping metrics for latency percentile accumulator", metric=?metrics);
+            }
+        }
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        LatencyPercentiles::from_timings(&self.timings)
+    }
+}
+
 impl PaymentMetricAccumulator for AverageAccumulator {
     type MetricOutput = Option<f64>;
 
@@ -193,8 +544,158 @@ impl PaymentMetricAccumulator for AverageAccumulator {
     }
 }
 
+impl PaymentMetricAccumulator for PaymentMethodsPerCustomerAccumulator {
+    type MetricOutput = Option<PaymentMethodsPerCustomer>;
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        let payment_method_count = metrics.total.as_ref().and_then(ToPrimitive::to_u64);
+        let customer_count = metrics.count.and_then(|count| u64::try_from(count).ok());
+
+        match (payment_method_count, customer_count) {
+            (Some(payment_method_count), Some(customer_count)) => {
+                self.counts.push((payment_method_count, customer_count))
+            }
+            _ => {
+                logger::error!(message="Dropping metrics for payment methods per customer accumulator", metric=?metrics);
+            }
+        }
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        PaymentMethodsPerCustomer::from_counts(&self.counts)
+    }
+}
+
+impl PaymentMetricAccumulator for AverageDurationAccumulator {
+    type MetricOutput = Option<f64>;
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        let average = metrics
+            .avg_time_to_success_seconds
+            .as_ref()
+            .and_then(ToPrimitive::to_f64);
+
+        match (average, metrics.count) {
+            (Some(average), Some(count)) if count > 0 => {
+                #[allow(clippy::as_conversions)]
+                let payments = count as f64;
+                self.total_seconds += average * payments;
+                self.count += count;
+            }
+            _ => {
+                logger::error!(message="Dropping metrics for average duration accumulator", metric=?metrics);
+            }
+        }
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        if self.count == 0 {
+            None
+        } else {
+            #[allow(clippy::as_conversions)]
+            let payments = self.count as f64;
+            Some(self.total_seconds / payments)
+        }
+    }
+}
+
+impl PaymentMetricAccumulator for ConnectorLatencyAccumulator {
+    type MetricOutput = (Option<f64>, Option<f64>);
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        let latency = metrics
+            .connector_latency
+            .and_then(|latency| u64::try_from(latency).ok());
+        let count = metrics.count.and_then(|count| u64::try_from(count).ok());
+
+        match (latency, count) {
+            (Some(latency), Some(count)) => self.timings.push((latency, count)),
+            _ => {
+                logger::error!(message="Dropping metrics for connector latency accumulator", metric=?metrics);
+            }
+        }
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        let (total, count) =
+            self.timings
+                .iter()
+                .fold((0u64, 0u64), |(total, payments), (latency, count)| {
+                    (
+                        total.saturating_add(latency.saturating_mul(*count)),
+                        payments.saturating_add(*count),
+                    )
+                });
+        if count == 0 {
+            return (None, None);
+        }
+
+        #[allow(clippy::as_conversions)]
+        let average = total as f64 / count as f64;
+        #[allow(clippy::as_conversions)]
+        let p95 = nearest_rank_percentile(&self.timings, 95).map(|latency| latency as f64);
+        (Some(average), p95)
+    }
+}
+
+impl PaymentMetricAccumulator for RetriesPerPaymentAccumulator {
+    type MetricOutput = Option<RetriesPerPayment>;
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        let attempt_count = metrics
+            .attempt_count
+            .and_then(|attempt_count| u64::try_from(attempt_count).ok());
+        let payment_count = metrics.count.and_then(|count| u64::try_from(count).ok());
+
+        match (attempt_count, metrics.succeeded, payment_count) {
+            (Some(attempt_count), Some(succeeded), Some(payment_count)) => {
+                self.counts.push((attempt_count, succeeded, payment_count))
+            }
+            _ => {
+                logger::error!(message="Dropping metrics for retries per payment accumulator", metric=?metrics);
+            }
+        }
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        RetriesPerPayment::from_counts(&self.counts)
+    }
+}
+
+impl PaymentMetricAccumulator for FailureClassificationAccumulator {
+    type MetricOutput = Option<FailureClassCounts>;
+
+    fn add_metrics_bucket(&mut self, metrics: &PaymentMetricRow) {
+        let Some(count) = metrics.count.and_then(|count| u64::try_from(count).ok()) else {
+            logger::error!(message="Dropping metrics for failure classification accumulator", metric=?metrics);
+            return;
+        };
+
+        let failures = match classify_failure(metrics.unified_code.as_deref()) {
+            FailureClass::IssuerDecline => &mut self.failures.issuer_decline,
+            FailureClass::GatewayError => &mut self.failures.gateway_error,
+            FailureClass::FraudBlock => &mut self.failures.fraud_block,
+            FailureClass::Unknown => &mut self.failures.unknown,
+        };
+        *failures = failures.saturating_add(count);
+    }
+
+    fn collect(self) -> Self::MetricOutput {
+        (self.failures != FailureClassCounts::default()).then_some(self.failures)
+    }
+}
+
 impl PaymentMetricsAccumulator {
     pub fn collect(self) -> PaymentMetricsBucketValue {
+        let (payment_success_rate_lower_bound, payment_success_rate_upper_bound) =
+            self.payment_success_rate_interval.collect();
+        let (captured_amount_in_reporting_currency, fx_rate_missing_dates) =
+            self.captured_amount_in_reporting_currency.collect();
+        let tokenization_latency = self.tokenization_latency.collect();
+        let payment_methods_per_customer = self.payment_methods_per_customer.collect();
+        let (avg_latency_ms, p95_latency_ms) = self.connector_latency.collect();
+        let (processed_amount_in_reporting_currency, processed_amount_without_fx_rate) =
+            self.processed_amount_in_reporting_currency.collect();
         PaymentMetricsBucketValue {
             payment_success_rate: self.payment_success_rate.collect(),
             payment_count: self.payment_count.collect(),
@@ -205,6 +706,96 @@ impl PaymentMetricsAccumulator {
             retries_count: self.retries_count.collect(),
             retries_amount_processed: self.retries_amount_processed.collect(),
             connector_success_rate: self.connector_success_rate.collect(),
+            chargeback_ratio: self.chargeback_ratio.collect(),
+            chargeback_ratio_threshold_exceeded: None,
+            surcharge_collected: self.surcharge_collected.collect(),
+            avg_surcharge_collected: self.avg_surcharge_collected.collect(),
+            payment_success_rate_lower_bound,
+            payment_success_rate_upper_bound,
+            captured_amount: self.captured_amount.collect(),
+            captured_amount_in_reporting_currency,
+            reporting_currency: None,
+            fx_rate_missing_dates,
+            decline_rate: self.decline_rate.collect(),
+            decline_spike: None,
+            auth_type_success_rate: self.auth_type_success_rate.collect(),
+            auth_type_payment_count: self.auth_type_payment_count.collect(),
+            tokenization_latency_p50: tokenization_latency.map(|latency| latency.p50),
+            tokenization_latency_p90: tokenization_latency.map(|latency| latency.p90),
+            tokenization_latency_p99: tokenization_latency.map(|latency| latency.p99),
+            client_source_success_rate: self.client_source_success_rate.collect(),
+            client_source_payment_count: self.client_source_payment_count.collect(),
+            net_revenue: self.net_revenue.collect(),
+            avg_payment_methods_per_customer: payment_methods_per_customer
+                .as_ref()
+                .map(|payment_methods_per_customer| payment_methods_per_customer.average),
+            payment_methods_per_customer_distribution: payment_methods_per_customer
+                .map(|payment_methods_per_customer| payment_methods_per_customer.distribution),
+            avg_time_to_success_seconds: self.avg_time_to_success.collect(),
+            retries_per_payment: self.retries_per_payment.collect(),
+            failure_classification: self.failure_classification.collect(),
+            processed_amount_in_reporting_currency,
+            processed_amount_without_fx_rate,
+            dispute_rate: self.dispute_rate.collect(),
+            first_attempt_success_rate: self.first_attempt_success_rate.collect(),
+            avg_latency_ms,
+            p95_latency_ms,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DBEnumWrapper;
+
+    fn success_rate_interval(success: i64, total: i64) -> (Option<f64>, Option<f64>) {
+        let mut accumulator = SuccessRateIntervalAccumulator::default();
+        for (status, count) in [
+            (storage_enums::AttemptStatus::Charged, success),
+            (storage_enums::AttemptStatus::Failure, total - success),
+        ] {
+            accumulator.add_metrics_bucket(&PaymentMetricRow {
+                status: Some(DBEnumWrapper(status)),
+                count: Some(count),
+                ..Default::default()
+            });
+        }
+        accumulator.collect()
+    }
+
+    #[test]
+    fn test_success_rate_interval_for_known_count() {
+        let (lower, upper) = success_rate_interval(8, 10);
+
+        assert!((lower.unwrap() - 49.0157).abs() < 1e-3);
+        assert!((upper.unwrap() - 94.3319).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_success_rate_interval_narrows_as_volume_grows() {
+        let widths = [(8, 10), (80, 100), (800, 1000)]
+            .into_iter()
+            .map(|(success, total)| {
+                let (lower, upper) = success_rate_interval(success, total);
+                let (lower, upper) = (lower.unwrap(), upper.unwrap());
+                assert!(lower < 80.0 && 80.0 < upper);
+                upper - lower
+            })
+            .collect::<Vec<_>>();
+
+        assert!(widths.windows(2).all(|pair| pair[1] < pair[0]));
+    }
+
+    #[test]
+    fn test_success_rate_interval_is_bounded() {
+        let (lower, upper) = success_rate_interval(5, 5);
+        assert!(upper.unwrap() <= 100.0);
+        assert!(lower.unwrap() > 0.0);
+
+        let (lower, _) = success_rate_interval(0, 5);
+        assert!(lower.unwrap().abs() < 1e-9);
+
+        assert_eq!(success_rate_interval(0, 0), (None, None));
+    }
+}
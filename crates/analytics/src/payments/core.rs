@@ -1,21 +1,23 @@
 #![allow(dead_code)]
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use api_models::analytics::{
     payments::{
-        MetricsBucketResponse, PaymentDimensions, PaymentDistributions, PaymentMetrics,
-        PaymentMetricsBucketIdentifier,
+        CurrencyTotals, MetricsBucketResponse, PaymentDimensions, PaymentDistributions,
+        PaymentFilters, PaymentMetrics, PaymentMetricsBucketIdentifier,
     },
-    AnalyticsMetadata, FilterValue, GetPaymentFiltersRequest, GetPaymentMetricRequest,
-    MetricsResponse, PaymentFiltersResponse,
+    FilterValue, GetPaymentFiltersRequest, GetPaymentMetricRequest, Granularity,
+    PaymentFiltersResponse, PaymentsAnalyticsMetadata, PaymentsMetricsResponse, TimeRange,
 };
+use bigdecimal::Zero;
 use common_utils::errors::CustomResult;
-use error_stack::ResultExt;
+use error_stack::{report, ResultExt};
 use router_env::{
     instrument, logger,
     metrics::add_attributes,
     tracing::{self, Instrument},
 };
+use time::PrimitiveDateTime;
 
 use super::{
     distribution::PaymentDistributionRow,
@@ -28,9 +30,23 @@ use crate::{
     errors::{AnalyticsError, AnalyticsResult},
     metrics,
     payments::{PaymentDistributionAccumulator, PaymentMetricAccumulator},
-    AnalyticsProvider,
+    query::SeriesBucket,
+    AnalyticsProvider, ReportingCurrency,
 };
 
+/// Chargeback ratio (in percentage) above which card networks place merchants under monitoring
+const DEFAULT_CHARGEBACK_RATIO_THRESHOLD: f64 = 0.9;
+
+/// Number of standard deviations above the trailing mean decline rate at which a time bucket is
+/// flagged as a decline spike
+const DEFAULT_DECLINE_SPIKE_STD_DEV_THRESHOLD: f64 = 3.0;
+
+/// Number of preceding time buckets the decline rate of a time bucket is compared with
+const DECLINE_SPIKE_TRAILING_WINDOW: usize = 24;
+
+/// Minimum number of preceding time buckets required to flag a decline spike
+const DECLINE_SPIKE_MIN_TRAILING_BUCKETS: usize = 3;
+
 #[derive(Debug)]
 pub enum TaskType {
     MetricTask(
@@ -47,8 +63,93 @@ pub enum TaskType {
 pub async fn get_metrics(
     pool: &AnalyticsProvider,
     auth: &AuthInfo,
-    req: GetPaymentMetricRequest,
-) -> AnalyticsResult<MetricsResponse<MetricsBucketResponse>> {
+    mut req: GetPaymentMetricRequest,
+    reporting_currency: &ReportingCurrency,
+) -> AnalyticsResult<PaymentsMetricsResponse<MetricsBucketResponse>> {
+    validate_card_bin_support(pool, &req.group_by_names, Some(&req.filters))?;
+
+    // Sub-totals are computed from the buckets, so the buckets must not mix currencies
+    if req.currency_totals && !req.group_by_names.contains(&PaymentDimensions::Currency) {
+        req.group_by_names.push(PaymentDimensions::Currency);
+    }
+
+    let granularity = req.time_series.map(|t| t.granularity);
+    let rollup_granularities = get_rollup_granularities(granularity, &req.rollup_granularities)?;
+
+    let mut query_data =
+        get_metrics_buckets(pool, auth, &req, granularity, reporting_currency).await?;
+
+    if let Some(granularity) = granularity {
+        let mut rollups = Vec::with_capacity(rollup_granularities.len());
+        for rollup_granularity in rollup_granularities.iter() {
+            rollups.push(
+                get_metrics_buckets(
+                    pool,
+                    auth,
+                    &req,
+                    Some(*rollup_granularity),
+                    reporting_currency,
+                )
+                .await?,
+            );
+        }
+
+        let mut finer_buckets = None;
+        for (rollup_granularity, mut buckets) in rollup_granularities.iter().zip(rollups).rev() {
+            if let Some(finer_buckets) = finer_buckets.take() {
+                nest_rollup_buckets(rollup_granularity, &mut buckets, finer_buckets)?;
+            }
+            finer_buckets = Some(buckets);
+        }
+        if let Some(finer_buckets) = finer_buckets {
+            nest_rollup_buckets(&granularity, &mut query_data, finer_buckets)?;
+        }
+    }
+
+    let currency_totals = req
+        .currency_totals
+        .then(|| CurrencyTotals::from_buckets(&query_data));
+
+    Ok(PaymentsMetricsResponse {
+        query_data,
+        meta_data: [PaymentsAnalyticsMetadata {
+            current_time_range: req.time_range,
+            currency_totals,
+        }],
+    })
+}
+
+/// Rejects the card bin dimension and filter when the results are served from Postgres, whose
+/// payment attempts do not record the card bin
+fn validate_card_bin_support(
+    pool: &AnalyticsProvider,
+    dimensions: &[PaymentDimensions],
+    filters: Option<&PaymentFilters>,
+) -> AnalyticsResult<()> {
+    let served_by_postgres = matches!(
+        pool,
+        AnalyticsProvider::Sqlx(_) | AnalyticsProvider::CombinedSqlx(..)
+    );
+    let uses_card_bin = dimensions.contains(&PaymentDimensions::CardBin)
+        || filters.is_some_and(|filters| !filters.card_bin.is_empty());
+
+    if served_by_postgres && uses_card_bin {
+        return Err(report!(AnalyticsError::NotImplemented(
+            "Card bin analytics on Postgres"
+        )))
+        .attach_printable("Postgres payment attempts do not record the card bin");
+    }
+    Ok(())
+}
+
+/// Metric buckets of the request at the given granularity
+async fn get_metrics_buckets(
+    pool: &AnalyticsProvider,
+    auth: &AuthInfo,
+    req: &GetPaymentMetricRequest,
+    granularity: Option<Granularity>,
+    reporting_currency: &ReportingCurrency,
+) -> AnalyticsResult<Vec<MetricsBucketResponse>> {
     let mut metrics_accumulator: HashMap<
         PaymentMetricsBucketIdentifier,
         PaymentMetricsAccumulator,
@@ -74,7 +175,7 @@ pub async fn get_metrics(
                         &req.group_by_names.clone(),
                         &auth_scoped,
                         &req.filters,
-                        &req.time_series.map(|t| t.granularity),
+                        &granularity,
                         &req.time_range,
                     )
                     .await
@@ -102,7 +203,7 @@ pub async fn get_metrics(
                         &req.group_by_names.clone(),
                         &auth_scoped,
                         &req.filters,
-                        &req.time_series.map(|t| t.granularity),
+                        &granularity,
                         &req.time_range,
                     )
                     .await
@@ -163,6 +264,88 @@ pub async fn get_metrics(
                                 .connector_success_rate
                                 .add_metrics_bucket(&value);
                         }
+                        PaymentMetrics::ChargebackRatio => {
+                            metrics_builder.chargeback_ratio.add_metrics_bucket(&value)
+                        }
+                        PaymentMetrics::SurchargeCollected => {
+                            metrics_builder
+                                .surcharge_collected
+                                .add_metrics_bucket(&value);
+                            metrics_builder
+                                .avg_surcharge_collected
+                                .add_metrics_bucket(&value)
+                        }
+                        PaymentMetrics::PaymentSuccessRateConfidenceInterval => {
+                            metrics_builder
+                                .payment_success_rate
+                                .add_metrics_bucket(&value);
+                            metrics_builder
+                                .payment_success_rate_interval
+                                .add_metrics_bucket(&value)
+                        }
+                        PaymentMetrics::CapturedAmountInReportingCurrency => {
+                            metrics_builder.captured_amount.add_metrics_bucket(&value);
+                            metrics_builder
+                                .captured_amount_in_reporting_currency
+                                .add_metrics_bucket(&value, reporting_currency)
+                        }
+                        PaymentMetrics::DeclineSpikes => {
+                            metrics_builder.decline_rate.add_metrics_bucket(&value)
+                        }
+                        PaymentMetrics::AuthTypeEffect => {
+                            metrics_builder
+                                .auth_type_success_rate
+                                .add_metrics_bucket(&value);
+                            metrics_builder
+                                .auth_type_payment_count
+                                .add_metrics_bucket(&value)
+                        }
+                        PaymentMetrics::TokenizationLatency => metrics_builder
+                            .tokenization_latency
+                            .add_metrics_bucket(&value),
+                        PaymentMetrics::ConversionByClientSource => {
+                            metrics_builder
+                                .client_source_success_rate
+                                .add_metrics_bucket(&value);
+                            metrics_builder
+                                .client_source_payment_count
+                                .add_metrics_bucket(&value)
+                        }
+                        PaymentMetrics::NetRevenue => {
+                            metrics_builder.net_revenue.add_metrics_bucket(&value)
+                        }
+                        PaymentMetrics::PaymentMethodsPerCustomer => {
+                            // Customers without a saved payment method are reported with a
+                            // payment method count of zero
+                            if req.include_customers_without_payment_methods
+                                || value.total.as_ref().map_or(true, |total| !total.is_zero())
+                            {
+                                metrics_builder
+                                    .payment_methods_per_customer
+                                    .add_metrics_bucket(&value)
+                            }
+                        }
+                        PaymentMetrics::AverageTimeToSuccess => metrics_builder
+                            .avg_time_to_success
+                            .add_metrics_bucket(&value),
+                        PaymentMetrics::RetriesPerPayment => metrics_builder
+                            .retries_per_payment
+                            .add_metrics_bucket(&value),
+                        PaymentMetrics::FailureClassification => metrics_builder
+                            .failure_classification
+                            .add_metrics_bucket(&value),
+                        PaymentMetrics::ProcessedAmountInReportingCurrency => metrics_builder
+                            .processed_amount_in_reporting_currency
+                            .add_metrics_bucket(&value, reporting_currency),
+                        PaymentMetrics::DisputeRate => {
+                            metrics_builder.dispute_rate.add_metrics_bucket(&value)
+                        }
+                        PaymentMetrics::FirstAttemptSuccessRate => metrics_builder
+                            .first_attempt_success_rate
+                            .add_metrics_bucket(&value),
+                        PaymentMetrics::ConnectorLatency => {
+                            metrics_builder.connector_latency.add_metrics_bucket(&value)
+                        }
                     }
                 }
 
@@ -204,20 +387,183 @@ pub async fn get_metrics(
         }
     }
 
-    let query_data: Vec<MetricsBucketResponse> = metrics_accumulator
+    let chargeback_ratio_threshold = req
+        .chargeback_ratio_threshold
+        .unwrap_or(DEFAULT_CHARGEBACK_RATIO_THRESHOLD);
+
+    let mut query_data: Vec<MetricsBucketResponse> = metrics_accumulator
         .into_iter()
-        .map(|(id, val)| MetricsBucketResponse {
-            values: val.collect(),
-            dimensions: id,
+        .map(|(id, val)| {
+            let mut values = val.collect();
+            values.chargeback_ratio_threshold_exceeded = values
+                .chargeback_ratio
+                .map(|ratio| ratio > chargeback_ratio_threshold);
+            if values.captured_amount_in_reporting_currency.is_some()
+                || values.fx_rate_missing_dates.is_some()
+                || values.processed_amount_in_reporting_currency.is_some()
+            {
+                values.reporting_currency = Some(reporting_currency.currency);
+            }
+            MetricsBucketResponse {
+                values,
+                dimensions: id,
+                rollup_buckets: Vec::new(),
+            }
         })
         .collect();
 
-    Ok(MetricsResponse {
-        query_data,
-        meta_data: [AnalyticsMetadata {
-            current_time_range: req.time_range,
-        }],
-    })
+    flag_decline_spikes(
+        &mut query_data,
+        req.decline_spike_std_dev_threshold
+            .unwrap_or(DEFAULT_DECLINE_SPIKE_STD_DEV_THRESHOLD),
+    );
+
+    Ok(query_data)
+}
+
+/// Length of the time buckets of the granularity, in minutes
+fn get_granularity_in_minutes(granularity: &Granularity) -> u32 {
+    match granularity {
+        Granularity::OneMin => 1,
+        Granularity::FiveMin => 5,
+        Granularity::FifteenMin => 15,
+        Granularity::ThirtyMin => 30,
+        Granularity::OneHour => 60,
+        Granularity::OneDay => 1440,
+    }
+}
+
+/// Rollup granularities of the request from the coarsest to the finest, each of which must be
+/// finer than the granularity of the time series
+fn get_rollup_granularities(
+    granularity: Option<Granularity>,
+    rollup_granularities: &[Granularity],
+) -> AnalyticsResult<Vec<Granularity>> {
+    if rollup_granularities.is_empty() {
+        return Ok(Vec::new());
+    }
+    let granularity = granularity.ok_or(AnalyticsError::InvalidRollupGranularity(
+        "rollup granularities require a time series granularity",
+    ))?;
+
+    let mut rollup_granularities = rollup_granularities.to_vec();
+    rollup_granularities.sort_by_key(|rollup_granularity| {
+        std::cmp::Reverse(get_granularity_in_minutes(rollup_granularity))
+    });
+    rollup_granularities
+        .dedup_by_key(|rollup_granularity| get_granularity_in_minutes(rollup_granularity));
+
+    if rollup_granularities.iter().any(|rollup_granularity| {
+        get_granularity_in_minutes(rollup_granularity) >= get_granularity_in_minutes(&granularity)
+    }) {
+        return Err(AnalyticsError::InvalidRollupGranularity(
+            "rollup granularities must be finer than the time series granularity",
+        )
+        .into());
+    }
+    Ok(rollup_granularities)
+}
+
+/// Identifier of the bucket with all the dimensions of `dimensions`, over the time bucket starting
+/// at `start_time`
+fn bucket_key(
+    dimensions: &PaymentMetricsBucketIdentifier,
+    start_time: PrimitiveDateTime,
+) -> PaymentMetricsBucketIdentifier {
+    PaymentMetricsBucketIdentifier {
+        time_bucket: TimeRange {
+            start_time,
+            end_time: None,
+        },
+        start_time,
+        ..dimensions.clone()
+    }
+}
+
+/// Nests each of the `finer_buckets` in the bucket of `buckets` with the same dimensions whose
+/// time bucket at `granularity` contains it. Finer buckets without such a bucket are dropped.
+fn nest_rollup_buckets(
+    granularity: &Granularity,
+    buckets: &mut [MetricsBucketResponse],
+    finer_buckets: Vec<MetricsBucketResponse>,
+) -> AnalyticsResult<()> {
+    let mut bucket_positions = HashMap::new();
+    for (position, bucket) in buckets.iter().enumerate() {
+        bucket_positions.insert(
+            bucket_key(&bucket.dimensions, bucket.dimensions.start_time),
+            position,
+        );
+    }
+
+    for finer_bucket in finer_buckets {
+        let start_time = granularity
+            .clip_to_start(finer_bucket.dimensions.start_time)
+            .change_context(AnalyticsError::UnknownError)?;
+        match bucket_positions
+            .get(&bucket_key(&finer_bucket.dimensions, start_time))
+            .and_then(|position| buckets.get_mut(*position))
+        {
+            Some(bucket) => bucket.rollup_buckets.push(finer_bucket),
+            None => {
+                logger::warn!(message="Dropping rollup bucket without a matching bucket", bucket=?finer_bucket.dimensions);
+            }
+        }
+    }
+
+    for bucket in buckets.iter_mut() {
+        bucket
+            .rollup_buckets
+            .sort_by_key(|rollup_bucket| rollup_bucket.dimensions.start_time);
+    }
+    Ok(())
+}
+
+/// Flags the time buckets whose decline rate exceeds the mean decline rate of the trailing time
+/// buckets of the same dimensions by more than `std_dev_threshold` standard deviations
+fn flag_decline_spikes(query_data: &mut [MetricsBucketResponse], std_dev_threshold: f64) {
+    let mut series: HashMap<_, Vec<&mut MetricsBucketResponse>> = HashMap::new();
+    for bucket in query_data
+        .iter_mut()
+        .filter(|bucket| bucket.values.decline_rate.is_some())
+    {
+        // All the time buckets of a series share the key of its dimensions
+        let series_key = bucket_key(&bucket.dimensions, PrimitiveDateTime::MIN);
+        series.entry(series_key).or_default().push(bucket);
+    }
+
+    for buckets in series.values_mut() {
+        buckets.sort_by_key(|bucket| bucket.dimensions.start_time);
+        let mut trailing_decline_rates = VecDeque::with_capacity(DECLINE_SPIKE_TRAILING_WINDOW);
+        for bucket in buckets.iter_mut() {
+            let Some(decline_rate) = bucket.values.decline_rate else {
+                continue;
+            };
+            bucket.values.decline_spike =
+                is_decline_spike(decline_rate, &trailing_decline_rates, std_dev_threshold);
+            if trailing_decline_rates.len() == DECLINE_SPIKE_TRAILING_WINDOW {
+                trailing_decline_rates.pop_front();
+            }
+            trailing_decline_rates.push_back(decline_rate);
+        }
+    }
+}
+
+fn is_decline_spike(
+    decline_rate: f64,
+    trailing_decline_rates: &VecDeque<f64>,
+    std_dev_threshold: f64,
+) -> Option<bool> {
+    if trailing_decline_rates.len() < DECLINE_SPIKE_MIN_TRAILING_BUCKETS {
+        return None;
+    }
+    let count = f64::from(u32::try_from(trailing_decline_rates.len()).ok()?);
+    let mean = trailing_decline_rates.iter().sum::<f64>() / count;
+    let variance = trailing_decline_rates
+        .iter()
+        .map(|rate| (rate - mean).powi(2))
+        .sum::<f64>()
+        / count;
+    Some(decline_rate > mean + std_dev_threshold * variance.sqrt())
 }
 
 pub async fn get_filters(
@@ -225,6 +571,8 @@ pub async fn get_filters(
     req: GetPaymentFiltersRequest,
     auth: &AuthInfo,
 ) -> AnalyticsResult<PaymentFiltersResponse> {
+    validate_card_bin_support(pool, &req.group_by_names, None)?;
+
     let mut res = PaymentFiltersResponse::default();
 
     for dim in req.group_by_names {
@@ -297,6 +645,11 @@ pub async fn get_filters(
             PaymentDimensions::ClientVersion => fil.client_version,
             PaymentDimensions::ProfileId => fil.profile_id,
             PaymentDimensions::CardNetwork => fil.card_network,
+            PaymentDimensions::DeviceFingerprint => fil.device_fingerprint,
+            PaymentDimensions::ThreeDsVersion => fil.three_ds_version,
+            PaymentDimensions::ThreeDsMessageVersion => fil.three_ds_message_version,
+            PaymentDimensions::CardFundingType => fil.card_funding_type,
+            PaymentDimensions::CardBin => fil.card_bin,
         })
         .collect::<Vec<String>>();
         res.query_data.push(FilterValue {
@@ -306,3 +659,302 @@ pub async fn get_filters(
     }
     Ok(res)
 }
+
+#[cfg(test)]
+mod tests {
+    use api_models::analytics::payments::PaymentMetricsBucketValue;
+    use time::{Date, Month, Time};
+
+    use super::*;
+    use crate::{
+        clickhouse::{ClickhouseClient, ClickhouseConfig},
+        payments::test_utils,
+    };
+
+    fn bucket(
+        granularity: &Granularity,
+        connector: &str,
+        hour: u8,
+        minute: u8,
+        payment_count: u64,
+    ) -> MetricsBucketResponse {
+        let created_at = PrimitiveDateTime::new(
+            Date::from_calendar_date(2024, Month::October, 1).unwrap(),
+            Time::from_hms(hour, minute, 0).unwrap(),
+        );
+        MetricsBucketResponse {
+            values: PaymentMetricsBucketValue {
+                payment_count: Some(payment_count),
+                ..Default::default()
+            },
+            dimensions: test_utils::bucket_identifier(
+                &PaymentMetricRow {
+                    connector: Some(connector.to_string()),
+                    ..Default::default()
+                },
+                TimeRange {
+                    start_time: granularity.clip_to_start(created_at).unwrap(),
+                    end_time: Some(granularity.clip_to_end(created_at).unwrap()),
+                },
+            ),
+            rollup_buckets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_rollup_granularities_are_sorted_from_coarsest_to_finest() {
+        let rollup_granularities = get_rollup_granularities(
+            Some(Granularity::OneDay),
+            &[
+                Granularity::FifteenMin,
+                Granularity::OneHour,
+                Granularity::FifteenMin,
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            rollup_granularities
+                .iter()
+                .map(get_granularity_in_minutes)
+                .collect::<Vec<_>>(),
+            vec![60, 15]
+        );
+    }
+
+    #[test]
+    fn test_rollup_granularities_must_be_finer_than_time_series() {
+        assert!(
+            get_rollup_granularities(Some(Granularity::OneHour), &[Granularity::OneDay]).is_err()
+        );
+        assert!(
+            get_rollup_granularities(Some(Granularity::OneHour), &[Granularity::OneHour]).is_err()
+        );
+        assert!(get_rollup_granularities(None, &[Granularity::OneHour]).is_err());
+    }
+
+    #[test]
+    fn test_hourly_buckets_are_nested_in_daily_buckets() {
+        let mut daily_buckets = vec![
+            bucket(&Granularity::OneDay, "stripe", 0, 0, 5),
+            bucket(&Granularity::OneDay, "adyen", 0, 0, 1),
+        ];
+        let hourly_buckets = vec![
+            bucket(&Granularity::OneHour, "stripe", 14, 30, 3),
+            bucket(&Granularity::OneHour, "stripe", 9, 5, 2),
+            bucket(&Granularity::OneHour, "adyen", 23, 59, 1),
+        ];
+
+        nest_rollup_buckets(&Granularity::OneDay, &mut daily_buckets, hourly_buckets).unwrap();
+
+        let rollups = daily_buckets
+            .iter()
+            .map(|bucket| {
+                (
+                    bucket.dimensions.connector.clone(),
+                    bucket
+                        .rollup_buckets
+                        .iter()
+                        .map(|rollup_bucket| {
+                            (
+                                rollup_bucket.dimensions.start_time.hour(),
+                                rollup_bucket.dimensions.start_time.minute(),
+                                rollup_bucket.values.payment_count,
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rollups,
+            vec![
+                (
+                    Some("stripe".to_string()),
+                    vec![(9, 0, Some(2)), (14, 0, Some(3))]
+                ),
+                (Some("adyen".to_string()), vec![(23, 0, Some(1))]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hourly_buckets_are_nested_in_the_daily_bucket_of_their_device_fingerprint() {
+        let with_device_fingerprint =
+            |mut bucket: MetricsBucketResponse, device_fingerprint: &str| {
+                bucket.dimensions.device_fingerprint = Some(device_fingerprint.to_string());
+                bucket
+            };
+        let mut daily_buckets = vec![
+            with_device_fingerprint(bucket(&Granularity::OneDay, "stripe", 0, 0, 2), "fp_1"),
+            with_device_fingerprint(bucket(&Granularity::OneDay, "stripe", 0, 0, 1), "fp_2"),
+        ];
+        let hourly_buckets = vec![
+            with_device_fingerprint(bucket(&Granularity::OneHour, "stripe", 9, 0, 2), "fp_1"),
+            with_device_fingerprint(bucket(&Granularity::OneHour, "stripe", 14, 0, 1), "fp_2"),
+        ];
+
+        nest_rollup_buckets(&Granularity::OneDay, &mut daily_buckets, hourly_buckets).unwrap();
+
+        let rollups = daily_buckets
+            .iter()
+            .map(|bucket| {
+                (
+                    bucket.dimensions.device_fingerprint.clone(),
+                    bucket
+                        .rollup_buckets
+                        .iter()
+                        .map(|rollup_bucket| rollup_bucket.dimensions.start_time.hour())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rollups,
+            vec![
+                (Some("fp_1".to_string()), vec![9]),
+                (Some("fp_2".to_string()), vec![14]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rows_of_different_three_ds_versions_are_separate_buckets() {
+        let mut payment_counts = HashMap::new();
+        for three_ds_version in ["2.1.0", "2.2.0", "2.2.0"] {
+            let (bucket_identifier, row) = test_utils::bucket(PaymentMetricRow {
+                three_ds_version: Some(three_ds_version.to_string()),
+                count: Some(1),
+                ..Default::default()
+            });
+            *payment_counts.entry(bucket_identifier).or_insert(0) += row.count.unwrap_or_default();
+        }
+
+        let mut payment_counts = payment_counts
+            .into_iter()
+            .map(|(bucket_identifier, count)| (bucket_identifier.three_ds_version, count))
+            .collect::<Vec<_>>();
+        payment_counts.sort();
+        assert_eq!(
+            payment_counts,
+            vec![
+                (Some("2.1.0".to_string()), 1),
+                (Some("2.2.0".to_string()), 2)
+            ]
+        );
+    }
+
+    fn decline_bucket(connector: &str, hour: u8, decline_rate: f64) -> MetricsBucketResponse {
+        let mut bucket = bucket(&Granularity::OneHour, connector, hour, 0, 100);
+        bucket.values.decline_rate = Some(decline_rate);
+        bucket
+    }
+
+    fn decline_spikes(query_data: &[MetricsBucketResponse]) -> Vec<(String, u8, Option<bool>)> {
+        query_data
+            .iter()
+            .map(|bucket| {
+                (
+                    bucket.dimensions.connector.clone().unwrap_or_default(),
+                    bucket.dimensions.start_time.hour(),
+                    bucket.values.decline_spike,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_injected_decline_spike_is_flagged() {
+        // Hourly decline rates around 10% with a spike to 40% at 15:00 on stripe only, the
+        // buckets are out of order as they are returned by the query
+        let mut query_data = [(14, 11.0), (10, 10.0), (15, 40.0), (11, 12.0), (12, 9.0)]
+            .into_iter()
+            .map(|(hour, decline_rate)| decline_bucket("stripe", hour, decline_rate))
+            .chain(
+                [(10, 40.0), (11, 41.0), (12, 39.0), (14, 40.0), (15, 40.5)]
+                    .into_iter()
+                    .map(|(hour, decline_rate)| decline_bucket("adyen", hour, decline_rate)),
+            )
+            .collect::<Vec<_>>();
+
+        flag_decline_spikes(&mut query_data, DEFAULT_DECLINE_SPIKE_STD_DEV_THRESHOLD);
+
+        let mut spikes = decline_spikes(&query_data);
+        spikes.sort();
+        assert_eq!(
+            spikes,
+            vec![
+                ("adyen".to_string(), 10, None),
+                ("adyen".to_string(), 11, None),
+                ("adyen".to_string(), 12, None),
+                ("adyen".to_string(), 14, Some(false)),
+                ("adyen".to_string(), 15, Some(false)),
+                ("stripe".to_string(), 10, None),
+                ("stripe".to_string(), 11, None),
+                ("stripe".to_string(), 12, None),
+                ("stripe".to_string(), 14, Some(false)),
+                ("stripe".to_string(), 15, Some(true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normal_decline_variation_is_not_flagged() {
+        let mut query_data = [10.0, 14.0, 8.0, 12.0, 9.0, 13.0, 11.0, 7.0]
+            .into_iter()
+            .zip(10..)
+            .map(|(decline_rate, hour)| decline_bucket("stripe", hour, decline_rate))
+            .collect::<Vec<_>>();
+
+        flag_decline_spikes(&mut query_data, DEFAULT_DECLINE_SPIKE_STD_DEV_THRESHOLD);
+
+        assert!(decline_spikes(&query_data)
+            .into_iter()
+            .all(|(_, _, decline_spike)| decline_spike != Some(true)));
+        assert_eq!(
+            decline_spikes(&query_data)
+                .into_iter()
+                .filter(|(_, _, decline_spike)| decline_spike.is_some())
+                .count(),
+            5
+        );
+    }
+
+    #[tokio::test]
+    async fn test_card_bin_is_rejected_on_postgres() {
+        let filters = PaymentFilters {
+            card_bin: vec!["424242".to_string()],
+            ..Default::default()
+        };
+        let postgres = AnalyticsProvider::default();
+
+        for (dimensions, filters) in [
+            (vec![PaymentDimensions::CardBin], None),
+            (vec![PaymentDimensions::Connector], Some(&filters)),
+        ] {
+            let error = validate_card_bin_support(&postgres, &dimensions, filters).unwrap_err();
+            assert!(matches!(
+                error.current_context(),
+                AnalyticsError::NotImplemented(_)
+            ));
+        }
+        assert!(validate_card_bin_support(
+            &postgres,
+            &[PaymentDimensions::Connector],
+            Some(&PaymentFilters::default())
+        )
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_card_bin_is_supported_on_clickhouse() {
+        let clickhouse = AnalyticsProvider::Clickhouse(ClickhouseClient {
+            config: std::sync::Arc::new(ClickhouseConfig::default()),
+            database: "default".to_string(),
+        });
+
+        assert!(
+            validate_card_bin_support(&clickhouse, &[PaymentDimensions::CardBin], None).is_ok()
+        );
+    }
+}
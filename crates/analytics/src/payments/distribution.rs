@@ -28,6 +28,11 @@ pub struct PaymentDistributionRow {
     pub client_source: Option<String>,
     pub client_version: Option<String>,
     pub profile_id: Option<String>,
+    pub card_funding_type: Option<String>,
+    pub card_bin: Option<String>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
     pub total: Option<bigdecimal::BigDecimal>,
     pub count: Option<i64>,
     pub error_message: Option<String>,
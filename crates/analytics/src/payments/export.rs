@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+
+use api_models::analytics::payments::{PaymentDimensions, PaymentMetricsBucketIdentifier};
+use error_stack::{report, ResultExt};
+use time::format_description::well_known::Rfc3339;
+
+use super::metrics::PaymentMetricRow;
+use crate::errors::{AnalyticsError, AnalyticsResult};
+
+/// Columns of the metric values of a row, in the order they are exported
+const METRIC_VALUE_COLUMNS: [&str; 8] = [
+    "count",
+    "total",
+    "dispute_count",
+    "tokenization_latency",
+    "avg_time_to_success_seconds",
+    "connector_latency",
+    "attempt_count",
+    "succeeded",
+];
+
+fn cell<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Whether `dimension` is part of the bucket identifier. Payment metrics are not bucketed by the
+/// card network, so this dimension cannot be exported.
+fn is_bucket_dimension(dimension: &PaymentDimensions) -> bool {
+    !matches!(dimension, PaymentDimensions::CardNetwork)
+}
+
+/// Value of `dimension` in the bucket. Dimensions that are not part of the bucket identifier are
+/// rejected before the buckets are exported.
+fn dimension_cell(
+    bucket: &PaymentMetricsBucketIdentifier,
+    dimension: &PaymentDimensions,
+) -> String {
+    match dimension {
+        PaymentDimensions::Connector => cell(bucket.connector.as_ref()),
+        PaymentDimensions::PaymentMethod => cell(bucket.payment_method.as_ref()),
+        PaymentDimensions::PaymentMethodType => cell(bucket.payment_method_type.as_ref()),
+        PaymentDimensions::Currency => cell(bucket.currency),
+        PaymentDimensions::AuthType => cell(bucket.auth_type),
+        PaymentDimensions::PaymentStatus => cell(bucket.status),
+        PaymentDimensions::ClientSource => cell(bucket.client_source.as_ref()),
+        PaymentDimensions::ClientVersion => cell(bucket.client_version.as_ref()),
+        PaymentDimensions::ProfileId => cell(bucket.profile_id.as_ref()),
+        PaymentDimensions::CardFundingType => cell(bucket.card_funding_type.as_ref()),
+        PaymentDimensions::CardBin => cell(bucket.card_bin.as_ref()),
+        PaymentDimensions::DeviceFingerprint => cell(bucket.device_fingerprint.as_ref()),
+        PaymentDimensions::ThreeDsVersion => cell(bucket.three_ds_version.as_ref()),
+        PaymentDimensions::ThreeDsMessageVersion => cell(bucket.three_ds_message_version.as_ref()),
+        PaymentDimensions::CardNetwork => String::new(),
+    }
+}
+
+fn metric_value_cells(row: &PaymentMetricRow) -> [String; 8] {
+    [
+        cell(row.count),
+        cell(row.total.as_ref()),
+        cell(row.dispute_count),
+        cell(row.tokenization_latency),
+        cell(row.avg_time_to_success_seconds.as_ref()),
+        cell(row.connector_latency),
+        cell(row.attempt_count),
+        cell(row.succeeded),
+    ]
+}
+
+/// Export the buckets of a payment metrics query as CSV for merchants to download.
+///
+/// The columns are the start of the time bucket in ISO 8601, followed by the requested
+/// `dimensions` in the order they were requested and then the metric values of the rows. Rows are
+/// ordered by time bucket and then by their dimension values, so that exports of the same data
+/// are identical. Missing dimensions and metric values are exported as empty cells, while
+/// dimensions that the buckets are not identified by are rejected.
+pub fn export_metrics_buckets_as_csv(
+    dimensions: &[PaymentDimensions],
+    buckets: &HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+) -> AnalyticsResult<String> {
+    if let Some(dimension) = dimensions
+        .iter()
+        .find(|dimension| !is_bucket_dimension(dimension))
+    {
+        return Err(report!(AnalyticsError::NotImplemented(
+            "Export of the card network dimension"
+        )))
+        .attach_printable(format!(
+            "Payment metrics are not bucketed by the {dimension} dimension"
+        ));
+    }
+
+    let mut rows = buckets
+        .iter()
+        .map(|(bucket, row)| {
+            let time_bucket = bucket
+                .start_time
+                .assume_utc()
+                .format(&Rfc3339)
+                .change_context(AnalyticsError::UnknownError)
+                .attach_printable("Failed to format time bucket")?;
+            let dimension_cells = dimensions
+                .iter()
+                .map(|dimension| dimension_cell(bucket, dimension))
+                .collect::<Vec<_>>();
+            Ok((time_bucket, dimension_cells, metric_value_cells(row)))
+        })
+        .collect::<AnalyticsResult<Vec<_>>>()?;
+    rows.sort();
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(
+            std::iter::once("time_bucket".to_string())
+                .chain(dimensions.iter().map(ToString::to_string))
+                .chain(METRIC_VALUE_COLUMNS.iter().map(ToString::to_string)),
+        )
+        .change_context(AnalyticsError::UnknownError)
+        .attach_printable("Failed to write CSV header")?;
+    for (time_bucket, dimension_cells, metric_value_cells) in rows {
+        writer
+            .write_record(
+                std::iter::once(time_bucket)
+                    .chain(dimension_cells)
+                    .chain(metric_value_cells),
+            )
+            .change_context(AnalyticsError::UnknownError)
+            .attach_printable("Failed to write CSV row")?;
+    }
+
+    let csv = writer
+        .into_inner()
+        .change_context(AnalyticsError::UnknownError)
+        .attach_printable("Failed to flush CSV")?;
+    String::from_utf8(csv)
+        .change_context(AnalyticsError::UnknownError)
+        .attach_printable("CSV is not valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use bigdecimal::BigDecimal;
+    use diesel_models::enums as storage_enums;
+
+    use super::*;
+    use crate::{payments::test_utils, types::DBEnumWrapper};
+
+    fn bucket(
+        connector: Option<&str>,
+        currency: storage_enums::Currency,
+        count: i64,
+        total: i64,
+    ) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+        test_utils::bucket(PaymentMetricRow {
+            currency: Some(DBEnumWrapper(currency)),
+            connector: connector.map(ToString::to_string),
+            total: Some(BigDecimal::from(total)),
+            count: Some(count),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_metrics_buckets_are_exported_as_csv() {
+        let buckets = HashSet::from([
+            bucket(Some("stripe"), storage_enums::Currency::USD, 3, 4500),
+            bucket(None, storage_enums::Currency::EUR, 1, 1000),
+        ]);
+
+        let csv = export_metrics_buckets_as_csv(
+            &[PaymentDimensions::Connector, PaymentDimensions::Currency],
+            &buckets,
+        )
+        .unwrap();
+
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            vec![
+                "time_bucket,connector,currency,count,total,dispute_count,tokenization_latency,avg_time_to_success_seconds,connector_latency,attempt_count,succeeded",
+                "2024-10-01T10:00:00Z,,EUR,1,1000,,,,,,",
+                "2024-10-01T10:00:00Z,stripe,USD,3,4500,,,,,,",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dimensions_not_in_the_bucket_are_rejected() {
+        let buckets = HashSet::from([bucket(
+            Some("stripe"),
+            storage_enums::Currency::USD,
+            3,
+            4500,
+        )]);
+
+        let error = export_metrics_buckets_as_csv(
+            &[PaymentDimensions::Connector, PaymentDimensions::CardNetwork],
+            &buckets,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            AnalyticsError::NotImplemented(_)
+        ));
+        assert!(
+            export_metrics_buckets_as_csv(&[PaymentDimensions::CardNetwork], &HashSet::new())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_device_fingerprint_is_exported() {
+        let (mut bucket_identifier, row) =
+            bucket(Some("stripe"), storage_enums::Currency::USD, 3, 4500);
+        bucket_identifier.device_fingerprint = Some("fingerprint_1".to_string());
+
+        let csv = export_metrics_buckets_as_csv(
+            &[PaymentDimensions::DeviceFingerprint],
+            &HashSet::from([(bucket_identifier, row)]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("2024-10-01T10:00:00Z,fingerprint_1,3,4500,,,,,,")
+        );
+    }
+
+    #[test]
+    fn test_three_ds_versions_are_exported() {
+        let (mut bucket_identifier, row) =
+            bucket(Some("stripe"), storage_enums::Currency::USD, 3, 4500);
+        bucket_identifier.three_ds_version = Some("2.2.0".to_string());
+        bucket_identifier.three_ds_message_version = Some("2.1.0".to_string());
+
+        let csv = export_metrics_buckets_as_csv(
+            &[
+                PaymentDimensions::ThreeDsVersion,
+                PaymentDimensions::ThreeDsMessageVersion,
+            ],
+            &HashSet::from([(bucket_identifier, row)]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("2024-10-01T10:00:00Z,2.2.0,2.1.0,3,4500,,,,,,")
+        );
+    }
+}
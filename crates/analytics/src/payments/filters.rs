@@ -44,7 +44,7 @@ where
     query_builder
         .execute_query::<PaymentFilterRow, _>(pool)
         .await
-        .change_context(FiltersError::QueryBuildingError)?
+        .switch()?
         .change_context(FiltersError::QueryExecutionFailure)
 }
 
@@ -60,4 +60,9 @@ pub struct PaymentFilterRow {
     pub client_version: Option<String>,
     pub profile_id: Option<String>,
     pub card_network: Option<String>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub card_funding_type: Option<String>,
+    pub card_bin: Option<String>,
 }
@@ -9,28 +9,62 @@ use time::PrimitiveDateTime;
 
 use crate::{
     enums::AuthInfo,
-    query::{Aggregate, GroupByClause, ToSql, Window},
+    query::{Aggregate, DurationInMillis, GroupByClause, ToSql, Window},
     types::{AnalyticsCollection, AnalyticsDataSource, DBEnumWrapper, LoadRow, MetricsResult},
 };
 
+mod auth_type_effect;
 mod avg_ticket_size;
+mod avg_time_to_success;
+mod captured_amount_in_reporting_currency;
+mod chargeback_ratio;
+mod connector_latency;
 mod connector_success_rate;
+mod conversion_by_client_source;
+mod dispute_rate;
+mod failure_classification;
+mod first_attempt_success_rate;
+mod net_revenue;
 mod payment_count;
+mod payment_methods_per_customer;
 mod payment_processed_amount;
 mod payment_success_count;
+mod processed_amount_in_reporting_currency;
 mod retries_count;
+mod retries_per_payment;
 mod success_rate;
+mod surcharge_collected;
+mod tokenization_latency;
 
+use auth_type_effect::AuthTypeEffect;
 use avg_ticket_size::AvgTicketSize;
+use avg_time_to_success::AverageTimeToSuccess;
+use captured_amount_in_reporting_currency::CapturedAmountInReportingCurrency;
+use chargeback_ratio::ChargebackRatio;
+use connector_latency::ConnectorLatency;
 use connector_success_rate::ConnectorSuccessRate;
+use conversion_by_client_source::ConversionByClientSource;
+use dispute_rate::DisputeRate;
+use failure_classification::FailureClassification;
+pub(super) use failure_classification::{classify_failure, FailureClass};
+use first_attempt_success_rate::FirstAttemptSuccessRate;
+use net_revenue::NetRevenue;
 use payment_count::PaymentCount;
+use payment_methods_per_customer::PaymentMethodsPerCustomer;
 use payment_processed_amount::PaymentProcessedAmount;
 use payment_success_count::PaymentSuccessCount;
+use processed_amount_in_reporting_currency::ProcessedAmountInReportingCurrency;
+use retries_per_payment::RetriesPerPayment;
 use success_rate::PaymentSuccessRate;
+use surcharge_collected::SurchargeCollected;
+use tokenization_latency::TokenizationLatency;
 
 use self::retries_count::RetriesCount;
 
-#[derive(Debug, PartialEq, Eq, serde::Deserialize, Hash)]
+/// Number of rows post-processed at once by the metrics whose results are streamed
+const STREAMED_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Default, PartialEq, Eq, serde::Deserialize, Hash)]
 pub struct PaymentMetricRow {
     pub currency: Option<DBEnumWrapper<storage_enums::Currency>>,
     pub status: Option<DBEnumWrapper<storage_enums::AttemptStatus>>,
@@ -41,8 +75,22 @@ pub struct PaymentMetricRow {
     pub client_source: Option<String>,
     pub client_version: Option<String>,
     pub profile_id: Option<String>,
+    pub card_funding_type: Option<String>,
+    pub card_bin: Option<String>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub unified_code: Option<String>,
     pub total: Option<bigdecimal::BigDecimal>,
     pub count: Option<i64>,
+    pub dispute_count: Option<i64>,
+    pub tokenization_latency: Option<i64>,
+    pub avg_time_to_success_seconds: Option<bigdecimal::BigDecimal>,
+    pub connector_latency: Option<i64>,
+    pub attempt_count: Option<i64>,
+    /// Whether the payments of the row succeeded, set by metrics that split payments by outcome
+    #[serde(default)]
+    pub succeeded: Option<bool>,
     #[serde(with = "common_utils::custom_serde::iso8601::option")]
     pub start_bucket: Option<PrimitiveDateTime>,
     #[serde(with = "common_utils::custom_serde::iso8601::option")]
@@ -76,6 +124,7 @@ where
     Granularity: GroupByClause<T>,
     Aggregate<&'static str>: ToSql<T>,
     Window<&'static str>: ToSql<T>,
+    DurationInMillis<&'static str>: ToSql<T>,
 {
     async fn load_metrics(
         &self,
@@ -87,7 +136,9 @@ where
         pool: &T,
     ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
         match self {
-            Self::PaymentSuccessRate => {
+            Self::PaymentSuccessRate
+            | Self::PaymentSuccessRateConfidenceInterval
+            | Self::DeclineSpikes => {
                 PaymentSuccessRate
                     .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
                     .await
@@ -122,6 +173,81 @@ where
                     .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
                     .await
             }
+            Self::ChargebackRatio => {
+                ChargebackRatio
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::SurchargeCollected => {
+                SurchargeCollected
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::CapturedAmountInReportingCurrency => {
+                CapturedAmountInReportingCurrency
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::AuthTypeEffect => {
+                AuthTypeEffect
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::TokenizationLatency => {
+                TokenizationLatency
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::ConversionByClientSource => {
+                ConversionByClientSource
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::NetRevenue => {
+                NetRevenue
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::PaymentMethodsPerCustomer => {
+                PaymentMethodsPerCustomer
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::AverageTimeToSuccess => {
+                AverageTimeToSuccess
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::RetriesPerPayment => {
+                RetriesPerPayment
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::FailureClassification => {
+                FailureClassification
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::ProcessedAmountInReportingCurrency => {
+                ProcessedAmountInReportingCurrency
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::DisputeRate => {
+                DisputeRate
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::FirstAttemptSuccessRate => {
+                FirstAttemptSuccessRate
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
+            Self::ConnectorLatency => {
+                ConnectorLatency
+                    .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+                    .await
+            }
         }
     }
 }
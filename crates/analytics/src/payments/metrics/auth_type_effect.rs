@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use time::PrimitiveDateTime;
+
+use super::{success_rate::PaymentSuccessRate, PaymentMetricRow};
+use crate::{
+    enums::AuthInfo,
+    query::{Aggregate, GroupByClause, ToSql, Window},
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsResult},
+};
+
+/// Volume and success rate of payments split by authentication type, so that the effect of
+/// 3DS on conversion can be compared with payments that skip it.
+///
+/// Payments are always grouped by authentication type, whether or not it was requested as a
+/// dimension.
+#[derive(Default)]
+pub(super) struct AuthTypeEffect;
+
+impl AuthTypeEffect {
+    fn dimensions(dimensions: &[PaymentDimensions]) -> Vec<PaymentDimensions> {
+        let mut dimensions = dimensions.to_vec();
+
+        if !dimensions.contains(&PaymentDimensions::AuthType) {
+            dimensions.push(PaymentDimensions::AuthType);
+        }
+
+        dimensions
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for AuthTypeEffect
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        PaymentSuccessRate
+            .load_metrics(
+                &Self::dimensions(dimensions),
+                auth,
+                filters,
+                granularity,
+                time_range,
+                pool,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use diesel_models::enums as storage_enums;
+
+    use super::*;
+    use crate::{
+        payments::{
+            accumulator::{CountAccumulator, PaymentMetricAccumulator, SuccessRateAccumulator},
+            test_utils,
+        },
+        types::DBEnumWrapper,
+    };
+
+    #[test]
+    fn test_payments_are_always_grouped_by_auth_type() {
+        assert_eq!(
+            AuthTypeEffect::dimensions(&[PaymentDimensions::Connector]),
+            vec![PaymentDimensions::Connector, PaymentDimensions::AuthType]
+        );
+        assert_eq!(
+            AuthTypeEffect::dimensions(&[PaymentDimensions::AuthType]),
+            vec![PaymentDimensions::AuthType]
+        );
+    }
+
+    #[test]
+    fn test_auth_type_effect_matches_fixture() {
+        let fixture = [
+            (
+                storage_enums::AuthenticationType::ThreeDs,
+                storage_enums::AttemptStatus::Charged,
+                45,
+            ),
+            (
+                storage_enums::AuthenticationType::ThreeDs,
+                storage_enums::AttemptStatus::Failure,
+                15,
+            ),
+            (
+                storage_enums::AuthenticationType::NoThreeDs,
+                storage_enums::AttemptStatus::Charged,
+                36,
+            ),
+            (
+                storage_enums::AuthenticationType::NoThreeDs,
+                storage_enums::AttemptStatus::AuthenticationFailed,
+                4,
+            ),
+        ];
+
+        let mut buckets: HashMap<_, (SuccessRateAccumulator, CountAccumulator)> = HashMap::new();
+        for (auth_type, status, count) in fixture {
+            let row = PaymentMetricRow {
+                authentication_type: Some(DBEnumWrapper(auth_type)),
+                status: Some(DBEnumWrapper(status)),
+                count: Some(count),
+                ..Default::default()
+            };
+            // The success rate query groups by status, which is not part of the bucket
+            let bucket = test_utils::bucket_identifier(
+                &PaymentMetricRow {
+                    authentication_type: Some(DBEnumWrapper(auth_type)),
+                    ..Default::default()
+                },
+                test_utils::time_range(),
+            );
+            let (success_rate, payment_count) = buckets.entry(bucket).or_default();
+            success_rate.add_metrics_bucket(&row);
+            payment_count.add_metrics_bucket(&row);
+        }
+
+        let mut auth_type_effect = buckets
+            .into_iter()
+            .map(|(bucket, (success_rate, payment_count))| {
+                (
+                    bucket.auth_type,
+                    payment_count.collect(),
+                    success_rate.collect(),
+                )
+            })
+            .collect::<Vec<_>>();
+        auth_type_effect.sort_by_key(|(auth_type, _, _)| auth_type.map(|i| i.to_string()));
+
+        assert_eq!(
+            auth_type_effect,
+            vec![
+                (
+                    Some(storage_enums::AuthenticationType::NoThreeDs),
+                    Some(40),
+                    Some(90.0)
+                ),
+                (
+                    Some(storage_enums::AuthenticationType::ThreeDs),
+                    Some(60),
+                    Some(75.0)
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use diesel_models::enums as storage_enums;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::{PaymentMetric, PaymentMetricRow};
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, GroupByClause, PostProcessingError, QueryBuilder, QueryFilter, SeriesBucket,
+        ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Average time successful payments take to succeed, in seconds.
+///
+/// Measured from the creation of the payment intent to the last update of its successful
+/// attempt, so that the time spent on failed attempts before a retry succeeded is included.
+/// Buckets without a successful payment have no rows, so the metric is omitted for them.
+#[derive(Default)]
+pub(super) struct AverageTimeToSuccess;
+
+impl AverageTimeToSuccess {
+    /// Query averaging the time to success of the charged attempts per requested dimension
+    fn build_query<T>(
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        let mut query_builder: QueryBuilder<T> =
+            QueryBuilder::new(AnalyticsCollection::PaymentWithIntentCreatedAt);
+
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+
+        query_builder
+            .add_select_column(Aggregate::AvgDuration {
+                start_field: "intent_created_at",
+                end_field: "modified_at",
+                alias: Some("avg_time_to_success_seconds"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        filters.set_filter_clause(&mut query_builder).switch()?;
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        query_builder
+            .add_filter_clause(
+                PaymentDimensions::PaymentStatus,
+                storage_enums::AttemptStatus::Charged,
+            )
+            .switch()?;
+
+        Ok(query_builder)
+    }
+
+    /// Bucket of a row by its dimensions, over the time bucket of the row when a granularity is
+    /// requested and over the whole time range otherwise
+    fn get_bucket(
+        i: PaymentMetricRow,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> error_stack::Result<(PaymentMetricsBucketIdentifier, PaymentMetricRow), PostProcessingError>
+    {
+        Ok((
+            PaymentMetricsBucketIdentifier::new(
+                i.currency.as_ref().map(|i| i.0),
+                i.status.as_ref().map(|i| i.0),
+                i.connector.clone(),
+                i.authentication_type.as_ref().map(|i| i.0),
+                i.payment_method.clone(),
+                i.payment_method_type.clone(),
+                i.client_source.clone(),
+                i.client_version.clone(),
+                i.profile_id.clone(),
+                i.card_funding_type.clone(),
+                i.card_bin.clone(),
+                i.device_fingerprint.clone(),
+                i.three_ds_version.clone(),
+                i.three_ds_message_version.clone(),
+                TimeRange {
+                    start_time: match (granularity, i.start_bucket) {
+                        (Some(g), Some(st)) => g.clip_to_start(st)?,
+                        _ => time_range.start_time,
+                    },
+                    end_time: granularity.as_ref().map_or_else(
+                        || Ok(time_range.end_time),
+                        |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                    )?,
+                },
+            ),
+            i,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> PaymentMetric<T> for AverageTimeToSuccess
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        Self::build_query::<T>(dimensions, auth, filters, granularity, time_range)?
+            .execute_query::<PaymentMetricRow, _>(pool)
+            .await
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)?
+            .into_iter()
+            .map(|row| Self::get_bucket(row, granularity, time_range))
+            .collect::<error_stack::Result<
+                HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+                PostProcessingError,
+            >>()
+            .change_context(MetricsError::PostProcessingFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bigdecimal::BigDecimal;
+
+    use super::*;
+    use crate::payments::{
+        accumulator::{AverageDurationAccumulator, PaymentMetricAccumulator},
+        test_utils,
+    };
+
+    fn row(avg_time_to_success_seconds: Option<i64>, count: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            count: Some(count),
+            avg_time_to_success_seconds: avg_time_to_success_seconds.map(BigDecimal::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_time_to_success_is_averaged_per_time_bucket() {
+        let rows = [
+            (row(Some(10), 3), test_utils::at(10, 15)),
+            (row(Some(30), 1), test_utils::at(10, 45)),
+            (row(Some(20), 2), test_utils::at(11, 5)),
+        ];
+
+        let mut accumulators = BTreeMap::<_, AverageDurationAccumulator>::new();
+        for (row, created_at) in rows {
+            let row = PaymentMetricRow {
+                connector: Some("stripe".to_string()),
+                start_bucket: Some(created_at),
+                ..row
+            };
+            let (bucket, row) = AverageTimeToSuccess::get_bucket(
+                row,
+                &Some(Granularity::OneHour),
+                &test_utils::time_range(),
+            )
+            .unwrap();
+            accumulators
+                .entry(bucket.time_bucket.start_time)
+                .or_default()
+                .add_metrics_bucket(&row);
+        }
+        let averages = accumulators
+            .into_iter()
+            .map(|(start_time, accumulator)| (start_time, accumulator.collect()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            averages,
+            vec![
+                (test_utils::at(10, 0), Some(15.0)),
+                (test_utils::at(11, 0), Some(20.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_average_time_to_success_is_weighted_by_payment_count() {
+        let mut accumulator = AverageDurationAccumulator::default();
+        accumulator.add_metrics_bucket(&row(Some(10), 3));
+        accumulator.add_metrics_bucket(&row(Some(30), 1));
+        accumulator.add_metrics_bucket(&row(None, 2));
+
+        assert_eq!(accumulator.collect(), Some(15.0));
+    }
+
+    #[test]
+    fn test_average_time_to_success_is_omitted_without_successful_payments() {
+        assert_eq!(AverageDurationAccumulator::default().collect(), None);
+    }
+}
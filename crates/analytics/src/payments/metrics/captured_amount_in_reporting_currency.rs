@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use diesel_models::enums as storage_enums;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::PaymentMetricRow;
+use crate::{
+    enums::AuthInfo,
+    query::{Aggregate, GroupByClause, QueryBuilder, QueryFilter, SeriesBucket, ToSql, Window},
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Captured amount per currency and day, so that each row can be converted to the reporting
+/// currency and the days with an amount that could not be converted can be reported.
+#[derive(Default)]
+pub(super) struct CapturedAmountInReportingCurrency;
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for CapturedAmountInReportingCurrency
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        let mut query_builder: QueryBuilder<T> = QueryBuilder::new(AnalyticsCollection::Payment);
+
+        let mut dimensions = dimensions.to_vec();
+        if !dimensions.contains(&PaymentDimensions::Currency) {
+            dimensions.push(PaymentDimensions::Currency);
+        }
+
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+
+        query_builder
+            .add_select_column(Aggregate::Sum {
+                field: "amount",
+                alias: Some("total"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        filters.set_filter_clause(&mut query_builder).switch()?;
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        // Rows never span more than a single day, so that days without a rate can be reported
+        Granularity::OneDay
+            .set_group_by_clause(&mut query_builder)
+            .attach_printable("Error grouping by day")
+            .switch()?;
+
+        query_builder
+            .add_filter_clause(
+                PaymentDimensions::PaymentStatus,
+                storage_enums::AttemptStatus::Charged,
+            )
+            .switch()?;
+
+        query_builder
+            .execute_query::<PaymentMetricRow, _>(pool)
+            .await
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)?
+            .into_iter()
+            .map(|i| {
+                Ok((
+                    PaymentMetricsBucketIdentifier::new(
+                        i.currency.as_ref().map(|i| i.0),
+                        None,
+                        i.connector.clone(),
+                        i.authentication_type.as_ref().map(|i| i.0),
+                        i.payment_method.clone(),
+                        i.payment_method_type.clone(),
+                        i.client_source.clone(),
+                        i.client_version.clone(),
+                        i.profile_id.clone(),
+                        i.card_funding_type.clone(),
+                        i.card_bin.clone(),
+                        i.device_fingerprint.clone(),
+                        i.three_ds_version.clone(),
+                        i.three_ds_message_version.clone(),
+                        TimeRange {
+                            start_time: match (granularity, i.start_bucket) {
+                                (Some(g), Some(st)) => g.clip_to_start(st)?,
+                                _ => time_range.start_time,
+                            },
+                            end_time: granularity.as_ref().map_or_else(
+                                || Ok(time_range.end_time),
+                                |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                            )?,
+                        },
+                    ),
+                    i,
+                ))
+            })
+            .collect::<error_stack::Result<
+                HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+                crate::query::PostProcessingError,
+            >>()
+            .change_context(MetricsError::PostProcessingFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use bigdecimal::BigDecimal;
+
+    use super::*;
+    use crate::{
+        payments::{
+            accumulator::ReportingCurrencyAmountAccumulator, test_utils::reporting_currency,
+        },
+        types::DBEnumWrapper,
+    };
+
+    fn row(currency: storage_enums::Currency, total: i64, day: u8) -> PaymentMetricRow {
+        let day = time::Date::from_calendar_date(2024, time::Month::October, day)
+            .unwrap()
+            .midnight();
+        PaymentMetricRow {
+            currency: Some(DBEnumWrapper(currency)),
+            total: Some(BigDecimal::from(total)),
+            start_bucket: Some(day),
+            end_bucket: Some(day),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_captured_amount_is_converted_with_forex_rates() {
+        let reporting_currency = reporting_currency();
+        let mut accumulator = ReportingCurrencyAmountAccumulator::default();
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::EUR, 2_000, 1),
+            &reporting_currency,
+        );
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::USD, 500, 2),
+            &reporting_currency,
+        );
+
+        assert_eq!(accumulator.collect(), (Some(2_700), None));
+    }
+
+    #[test]
+    fn test_days_with_an_amount_without_rate_are_flagged() {
+        let reporting_currency = reporting_currency();
+        let mut accumulator = ReportingCurrencyAmountAccumulator::default();
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::EUR, 2_000, 1),
+            &reporting_currency,
+        );
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::GBP, 300, 2),
+            &reporting_currency,
+        );
+
+        assert_eq!(
+            accumulator.collect(),
+            (Some(2_200), Some(vec!["2024-10-02".to_string()]))
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use time::PrimitiveDateTime;
+
+use super::{dispute_rate::DisputeRate, PaymentMetric, PaymentMetricRow};
+use crate::{
+    enums::AuthInfo,
+    query::{Aggregate, GroupByClause, ToSql, Window},
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsResult},
+};
+
+/// Ratio of disputes raised against the payments processed, per connector.
+///
+/// Counted the same way as the dispute rate, the two only differ in how they are reported: the
+/// chargeback ratio is a percentage, so that it can be compared against the thresholds of the
+/// card networks.
+#[derive(Default)]
+pub(super) struct ChargebackRatio;
+
+#[async_trait::async_trait]
+impl<T> PaymentMetric<T> for ChargebackRatio
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        DisputeRate
+            .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payments::accumulator::{
+        ChargebackRatioAccumulator, DisputeRateAccumulator, PaymentMetricAccumulator,
+    };
+
+    #[test]
+    fn test_chargeback_ratio_is_the_dispute_rate_in_percentage() {
+        let row = PaymentMetricRow {
+            connector: Some("stripe".to_string()),
+            count: Some(200),
+            dispute_count: Some(4),
+            ..Default::default()
+        };
+
+        let mut chargeback_ratio = ChargebackRatioAccumulator::default();
+        chargeback_ratio.add_metrics_bucket(&row);
+        let mut dispute_rate = DisputeRateAccumulator::default();
+        dispute_rate.add_metrics_bucket(&row);
+
+        assert_eq!(chargeback_ratio.collect(), Some(2.0));
+        assert_eq!(dispute_rate.collect(), Some(0.02));
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::{PaymentMetric, PaymentMetricRow};
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, DurationInMillis, FilterTypes, GroupByClause, PostProcessingError, QueryBuilder,
+        QueryFilter, SeriesBucket, ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Average and 95th percentile time connectors take to process payment attempts.
+///
+/// Measured in milliseconds from the creation of the attempt to the first response received from
+/// the connector. Attempts are always grouped by connector, whether or not it was requested as a
+/// dimension. Attempts without a connector response are left out instead of counting as instant.
+#[derive(Default)]
+pub(super) struct ConnectorLatency;
+
+impl ConnectorLatency {
+    /// Query counting the attempts that got a connector response per latency, connector and
+    /// requested dimension
+    fn build_query<T>(
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+        DurationInMillis<&'static str>: ToSql<T>,
+    {
+        let mut query_builder: QueryBuilder<T> = QueryBuilder::new(AnalyticsCollection::Payment);
+        let mut dimensions = dimensions.to_vec();
+
+        if !dimensions.contains(&PaymentDimensions::Connector) {
+            dimensions.push(PaymentDimensions::Connector);
+        }
+
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+
+        query_builder
+            .add_select_column(DurationInMillis {
+                start_field: "created_at",
+                end_field: "connector_response_received_at",
+                alias: Some("connector_latency"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        filters.set_filter_clause(&mut query_builder).switch()?;
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        query_builder
+            .add_custom_filter_clause(PaymentDimensions::Connector, "NULL", FilterTypes::IsNotNull)
+            .switch()?;
+        query_builder
+            .add_custom_filter_clause(
+                "connector_response_received_at",
+                "NULL",
+                FilterTypes::IsNotNull,
+            )
+            .switch()?;
+
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+
+        // The average and percentile are computed from the count of attempts per latency so that
+        // the rows of several connectors or time buckets can be combined
+        query_builder
+            .add_group_by_clause("connector_latency")
+            .attach_printable("Error grouping by connector latency")
+            .switch()?;
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        Ok(query_builder)
+    }
+
+    /// Bucket of a row by its dimensions, over the time bucket of the row when a granularity is
+    /// requested and over the whole time range otherwise
+    fn get_bucket(
+        i: PaymentMetricRow,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> error_stack::Result<(PaymentMetricsBucketIdentifier, PaymentMetricRow), PostProcessingError>
+    {
+        Ok((
+            PaymentMetricsBucketIdentifier::new(
+                i.currency.as_ref().map(|i| i.0),
+                i.status.as_ref().map(|i| i.0),
+                i.connector.clone(),
+                i.authentication_type.as_ref().map(|i| i.0),
+                i.payment_method.clone(),
+                i.payment_method_type.clone(),
+                i.client_source.clone(),
+                i.client_version.clone(),
+                i.profile_id.clone(),
+                i.card_funding_type.clone(),
+                i.card_bin.clone(),
+                i.device_fingerprint.clone(),
+                i.three_ds_version.clone(),
+                i.three_ds_message_version.clone(),
+                TimeRange {
+                    start_time: match (granularity, i.start_bucket) {
+                        (Some(g), Some(st)) => g.clip_to_start(st)?,
+                        _ => time_range.start_time,
+                    },
+                    end_time: granularity.as_ref().map_or_else(
+                        || Ok(time_range.end_time),
+                        |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                    )?,
+                },
+            ),
+            i,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> PaymentMetric<T> for ConnectorLatency
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+    DurationInMillis<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        Self::build_query::<T>(dimensions, auth, filters, granularity, time_range)?
+            .execute_query::<PaymentMetricRow, _>(pool)
+            .await
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)?
+            .into_iter()
+            .map(|row| Self::get_bucket(row, granularity, time_range))
+            .collect::<error_stack::Result<
+                HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+                PostProcessingError,
+            >>()
+            .change_context(MetricsError::PostProcessingFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::payments::{
+        accumulator::{ConnectorLatencyAccumulator, PaymentMetricAccumulator},
+        test_utils,
+    };
+
+    fn row(connector: &str, latency: Option<i64>, count: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            connector: Some(connector.to_string()),
+            count: Some(count),
+            connector_latency: latency,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_latencies_are_bucketed_per_connector_and_time_bucket() {
+        let rows = [
+            (row("stripe", Some(120), 3), test_utils::at(10, 5)),
+            (row("stripe", Some(180), 1), test_utils::at(10, 40)),
+            (row("stripe", Some(900), 1), test_utils::at(11, 10)),
+            (row("adyen", Some(2400), 1), test_utils::at(10, 20)),
+        ];
+
+        let mut accumulators = BTreeMap::<_, ConnectorLatencyAccumulator>::new();
+        for (row, created_at) in rows {
+            let row = PaymentMetricRow {
+                start_bucket: Some(created_at),
+                ..row
+            };
+            let (bucket, row) = ConnectorLatency::get_bucket(
+                row,
+                &Some(Granularity::OneHour),
+                &test_utils::time_range(),
+            )
+            .unwrap();
+            accumulators
+                .entry((bucket.connector, bucket.time_bucket.start_time))
+                .or_default()
+                .add_metrics_bucket(&row);
+        }
+        let latencies = accumulators
+            .into_iter()
+            .map(|(bucket, accumulator)| (bucket, accumulator.collect()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            latencies,
+            vec![
+                (
+                    (Some("adyen".to_string()), test_utils::at(10, 0)),
+                    (Some(2400.0), Some(2400.0))
+                ),
+                (
+                    (Some("stripe".to_string()), test_utils::at(10, 0)),
+                    (Some(135.0), Some(180.0))
+                ),
+                (
+                    (Some("stripe".to_string()), test_utils::at(11, 0)),
+                    (Some(900.0), Some(900.0))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_p95_is_computed_across_rows() {
+        // 20 attempts, 19 of them fast on one connector and a single slow one on another. The
+        // 95th percentile is the 19th fastest attempt, not the highest p95 of either connector.
+        let mut accumulator = ConnectorLatencyAccumulator::default();
+        accumulator.add_metrics_bucket(&row("stripe", Some(120), 10));
+        accumulator.add_metrics_bucket(&row("stripe", Some(180), 9));
+        accumulator.add_metrics_bucket(&row("adyen", Some(2400), 1));
+
+        assert_eq!(accumulator.collect(), (Some(261.0), Some(180.0)));
+    }
+
+    #[test]
+    fn test_sub_second_latencies_are_kept() {
+        let mut accumulator = ConnectorLatencyAccumulator::default();
+        accumulator.add_metrics_bucket(&row("stripe", Some(250), 3));
+        accumulator.add_metrics_bucket(&row("stripe", Some(650), 1));
+
+        assert_eq!(accumulator.collect(), (Some(350.0), Some(650.0)));
+    }
+
+    #[test]
+    fn test_attempts_without_latency_are_excluded() {
+        let mut accumulator = ConnectorLatencyAccumulator::default();
+        accumulator.add_metrics_bucket(&row("stripe", None, 4));
+
+        assert_eq!(accumulator.collect(), (None, None));
+    }
+}
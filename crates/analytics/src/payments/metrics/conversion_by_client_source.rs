@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use time::PrimitiveDateTime;
+
+use super::{success_rate::PaymentSuccessRate, PaymentMetricRow};
+use crate::{
+    enums::AuthInfo,
+    query::{Aggregate, GroupByClause, ToSql, Window},
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsResult},
+};
+
+/// Client source reported for payments whose attempt does not carry one
+pub const UNKNOWN_CLIENT_SOURCE: &str = "unknown";
+
+/// Volume and success rate of payments split by the client the payment was made from, such as
+/// the web SDK, the mobile SDKs or a direct API call.
+///
+/// Payments are always grouped by client source, whether or not it was requested as a
+/// dimension. Payments without a client source are bucketed as `unknown`.
+#[derive(Default)]
+pub(super) struct ConversionByClientSource;
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for ConversionByClientSource
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        let mut dimensions = dimensions.to_vec();
+
+        if !dimensions.contains(&PaymentDimensions::ClientSource) {
+            dimensions.push(PaymentDimensions::ClientSource);
+        }
+
+        Ok(PaymentSuccessRate
+            .load_metrics(&dimensions, auth, filters, granularity, time_range, pool)
+            .await?
+            .into_iter()
+            .map(bucket_unknown_client_source)
+            .collect())
+    }
+}
+
+fn bucket_unknown_client_source(
+    (mut identifier, mut row): (PaymentMetricsBucketIdentifier, PaymentMetricRow),
+) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+    if identifier
+        .client_source
+        .as_deref()
+        .map_or(true, |client_source| client_source.trim().is_empty())
+    {
+        identifier.client_source = Some(UNKNOWN_CLIENT_SOURCE.to_string());
+        row.client_source = Some(UNKNOWN_CLIENT_SOURCE.to_string());
+    }
+    (identifier, row)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use diesel_models::enums as storage_enums;
+
+    use super::*;
+    use crate::{
+        payments::{
+            accumulator::{CountAccumulator, PaymentMetricAccumulator, SuccessRateAccumulator},
+            test_utils,
+        },
+        types::DBEnumWrapper,
+    };
+
+    fn bucket(
+        client_source: Option<&str>,
+        status: storage_enums::AttemptStatus,
+        count: i64,
+    ) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+        test_utils::bucket(PaymentMetricRow {
+            status: Some(DBEnumWrapper(status)),
+            client_source: client_source.map(ToString::to_string),
+            count: Some(count),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_conversion_is_split_by_client_source() {
+        let buckets = vec![
+            bucket(Some("web"), storage_enums::AttemptStatus::Charged, 3),
+            bucket(Some("web"), storage_enums::AttemptStatus::Failure, 1),
+            bucket(Some("ios"), storage_enums::AttemptStatus::Charged, 1),
+            bucket(Some("ios"), storage_enums::AttemptStatus::Failure, 1),
+            bucket(None, storage_enums::AttemptStatus::Charged, 2),
+            bucket(Some(""), storage_enums::AttemptStatus::Failure, 2),
+        ];
+
+        let mut conversion: HashMap<String, (SuccessRateAccumulator, CountAccumulator)> =
+            HashMap::new();
+        for (identifier, row) in buckets.into_iter().map(bucket_unknown_client_source) {
+            let (success_rate, payment_count) = conversion
+                .entry(identifier.client_source.unwrap_or_default())
+                .or_default();
+            success_rate.add_metrics_bucket(&row);
+            payment_count.add_metrics_bucket(&row);
+        }
+        let mut conversion = conversion
+            .into_iter()
+            .map(|(client_source, (success_rate, payment_count))| {
+                (
+                    client_source,
+                    success_rate.collect(),
+                    payment_count.collect(),
+                )
+            })
+            .collect::<Vec<_>>();
+        conversion.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            conversion,
+            vec![
+                ("ios".to_string(), Some(50.0), Some(2)),
+                (UNKNOWN_CLIENT_SOURCE.to_string(), Some(50.0), Some(4)),
+                ("web".to_string(), Some(75.0), Some(4)),
+            ]
+        );
+    }
+}
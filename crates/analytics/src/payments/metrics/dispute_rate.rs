@@ -0,0 +1,624 @@
+use std::collections::{HashMap, HashSet};
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use diesel_models::enums as storage_enums;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::PaymentMetricRow;
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, FilterTypes, GroupByClause, PostProcessingError, QueryBuilder, QueryFilter,
+        SeriesBucket, ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Ratio of disputes raised to successful payments, per connector and requested dimension.
+///
+/// Disputes are joined to the payment attempt they were raised against, so that they can be
+/// counted per payment method along with the connector, currency and profile they record. Both
+/// are counted per bucket and combined. Buckets without a successful payment are left out,
+/// including those that only have disputes.
+#[derive(Default)]
+pub(super) struct DisputeRate;
+
+/// Dimensions recorded on both payments and disputes joined to their payment, the only ones the
+/// two collections can be grouped by
+const DISPUTE_DIMENSIONS: [PaymentDimensions; 5] = [
+    PaymentDimensions::Connector,
+    PaymentDimensions::PaymentMethod,
+    PaymentDimensions::PaymentMethodType,
+    PaymentDimensions::Currency,
+    PaymentDimensions::ProfileId,
+];
+
+/// Whether a filter is set on a column that disputes joined to their payment do not have, other
+/// than the connector, payment method, currency and profile
+fn has_unsupported_filter(filters: &PaymentFilters) -> bool {
+    let PaymentFilters {
+        currency: _,
+        connector: _,
+        payment_method: _,
+        payment_method_type: _,
+        profile_id: _,
+        status,
+        auth_type,
+        client_source,
+        client_version,
+        card_network,
+        device_fingerprint,
+        three_ds_version,
+        three_ds_message_version,
+        card_funding_type,
+        card_bin,
+    } = filters;
+
+    !(status.is_empty()
+        && auth_type.is_empty()
+        && client_source.is_empty()
+        && client_version.is_empty()
+        && card_network.is_empty()
+        && device_fingerprint.is_empty()
+        && three_ds_version.is_empty()
+        && three_ds_message_version.is_empty()
+        && card_funding_type.is_empty()
+        && card_bin.is_empty())
+}
+
+impl DisputeRate {
+    /// Query counting the rows of the collection per connector, requested dimension and time
+    /// bucket, payments are only counted if they are in `payment_status` when it is given
+    fn build_count_query<T>(
+        collection: AnalyticsCollection,
+        payment_status: Option<storage_enums::AttemptStatus>,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        if dimensions
+            .iter()
+            .any(|dimension| !DISPUTE_DIMENSIONS.contains(dimension))
+        {
+            return Err(
+                error_stack::report!(MetricsError::NotImplemented).attach_printable(
+                    "Disputes can only be grouped by connector, payment method, currency and profile",
+                ),
+            );
+        }
+        if has_unsupported_filter(filters) {
+            return Err(
+                error_stack::report!(MetricsError::NotImplemented).attach_printable(
+                    "Disputes can only be filtered by connector, payment method, currency and profile",
+                ),
+            );
+        }
+
+        let mut query_builder: QueryBuilder<T> = QueryBuilder::new(collection);
+        let mut dimensions = dimensions.to_vec();
+        if !dimensions.contains(&PaymentDimensions::Connector) {
+            dimensions.insert(0, PaymentDimensions::Connector);
+        }
+
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        if !filters.connector.is_empty() {
+            query_builder
+                .add_filter_in_range_clause(PaymentDimensions::Connector, &filters.connector)
+                .attach_printable("Error adding connector filter")
+                .switch()?;
+        }
+        if !filters.payment_method.is_empty() {
+            query_builder
+                .add_filter_in_range_clause(
+                    PaymentDimensions::PaymentMethod,
+                    &filters.payment_method,
+                )
+                .attach_printable("Error adding payment method filter")
+                .switch()?;
+        }
+        if !filters.payment_method_type.is_empty() {
+            query_builder
+                .add_filter_in_range_clause(
+                    PaymentDimensions::PaymentMethodType,
+                    &filters.payment_method_type,
+                )
+                .attach_printable("Error adding payment method type filter")
+                .switch()?;
+        }
+        if !filters.currency.is_empty() {
+            query_builder
+                .add_filter_in_range_clause(PaymentDimensions::Currency, &filters.currency)
+                .attach_printable("Error adding currency filter")
+                .switch()?;
+        }
+        if !filters.profile_id.is_empty() {
+            query_builder
+                .add_filter_in_range_clause(PaymentDimensions::ProfileId, &filters.profile_id)
+                .attach_printable("Error adding profile id filter")
+                .switch()?;
+        }
+
+        if let Some(payment_status) = payment_status {
+            query_builder
+                .add_filter_clause(PaymentDimensions::PaymentStatus, payment_status)
+                .attach_printable("Error adding payment status filter")
+                .switch()?;
+        }
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        query_builder
+            .add_custom_filter_clause(PaymentDimensions::Connector, "NULL", FilterTypes::IsNotNull)
+            .switch()?;
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        Ok(query_builder)
+    }
+
+    /// Count the rows of the collection per connector, requested dimension and time bucket,
+    /// payments are only counted if they are in `payment_status` when it is given
+    #[allow(clippy::too_many_arguments)]
+    async fn load_buckets<T>(
+        collection: AnalyticsCollection,
+        payment_status: Option<storage_enums::AttemptStatus>,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<Vec<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>>
+    where
+        T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        Self::build_count_query(
+            collection,
+            payment_status,
+            dimensions,
+            auth,
+            filters,
+            granularity,
+            time_range,
+        )?
+        .execute_query::<PaymentMetricRow, _>(pool)
+        .await
+        .switch()?
+        .change_context(MetricsError::QueryExecutionFailure)?
+        .into_iter()
+        .map(|row| {
+            Self::get_bucket_identifier(&row, granularity, time_range).map(|bucket| (bucket, row))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .change_context(MetricsError::PostProcessingFailure)
+    }
+
+    fn get_bucket_identifier(
+        row: &PaymentMetricRow,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> error_stack::Result<PaymentMetricsBucketIdentifier, PostProcessingError> {
+        Ok(PaymentMetricsBucketIdentifier::new(
+            row.currency.as_ref().map(|i| i.0),
+            None,
+            row.connector.clone(),
+            None,
+            row.payment_method.clone(),
+            row.payment_method_type.clone(),
+            None,
+            None,
+            row.profile_id.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            TimeRange {
+                start_time: match (granularity, row.start_bucket) {
+                    (Some(g), Some(st)) => g.clip_to_start(st)?,
+                    _ => time_range.start_time,
+                },
+                end_time: granularity.as_ref().map_or_else(
+                    || Ok(time_range.end_time),
+                    |g| row.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                )?,
+            },
+        ))
+    }
+}
+
+/// Attach the dispute count of each bucket to the successful payment row of the bucket, dropping
+/// buckets without a successful payment
+fn combine_dispute_counts(
+    payment_buckets: Vec<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+    dispute_buckets: Vec<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+) -> HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)> {
+    let mut dispute_counts: HashMap<PaymentMetricsBucketIdentifier, i64> = HashMap::new();
+    for (bucket, row) in dispute_buckets {
+        *dispute_counts.entry(bucket).or_default() += row.count.unwrap_or_default();
+    }
+
+    payment_buckets
+        .into_iter()
+        .filter(|(_, row)| row.count.is_some_and(|count| count > 0))
+        .map(|(bucket, mut row)| {
+            row.dispute_count = Some(dispute_counts.get(&bucket).copied().unwrap_or_default());
+            (bucket, row)
+        })
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for DisputeRate
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        let payment_buckets = Self::load_buckets(
+            AnalyticsCollection::Payment,
+            Some(storage_enums::AttemptStatus::Charged),
+            dimensions,
+            auth,
+            filters,
+            granularity,
+            time_range,
+            pool,
+        )
+        .await?;
+        let dispute_buckets = Self::load_buckets(
+            AnalyticsCollection::DisputeWithPayment,
+            None,
+            dimensions,
+            auth,
+            filters,
+            granularity,
+            time_range,
+            pool,
+        )
+        .await?;
+
+        Ok(combine_dispute_counts(payment_buckets, dispute_buckets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_enums::{Currency, PaymentMethod};
+
+    use super::*;
+    use crate::{
+        clickhouse::ClickhouseClient,
+        payments::{
+            accumulator::{DisputeRateAccumulator, PaymentMetricAccumulator},
+            test_utils,
+        },
+        types::DBEnumWrapper,
+    };
+
+    fn count_query(
+        collection: AnalyticsCollection,
+        payment_status: Option<storage_enums::AttemptStatus>,
+        dimensions: &[PaymentDimensions],
+        filters: &PaymentFilters,
+    ) -> MetricsResult<String> {
+        Ok(DisputeRate::build_count_query::<ClickhouseClient>(
+            collection,
+            payment_status,
+            dimensions,
+            &test_utils::org_auth(),
+            filters,
+            &None,
+            &test_utils::time_range(),
+        )?
+        .build_query()
+        .unwrap())
+    }
+
+    fn bucket(connector: &str, count: i64) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+        bucket_of(
+            PaymentMetricRow {
+                connector: Some(connector.to_string()),
+                ..Default::default()
+            },
+            count,
+        )
+    }
+
+    fn bucket_of(
+        row: PaymentMetricRow,
+        count: i64,
+    ) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+        let row = PaymentMetricRow {
+            count: Some(count),
+            ..row
+        };
+        let bucket =
+            DisputeRate::get_bucket_identifier(&row, &None, &test_utils::time_range()).unwrap();
+        (bucket, row)
+    }
+
+    fn hourly_bucket(
+        row: PaymentMetricRow,
+        count: i64,
+        created_at: PrimitiveDateTime,
+    ) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+        let row = PaymentMetricRow {
+            connector: Some("stripe".to_string()),
+            count: Some(count),
+            start_bucket: Some(created_at),
+            ..row
+        };
+        let bucket = DisputeRate::get_bucket_identifier(
+            &row,
+            &Some(Granularity::OneHour),
+            &test_utils::time_range(),
+        )
+        .unwrap();
+        (bucket, row)
+    }
+
+    fn currency_bucket(
+        currency: Currency,
+        count: i64,
+    ) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+        bucket_of(
+            PaymentMetricRow {
+                connector: Some("stripe".to_string()),
+                currency: Some(DBEnumWrapper(currency)),
+                ..Default::default()
+            },
+            count,
+        )
+    }
+
+    fn payment_method_bucket(
+        payment_method: PaymentMethod,
+        count: i64,
+    ) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+        bucket_of(
+            PaymentMetricRow {
+                connector: Some("stripe".to_string()),
+                payment_method: Some(payment_method.to_string()),
+                ..Default::default()
+            },
+            count,
+        )
+    }
+
+    fn dispute_rates<K: Ord>(
+        buckets: HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+        key: impl Fn(PaymentMetricsBucketIdentifier) -> K,
+    ) -> Vec<(K, Option<f64>)> {
+        let mut dispute_rates = buckets
+            .into_iter()
+            .map(|(bucket, row)| {
+                let mut accumulator = DisputeRateAccumulator::default();
+                accumulator.add_metrics_bucket(&row);
+                (key(bucket), accumulator.collect())
+            })
+            .collect::<Vec<_>>();
+        dispute_rates.sort_by(|a, b| a.0.cmp(&b.0));
+        dispute_rates
+    }
+
+    #[test]
+    fn test_dispute_rate_is_disputes_per_successful_payment() {
+        let buckets = combine_dispute_counts(
+            vec![bucket("adyen", 50), bucket("stripe", 200)],
+            vec![bucket("stripe", 3), bucket("stripe", 1)],
+        );
+
+        assert_eq!(
+            dispute_rates(buckets, |bucket| bucket.connector),
+            vec![
+                (Some("adyen".to_string()), Some(0.0)),
+                (Some("stripe".to_string()), Some(0.02)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_buckets_without_successful_payments_are_suppressed() {
+        let buckets = combine_dispute_counts(
+            vec![bucket("stripe", 10), bucket("adyen", 0)],
+            vec![
+                bucket("adyen", 2),
+                bucket("checkout", 4),
+                bucket("stripe", 1),
+            ],
+        );
+
+        assert_eq!(
+            dispute_rates(buckets, |bucket| bucket.connector),
+            vec![(Some("stripe".to_string()), Some(0.1))]
+        );
+    }
+
+    #[test]
+    fn test_successful_payments_share_the_bucket_of_their_disputes() {
+        // Payments are counted with their status, disputes are not
+        let charged = PaymentMetricRow {
+            status: Some(DBEnumWrapper(storage_enums::AttemptStatus::Charged)),
+            ..Default::default()
+        };
+        let buckets = combine_dispute_counts(
+            vec![bucket_of(
+                PaymentMetricRow {
+                    connector: Some("stripe".to_string()),
+                    ..charged
+                },
+                50,
+            )],
+            vec![bucket("stripe", 1)],
+        );
+
+        assert!(buckets.iter().all(|(bucket, _)| bucket.status.is_none()));
+        assert_eq!(
+            dispute_rates(buckets, |bucket| bucket.connector),
+            vec![(Some("stripe".to_string()), Some(0.02))]
+        );
+    }
+
+    #[test]
+    fn test_disputes_are_counted_in_the_time_bucket_they_were_raised_in() {
+        let buckets = combine_dispute_counts(
+            vec![
+                hourly_bucket(PaymentMetricRow::default(), 100, test_utils::at(10, 5)),
+                hourly_bucket(PaymentMetricRow::default(), 50, test_utils::at(11, 20)),
+            ],
+            vec![
+                hourly_bucket(PaymentMetricRow::default(), 2, test_utils::at(10, 50)),
+                hourly_bucket(PaymentMetricRow::default(), 1, test_utils::at(12, 0)),
+            ],
+        );
+
+        assert_eq!(
+            dispute_rates(buckets, |bucket| bucket.time_bucket.start_time),
+            vec![
+                (test_utils::at(10, 0), Some(0.02)),
+                (test_utils::at(11, 0), Some(0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dimensions_and_filters_not_recorded_on_disputes_are_rejected() {
+        let dimension_error = count_query(
+            AnalyticsCollection::DisputeWithPayment,
+            None,
+            &[PaymentDimensions::AuthType],
+            &PaymentFilters::default(),
+        )
+        .unwrap_err();
+        let filter_error = count_query(
+            AnalyticsCollection::DisputeWithPayment,
+            None,
+            &[],
+            &PaymentFilters {
+                status: vec![storage_enums::AttemptStatus::Failure],
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+
+        for error in [dimension_error, filter_error] {
+            assert!(matches!(
+                error.current_context(),
+                MetricsError::NotImplemented
+            ));
+        }
+    }
+
+    #[test]
+    fn test_dispute_rate_is_split_by_currency() {
+        let buckets = combine_dispute_counts(
+            vec![
+                currency_bucket(Currency::USD, 100),
+                currency_bucket(Currency::EUR, 50),
+            ],
+            vec![currency_bucket(Currency::USD, 2)],
+        );
+
+        assert_eq!(
+            dispute_rates(buckets, |bucket| bucket
+                .currency
+                .map(|currency| currency.to_string())),
+            vec![
+                (Some("EUR".to_string()), Some(0.0)),
+                (Some("USD".to_string()), Some(0.02)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dispute_rate_is_split_by_payment_method() {
+        let buckets = combine_dispute_counts(
+            vec![
+                payment_method_bucket(PaymentMethod::Card, 100),
+                payment_method_bucket(PaymentMethod::Wallet, 20),
+            ],
+            vec![
+                payment_method_bucket(PaymentMethod::Card, 1),
+                payment_method_bucket(PaymentMethod::Wallet, 2),
+            ],
+        );
+
+        assert_eq!(
+            dispute_rates(buckets, |bucket| bucket.payment_method),
+            vec![
+                (Some("card".to_string()), Some(0.01)),
+                (Some("wallet".to_string()), Some(0.1)),
+            ]
+        );
+    }
+}
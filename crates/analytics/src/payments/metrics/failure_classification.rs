@@ -0,0 +1,323 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use diesel_models::enums as storage_enums;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::{PaymentMetric, PaymentMetricRow};
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, GroupByClause, PostProcessingError, QueryBuilder, QueryFilter, SeriesBucket,
+        ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Reason a failed payment attempt failed for, derived from the unified error code it was
+/// stored with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// Declined by the bank that issued the payment method
+    IssuerDecline,
+    /// Failed because of an error at the connector or in the request
+    GatewayError,
+    /// Blocked as potentially fraudulent
+    FraudBlock,
+    /// Unified code not present in `UNIFIED_CODE_CLASSES`
+    Unknown,
+}
+
+/// Class of each unified error code. The error codes of every connector are mapped to a unified
+/// code by the gateway status map when the attempt fails, so new connectors and error codes are
+/// classified by adding them there. Codes that are not listed, including the default unified
+/// code, are classified as `FailureClass::Unknown`.
+const UNIFIED_CODE_CLASSES: &[(&str, FailureClass)] = &[
+    // Issue with the payment method details
+    ("UE_1000", FailureClass::IssuerDecline),
+    // Issue with the payment request
+    ("UE_2000", FailureClass::GatewayError),
+    // Technical issue at the connector
+    ("UE_3000", FailureClass::GatewayError),
+    // Declined by a fraud or risk check
+    ("UE_4000", FailureClass::FraudBlock),
+];
+
+/// Classify a failed payment attempt by the unified error code it was stored with
+pub fn classify_failure(unified_code: Option<&str>) -> FailureClass {
+    unified_code
+        .and_then(|unified_code| {
+            UNIFIED_CODE_CLASSES
+                .iter()
+                .find(|(code, _)| code.eq_ignore_ascii_case(unified_code.trim()))
+        })
+        .map(|(_, class)| *class)
+        .unwrap_or(FailureClass::Unknown)
+}
+
+/// Number of failed payment attempts per failure class: declined by the issuer, failed at the
+/// connector or blocked as fraud.
+#[derive(Default)]
+pub(super) struct FailureClassification;
+
+impl FailureClassification {
+    /// Query counting the failed attempts per unified error code and requested dimension
+    fn build_query<T>(
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        let mut query_builder: QueryBuilder<T> = QueryBuilder::new(AnalyticsCollection::Payment);
+
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+        query_builder.add_select_column("unified_code").switch()?;
+
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        filters.set_filter_clause(&mut query_builder).switch()?;
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        query_builder
+            .add_filter_clause(
+                PaymentDimensions::PaymentStatus,
+                storage_enums::AttemptStatus::Failure,
+            )
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+        query_builder
+            .add_group_by_clause("unified_code")
+            .attach_printable("Error grouping by unified code")
+            .switch()?;
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        Ok(query_builder)
+    }
+
+    /// Bucket of a row by its dimensions, over the time bucket of the row when a granularity is
+    /// requested and over the whole time range otherwise
+    fn get_bucket(
+        i: PaymentMetricRow,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> error_stack::Result<(PaymentMetricsBucketIdentifier, PaymentMetricRow), PostProcessingError>
+    {
+        Ok((
+            PaymentMetricsBucketIdentifier::new(
+                i.currency.as_ref().map(|i| i.0),
+                i.status.as_ref().map(|i| i.0),
+                i.connector.clone(),
+                i.authentication_type.as_ref().map(|i| i.0),
+                i.payment_method.clone(),
+                i.payment_method_type.clone(),
+                i.client_source.clone(),
+                i.client_version.clone(),
+                i.profile_id.clone(),
+                i.card_funding_type.clone(),
+                i.card_bin.clone(),
+                i.device_fingerprint.clone(),
+                i.three_ds_version.clone(),
+                i.three_ds_message_version.clone(),
+                TimeRange {
+                    start_time: match (granularity, i.start_bucket) {
+                        (Some(g), Some(st)) => g.clip_to_start(st)?,
+                        _ => time_range.start_time,
+                    },
+                    end_time: granularity.as_ref().map_or_else(
+                        || Ok(time_range.end_time),
+                        |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                    )?,
+                },
+            ),
+            i,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> PaymentMetric<T> for FailureClassification
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        Self::build_query::<T>(dimensions, auth, filters, granularity, time_range)?
+            .execute_query::<PaymentMetricRow, _>(pool)
+            .await
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)?
+            .into_iter()
+            .map(|row| Self::get_bucket(row, granularity, time_range))
+            .collect::<error_stack::Result<
+                HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+                PostProcessingError,
+            >>()
+            .change_context(MetricsError::PostProcessingFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::payments::{
+        accumulator::{FailureClassificationAccumulator, PaymentMetricAccumulator},
+        test_utils,
+    };
+
+    fn row(unified_code: Option<&str>, count: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            unified_code: unified_code.map(ToString::to_string),
+            count: Some(count),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unified_codes_of_a_bucket_are_counted_together() {
+        let rows = [
+            ("card", Some("UE_1000"), 5),
+            ("card", Some("UE_3000"), 2),
+            ("wallet", Some("UE_4000"), 1),
+            ("wallet", None, 2),
+        ];
+
+        let mut accumulators = BTreeMap::<_, FailureClassificationAccumulator>::new();
+        for (payment_method, unified_code, count) in rows {
+            let row = PaymentMetricRow {
+                payment_method: Some(payment_method.to_string()),
+                ..row(unified_code, count)
+            };
+            let (bucket, row) =
+                FailureClassification::get_bucket(row, &None, &test_utils::time_range()).unwrap();
+            accumulators
+                .entry(bucket.payment_method)
+                .or_default()
+                .add_metrics_bucket(&row);
+        }
+        let failures = accumulators
+            .into_iter()
+            .map(|(payment_method, accumulator)| {
+                let failures = accumulator.collect().unwrap();
+                (
+                    payment_method,
+                    failures.issuer_decline,
+                    failures.gateway_error,
+                    failures.fraud_block,
+                    failures.unknown,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            failures,
+            vec![
+                (Some("card".to_string()), 5, 2, 0, 0),
+                (Some("wallet".to_string()), 0, 0, 1, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failures_are_classified_by_unified_code() {
+        assert_eq!(
+            classify_failure(Some("UE_1000")),
+            FailureClass::IssuerDecline
+        );
+        assert_eq!(
+            classify_failure(Some(" ue_4000 ")),
+            FailureClass::FraudBlock
+        );
+        assert_eq!(
+            classify_failure(Some("UE_3000")),
+            FailureClass::GatewayError
+        );
+        assert_eq!(classify_failure(Some("UE_000")), FailureClass::Unknown);
+        assert_eq!(classify_failure(None), FailureClass::Unknown);
+    }
+
+    #[test]
+    fn test_failures_are_counted_per_class() {
+        // Failed attempts as they are stored: Stripe declines are stored with the error code
+        // `card_declined` and classified by the unified code the gateway status map assigned,
+        // attempts without a mapping keep the default unified code or none at all
+        let mut accumulator = FailureClassificationAccumulator::default();
+        accumulator.add_metrics_bucket(&row(Some("UE_1000"), 5));
+        accumulator.add_metrics_bucket(&row(Some("UE_2000"), 1));
+        accumulator.add_metrics_bucket(&row(Some("UE_3000"), 1));
+        accumulator.add_metrics_bucket(&row(Some("UE_4000"), 1));
+        accumulator.add_metrics_bucket(&row(Some("UE_000"), 3));
+        accumulator.add_metrics_bucket(&row(None, 1));
+
+        let failures = accumulator.collect().unwrap();
+
+        assert_eq!(failures.issuer_decline, 5);
+        assert_eq!(failures.gateway_error, 2);
+        assert_eq!(failures.fraud_block, 1);
+        assert_eq!(failures.unknown, 4);
+    }
+}
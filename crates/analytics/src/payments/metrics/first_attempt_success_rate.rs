@@ -0,0 +1,263 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::PaymentMetricRow;
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, GroupByClause, PostProcessingError, QueryBuilder, QueryFilter, SeriesBucket,
+        ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Success rate of the first attempt of payments, leaving out the effect of retries.
+///
+/// Only first attempts are counted, which are the attempts created before any other attempt of
+/// their payment. Buckets that only have retried attempts, such as the connector a payment was
+/// retried on, are left out of the metric.
+#[derive(Default)]
+pub(super) struct FirstAttemptSuccessRate;
+
+impl FirstAttemptSuccessRate {
+    /// Query counting the first attempts per requested dimension and status
+    fn build_query<T>(
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        let mut query_builder: QueryBuilder<T> =
+            QueryBuilder::new(AnalyticsCollection::PaymentWithFirstAttempt);
+        let mut dimensions = dimensions.to_vec();
+
+        dimensions.push(PaymentDimensions::PaymentStatus);
+
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        filters.set_filter_clause(&mut query_builder).switch()?;
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        query_builder
+            .add_bool_filter_clause("first_attempt", &true)
+            .attach_printable("Error filtering first attempts")
+            .switch()?;
+
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        Ok(query_builder)
+    }
+
+    /// Bucket of a row by its dimensions other than the status, so that the first attempts of
+    /// all statuses are counted together
+    fn get_bucket(
+        i: PaymentMetricRow,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> error_stack::Result<(PaymentMetricsBucketIdentifier, PaymentMetricRow), PostProcessingError>
+    {
+        Ok((
+            PaymentMetricsBucketIdentifier::new(
+                i.currency.as_ref().map(|i| i.0),
+                None,
+                i.connector.clone(),
+                i.authentication_type.as_ref().map(|i| i.0),
+                i.payment_method.clone(),
+                i.payment_method_type.clone(),
+                i.client_source.clone(),
+                i.client_version.clone(),
+                i.profile_id.clone(),
+                i.card_funding_type.clone(),
+                i.card_bin.clone(),
+                i.device_fingerprint.clone(),
+                i.three_ds_version.clone(),
+                i.three_ds_message_version.clone(),
+                TimeRange {
+                    start_time: match (granularity, i.start_bucket) {
+                        (Some(g), Some(st)) => g.clip_to_start(st)?,
+                        _ => time_range.start_time,
+                    },
+                    end_time: granularity.as_ref().map_or_else(
+                        || Ok(time_range.end_time),
+                        |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                    )?,
+                },
+            ),
+            i,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for FirstAttemptSuccessRate
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        let mut query_builder =
+            Self::build_query::<T>(dimensions, auth, filters, granularity, time_range)?;
+
+        query_builder
+            .execute_query::<PaymentMetricRow, _>(pool)
+            .await
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)?
+            .into_iter()
+            .map(|row| Self::get_bucket(row, granularity, time_range))
+            .collect::<error_stack::Result<
+                HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+                PostProcessingError,
+            >>()
+            .change_context(MetricsError::PostProcessingFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use diesel_models::enums as storage_enums;
+
+    use super::*;
+    use crate::{
+        payments::{
+            accumulator::{PaymentMetricAccumulator, SuccessRateAccumulator},
+            test_utils,
+        },
+        types::DBEnumWrapper,
+    };
+
+    fn first_attempts(status: storage_enums::AttemptStatus, count: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            status: Some(DBEnumWrapper(status)),
+            count: Some(count),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_first_attempts_of_all_statuses_share_a_bucket() {
+        let rows = [
+            ("stripe", storage_enums::AttemptStatus::Charged, 3),
+            ("stripe", storage_enums::AttemptStatus::Failure, 1),
+            ("adyen", storage_enums::AttemptStatus::Charged, 1),
+            (
+                "adyen",
+                storage_enums::AttemptStatus::AuthorizationFailed,
+                1,
+            ),
+        ];
+
+        let mut accumulators = BTreeMap::<_, SuccessRateAccumulator>::new();
+        for (connector, status, count) in rows {
+            let row = PaymentMetricRow {
+                connector: Some(connector.to_string()),
+                ..first_attempts(status, count)
+            };
+            let (bucket, row) =
+                FirstAttemptSuccessRate::get_bucket(row, &None, &test_utils::time_range()).unwrap();
+            assert_eq!(bucket.status, None);
+            accumulators
+                .entry(bucket.connector)
+                .or_default()
+                .add_metrics_bucket(&row);
+        }
+        let success_rates = accumulators
+            .into_iter()
+            .map(|(connector, accumulator)| (connector, accumulator.collect()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            success_rates,
+            vec![
+                (Some("adyen".to_string()), Some(50.0)),
+                (Some("stripe".to_string()), Some(75.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_first_attempt_success_rate_counts_first_attempts() {
+        let mut accumulator = SuccessRateAccumulator::default();
+        accumulator.add_metrics_bucket(&first_attempts(storage_enums::AttemptStatus::Charged, 3));
+        accumulator.add_metrics_bucket(&first_attempts(storage_enums::AttemptStatus::Failure, 1));
+
+        assert_eq!(accumulator.collect(), Some(75.0));
+    }
+
+    #[test]
+    fn test_buckets_with_only_retried_payments_are_suppressed() {
+        // Retried attempts are filtered out by the query, so these buckets have no rows
+        let accumulator = SuccessRateAccumulator::default();
+
+        assert_eq!(accumulator.collect(), None);
+    }
+}
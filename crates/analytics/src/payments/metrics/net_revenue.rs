@@ -0,0 +1,496 @@
+use std::collections::{HashMap, HashSet};
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    refunds::RefundDimensions,
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use diesel_models::enums as storage_enums;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::PaymentMetricRow;
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, FilterTypes, GroupByClause, PostProcessingError, QueryBuilder, QueryFilter,
+        SeriesBucket, ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Net revenue per connector, currency and requested dimension, computed as the captured amount
+/// minus the amount refunded and the fees charged by the connector.
+///
+/// Captures and fees are read from the payment attempts and refunds from the refunds joined with
+/// the attempt they refund, so all three are totalled per bucket of the attempts and then
+/// combined. Refunds and fees are bucketed by the creation time of the attempt, like captures.
+/// Fees are bucketed by the currency the connector charged them in.
+#[derive(Default)]
+pub(super) struct NetRevenue;
+
+/// Amounts that make up the net revenue of a bucket
+#[derive(Clone, Copy)]
+enum NetRevenueComponent {
+    Captured,
+    Refunded,
+    Fee,
+}
+
+/// Statuses of attempts that captured funds, fully or partially
+const CAPTURED_STATUSES: [storage_enums::AttemptStatus; 3] = [
+    storage_enums::AttemptStatus::Charged,
+    storage_enums::AttemptStatus::PartialCharged,
+    storage_enums::AttemptStatus::PartialChargedAndChargeable,
+];
+
+/// Amount captured by an attempt. With multiple captures the amount that is still capturable is
+/// left out, a single partial capture captures the amount that was requested and any other
+/// charged attempt captured its whole amount.
+const CAPTURED_AMOUNT: &str = "CASE \
+    WHEN multiple_capture_count IS NOT NULL \
+    THEN coalesce(net_amount, amount) - coalesce(amount_capturable, 0) \
+    WHEN status = 'charged' THEN coalesce(net_amount, amount) \
+    ELSE coalesce(amount_to_capture, 0) END";
+
+impl NetRevenue {
+    /// Query totalling `amount_field` per requested dimension, with the currency read from
+    /// `currency_field`. The connector and currency are always part of the dimensions.
+    #[allow(clippy::too_many_arguments)]
+    fn get_totals_query<T>(
+        collection: AnalyticsCollection,
+        amount_field: &'static str,
+        currency_field: &'static str,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        let mut query_builder: QueryBuilder<T> = QueryBuilder::new(collection);
+
+        for dim in Self::get_dimensions(dimensions).iter() {
+            if *dim == PaymentDimensions::Currency {
+                query_builder
+                    .add_select_column(format!("{currency_field} AS currency"))
+                    .switch()?;
+            } else {
+                query_builder.add_select_column(dim).switch()?;
+            }
+        }
+        query_builder
+            .add_select_column(Aggregate::Sum {
+                field: amount_field,
+                alias: Some("total"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        // The currency filter applies to the currency the amount is in, which is not the
+        // currency of the payment for fees
+        PaymentFilters {
+            currency: Vec::new(),
+            ..filters.clone()
+        }
+        .set_filter_clause(&mut query_builder)
+        .switch()?;
+        if !filters.currency.is_empty() {
+            query_builder
+                .add_filter_in_range_clause(currency_field, &filters.currency)
+                .attach_printable("Error adding currency filter")
+                .switch()?;
+        }
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        query_builder
+            .add_custom_filter_clause(PaymentDimensions::Connector, "NULL", FilterTypes::IsNotNull)
+            .switch()?;
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in Self::get_dimensions(dimensions).iter() {
+            if *dim == PaymentDimensions::Currency {
+                query_builder
+                    .add_group_by_clause(currency_field)
+                    .attach_printable("Error grouping by currency")
+                    .switch()?;
+            } else {
+                query_builder
+                    .add_group_by_clause(dim)
+                    .attach_printable("Error grouping by dimensions")
+                    .switch()?;
+            }
+        }
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        Ok(query_builder)
+    }
+
+    /// Requested dimensions along with the connector and currency, as amounts in different
+    /// currencies cannot be added together
+    fn get_dimensions(dimensions: &[PaymentDimensions]) -> Vec<PaymentDimensions> {
+        let mut dimensions = dimensions.to_vec();
+        for dimension in [PaymentDimensions::Connector, PaymentDimensions::Currency] {
+            if !dimensions.contains(&dimension) {
+                dimensions.push(dimension);
+            }
+        }
+        dimensions
+    }
+
+    /// Queries totalling the captured, refunded and fee amounts of each bucket
+    fn get_component_queries<T>(
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<[(NetRevenueComponent, QueryBuilder<T>); 3]>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        let mut captured_query = Self::get_totals_query(
+            AnalyticsCollection::Payment,
+            CAPTURED_AMOUNT,
+            "currency",
+            dimensions,
+            auth,
+            filters,
+            granularity,
+            time_range,
+        )?;
+        captured_query
+            .add_filter_clause_in(PaymentDimensions::PaymentStatus, &CAPTURED_STATUSES)
+            .switch()?;
+
+        let mut refunded_query = Self::get_totals_query(
+            AnalyticsCollection::RefundWithPayment,
+            "refund_amount",
+            "currency",
+            dimensions,
+            auth,
+            filters,
+            granularity,
+            time_range,
+        )?;
+        refunded_query
+            .add_filter_clause(
+                RefundDimensions::RefundStatus,
+                storage_enums::RefundStatus::Success,
+            )
+            .switch()?;
+
+        let mut fee_query = Self::get_totals_query(
+            AnalyticsCollection::Payment,
+            "connector_fee_amount",
+            "connector_fee_currency",
+            dimensions,
+            auth,
+            filters,
+            granularity,
+            time_range,
+        )?;
+        fee_query
+            .add_custom_filter_clause("connector_fee_amount", "NULL", FilterTypes::IsNotNull)
+            .switch()?;
+
+        Ok([
+            (NetRevenueComponent::Captured, captured_query),
+            (NetRevenueComponent::Refunded, refunded_query),
+            (NetRevenueComponent::Fee, fee_query),
+        ])
+    }
+
+    fn get_bucket_identifier(
+        row: &PaymentMetricRow,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> error_stack::Result<PaymentMetricsBucketIdentifier, PostProcessingError> {
+        Ok(PaymentMetricsBucketIdentifier::new(
+            row.currency.as_ref().map(|i| i.0),
+            row.status.as_ref().map(|i| i.0),
+            row.connector.clone(),
+            row.authentication_type.as_ref().map(|i| i.0),
+            row.payment_method.clone(),
+            row.payment_method_type.clone(),
+            row.client_source.clone(),
+            row.client_version.clone(),
+            row.profile_id.clone(),
+            row.card_funding_type.clone(),
+            row.card_bin.clone(),
+            row.device_fingerprint.clone(),
+            row.three_ds_version.clone(),
+            row.three_ds_message_version.clone(),
+            TimeRange {
+                start_time: match (granularity, row.start_bucket) {
+                    (Some(g), Some(st)) => g.clip_to_start(st)?,
+                    _ => time_range.start_time,
+                },
+                end_time: granularity.as_ref().map_or_else(
+                    || Ok(time_range.end_time),
+                    |g| row.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                )?,
+            },
+        ))
+    }
+}
+
+/// Combine the captured, refunded and fee totals of each bucket into a single row whose `total`
+/// is the net revenue of the bucket. Buckets with only refunds or fees report a negative total.
+fn combine_net_revenue(
+    components: Vec<(
+        NetRevenueComponent,
+        PaymentMetricsBucketIdentifier,
+        PaymentMetricRow,
+    )>,
+) -> HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)> {
+    let mut buckets: HashMap<PaymentMetricsBucketIdentifier, PaymentMetricRow> = HashMap::new();
+
+    for (component, bucket, row) in components {
+        let amount = row.total.clone().unwrap_or_default();
+        let amount = match component {
+            NetRevenueComponent::Captured => amount,
+            NetRevenueComponent::Refunded | NetRevenueComponent::Fee => -amount,
+        };
+        let net_revenue_row = buckets
+            .entry(bucket)
+            .or_insert_with(|| PaymentMetricRow { total: None, ..row });
+        net_revenue_row.total = Some(net_revenue_row.total.take().unwrap_or_default() + amount);
+    }
+
+    buckets.into_iter().collect()
+}
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for NetRevenue
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        let mut components = Vec::new();
+        for (component, mut query_builder) in
+            Self::get_component_queries::<T>(dimensions, auth, filters, granularity, time_range)?
+        {
+            let rows = query_builder
+                .execute_query::<PaymentMetricRow, _>(pool)
+                .await
+                .switch()?
+                .change_context(MetricsError::QueryExecutionFailure)?;
+            for row in rows {
+                let bucket = Self::get_bucket_identifier(&row, granularity, time_range)
+                    .change_context(MetricsError::PostProcessingFailure)?;
+                components.push((component, bucket, row));
+            }
+        }
+
+        Ok(combine_net_revenue(components))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bigdecimal::BigDecimal;
+
+    use super::*;
+    use crate::{
+        payments::{
+            accumulator::{NetRevenueAccumulator, PaymentMetricAccumulator},
+            test_utils,
+        },
+        types::DBEnumWrapper,
+    };
+
+    fn component(
+        component: NetRevenueComponent,
+        connector: &str,
+        currency: storage_enums::Currency,
+        total: i64,
+    ) -> (
+        NetRevenueComponent,
+        PaymentMetricsBucketIdentifier,
+        PaymentMetricRow,
+    ) {
+        let row = PaymentMetricRow {
+            currency: Some(DBEnumWrapper(currency)),
+            connector: Some(connector.to_string()),
+            total: Some(BigDecimal::from(total)),
+            ..Default::default()
+        };
+        let bucket =
+            NetRevenue::get_bucket_identifier(&row, &None, &test_utils::time_range()).unwrap();
+        (component, bucket, row)
+    }
+
+    fn hourly_component(
+        component: NetRevenueComponent,
+        total: i64,
+        created_at: PrimitiveDateTime,
+    ) -> (
+        NetRevenueComponent,
+        PaymentMetricsBucketIdentifier,
+        PaymentMetricRow,
+    ) {
+        let row = PaymentMetricRow {
+            currency: Some(DBEnumWrapper(storage_enums::Currency::USD)),
+            connector: Some("stripe".to_string()),
+            total: Some(BigDecimal::from(total)),
+            start_bucket: Some(created_at),
+            ..Default::default()
+        };
+        let bucket = NetRevenue::get_bucket_identifier(
+            &row,
+            &Some(Granularity::OneHour),
+            &test_utils::time_range(),
+        )
+        .unwrap();
+        (component, bucket, row)
+    }
+
+    #[test]
+    fn test_components_are_combined_per_time_bucket() {
+        let net_revenue = combine_net_revenue(vec![
+            hourly_component(NetRevenueComponent::Captured, 10_000, test_utils::at(10, 5)),
+            hourly_component(NetRevenueComponent::Refunded, 2_000, test_utils::at(10, 30)),
+            hourly_component(NetRevenueComponent::Captured, 5_000, test_utils::at(11, 10)),
+            hourly_component(NetRevenueComponent::Fee, 100, test_utils::at(12, 0)),
+        ]);
+
+        let mut net_revenue = net_revenue
+            .into_iter()
+            .map(|(bucket, row)| {
+                let mut accumulator = NetRevenueAccumulator::default();
+                accumulator.add_metrics_bucket(&row);
+                (bucket.time_bucket.start_time, accumulator.collect())
+            })
+            .collect::<Vec<_>>();
+        net_revenue.sort();
+
+        assert_eq!(
+            net_revenue,
+            vec![
+                (test_utils::at(10, 0), Some(8_000)),
+                (test_utils::at(11, 0), Some(5_000)),
+                (test_utils::at(12, 0), Some(-100)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_net_revenue_subtracts_refunds_and_fees_from_captures() {
+        let net_revenue = combine_net_revenue(vec![
+            component(
+                NetRevenueComponent::Captured,
+                "stripe",
+                storage_enums::Currency::USD,
+                10_000,
+            ),
+            component(
+                NetRevenueComponent::Refunded,
+                "stripe",
+                storage_enums::Currency::USD,
+                2_500,
+            ),
+            component(
+                NetRevenueComponent::Fee,
+                "stripe",
+                storage_enums::Currency::USD,
+                320,
+            ),
+            component(
+                NetRevenueComponent::Captured,
+                "stripe",
+                storage_enums::Currency::EUR,
+                5_000,
+            ),
+            component(
+                NetRevenueComponent::Refunded,
+                "adyen",
+                storage_enums::Currency::USD,
+                1_000,
+            ),
+        ]);
+
+        let mut net_revenue = net_revenue
+            .into_iter()
+            .map(|(bucket, row)| {
+                let mut accumulator = NetRevenueAccumulator::default();
+                accumulator.add_metrics_bucket(&row);
+                (bucket.connector, bucket.currency, accumulator.collect())
+            })
+            .collect::<Vec<_>>();
+        net_revenue.sort_by_key(|(connector, currency, _)| {
+            (
+                connector.clone(),
+                currency.map(|currency| currency.to_string()),
+            )
+        });
+
+        assert_eq!(
+            net_revenue,
+            vec![
+                (
+                    Some("adyen".to_string()),
+                    Some(storage_enums::Currency::USD),
+                    Some(-1_000)
+                ),
+                (
+                    Some("stripe".to_string()),
+                    Some(storage_enums::Currency::EUR),
+                    Some(5_000)
+                ),
+                (
+                    Some("stripe".to_string()),
+                    Some(storage_enums::Currency::USD),
+                    Some(7_180)
+                ),
+            ]
+        );
+    }
+}
@@ -11,13 +11,64 @@ use time::PrimitiveDateTime;
 use super::PaymentMetricRow;
 use crate::{
     enums::AuthInfo,
-    query::{Aggregate, GroupByClause, QueryBuilder, QueryFilter, SeriesBucket, ToSql, Window},
-    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+    query::{
+        Aggregate, GroupByClause, PostProcessingError, QueryBuilder, QueryFilter, SeriesBucket,
+        ToSql, Window,
+    },
+    types::{
+        AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult, QueryExecutionError,
+    },
 };
 
+/// Payment count per requested dimension.
+///
+/// The payments can be grouped by many dimensions at once, which gives a row per combination of
+/// their values, so the rows are streamed and their buckets built a batch of rows at a time.
 #[derive(Default)]
 pub(super) struct PaymentCount;
 
+/// Add the buckets of a batch of rows to the buckets of the earlier batches
+fn add_buckets(
+    mut buckets: HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+    rows: Vec<PaymentMetricRow>,
+    granularity: &Option<Granularity>,
+    time_range: &TimeRange,
+) -> error_stack::Result<
+    HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+    PostProcessingError,
+> {
+    for i in rows {
+        let bucket = PaymentMetricsBucketIdentifier::new(
+            i.currency.as_ref().map(|i| i.0),
+            i.status.as_ref().map(|i| i.0),
+            i.connector.clone(),
+            i.authentication_type.as_ref().map(|i| i.0),
+            i.payment_method.clone(),
+            i.payment_method_type.clone(),
+            i.client_source.clone(),
+            i.client_version.clone(),
+            i.profile_id.clone(),
+            i.card_funding_type.clone(),
+            i.card_bin.clone(),
+            i.device_fingerprint.clone(),
+            i.three_ds_version.clone(),
+            i.three_ds_message_version.clone(),
+            TimeRange {
+                start_time: match (granularity, i.start_bucket) {
+                    (Some(g), Some(st)) => g.clip_to_start(st)?,
+                    _ => time_range.start_time,
+                },
+                end_time: granularity.as_ref().map_or_else(
+                    || Ok(time_range.end_time),
+                    |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                )?,
+            },
+        );
+        buckets.insert((bucket, i));
+    }
+    Ok(buckets)
+}
+
 #[async_trait::async_trait]
 impl<T> super::PaymentMetric<T> for PaymentCount
 where
@@ -86,38 +137,60 @@ where
         }
 
         query_builder
-            .execute_query::<PaymentMetricRow, _>(pool)
+            .execute_query_streamed::<PaymentMetricRow, _, _, _>(
+                pool,
+                super::STREAMED_BATCH_SIZE,
+                HashSet::new(),
+                |buckets, rows| {
+                    add_buckets(buckets, rows, granularity, time_range)
+                        .change_context(QueryExecutionError::RowExtractionFailure)
+                },
+            )
             .await
-            .change_context(MetricsError::QueryBuildingError)?
-            .change_context(MetricsError::QueryExecutionFailure)?
-            .into_iter()
-            .map(|i| {
-                Ok((
-                    PaymentMetricsBucketIdentifier::new(
-                        i.currency.as_ref().map(|i| i.0),
-                        i.status.as_ref().map(|i| i.0),
-                        i.connector.clone(),
-                        i.authentication_type.as_ref().map(|i| i.0),
-                        i.payment_method.clone(),
-                        i.payment_method_type.clone(),
-                        i.client_source.clone(),
-                        i.client_version.clone(),
-                        i.profile_id.clone(),
-                        TimeRange {
-                            start_time: match (granularity, i.start_bucket) {
-                                (Some(g), Some(st)) => g.clip_to_start(st)?,
-                                _ => time_range.start_time,
-                            },
-                            end_time: granularity.as_ref().map_or_else(
-                                || Ok(time_range.end_time),
-                                |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
-                            )?,
-                        },
-                    ),
-                    i,
-                ))
-            })
-            .collect::<error_stack::Result<HashSet<_>, crate::query::PostProcessingError>>()
-            .change_context(MetricsError::PostProcessingFailure)
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_enums::Currency;
+
+    use super::*;
+    use crate::{payments::test_utils, types::DBEnumWrapper};
+
+    fn row(connector: &str, currency: Currency, count: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            connector: Some(connector.to_string()),
+            currency: Some(DBEnumWrapper(currency)),
+            count: Some(count),
+            ..Default::default()
+        }
+    }
+
+    fn rows() -> Vec<PaymentMetricRow> {
+        vec![
+            row("stripe", Currency::USD, 10),
+            row("stripe", Currency::EUR, 4),
+            row("adyen", Currency::USD, 7),
+            row("adyen", Currency::GBP, 2),
+            row("checkout", Currency::USD, 1),
+        ]
+    }
+
+    #[test]
+    fn test_buckets_built_per_batch_match_buckets_built_at_once() {
+        let time_range = test_utils::time_range();
+        let buckets_at_once = add_buckets(HashSet::new(), rows(), &None, &time_range).unwrap();
+
+        let mut rows = rows();
+        let mut buckets_per_batch = HashSet::new();
+        while !rows.is_empty() {
+            let batch = rows.drain(..rows.len().min(2)).collect();
+            buckets_per_batch = add_buckets(buckets_per_batch, batch, &None, &time_range).unwrap();
+        }
+
+        assert_eq!(buckets_at_once.len(), 5);
+        assert_eq!(buckets_per_batch, buckets_at_once);
     }
 }
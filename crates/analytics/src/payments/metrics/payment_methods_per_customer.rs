@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::PaymentMetricRow;
+use crate::{
+    enums::AuthInfo,
+    query::{Aggregate, GroupByClause, QueryBuilder, QueryFilter, ToSql, Window},
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Number of active payment methods saved per customer.
+///
+/// Customers created in the time range are counted per number of active payment methods they
+/// have saved, whenever the payment methods were saved, with the number of payment methods in
+/// `total` and the number of customers in `count`. Customers without a saved payment method are
+/// reported with a `total` of zero. The metric is not split by dimensions or time buckets.
+///
+/// Payment methods and customers are only stored in Postgres and are scoped to merchants, so the
+/// metric is not available from Clickhouse or for organization and profile level requests.
+#[derive(Default)]
+pub(super) struct PaymentMethodsPerCustomer;
+
+impl PaymentMethodsPerCustomer {
+    fn set_merchant_filter_clause<T>(
+        auth: &AuthInfo,
+        query_builder: &mut QueryBuilder<T>,
+    ) -> MetricsResult<()>
+    where
+        T: AnalyticsDataSource,
+        AnalyticsCollection: ToSql<T>,
+    {
+        match auth {
+            AuthInfo::MerchantLevel { merchant_ids, .. } => query_builder
+                .add_filter_in_range_clause("merchant_id", merchant_ids)
+                .attach_printable("Error adding merchant_id filter")
+                .switch(),
+            AuthInfo::OrgLevel { .. } | AuthInfo::ProfileLevel { .. } => Err(error_stack::report!(
+                MetricsError::NotImplemented
+            )
+            .attach_printable(
+                "Payment methods per customer are only available for merchant level requests",
+            )),
+        }
+    }
+
+    /// Query counting the customers of the merchants per number of saved payment methods
+    fn build_query<T>(auth: &AuthInfo, time_range: &TimeRange) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Aggregate<&'static str>: ToSql<T>,
+    {
+        let mut query_builder: QueryBuilder<T> =
+            QueryBuilder::new(AnalyticsCollection::CustomerPaymentMethods);
+
+        query_builder.add_select_column("total").switch()?;
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        Self::set_merchant_filter_clause(auth, &mut query_builder)?;
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        query_builder
+            .add_group_by_clause("total")
+            .attach_printable("Error grouping by payment method count")
+            .switch()?;
+
+        Ok(query_builder)
+    }
+
+    /// Bucket of a row over the whole time range, the metric not being split by dimensions or
+    /// time buckets
+    fn get_bucket(
+        row: PaymentMetricRow,
+        time_range: &TimeRange,
+    ) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+        (
+            PaymentMetricsBucketIdentifier::new(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                *time_range,
+            ),
+            row,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for PaymentMethodsPerCustomer
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        _dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        _filters: &PaymentFilters,
+        _granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        Ok(Self::build_query::<T>(auth, time_range)?
+            .execute_query::<PaymentMetricRow, _>(pool)
+            .await
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)?
+            .into_iter()
+            .map(|row| Self::get_bucket(row, time_range))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api_models::analytics::payments::{
+        PaymentMethodCountBucket, PaymentMethodsPerCustomer as PaymentMethodsPerCustomerMetric,
+    };
+    use common_utils::id_type;
+
+    use super::*;
+    use crate::{
+        clickhouse::ClickhouseClient,
+        payments::{
+            accumulator::{PaymentMethodsPerCustomerAccumulator, PaymentMetricAccumulator},
+            test_utils,
+        },
+        sqlx::SqlxClient,
+    };
+
+    fn merchant_auth() -> AuthInfo {
+        AuthInfo::MerchantLevel {
+            org_id: id_type::OrganizationId::try_from(std::borrow::Cow::from("org_1")).unwrap(),
+            merchant_ids: vec![
+                id_type::MerchantId::try_from(std::borrow::Cow::from("merchant_1")).unwrap(),
+            ],
+        }
+    }
+
+    fn customers(payment_method_count: i64, customer_count: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            total: Some(bigdecimal::BigDecimal::from(payment_method_count)),
+            count: Some(customer_count),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_customers_of_all_payment_method_counts_share_a_single_bucket() {
+        let time_range = test_utils::time_range();
+        let buckets = [customers(0, 2), customers(1, 5), customers(3, 1)]
+            .into_iter()
+            .map(|row| PaymentMethodsPerCustomer::get_bucket(row, &time_range))
+            .collect::<Vec<_>>();
+
+        let mut accumulator = PaymentMethodsPerCustomerAccumulator::default();
+        for (bucket, row) in buckets.iter() {
+            assert_eq!(bucket, &buckets[0].0);
+            assert_eq!(bucket.time_bucket, time_range);
+            accumulator.add_metrics_bucket(row);
+        }
+
+        assert_eq!(
+            accumulator.collect(),
+            Some(PaymentMethodsPerCustomerMetric {
+                average: 1.0,
+                distribution: [(0, 2), (1, 5), (3, 1)]
+                    .into_iter()
+                    .map(
+                        |(payment_method_count, customer_count)| PaymentMethodCountBucket {
+                            payment_method_count,
+                            customer_count,
+                        }
+                    )
+                    .collect(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_payment_methods_per_customer_is_not_available_for_org_level_requests() {
+        assert!(PaymentMethodsPerCustomer::build_query::<SqlxClient>(
+            &test_utils::org_auth(),
+            &test_utils::time_range(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_payment_methods_per_customer_is_not_available_from_clickhouse() {
+        let query_builder = PaymentMethodsPerCustomer::build_query::<ClickhouseClient>(
+            &merchant_auth(),
+            &test_utils::time_range(),
+        )
+        .unwrap();
+
+        assert!(query_builder.build_query().is_err());
+    }
+}
@@ -96,7 +96,7 @@ where
         query_builder
             .execute_query::<PaymentMetricRow, _>(pool)
             .await
-            .change_context(MetricsError::QueryBuildingError)?
+            .switch()?
             .change_context(MetricsError::QueryExecutionFailure)?
             .into_iter()
             .map(|i| {
@@ -111,6 +111,11 @@ where
                         i.client_source.clone(),
                         i.client_version.clone(),
                         i.profile_id.clone(),
+                        i.card_funding_type.clone(),
+                        i.card_bin.clone(),
+                        i.device_fingerprint.clone(),
+                        i.three_ds_version.clone(),
+                        i.three_ds_message_version.clone(),
                         TimeRange {
                             start_time: match (granularity, i.start_bucket) {
                                 (Some(g), Some(st)) => g.clip_to_start(st)?,
@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use time::PrimitiveDateTime;
+
+use super::{
+    captured_amount_in_reporting_currency::CapturedAmountInReportingCurrency, PaymentMetricRow,
+};
+use crate::{
+    enums::AuthInfo,
+    query::{Aggregate, GroupByClause, ToSql, Window},
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsResult},
+};
+
+/// Processed amount of all currencies as a single figure in the reporting currency.
+///
+/// Rows are loaded per currency and day like `CapturedAmountInReportingCurrency`, but the
+/// currency is only part of the bucket when it was requested as a dimension, so that the
+/// converted amounts of all currencies are summed into the same bucket.
+#[derive(Default)]
+pub(super) struct ProcessedAmountInReportingCurrency;
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for ProcessedAmountInReportingCurrency
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        let is_currency_requested = dimensions.contains(&PaymentDimensions::Currency);
+
+        Ok(CapturedAmountInReportingCurrency
+            .load_metrics(dimensions, auth, filters, granularity, time_range, pool)
+            .await?
+            .into_iter()
+            .map(|(mut identifier, row)| {
+                if !is_currency_requested {
+                    identifier.currency = None;
+                }
+                (identifier, row)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api_models::analytics::payments::CurrencyAmount;
+    use bigdecimal::BigDecimal;
+    use diesel_models::enums as storage_enums;
+
+    use super::*;
+    use crate::{
+        payments::{
+            accumulator::ReportingCurrencyNormalizedAmountAccumulator,
+            test_utils::reporting_currency,
+        },
+        types::DBEnumWrapper,
+        ReportingCurrency,
+    };
+
+    fn row(currency: storage_enums::Currency, total: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            currency: Some(DBEnumWrapper(currency)),
+            total: Some(BigDecimal::from(total)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_amounts_of_all_currencies_are_converted_to_reporting_currency() {
+        let reporting_currency = reporting_currency();
+        let mut accumulator = ReportingCurrencyNormalizedAmountAccumulator::default();
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::USD, 1_000),
+            &reporting_currency,
+        );
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::EUR, 2_000),
+            &reporting_currency,
+        );
+
+        assert_eq!(accumulator.collect(), (Some(3_200), None));
+    }
+
+    #[test]
+    fn test_amounts_without_rate_are_reported_apart_from_the_total() {
+        let reporting_currency = reporting_currency();
+        let mut accumulator = ReportingCurrencyNormalizedAmountAccumulator::default();
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::USD, 1_000),
+            &reporting_currency,
+        );
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::EUR, 2_000),
+            &reporting_currency,
+        );
+        accumulator
+            .add_metrics_bucket(&row(storage_enums::Currency::GBP, 300), &reporting_currency);
+        accumulator
+            .add_metrics_bucket(&row(storage_enums::Currency::GBP, 200), &reporting_currency);
+
+        assert_eq!(
+            accumulator.collect(),
+            (
+                Some(3_200),
+                Some(vec![CurrencyAmount {
+                    currency: storage_enums::Currency::GBP,
+                    amount: 500,
+                }])
+            )
+        );
+    }
+
+    #[test]
+    fn test_amounts_are_not_converted_when_forex_rates_are_unavailable() {
+        let reporting_currency = ReportingCurrency {
+            exchange_rates: None,
+            ..reporting_currency()
+        };
+        let mut accumulator = ReportingCurrencyNormalizedAmountAccumulator::default();
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::USD, 1_000),
+            &reporting_currency,
+        );
+        accumulator.add_metrics_bucket(
+            &row(storage_enums::Currency::EUR, 2_000),
+            &reporting_currency,
+        );
+
+        assert_eq!(
+            accumulator.collect(),
+            (
+                Some(1_000),
+                Some(vec![CurrencyAmount {
+                    currency: storage_enums::Currency::EUR,
+                    amount: 2_000,
+                }])
+            )
+        );
+    }
+}
@@ -0,0 +1,341 @@
+use std::collections::HashSet;
+
+use api_models::{
+    analytics::{
+        payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+        Granularity, TimeRange,
+    },
+    enums::IntentStatus,
+};
+use common_utils::errors::ReportSwitchExt;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::PaymentMetricRow;
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, GroupByClause, PostProcessingError, QueryBuilder, QueryFilter, SeriesBucket,
+        ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+/// Number of payments per number of attempts made, split by whether the payment eventually
+/// succeeded.
+///
+/// Payments are counted from the payment intents, using the number of attempts recorded on the
+/// intent. Only payments in a terminal status are counted, payments that may still succeed are
+/// left out. Only the currency and profile dimensions are available on payment intents, other
+/// requested dimensions are ignored.
+#[derive(Default)]
+pub(super) struct RetriesPerPayment;
+
+/// Terminal statuses of payments that captured funds
+const SUCCEEDED_STATUSES: [IntentStatus; 2] =
+    [IntentStatus::Succeeded, IntentStatus::PartiallyCaptured];
+
+/// Terminal statuses of payments that never captured funds
+const FAILED_STATUSES: [IntentStatus; 2] = [IntentStatus::Failed, IntentStatus::Cancelled];
+
+impl RetriesPerPayment {
+    /// Query counting the payments that `succeeded`, or that failed, per number of attempts and
+    /// requested dimension
+    fn build_query<T>(
+        succeeded: bool,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        let dimensions = dimensions
+            .iter()
+            .filter(|dimension| {
+                matches!(
+                    dimension,
+                    PaymentDimensions::Currency | PaymentDimensions::ProfileId
+                )
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut query_builder: QueryBuilder<T> =
+            QueryBuilder::new(AnalyticsCollection::PaymentIntent);
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+        query_builder.add_select_column("attempt_count").switch()?;
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        if !filters.currency.is_empty() {
+            query_builder
+                .add_filter_in_range_clause(PaymentDimensions::Currency, &filters.currency)
+                .attach_printable("Error adding currency filter")
+                .switch()?;
+        }
+        if !filters.profile_id.is_empty() {
+            query_builder
+                .add_filter_in_range_clause(PaymentDimensions::ProfileId, &filters.profile_id)
+                .attach_printable("Error adding profile id filter")
+                .switch()?;
+        }
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        let statuses = if succeeded {
+            SUCCEEDED_STATUSES
+        } else {
+            FAILED_STATUSES
+        };
+        query_builder
+            .add_filter_clause_in("status", &statuses)
+            .switch()?;
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+        query_builder
+            .add_group_by_clause("attempt_count")
+            .attach_printable("Error grouping by attempt count")
+            .switch()?;
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        Ok(query_builder)
+    }
+
+    async fn load_attempt_counts<T>(
+        succeeded: bool,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>>
+    where
+        T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        Self::build_query::<T>(
+            succeeded,
+            dimensions,
+            auth,
+            filters,
+            granularity,
+            time_range,
+        )?
+        .execute_query::<PaymentMetricRow, _>(pool)
+        .await
+        .switch()?
+        .change_context(MetricsError::QueryExecutionFailure)?
+        .into_iter()
+        .map(|row| Self::get_bucket(succeeded, row, granularity, time_range))
+        .collect::<error_stack::Result<
+            HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+            PostProcessingError,
+        >>()
+        .change_context(MetricsError::PostProcessingFailure)
+    }
+
+    /// Bucket of a row counting the payments that `succeeded`, or that failed, by currency and
+    /// profile
+    fn get_bucket(
+        succeeded: bool,
+        i: PaymentMetricRow,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> error_stack::Result<(PaymentMetricsBucketIdentifier, PaymentMetricRow), PostProcessingError>
+    {
+        Ok((
+            PaymentMetricsBucketIdentifier::new(
+                i.currency.as_ref().map(|i| i.0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                i.profile_id.clone(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                TimeRange {
+                    start_time: match (granularity, i.start_bucket) {
+                        (Some(g), Some(st)) => g.clip_to_start(st)?,
+                        _ => time_range.start_time,
+                    },
+                    end_time: granularity.as_ref().map_or_else(
+                        || Ok(time_range.end_time),
+                        |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                    )?,
+                },
+            ),
+            PaymentMetricRow {
+                succeeded: Some(succeeded),
+                ..i
+            },
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for RetriesPerPayment
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        let mut buckets = Self::load_attempt_counts(
+            true,
+            dimensions,
+            auth,
+            filters,
+            granularity,
+            time_range,
+            pool,
+        )
+        .await?;
+        buckets.extend(
+            Self::load_attempt_counts(
+                false,
+                dimensions,
+                auth,
+                filters,
+                granularity,
+                time_range,
+                pool,
+            )
+            .await?,
+        );
+        Ok(buckets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api_models::analytics::payments::{AttemptCountBucket, RetriesPerPayment as Retries};
+    use common_enums::Currency;
+
+    use super::*;
+    use crate::{
+        payments::{
+            accumulator::{PaymentMetricAccumulator, RetriesPerPaymentAccumulator},
+            test_utils,
+        },
+        types::DBEnumWrapper,
+    };
+
+    fn attempt_count(attempt_count: i64, count: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            currency: Some(DBEnumWrapper(Currency::USD)),
+            connector: Some("stripe".to_string()),
+            attempt_count: Some(attempt_count),
+            count: Some(count),
+            ..Default::default()
+        }
+    }
+
+    fn bucket(
+        succeeded: bool,
+        row: PaymentMetricRow,
+    ) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+        RetriesPerPayment::get_bucket(succeeded, row, &None, &test_utils::time_range()).unwrap()
+    }
+
+    #[test]
+    fn test_rows_are_bucketed_by_currency_and_profile_only() {
+        let (bucket, row) = bucket(true, attempt_count(2, 5));
+
+        assert_eq!(bucket.currency, Some(Currency::USD));
+        assert_eq!(bucket.connector, None);
+        assert_eq!(row.succeeded, Some(true));
+    }
+
+    #[test]
+    fn test_succeeded_and_failed_payments_are_counted_apart() {
+        let mut accumulator = RetriesPerPaymentAccumulator::default();
+        for (succeeded, row) in [
+            (true, attempt_count(1, 7)),
+            (true, attempt_count(2, 3)),
+            (false, attempt_count(2, 4)),
+            (false, attempt_count(5, 1)),
+        ] {
+            accumulator.add_metrics_bucket(&bucket(succeeded, row).1);
+        }
+
+        let payment_counts = |counts: [u64; 4]| {
+            ["1", "2", "3", "4+"]
+                .into_iter()
+                .zip(counts)
+                .map(|(attempts, payment_count)| AttemptCountBucket {
+                    attempts,
+                    payment_count,
+                })
+                .collect()
+        };
+        assert_eq!(
+            accumulator.collect(),
+            Some(Retries {
+                succeeded: payment_counts([7, 3, 0, 0]),
+                not_succeeded: payment_counts([0, 4, 0, 1]),
+            })
+        );
+    }
+}
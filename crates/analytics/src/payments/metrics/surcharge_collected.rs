@@ -0,0 +1,291 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use diesel_models::enums as storage_enums;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::PaymentMetricRow;
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, FilterTypes, GroupByClause, PostProcessingError, QueryBuilder, QueryFilter,
+        SeriesBucket, ToSql, Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+#[derive(Default)]
+pub(super) struct SurchargeCollected;
+
+impl SurchargeCollected {
+    /// Query summing the surcharge of the charged attempts per currency and requested dimension
+    fn build_query<T>(
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> MetricsResult<QueryBuilder<T>>
+    where
+        T: AnalyticsDataSource,
+        PrimitiveDateTime: ToSql<T>,
+        AnalyticsCollection: ToSql<T>,
+        Granularity: GroupByClause<T>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        let mut query_builder: QueryBuilder<T> = QueryBuilder::new(AnalyticsCollection::Payment);
+        let mut dimensions = dimensions.to_vec();
+
+        // Surcharge amounts are only comparable within the same currency
+        if !dimensions.contains(&PaymentDimensions::Currency) {
+            dimensions.push(PaymentDimensions::Currency);
+        }
+
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+
+        query_builder
+            .add_select_column(Aggregate::Sum {
+                field: "surcharge_amount",
+                alias: Some("total"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        filters.set_filter_clause(&mut query_builder).switch()?;
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        query_builder
+            .add_custom_filter_clause("surcharge_amount", "NULL", FilterTypes::IsNotNull)
+            .switch()?;
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        query_builder
+            .add_filter_clause(
+                PaymentDimensions::PaymentStatus,
+                storage_enums::AttemptStatus::Charged,
+            )
+            .switch()?;
+
+        Ok(query_builder)
+    }
+
+    /// Bucket of a row by its dimensions other than the status, only charged attempts being
+    /// counted
+    fn get_bucket(
+        i: PaymentMetricRow,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+    ) -> error_stack::Result<(PaymentMetricsBucketIdentifier, PaymentMetricRow), PostProcessingError>
+    {
+        Ok((
+            PaymentMetricsBucketIdentifier::new(
+                i.currency.as_ref().map(|i| i.0),
+                None,
+                i.connector.clone(),
+                i.authentication_type.as_ref().map(|i| i.0),
+                i.payment_method.clone(),
+                i.payment_method_type.clone(),
+                i.client_source.clone(),
+                i.client_version.clone(),
+                i.profile_id.clone(),
+                i.card_funding_type.clone(),
+                i.card_bin.clone(),
+                i.device_fingerprint.clone(),
+                i.three_ds_version.clone(),
+                i.three_ds_message_version.clone(),
+                TimeRange {
+                    start_time: match (granularity, i.start_bucket) {
+                        (Some(g), Some(st)) => g.clip_to_start(st)?,
+                        _ => time_range.start_time,
+                    },
+                    end_time: granularity.as_ref().map_or_else(
+                        || Ok(time_range.end_time),
+                        |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                    )?,
+                },
+            ),
+            i,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> super::PaymentMetric<T> for SurchargeCollected
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        Self::build_query::<T>(dimensions, auth, filters, granularity, time_range)?
+            .execute_query::<PaymentMetricRow, _>(pool)
+            .await
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)?
+            .into_iter()
+            .map(|row| Self::get_bucket(row, granularity, time_range))
+            .collect::<error_stack::Result<
+                HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+                PostProcessingError,
+            >>()
+            .change_context(MetricsError::PostProcessingFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use common_enums::Currency;
+
+    use super::*;
+    use crate::{
+        payments::{
+            accumulator::{AverageAccumulator, PaymentMetricAccumulator, SumAccumulator},
+            test_utils,
+        },
+        types::DBEnumWrapper,
+    };
+
+    fn surcharged_attempts(currency: Currency, total: i64, count: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            currency: Some(DBEnumWrapper(currency)),
+            total: Some(bigdecimal::BigDecimal::from(total)),
+            count: Some(count),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_surcharge_is_summed_per_connector_and_currency() {
+        let rows = [
+            ("stripe", Currency::USD, 250, 2),
+            ("adyen", Currency::USD, 50, 1),
+            ("stripe", Currency::EUR, 75, 1),
+        ];
+
+        let mut accumulators = BTreeMap::<_, SumAccumulator>::new();
+        for (connector, currency, total, count) in rows {
+            let row = PaymentMetricRow {
+                connector: Some(connector.to_string()),
+                status: Some(DBEnumWrapper(storage_enums::AttemptStatus::Charged)),
+                ..surcharged_attempts(currency, total, count)
+            };
+            let (bucket, row) =
+                SurchargeCollected::get_bucket(row, &None, &test_utils::time_range()).unwrap();
+            assert_eq!(bucket.status, None);
+            accumulators
+                .entry((bucket.connector, bucket.currency.map(|i| i.to_string())))
+                .or_default()
+                .add_metrics_bucket(&row);
+        }
+        let totals = accumulators
+            .into_iter()
+            .map(|(bucket, accumulator)| (bucket, accumulator.collect()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            totals,
+            vec![
+                (
+                    (Some("adyen".to_string()), Some("USD".to_string())),
+                    Some(50)
+                ),
+                (
+                    (Some("stripe".to_string()), Some("EUR".to_string())),
+                    Some(75)
+                ),
+                (
+                    (Some("stripe".to_string()), Some("USD".to_string())),
+                    Some(250)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_surcharge_collected_matches_fixture() {
+        // Surcharged charged attempts per connector, 3 attempts of 100, 150 and 50 in USD and a
+        // single attempt of 75 in EUR
+        let fixture = [
+            (Currency::USD, vec![(250, 2), (50, 1)]),
+            (Currency::EUR, vec![(75, 1)]),
+        ];
+
+        let totals = fixture
+            .into_iter()
+            .map(|(currency, rows)| {
+                let mut sum = SumAccumulator::default();
+                let mut average = AverageAccumulator::default();
+                for (total, count) in rows {
+                    let row = surcharged_attempts(currency, total, count);
+                    sum.add_metrics_bucket(&row);
+                    average.add_metrics_bucket(&row);
+                }
+                (currency, sum.collect(), average.collect())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            totals,
+            vec![
+                (Currency::USD, Some(300), Some(100.0)),
+                (Currency::EUR, Some(75), Some(75.0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+
+use api_models::analytics::{
+    payments::{PaymentDimensions, PaymentFilters, PaymentMetricsBucketIdentifier},
+    Granularity, TimeRange,
+};
+use common_utils::errors::ReportSwitchExt;
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use super::{PaymentMetric, PaymentMetricRow};
+use crate::{
+    enums::AuthInfo,
+    query::{
+        Aggregate, FilterTypes, GroupByClause, QueryBuilder, QueryFilter, SeriesBucket, ToSql,
+        Window,
+    },
+    types::{AnalyticsCollection, AnalyticsDataSource, MetricsError, MetricsResult},
+};
+
+#[derive(Default)]
+pub(super) struct TokenizationLatency;
+
+#[async_trait::async_trait]
+impl<T> PaymentMetric<T> for TokenizationLatency
+where
+    T: AnalyticsDataSource + super::PaymentMetricAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    async fn load_metrics(
+        &self,
+        dimensions: &[PaymentDimensions],
+        auth: &AuthInfo,
+        filters: &PaymentFilters,
+        granularity: &Option<Granularity>,
+        time_range: &TimeRange,
+        pool: &T,
+    ) -> MetricsResult<HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>> {
+        let mut query_builder: QueryBuilder<T> = QueryBuilder::new(AnalyticsCollection::Payment);
+
+        for dim in dimensions.iter() {
+            query_builder.add_select_column(dim).switch()?;
+        }
+
+        query_builder
+            .add_select_column("tokenization_latency")
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Count {
+                field: None,
+                alias: Some("count"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Min {
+                field: "created_at",
+                alias: Some("start_bucket"),
+            })
+            .switch()?;
+        query_builder
+            .add_select_column(Aggregate::Max {
+                field: "created_at",
+                alias: Some("end_bucket"),
+            })
+            .switch()?;
+
+        filters.set_filter_clause(&mut query_builder).switch()?;
+
+        auth.set_filter_clause(&mut query_builder).switch()?;
+
+        time_range
+            .set_filter_clause(&mut query_builder)
+            .attach_printable("Error filtering time range")
+            .switch()?;
+
+        for dim in dimensions.iter() {
+            query_builder
+                .add_group_by_clause(dim)
+                .attach_printable("Error grouping by dimensions")
+                .switch()?;
+        }
+
+        // The percentiles are computed from the count of payments per latency so that they are
+        // the same for every analytics data source
+        query_builder
+            .add_group_by_clause("tokenization_latency")
+            .attach_printable("Error grouping by tokenization latency")
+            .switch()?;
+
+        if let Some(granularity) = granularity.as_ref() {
+            granularity
+                .set_group_by_clause(&mut query_builder)
+                .attach_printable("Error adding granularity")
+                .switch()?;
+        }
+
+        query_builder
+            .add_custom_filter_clause("tokenization_latency", "NULL", FilterTypes::IsNotNull)
+            .switch()?;
+
+        query_builder
+            .execute_query::<PaymentMetricRow, _>(pool)
+            .await
+            .switch()?
+            .change_context(MetricsError::QueryExecutionFailure)?
+            .into_iter()
+            .map(|i| {
+                Ok((
+                    PaymentMetricsBucketIdentifier::new(
+                        i.currency.as_ref().map(|i| i.0),
+                        i.status.as_ref().map(|i| i.0),
+                        i.connector.clone(),
+                        i.authentication_type.as_ref().map(|i| i.0),
+                        i.payment_method.clone(),
+                        i.payment_method_type.clone(),
+                        i.client_source.clone(),
+                        i.client_version.clone(),
+                        i.profile_id.clone(),
+                        i.card_funding_type.clone(),
+                        i.card_bin.clone(),
+                        i.device_fingerprint.clone(),
+                        i.three_ds_version.clone(),
+                        i.three_ds_message_version.clone(),
+                        TimeRange {
+                            start_time: match (granularity, i.start_bucket) {
+                                (Some(g), Some(st)) => g.clip_to_start(st)?,
+                                _ => time_range.start_time,
+                            },
+                            end_time: granularity.as_ref().map_or_else(
+                                || Ok(time_range.end_time),
+                                |g| i.end_bucket.map(|et| g.clip_to_end(et)).transpose(),
+                            )?,
+                        },
+                    ),
+                    i,
+                ))
+            })
+            .collect::<error_stack::Result<
+                HashSet<(PaymentMetricsBucketIdentifier, PaymentMetricRow)>,
+                crate::query::PostProcessingError,
+            >>()
+            .change_context(MetricsError::PostProcessingFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api_models::analytics::payments::LatencyPercentiles;
+
+    use super::*;
+    use crate::payments::accumulator::{LatencyPercentileAccumulator, PaymentMetricAccumulator};
+
+    fn timings(tokenization_latency: Option<i64>, count: i64) -> PaymentMetricRow {
+        PaymentMetricRow {
+            tokenization_latency,
+            count: Some(count),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_tokenization_latency_percentiles_match_fixture() {
+        // 100 payments of a bucket, counted per tokenization latency in milliseconds
+        let fixture = [
+            timings(Some(900), 9),
+            timings(Some(120), 50),
+            timings(Some(3000), 1),
+            timings(Some(250), 40),
+            // Payments vaulted without timing are excluded by the query, and dropped otherwise
+            timings(None, 10),
+        ];
+
+        let mut accumulator = LatencyPercentileAccumulator::default();
+        for row in fixture.iter() {
+            accumulator.add_metrics_bucket(row);
+        }
+
+        assert_eq!(
+            accumulator.collect(),
+            Some(LatencyPercentiles {
+                p50: 120,
+                p90: 250,
+                p99: 900,
+            })
+        );
+    }
+
+    #[test]
+    fn test_bucket_without_timings_has_no_percentiles() {
+        let mut accumulator = LatencyPercentileAccumulator::default();
+        accumulator.add_metrics_bucket(&timings(None, 3));
+
+        assert_eq!(accumulator.collect(), None);
+    }
+}
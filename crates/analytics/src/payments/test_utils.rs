@@ -0,0 +1,82 @@
+#![allow(clippy::unwrap_used)]
+
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
+
+use api_models::analytics::{payments::PaymentMetricsBucketIdentifier, TimeRange};
+use common_enums::Currency;
+use common_utils::id_type;
+use currency_conversion::types::{CurrencyFactors, ExchangeRates};
+use rust_decimal::Decimal;
+use time::{Date, Month, PrimitiveDateTime, Time};
+
+use super::metrics::PaymentMetricRow;
+use crate::{enums::AuthInfo, ReportingCurrency};
+
+/// Time range of the buckets built in tests, starting at 2024-10-01 10:00
+pub(crate) fn time_range() -> TimeRange {
+    TimeRange {
+        start_time: at(10, 0),
+        end_time: None,
+    }
+}
+
+/// 2024-10-01 at `hour`:`minute`, the day of [`time_range`]
+pub(crate) fn at(hour: u8, minute: u8) -> PrimitiveDateTime {
+    PrimitiveDateTime::new(
+        Date::from_calendar_date(2024, Month::October, 1).unwrap(),
+        Time::from_hms(hour, minute, 0).unwrap(),
+    )
+}
+
+/// Organization level access to `org_1`, the auth metric queries are built with in tests
+pub(crate) fn org_auth() -> AuthInfo {
+    AuthInfo::OrgLevel {
+        org_id: id_type::OrganizationId::try_from(Cow::from("org_1")).unwrap(),
+    }
+}
+
+/// Identifier of the bucket of `row` in `time_range`, made of the dimensions set on the row
+pub(crate) fn bucket_identifier(
+    row: &PaymentMetricRow,
+    time_range: TimeRange,
+) -> PaymentMetricsBucketIdentifier {
+    PaymentMetricsBucketIdentifier::new(
+        row.currency.as_ref().map(|i| i.0),
+        row.status.as_ref().map(|i| i.0),
+        row.connector.clone(),
+        row.authentication_type.as_ref().map(|i| i.0),
+        row.payment_method.clone(),
+        row.payment_method_type.clone(),
+        row.client_source.clone(),
+        row.client_version.clone(),
+        row.profile_id.clone(),
+        row.card_funding_type.clone(),
+        row.card_bin.clone(),
+        row.device_fingerprint.clone(),
+        row.three_ds_version.clone(),
+        row.three_ds_message_version.clone(),
+        time_range,
+    )
+}
+
+/// Row with the dimensions and values set on it, in its bucket over [`time_range`]
+pub(crate) fn bucket(row: PaymentMetricRow) -> (PaymentMetricsBucketIdentifier, PaymentMetricRow) {
+    (bucket_identifier(&row, time_range()), row)
+}
+
+/// USD reporting currency with forex rates for EUR only, one EUR being worth 1.1 USD
+pub(crate) fn reporting_currency() -> ReportingCurrency {
+    ReportingCurrency {
+        currency: Currency::USD,
+        exchange_rates: Some(Arc::new(ExchangeRates::new(
+            Currency::USD,
+            HashMap::from([(
+                Currency::EUR,
+                CurrencyFactors::new(
+                    Decimal::new(1, 0) / Decimal::new(11, 1),
+                    Decimal::new(11, 1),
+                ),
+            )]),
+        ))),
+    }
+}
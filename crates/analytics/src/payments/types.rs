@@ -84,6 +84,84 @@ where
                 )
                 .attach_printable("Error adding card network filter")?;
         }
+        if !self.device_fingerprint.is_empty() {
+            builder
+                .add_filter_in_range_clause(
+                    PaymentDimensions::DeviceFingerprint,
+                    &self.device_fingerprint,
+                )
+                .attach_printable("Error adding device fingerprint filter")?;
+        }
+        if !self.three_ds_version.is_empty() {
+            builder
+                .add_filter_in_range_clause(
+                    PaymentDimensions::ThreeDsVersion,
+                    &self.three_ds_version,
+                )
+                .attach_printable("Error adding 3DS version filter")?;
+        }
+        if !self.three_ds_message_version.is_empty() {
+            builder
+                .add_filter_in_range_clause(
+                    PaymentDimensions::ThreeDsMessageVersion,
+                    &self.three_ds_message_version,
+                )
+                .attach_printable("Error adding 3DS message version filter")?;
+        }
+        if !self.card_funding_type.is_empty() {
+            builder
+                .add_filter_in_range_clause(
+                    PaymentDimensions::CardFundingType,
+                    &self.card_funding_type,
+                )
+                .attach_printable("Error adding card funding type filter")?;
+        }
+        if !self.card_bin.is_empty() {
+            builder
+                .add_filter_in_range_clause(PaymentDimensions::CardBin, &self.card_bin)
+                .attach_printable("Error adding card bin filter")?;
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clickhouse::ClickhouseClient, sqlx::SqlxClient};
+
+    #[test]
+    fn test_payments_are_filtered_by_device_fingerprint() {
+        let filters = PaymentFilters {
+            device_fingerprint: vec!["fp_1".to_string(), "fp_2".to_string()],
+            ..Default::default()
+        };
+
+        let mut clickhouse_query_builder: QueryBuilder<ClickhouseClient> =
+            QueryBuilder::new(AnalyticsCollection::Payment);
+        clickhouse_query_builder
+            .add_select_column("connector")
+            .unwrap();
+        filters
+            .set_filter_clause(&mut clickhouse_query_builder)
+            .unwrap();
+
+        let mut postgres_query_builder: QueryBuilder<SqlxClient> =
+            QueryBuilder::new(AnalyticsCollection::Payment);
+        postgres_query_builder
+            .add_select_column("connector")
+            .unwrap();
+        filters
+            .set_filter_clause(&mut postgres_query_builder)
+            .unwrap();
+
+        assert_eq!(
+            clickhouse_query_builder.build_query().unwrap(),
+            "SELECT connector FROM payment_attempts WHERE device_fingerprint IN ('fp_1', 'fp_2')"
+        );
+        assert_eq!(
+            postgres_query_builder.build_query().unwrap(),
+            "SELECT connector FROM payment_attempt WHERE device_fingerprint IN ('fp_1', 'fp_2')"
+        );
+    }
+}
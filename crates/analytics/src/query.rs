@@ -14,8 +14,8 @@ use api_models::{
         Granularity,
     },
     enums::{
-        AttemptStatus, AuthenticationType, Connector, Currency, DisputeStage, IntentStatus,
-        PaymentMethod, PaymentMethodType,
+        AttemptStatus, AuthenticationType, CardFundingType, Connector, Currency, DisputeStage,
+        IntentStatus, PaymentMethod, PaymentMethodType,
     },
     refunds::RefundStatus,
 };
@@ -25,6 +25,7 @@ use common_utils::{
 };
 use diesel_models::{enums as storage_enums, enums::FraudCheckStatus};
 use error_stack::ResultExt;
+use futures::TryStreamExt;
 use router_env::{logger, Flow};
 
 use super::types::{AnalyticsCollection, AnalyticsDataSource, LoadRow, TableEngine};
@@ -225,8 +226,14 @@ pub enum QueryBuildingError {
     NotImplemented(String),
     #[error("Failed to Serialize to SQL")]
     SqlSerializeError,
-    #[error("Failed to build sql query: {0}")]
-    InvalidQuery(&'static str),
+    #[error("Empty column name")]
+    EmptyColumn,
+    #[error("Invalid limit: {0}")]
+    InvalidLimit(u64),
+    #[error("No select fields provided")]
+    EmptySelect,
+    #[error("Filter has no values")]
+    EmptyFilter,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -253,6 +260,7 @@ pub enum Aggregate<R> {
         field: R,
         alias: Option<&'static str>,
     },
+    /// Value of `field` at `percentile`, out of 100. Defaults to the median.
     Percentile {
         field: R,
         alias: Option<&'static str>,
@@ -262,6 +270,28 @@ pub enum Aggregate<R> {
         field: R,
         alias: Option<&'static str>,
     },
+    /// Average time from `start_field` to `end_field`, in seconds
+    AvgDuration {
+        start_field: R,
+        end_field: R,
+        alias: Option<&'static str>,
+    },
+}
+
+/// Time from `start_field` to `end_field` in whole milliseconds, so that rows can be grouped by
+/// it and percentiles computed from the counts
+#[derive(Debug)]
+pub struct DurationInMillis<R> {
+    pub start_field: R,
+    pub end_field: R,
+    pub alias: Option<&'static str>,
+}
+
+/// Percentile of an `Aggregate::Percentile` as the fraction expected by the data sources, such as
+/// `0.95` for the 95th percentile. Percentiles above 100 are capped at 100.
+pub(crate) fn percentile_fraction(percentile: Option<&u8>) -> String {
+    let percentile = percentile.copied().unwrap_or(50).min(100);
+    format!("{:.2}", f64::from(percentile) / 100.0)
 }
 
 // Window functions in query
@@ -335,6 +365,28 @@ pub struct TopN {
     pub order: Order,
 }
 
+#[derive(Debug)]
+enum Filter {
+    Clause(String, FilterTypes, String),
+    AnyOf(Vec<(String, FilterTypes, String)>),
+}
+
+impl Filter {
+    fn to_sql(&self) -> String {
+        match self {
+            Self::Clause(l, op, r) => filter_type_to_sql(l, op, r),
+            Self::AnyOf(filters) => format!(
+                "({})",
+                filters
+                    .iter()
+                    .map(|(l, op, r)| filter_type_to_sql(l, op, r))
+                    .collect::<Vec<String>>()
+                    .join(" OR ")
+            ),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct QueryBuilder<T>
 where
@@ -342,7 +394,7 @@ where
     AnalyticsCollection: ToSql<T>,
 {
     columns: Vec<String>,
-    filters: Vec<(String, FilterTypes, String)>,
+    filters: Vec<Filter>,
     group_by: Vec<String>,
     having: Option<Vec<(String, FilterTypes, String)>>,
     outer_select: Vec<String>,
@@ -415,6 +467,7 @@ impl_to_sql_for_to_string!(
     PaymentMethod,
     PaymentMethodType,
     AuthenticationType,
+    CardFundingType,
     Connector,
     AttemptStatus,
     IntentStatus,
@@ -473,6 +526,96 @@ pub fn filter_type_to_sql(l: &String, op: &FilterTypes, r: &String) -> String {
     }
 }
 
+fn custom_filter_clause_to_sql<T>(
+    table_engine: &TableEngine,
+    lhs: impl ToSql<T>,
+    rhs: impl ToSql<T>,
+    comparison: FilterTypes,
+) -> QueryResult<(String, FilterTypes, String)>
+where
+    T: AnalyticsDataSource,
+{
+    Ok((
+        lhs.to_sql(table_engine)
+            .change_context(QueryBuildingError::SqlSerializeError)
+            .attach_printable("Error serializing filter key")?,
+        comparison,
+        rhs.to_sql(table_engine)
+            .change_context(QueryBuildingError::SqlSerializeError)
+            .attach_printable("Error serializing filter value")?,
+    ))
+}
+
+/// Serialize the values of an `IN` filter as a list of escaped string literals
+fn filter_in_values_to_sql<T>(
+    table_engine: &TableEngine,
+    values: &[impl ToSql<T>],
+) -> QueryResult<String>
+where
+    T: AnalyticsDataSource,
+{
+    if values.is_empty() {
+        Err(QueryBuildingError::EmptyFilter)?;
+    }
+    Ok(values
+        .iter()
+        .map(|value| {
+            value
+                .to_sql(table_engine)
+                .map(|value| format!("'{}'", T::escape_string_literal(&value)))
+        })
+        .collect::<error_stack::Result<Vec<String>, ParsingError>>()
+        .change_context(QueryBuildingError::SqlSerializeError)
+        .attach_printable("Error serializing filter value")?
+        .join(", "))
+}
+
+/// Filters that are combined with OR and added to a query as a single filter, such as a status
+/// that is one of several failure statuses or has an error code.
+/// Use via `add_or_filter_clause` of the query builder.
+pub struct OrFilterGroup<'a, T> {
+    table_engine: &'a TableEngine,
+    filters: Vec<(String, FilterTypes, String)>,
+    db_type: PhantomData<T>,
+}
+
+impl<T> OrFilterGroup<'_, T>
+where
+    T: AnalyticsDataSource,
+{
+    pub fn add_filter_clause(
+        &mut self,
+        key: impl ToSql<T>,
+        value: impl ToSql<T>,
+    ) -> QueryResult<()> {
+        self.add_custom_filter_clause(key, value, FilterTypes::Equal)
+    }
+
+    pub fn add_custom_filter_clause(
+        &mut self,
+        lhs: impl ToSql<T>,
+        rhs: impl ToSql<T>,
+        comparison: FilterTypes,
+    ) -> QueryResult<()> {
+        self.filters.push(custom_filter_clause_to_sql(
+            self.table_engine,
+            lhs,
+            rhs,
+            comparison,
+        )?);
+        Ok(())
+    }
+
+    pub fn add_filter_clause_in(
+        &mut self,
+        key: impl ToSql<T>,
+        values: &[impl ToSql<T>],
+    ) -> QueryResult<()> {
+        let list = filter_in_values_to_sql(self.table_engine, values)?;
+        self.add_custom_filter_clause(key, list, FilterTypes::In)
+    }
+}
+
 impl<T> QueryBuilder<T>
 where
     T: AnalyticsDataSource,
@@ -494,15 +637,23 @@ where
     }
 
     pub fn add_select_column(&mut self, column: impl ToSql<T>) -> QueryResult<()> {
-        self.columns.push(
-            column
-                .to_sql(&self.table_engine)
-                .change_context(QueryBuildingError::SqlSerializeError)
-                .attach_printable("Error serializing select column")?,
-        );
+        let column = column
+            .to_sql(&self.table_engine)
+            .change_context(QueryBuildingError::SqlSerializeError)
+            .attach_printable("Error serializing select column")?;
+        self.columns.push(Self::validate_column(column)?);
         Ok(())
     }
 
+    /// Columns that serialize to an empty string do not map to a column of the table, which would
+    /// otherwise only fail once the query is run
+    fn validate_column(column: String) -> QueryResult<String> {
+        if column.trim().is_empty() {
+            Err(QueryBuildingError::EmptyColumn)?;
+        }
+        Ok(column)
+    }
+
     pub fn transform_to_sql_values(&mut self, values: &[impl ToSql<T>]) -> QueryResult<String> {
         let res = values
             .iter()
@@ -524,6 +675,9 @@ where
     where
         Window<&'static str>: ToSql<T>,
     {
+        if count == 0 {
+            Err(QueryBuildingError::InvalidLimit(count))?;
+        }
         let partition_by_columns = self.transform_to_sql_values(columns)?;
         let order_by_column = order_column
             .to_sql(&self.table_engine)
@@ -580,15 +734,39 @@ where
         rhs: impl ToSql<T>,
         comparison: FilterTypes,
     ) -> QueryResult<()> {
-        self.filters.push((
-            lhs.to_sql(&self.table_engine)
-                .change_context(QueryBuildingError::SqlSerializeError)
-                .attach_printable("Error serializing filter key")?,
-            comparison,
-            rhs.to_sql(&self.table_engine)
-                .change_context(QueryBuildingError::SqlSerializeError)
-                .attach_printable("Error serializing filter value")?,
-        ));
+        let (lhs, comparison, rhs) =
+            custom_filter_clause_to_sql(&self.table_engine, lhs, rhs, comparison)?;
+        self.filters.push(Filter::Clause(lhs, comparison, rhs));
+        Ok(())
+    }
+
+    /// Filter `key` to be one of `values`. The values are escaped as string literals of the data
+    /// source, unlike `add_filter_in_range_clause` which only strips whitespace from them.
+    pub fn add_filter_clause_in(
+        &mut self,
+        key: impl ToSql<T>,
+        values: &[impl ToSql<T>],
+    ) -> QueryResult<()> {
+        let list = filter_in_values_to_sql(&self.table_engine, values)?;
+        self.add_custom_filter_clause(key, list, FilterTypes::In)
+    }
+
+    /// Add the filters added to the group by `add_filters` as a single filter that matches when
+    /// any of them matches
+    pub fn add_or_filter_clause(
+        &mut self,
+        add_filters: impl FnOnce(&mut OrFilterGroup<'_, T>) -> QueryResult<()>,
+    ) -> QueryResult<()> {
+        let mut group = OrFilterGroup {
+            table_engine: &self.table_engine,
+            filters: Vec::new(),
+            db_type: PhantomData,
+        };
+        add_filters(&mut group)?;
+        if group.filters.is_empty() {
+            Err(QueryBuildingError::EmptyFilter)?;
+        }
+        self.filters.push(Filter::AnyOf(group.filters));
         Ok(())
     }
 
@@ -614,12 +792,11 @@ where
     }
 
     pub fn add_group_by_clause(&mut self, column: impl ToSql<T>) -> QueryResult<()> {
-        self.group_by.push(
-            column
-                .to_sql(&self.table_engine)
-                .change_context(QueryBuildingError::SqlSerializeError)
-                .attach_printable("Error serializing group by field")?,
-        );
+        let column = column
+            .to_sql(&self.table_engine)
+            .change_context(QueryBuildingError::SqlSerializeError)
+            .attach_printable("Error serializing group by field")?;
+        self.group_by.push(Self::validate_column(column)?);
         Ok(())
     }
 
@@ -641,7 +818,7 @@ where
     fn get_filter_clause(&self) -> String {
         self.filters
             .iter()
-            .map(|(l, op, r)| filter_type_to_sql(l, op, r))
+            .map(Filter::to_sql)
             .collect::<Vec<String>>()
             .join(" AND ")
     }
@@ -709,9 +886,7 @@ where
         Window<&'static str>: ToSql<T>,
     {
         if self.columns.is_empty() {
-            Err(QueryBuildingError::InvalidQuery(
-                "No select fields provided",
-            ))?;
+            Err(QueryBuildingError::EmptySelect)?;
         }
         let mut query = String::from("SELECT ");
 
@@ -787,11 +962,42 @@ where
     {
         let query = self
             .build_query()
-            .change_context(QueryBuildingError::SqlSerializeError)
             .attach_printable("Failed to execute query")?;
 
         Ok(store.load_results(query.as_str()).await)
     }
+
+    /// Run the query and fold its results in batches of at most `batch_size` rows, for queries
+    /// whose results are too large to be loaded at once. `fold` is called with the accumulated
+    /// value and each batch, in the order the rows are returned by the data source.
+    pub async fn execute_query_streamed<R, P, A, F>(
+        &mut self,
+        store: &P,
+        batch_size: usize,
+        init: A,
+        mut fold: F,
+    ) -> CustomResult<CustomResult<A, QueryExecutionError>, QueryBuildingError>
+    where
+        P: LoadRow<R> + AnalyticsDataSource,
+        R: Send,
+        F: FnMut(A, Vec<R>) -> CustomResult<A, QueryExecutionError>,
+        Aggregate<&'static str>: ToSql<T>,
+        Window<&'static str>: ToSql<T>,
+    {
+        if batch_size == 0 {
+            Err(QueryBuildingError::InvalidLimit(0))?;
+        }
+        let query = self
+            .build_query()
+            .attach_printable("Failed to execute query")?;
+
+        Ok(store
+            .load_results_in_batches::<R>(query.as_str(), batch_size)
+            .try_fold(init, |accumulated, batch| {
+                futures::future::ready(fold(accumulated, batch))
+            })
+            .await)
+    }
 }
 
 impl<T> QueryFilter<T> for AuthInfo
@@ -836,3 +1042,327 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use common_utils::errors::ErrorSwitch;
+
+    use super::*;
+    use crate::{
+        clickhouse::{ClickhouseClient, ClickhouseConfig},
+        payments::metrics::PaymentMetricRow,
+        sqlx::SqlxClient,
+        types::MetricsError,
+    };
+
+    fn percentile(percentile: &'static u8) -> Aggregate<&'static str> {
+        Aggregate::Percentile {
+            field: "settlement_time",
+            alias: Some("settlement_time_percentile"),
+            percentile: Some(percentile),
+        }
+    }
+
+    #[test]
+    fn test_percentile_to_clickhouse_sql() {
+        let sql = |aggregate: Aggregate<&'static str>| {
+            <Aggregate<&'static str> as ToSql<ClickhouseClient>>::to_sql(
+                &aggregate,
+                &TableEngine::BasicTree,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            sql(percentile(&50)),
+            "quantileExact(0.50)(settlement_time) as settlement_time_percentile"
+        );
+        assert_eq!(
+            sql(percentile(&95)),
+            "quantileExact(0.95)(settlement_time) as settlement_time_percentile"
+        );
+        assert_eq!(
+            sql(percentile(&99)),
+            "quantileExact(0.99)(settlement_time) as settlement_time_percentile"
+        );
+    }
+
+    #[test]
+    fn test_percentile_to_postgres_sql() {
+        let sql = |aggregate: Aggregate<&'static str>| {
+            <Aggregate<&'static str> as ToSql<SqlxClient>>::to_sql(
+                &aggregate,
+                &TableEngine::BasicTree,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            sql(percentile(&50)),
+            "percentile_cont(0.50) within group (order by settlement_time asc) as settlement_time_percentile"
+        );
+        assert_eq!(
+            sql(percentile(&95)),
+            "percentile_cont(0.95) within group (order by settlement_time asc) as settlement_time_percentile"
+        );
+        assert_eq!(
+            sql(percentile(&99)),
+            "percentile_cont(0.99) within group (order by settlement_time asc) as settlement_time_percentile"
+        );
+    }
+
+    #[test]
+    fn test_duration_in_millis_to_sql() {
+        let duration = DurationInMillis {
+            start_field: "created_at",
+            end_field: "connector_response_received_at",
+            alias: Some("connector_latency"),
+        };
+
+        assert_eq!(
+            <DurationInMillis<&'static str> as ToSql<ClickhouseClient>>::to_sql(
+                &duration,
+                &TableEngine::BasicTree,
+            )
+            .unwrap(),
+            "dateDiff('millisecond', created_at, connector_response_received_at) as connector_latency"
+        );
+        assert_eq!(
+            <DurationInMillis<&'static str> as ToSql<SqlxClient>>::to_sql(
+                &duration,
+                &TableEngine::BasicTree,
+            )
+            .unwrap(),
+            "cast(extract(epoch from (connector_response_received_at - created_at)) * 1000 as bigint) as connector_latency"
+        );
+    }
+
+    #[test]
+    fn test_percentile_fraction() {
+        assert_eq!(percentile_fraction(None), "0.50");
+        assert_eq!(percentile_fraction(Some(&5)), "0.05");
+        assert_eq!(percentile_fraction(Some(&100)), "1.00");
+        assert_eq!(percentile_fraction(Some(&150)), "1.00");
+    }
+
+    fn query_builder() -> QueryBuilder<ClickhouseClient> {
+        QueryBuilder::new(AnalyticsCollection::Payment)
+    }
+
+    #[test]
+    fn test_empty_column_is_rejected() {
+        let mut query_builder = query_builder();
+
+        assert!(matches!(
+            query_builder
+                .add_select_column("")
+                .unwrap_err()
+                .current_context(),
+            QueryBuildingError::EmptyColumn
+        ));
+        assert!(matches!(
+            query_builder
+                .add_group_by_clause(" ")
+                .unwrap_err()
+                .current_context(),
+            QueryBuildingError::EmptyColumn
+        ));
+    }
+
+    #[test]
+    fn test_invalid_limit_is_rejected() {
+        let mut query_builder = query_builder();
+
+        assert!(matches!(
+            query_builder
+                .add_top_n_clause(&["connector"], 0, "count", Order::Descending)
+                .unwrap_err()
+                .current_context(),
+            QueryBuildingError::InvalidLimit(0)
+        ));
+    }
+
+    #[test]
+    fn test_empty_select_is_rejected() {
+        let mut query_builder = query_builder();
+        query_builder
+            .add_filter_clause("connector", "stripe")
+            .unwrap();
+
+        assert!(matches!(
+            query_builder.build_query().unwrap_err().current_context(),
+            QueryBuildingError::EmptySelect
+        ));
+    }
+
+    #[test]
+    fn test_query_building_errors_switch_to_metrics_errors() {
+        assert!(matches!(
+            QueryBuildingError::EmptyColumn.switch(),
+            MetricsError::EmptyColumn
+        ));
+        assert!(matches!(
+            QueryBuildingError::InvalidLimit(0).switch(),
+            MetricsError::InvalidLimit
+        ));
+        assert!(matches!(
+            QueryBuildingError::EmptySelect.switch(),
+            MetricsError::EmptySelect
+        ));
+        assert!(matches!(
+            QueryBuildingError::SqlSerializeError.switch(),
+            MetricsError::QueryBuildingError
+        ));
+    }
+
+    fn clickhouse_client() -> ClickhouseClient {
+        ClickhouseClient {
+            config: std::sync::Arc::new(ClickhouseConfig::default()),
+            database: "default".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_query_keeps_query_building_error() {
+        let mut query_builder = query_builder();
+        query_builder
+            .add_filter_clause("connector", "stripe")
+            .unwrap();
+
+        let error = query_builder
+            .execute_query::<PaymentMetricRow, _>(&clickhouse_client())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.current_context(),
+            QueryBuildingError::EmptySelect
+        ));
+        assert!(matches!(
+            error.current_context().switch(),
+            MetricsError::EmptySelect
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_query_streamed_keeps_query_building_error() {
+        let mut query_builder = query_builder();
+        query_builder
+            .add_filter_clause("connector", "stripe")
+            .unwrap();
+
+        let error = query_builder
+            .execute_query_streamed::<PaymentMetricRow, _, _, _>(
+                &clickhouse_client(),
+                100,
+                0,
+                |count, batch| Ok(count + batch.len()),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            QueryBuildingError::EmptySelect
+        ));
+
+        let error = query_builder
+            .execute_query_streamed::<PaymentMetricRow, _, _, _>(
+                &clickhouse_client(),
+                0,
+                0,
+                |count, batch| Ok(count + batch.len()),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            QueryBuildingError::InvalidLimit(0)
+        ));
+    }
+
+    #[test]
+    fn test_filter_clause_in_to_sql() {
+        let mut query_builder = query_builder();
+        query_builder.add_select_column("connector").unwrap();
+        query_builder
+            .add_filter_clause_in(
+                "status",
+                &[AttemptStatus::Failure, AttemptStatus::CaptureFailed],
+            )
+            .unwrap();
+
+        assert_eq!(
+            query_builder.build_query().unwrap(),
+            "SELECT connector FROM payment_attempts WHERE status IN ('failure', 'capture_failed')"
+        );
+    }
+
+    #[test]
+    fn test_filter_clause_in_values_are_escaped() {
+        let mut clickhouse_query_builder = query_builder();
+        clickhouse_query_builder
+            .add_select_column("connector")
+            .unwrap();
+        clickhouse_query_builder
+            .add_filter_clause_in("error_code", &["o'brien\\"])
+            .unwrap();
+
+        let mut postgres_query_builder: QueryBuilder<SqlxClient> =
+            QueryBuilder::new(AnalyticsCollection::Payment);
+        postgres_query_builder
+            .add_select_column("connector")
+            .unwrap();
+        postgres_query_builder
+            .add_filter_clause_in("error_code", &["o'brien\\"])
+            .unwrap();
+
+        assert_eq!(
+            clickhouse_query_builder.build_query().unwrap(),
+            "SELECT connector FROM payment_attempts WHERE error_code IN ('o\\'brien\\\\')"
+        );
+        assert_eq!(
+            postgres_query_builder.build_query().unwrap(),
+            "SELECT connector FROM payment_attempt WHERE error_code IN ('o''brien\\')"
+        );
+    }
+
+    #[test]
+    fn test_or_filter_clause_to_sql() {
+        let mut query_builder = query_builder();
+        query_builder.add_select_column("connector").unwrap();
+        query_builder
+            .add_filter_clause("merchant_id", "merchant_1")
+            .unwrap();
+        query_builder
+            .add_or_filter_clause(|group| {
+                group.add_filter_clause("status", AttemptStatus::Failure)?;
+                group.add_filter_clause_in("error_code", &["05", "51"])
+            })
+            .unwrap();
+
+        assert_eq!(
+            query_builder.build_query().unwrap(),
+            "SELECT connector FROM payment_attempts WHERE merchant_id = 'merchant_1' AND (status = 'failure' OR error_code IN ('05', '51'))"
+        );
+    }
+
+    #[test]
+    fn test_empty_filters_are_rejected() {
+        let mut query_builder = query_builder();
+
+        assert!(matches!(
+            query_builder
+                .add_filter_clause_in("status", &[] as &[AttemptStatus])
+                .unwrap_err()
+                .current_context(),
+            QueryBuildingError::EmptyFilter
+        ));
+        assert!(matches!(
+            query_builder
+                .add_or_filter_clause(|_| Ok(()))
+                .unwrap_err()
+                .current_context(),
+            QueryBuildingError::EmptyFilter
+        ));
+    }
+}
@@ -46,7 +46,7 @@ where
     query_builder
         .execute_query::<RefundFilterRow, _>(pool)
         .await
-        .change_context(FiltersError::QueryBuildingError)?
+        .switch()?
         .change_context(FiltersError::QueryExecutionFailure)
 }
 #[derive(Debug, serde::Serialize, Eq, PartialEq, serde::Deserialize)]
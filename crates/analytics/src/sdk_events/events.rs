@@ -52,7 +52,7 @@ where
     query_builder
         .execute_query::<SdkEventsResult, _>(pool)
         .await
-        .change_context(FiltersError::QueryBuildingError)?
+        .switch()?
         .change_context(FiltersError::QueryExecutionFailure)
 }
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
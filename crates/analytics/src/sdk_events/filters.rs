@@ -42,7 +42,7 @@ where
     query_builder
         .execute_query::<SdkEventFilter, _>(pool)
         .await
-        .change_context(FiltersError::QueryBuildingError)?
+        .switch()?
         .change_context(FiltersError::QueryExecutionFailure)
 }
 
@@ -13,6 +13,10 @@ use diesel_models::enums::{
     RefundStatus,
 };
 use error_stack::ResultExt;
+use futures::{
+    stream::{BoxStream, TryChunksError},
+    StreamExt, TryStreamExt,
+};
 use sqlx::{
     postgres::{PgArgumentBuffer, PgPoolOptions, PgRow, PgTypeInfo, PgValueRef},
     Decode, Encode,
@@ -24,7 +28,7 @@ use time::PrimitiveDateTime;
 
 use super::{
     health_check::HealthCheck,
-    query::{Aggregate, ToSql, Window},
+    query::{percentile_fraction, Aggregate, DurationInMillis, ToSql, Window},
     types::{
         AnalyticsCollection, AnalyticsDataSource, DBEnumWrapper, LoadRow, QueryExecutionError,
         TableEngine,
@@ -177,6 +181,30 @@ impl AnalyticsDataSource for SqlxClient {
             .collect::<Result<Vec<_>, _>>()
             .change_context(QueryExecutionError::RowExtractionFailure)
     }
+
+    fn load_results_in_batches<'a, T>(
+        &'a self,
+        query: &'a str,
+        batch_size: usize,
+    ) -> BoxStream<'a, CustomResult<Vec<T>, QueryExecutionError>>
+    where
+        Self: LoadRow<T>,
+        T: Send + 'a,
+    {
+        sqlx::query(query)
+            .fetch(&self.pool)
+            .map(move |row| {
+                row.change_context(QueryExecutionError::DatabaseError)
+                    .attach_printable_lazy(|| format!("Failed to run query {query}"))
+                    .and_then(|row| {
+                        Self::load_row(row)
+                            .change_context(QueryExecutionError::RowExtractionFailure)
+                    })
+            })
+            .try_chunks(batch_size.max(1))
+            .map_err(|TryChunksError(_, error)| error)
+            .boxed()
+    }
 }
 #[async_trait::async_trait]
 impl HealthCheck for SqlxClient {
@@ -330,6 +358,31 @@ impl<'a> FromRow<'a, PgRow> for super::payments::metrics::PaymentMetricRow {
             ColumnNotFound(_) => Ok(Default::default()),
             e => Err(e),
         })?;
+        let card_funding_type: Option<String> =
+            row.try_get("card_funding_type").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let card_bin: Option<String> = row.try_get("card_bin").or_else(|e| match e {
+            ColumnNotFound(_) => Ok(Default::default()),
+            e => Err(e),
+        })?;
+        let device_fingerprint: Option<String> =
+            row.try_get("device_fingerprint").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let three_ds_version: Option<String> =
+            row.try_get("three_ds_version").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let three_ds_message_version: Option<String> = row
+            .try_get("three_ds_message_version")
+            .or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
         let total: Option<bigdecimal::BigDecimal> = row.try_get("total").or_else(|e| match e {
             ColumnNotFound(_) => Ok(Default::default()),
             e => Err(e),
@@ -338,6 +391,37 @@ impl<'a> FromRow<'a, PgRow> for super::payments::metrics::PaymentMetricRow {
             ColumnNotFound(_) => Ok(Default::default()),
             e => Err(e),
         })?;
+        let dispute_count: Option<i64> = row.try_get("dispute_count").or_else(|e| match e {
+            ColumnNotFound(_) => Ok(Default::default()),
+            e => Err(e),
+        })?;
+        let tokenization_latency: Option<i64> =
+            row.try_get("tokenization_latency").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let avg_time_to_success_seconds: Option<bigdecimal::BigDecimal> = row
+            .try_get("avg_time_to_success_seconds")
+            .or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let connector_latency: Option<i64> =
+            row.try_get("connector_latency").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let unified_code: Option<String> = row.try_get("unified_code").or_else(|e| match e {
+            ColumnNotFound(_) => Ok(Default::default()),
+            e => Err(e),
+        })?;
+        let attempt_count: Option<i64> = row
+            .try_get::<Option<i16>, _>("attempt_count")
+            .map(|attempt_count| attempt_count.map(i64::from))
+            .or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
         // Removing millisecond precision to get accurate diffs against clickhouse
         let start_bucket: Option<PrimitiveDateTime> = row
             .try_get::<Option<PrimitiveDateTime>, _>("start_bucket")?
@@ -355,8 +439,20 @@ impl<'a> FromRow<'a, PgRow> for super::payments::metrics::PaymentMetricRow {
             client_source,
             client_version,
             profile_id,
+            card_funding_type,
+            card_bin,
+            device_fingerprint,
+            three_ds_version,
+            three_ds_message_version,
+            unified_code,
             total,
             count,
+            dispute_count,
+            tokenization_latency,
+            avg_time_to_success_seconds,
+            connector_latency,
+            attempt_count,
+            succeeded: None,
             start_bucket,
             end_bucket,
         })
@@ -407,6 +503,31 @@ impl<'a> FromRow<'a, PgRow> for super::payments::distribution::PaymentDistributi
             ColumnNotFound(_) => Ok(Default::default()),
             e => Err(e),
         })?;
+        let card_funding_type: Option<String> =
+            row.try_get("card_funding_type").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let card_bin: Option<String> = row.try_get("card_bin").or_else(|e| match e {
+            ColumnNotFound(_) => Ok(Default::default()),
+            e => Err(e),
+        })?;
+        let device_fingerprint: Option<String> =
+            row.try_get("device_fingerprint").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let three_ds_version: Option<String> =
+            row.try_get("three_ds_version").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let three_ds_message_version: Option<String> = row
+            .try_get("three_ds_message_version")
+            .or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
         let total: Option<bigdecimal::BigDecimal> = row.try_get("total").or_else(|e| match e {
             ColumnNotFound(_) => Ok(Default::default()),
             e => Err(e),
@@ -436,6 +557,11 @@ impl<'a> FromRow<'a, PgRow> for super::payments::distribution::PaymentDistributi
             client_source,
             client_version,
             profile_id,
+            card_funding_type,
+            card_bin,
+            device_fingerprint,
+            three_ds_version,
+            three_ds_message_version,
             total,
             count,
             error_message,
@@ -493,6 +619,31 @@ impl<'a> FromRow<'a, PgRow> for super::payments::filters::PaymentFilterRow {
             ColumnNotFound(_) => Ok(Default::default()),
             e => Err(e),
         })?;
+        let device_fingerprint: Option<String> =
+            row.try_get("device_fingerprint").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let three_ds_version: Option<String> =
+            row.try_get("three_ds_version").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let three_ds_message_version: Option<String> = row
+            .try_get("three_ds_message_version")
+            .or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let card_funding_type: Option<String> =
+            row.try_get("card_funding_type").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
+        let card_bin: Option<String> = row.try_get("card_bin").or_else(|e| match e {
+            ColumnNotFound(_) => Ok(Default::default()),
+            e => Err(e),
+        })?;
         Ok(Self {
             currency,
             status,
@@ -504,6 +655,11 @@ impl<'a> FromRow<'a, PgRow> for super::payments::filters::PaymentFilterRow {
             client_version,
             profile_id,
             card_network,
+            device_fingerprint,
+            three_ds_version,
+            three_ds_message_version,
+            card_funding_type,
+            card_bin,
         })
     }
 }
@@ -539,6 +695,11 @@ impl<'a> FromRow<'a, PgRow> for super::payment_intents::metrics::PaymentIntentMe
         let end_bucket: Option<PrimitiveDateTime> = row
             .try_get::<Option<PrimitiveDateTime>, _>("end_bucket")?
             .and_then(|dt| dt.replace_millisecond(0).ok());
+        let is_repeat_customer: Option<bool> =
+            row.try_get("is_repeat_customer").or_else(|e| match e {
+                ColumnNotFound(_) => Ok(Default::default()),
+                e => Err(e),
+            })?;
         Ok(Self {
             status,
             currency,
@@ -547,6 +708,7 @@ impl<'a> FromRow<'a, PgRow> for super::payment_intents::metrics::PaymentIntentMe
             count,
             start_bucket,
             end_bucket,
+            is_repeat_customer,
         })
     }
 }
@@ -716,6 +878,47 @@ impl ToSql<SqlxClient> for AnalyticsCollection {
     fn to_sql(&self, _table_engine: &TableEngine) -> error_stack::Result<String, ParsingError> {
         match self {
             Self::Payment => Ok("payment_attempt".to_string()),
+            Self::PaymentWithIntentCreatedAt => Ok(
+                "(SELECT payment_attempt.*, payment_intent.created_at AS intent_created_at \
+                FROM payment_attempt INNER JOIN payment_intent \
+                ON payment_intent.merchant_id = payment_attempt.merchant_id \
+                AND payment_intent.payment_id = payment_attempt.payment_id) payment_attempt"
+                    .to_string(),
+            ),
+            Self::PaymentWithFirstAttempt => Ok(
+                "(SELECT payment_attempt.*, NOT EXISTS (SELECT 1 FROM payment_attempt earlier_attempt \
+                WHERE earlier_attempt.merchant_id = payment_attempt.merchant_id \
+                AND earlier_attempt.payment_id = payment_attempt.payment_id \
+                AND earlier_attempt.created_at < payment_attempt.created_at) AS first_attempt \
+                FROM payment_attempt) payment_attempt"
+                    .to_string(),
+            ),
+            Self::RefundWithPayment => Ok(
+                "(SELECT payment_attempt.*, refund.refund_amount, refund.refund_status \
+                FROM refund INNER JOIN payment_attempt \
+                ON payment_attempt.merchant_id = refund.merchant_id \
+                AND payment_attempt.attempt_id = refund.attempt_id) payment_attempt"
+                    .to_string(),
+            ),
+            Self::DisputeWithPayment => Ok(
+                "(SELECT dispute.*, payment_attempt.payment_method, \
+                payment_attempt.payment_method_type \
+                FROM dispute INNER JOIN payment_attempt \
+                ON payment_attempt.merchant_id = dispute.merchant_id \
+                AND payment_attempt.attempt_id = dispute.attempt_id) dispute"
+                    .to_string(),
+            ),
+            // Looked up per payment intent, so that only the intents in the filtered time range
+            // are checked against the earlier intents of their customer
+            Self::PaymentIntentWithRepeatCustomer => Ok(
+                "(SELECT payment_intent.*, EXISTS (SELECT 1 FROM payment_intent first_success \
+                WHERE first_success.merchant_id = payment_intent.merchant_id \
+                AND first_success.customer_id = payment_intent.customer_id \
+                AND first_success.status = 'succeeded' \
+                AND first_success.created_at < payment_intent.created_at) AS is_repeat_customer \
+                FROM payment_intent) payment_intent"
+                    .to_string(),
+            ),
             Self::Refund => Ok("refund".to_string()),
             Self::SdkEvents => Err(error_stack::report!(ParsingError::UnknownError)
                 .attach_printable("SdkEventsAudit table is not implemented for Sqlx"))?,
@@ -734,6 +937,15 @@ impl ToSql<SqlxClient> for AnalyticsCollection {
             Self::OutgoingWebhookEvent => Err(error_stack::report!(ParsingError::UnknownError)
                 .attach_printable("OutgoingWebhookEvents table is not implemented for Sqlx"))?,
             Self::Dispute => Ok("dispute".to_string()),
+            Self::CustomerPaymentMethods => {
+                Ok("(SELECT customers.merchant_id, customers.created_at, \
+                (SELECT CAST(COUNT(*) AS NUMERIC) FROM payment_methods \
+                WHERE payment_methods.merchant_id = customers.merchant_id \
+                AND payment_methods.customer_id = customers.customer_id \
+                AND payment_methods.status = 'active') AS total \
+                FROM customers) customers"
+                    .to_string())
+            }
         }
     }
 }
@@ -783,8 +995,8 @@ where
                 percentile,
             } => {
                 format!(
-                    "percentile_cont(0.{}) within group (order by {} asc){}",
-                    percentile.map_or_else(|| "50".to_owned(), |percentile| percentile.to_string()),
+                    "percentile_cont({}) within group (order by {} asc){}",
+                    percentile_fraction(*percentile),
                     field
                         .to_sql(table_engine)
                         .attach_printable("Failed to percentile aggregate")?,
@@ -800,10 +1012,45 @@ where
                     alias.map_or_else(|| "".to_owned(), |alias| format!(" as {}", alias))
                 )
             }
+            Self::AvgDuration {
+                start_field,
+                end_field,
+                alias,
+            } => {
+                format!(
+                    "cast(avg(extract(epoch from ({} - {}))) as numeric){}",
+                    end_field
+                        .to_sql(table_engine)
+                        .attach_printable("Failed to average duration aggregate")?,
+                    start_field
+                        .to_sql(table_engine)
+                        .attach_printable("Failed to average duration aggregate")?,
+                    alias.map_or_else(|| "".to_owned(), |alias| format!(" as {}", alias))
+                )
+            }
         })
     }
 }
 
+impl<T> ToSql<SqlxClient> for DurationInMillis<T>
+where
+    T: ToSql<SqlxClient>,
+{
+    fn to_sql(&self, table_engine: &TableEngine) -> error_stack::Result<String, ParsingError> {
+        Ok(format!(
+            "cast(extract(epoch from ({} - {})) * 1000 as bigint){}",
+            self.end_field
+                .to_sql(table_engine)
+                .attach_printable("Failed to build millisecond duration")?,
+            self.start_field
+                .to_sql(table_engine)
+                .attach_printable("Failed to build millisecond duration")?,
+            self.alias
+                .map_or_else(|| "".to_owned(), |alias| format!(" as {}", alias))
+        ))
+    }
+}
+
 impl<T> ToSql<SqlxClient> for Window<T>
 where
     T: ToSql<SqlxClient>,
@@ -6,6 +6,7 @@ use common_utils::{
     impl_api_event_type,
 };
 use error_stack::{report, Report, ResultExt};
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt, TryStreamExt};
 
 use super::query::QueryBuildingError;
 use crate::errors::AnalyticsError;
@@ -26,6 +27,21 @@ pub enum AnalyticsDomain {
 #[derive(Debug, strum::AsRefStr, strum::Display, Clone, Copy)]
 pub enum AnalyticsCollection {
     Payment,
+    /// Payment attempts along with the creation time of their payment intent, as
+    /// `intent_created_at`
+    PaymentWithIntentCreatedAt,
+    /// Payment attempts along with whether they were created before any other attempt of their
+    /// payment, as `first_attempt`
+    PaymentWithFirstAttempt,
+    /// Refunds along with the columns of the payment attempt they refund, other than the refund
+    /// amount and status
+    RefundWithPayment,
+    /// Disputes along with the payment method and payment method type of the payment attempt
+    /// they were raised against
+    DisputeWithPayment,
+    /// Payment intents along with whether their customer had a successful payment created before
+    /// them, as `is_repeat_customer`
+    PaymentIntentWithRepeatCustomer,
     Refund,
     FraudCheck,
     SdkEvents,
@@ -37,6 +53,8 @@ pub enum AnalyticsCollection {
     Dispute,
     ApiEventsAnalytics,
     ActivePaymentsAnalytics,
+    /// Customers along with the number of active payment methods they have saved, as `total`
+    CustomerPaymentMethods,
 }
 
 #[allow(dead_code)]
@@ -80,9 +98,45 @@ where
     where
         Self: LoadRow<T>;
 
+    /// Load the results of the query in batches of at most `batch_size` rows, so that large
+    /// results can be processed without holding all of the rows in memory. Data sources that can't
+    /// stream rows load all of the results and split them into batches.
+    fn load_results_in_batches<'a, T>(
+        &'a self,
+        query: &'a str,
+        batch_size: usize,
+    ) -> BoxStream<'a, CustomResult<Vec<T>, QueryExecutionError>>
+    where
+        Self: LoadRow<T>,
+        T: Send + 'a,
+    {
+        let results: BoxFuture<'a, _> = self.load_results::<T>(query);
+        results
+            .into_stream()
+            .map_ok(move |rows| {
+                futures::stream::iter(split_into_batches(rows, batch_size).into_iter().map(Ok))
+            })
+            .try_flatten()
+            .boxed()
+    }
+
     fn get_table_engine(_table: AnalyticsCollection) -> TableEngine {
         TableEngine::BasicTree
     }
+
+    /// Escape a value to be used inside a single quoted string literal
+    fn escape_string_literal(value: &str) -> String {
+        value.replace('\'', "''")
+    }
+}
+
+fn split_into_batches<T>(rows: Vec<T>, batch_size: usize) -> Vec<Vec<T>> {
+    let mut rows = rows.into_iter();
+    std::iter::from_fn(|| {
+        let batch = rows.by_ref().take(batch_size.max(1)).collect::<Vec<_>>();
+        (!batch.is_empty()).then_some(batch)
+    })
+    .collect()
 }
 
 pub trait LoadRow<T>
@@ -97,6 +151,12 @@ where
 pub enum MetricsError {
     #[error("Error building query")]
     QueryBuildingError,
+    #[error("Error building query: empty column name")]
+    EmptyColumn,
+    #[error("Error building query: invalid limit")]
+    InvalidLimit,
+    #[error("Error building query: no select fields provided")]
+    EmptySelect,
     #[error("Error running Query")]
     QueryExecutionFailure,
     #[error("Error processing query results")]
@@ -118,7 +178,14 @@ pub type MetricsResult<T> = CustomResult<T, MetricsError>;
 
 impl ErrorSwitch<MetricsError> for QueryBuildingError {
     fn switch(&self) -> MetricsError {
-        MetricsError::QueryBuildingError
+        match self {
+            Self::EmptyColumn => MetricsError::EmptyColumn,
+            Self::InvalidLimit(_) => MetricsError::InvalidLimit,
+            Self::EmptySelect => MetricsError::EmptySelect,
+            Self::NotImplemented(_) | Self::SqlSerializeError | Self::EmptyFilter => {
+                MetricsError::QueryBuildingError
+            }
+        }
     }
 }
 
@@ -151,3 +218,66 @@ impl ErrorSwitch<AnalyticsError> for FiltersError {
 }
 
 impl_api_event_type!(Miscellaneous, (AnalyticsDomain));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestDataSource {
+        rows: Vec<i64>,
+    }
+
+    #[async_trait::async_trait]
+    impl AnalyticsDataSource for TestDataSource {
+        type Row = i64;
+
+        async fn load_results<T>(&self, _query: &str) -> CustomResult<Vec<T>, QueryExecutionError>
+        where
+            Self: LoadRow<T>,
+        {
+            self.rows.iter().copied().map(Self::load_row).collect()
+        }
+    }
+
+    impl LoadRow<i64> for TestDataSource {
+        fn load_row(row: i64) -> CustomResult<i64, QueryExecutionError> {
+            Ok(row)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batched_results_match_results_loaded_at_once() {
+        let source = TestDataSource {
+            rows: (1..=10).collect(),
+        };
+        let query = "SELECT count FROM payment_attempts";
+
+        let rows = source.load_results::<i64>(query).await.unwrap();
+        let batches = source
+            .load_results_in_batches::<i64>(query, 3)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert!(batches.iter().all(|batch| batch.len() <= 3));
+        assert_eq!(batches.len(), 4);
+        assert_eq!(
+            batches.iter().flatten().sum::<i64>(),
+            rows.iter().sum::<i64>()
+        );
+        assert_eq!(batches.concat(), rows);
+    }
+
+    #[tokio::test]
+    async fn test_empty_results_have_no_batches() {
+        let source = TestDataSource { rows: Vec::new() };
+
+        let batches = source
+            .load_results_in_batches::<i64>("SELECT count FROM payment_attempts", 3)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert!(batches.is_empty());
+    }
+}
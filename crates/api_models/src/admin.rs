@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+pub use common_utils::types::{ConfirmResponseMaskingPolicy, IssuerCountryConnectorPreferences};
 use common_utils::{
     consts,
     crypto::Encryptable,
@@ -8,7 +9,7 @@ use common_utils::{
     id_type, link_utils, pii,
 };
 #[cfg(feature = "v1")]
-use common_utils::{crypto::OptionalEncryptableName, ext_traits::ValueExt};
+use common_utils::{crypto::OptionalEncryptableName, ext_traits::ValueExt, types::MinorUnit};
 #[cfg(feature = "v2")]
 use masking::ExposeInterface;
 use masking::Secret;
@@ -627,6 +628,11 @@ pub struct WebhookDetails {
     /// If this property is true, a webhook message is posted whenever a payment fails
     #[schema(example = true)]
     pub payment_failed_enabled: Option<bool>,
+
+    /// Names to send in place of the payment statuses in payment webhooks, keyed by the status.
+    /// Statuses that are not mapped are sent as is
+    #[schema(value_type = Option<Object>, example = json!({"succeeded": "PAID", "failed": "DECLINED"}))]
+    pub status_mapping: Option<HashMap<String, String>>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -1981,6 +1987,50 @@ pub struct ProfileCreate {
 
     /// Maximum number of auto retries allowed for a payment
     pub max_auto_retries_enabled: Option<u8>,
+
+    /// Indicates if the token reference of the card used (Hyperswitch payment method id and network token reference) should be returned in the confirm response.
+    /// The card number is never returned.
+    pub is_payment_method_token_response_enabled: Option<bool>,
+
+    /// Maximum amount a customer can be charged, across payments in the same currency, within `customer_spending_limit_window_in_secs`.
+    /// The limit is checked when a payment is confirmed.
+    #[schema(value_type = Option<i64>, example = 100000)]
+    pub customer_spending_limit: Option<MinorUnit>,
+
+    /// Duration in seconds over which the captured amount is summed up for the customer spending limit, defaults to 30 days
+    #[schema(value_type = Option<u32>, example = 2592000)]
+    pub customer_spending_limit_window_in_secs: Option<u32>,
+
+    /// Indicates if the routing rule that selected the connector should be returned in the confirm response
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+
+    /// Fields of the confirm response that are allowed to be revealed, every other field is masked.
+    /// When not set, the confirm response is not masked.
+    #[schema(value_type = Option<ConfirmResponseMaskingPolicy>)]
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+
+    /// Action to be taken when a connector fails a 3DS payment with a soft decline recommending a retry.
+    /// When not set, the payment is not retried for such declines.
+    #[schema(value_type = Option<SoftThreeDsFailurePolicy>, example = "retry_frictionless")]
+    pub soft_three_ds_failure_policy: Option<api_enums::SoftThreeDsFailurePolicy>,
+
+    /// Whether the card number can be stored in the locker when a card is saved. With `token_only`,
+    /// the card is converted to a network token and only the network token is stored, which
+    /// requires network tokenization to be enabled for the profile.
+    /// When not set, the card number is stored.
+    #[schema(value_type = Option<PanStoragePolicy>, example = "token_only")]
+    pub pan_storage_policy: Option<api_enums::PanStoragePolicy>,
+
+    /// Time in seconds after creation, after which a payment that was never confirmed is cancelled.
+    /// Payments for which a confirm was already attempted are not cancelled. When not set,
+    /// payments are not cancelled automatically.
+    #[schema(example = 3600)]
+    pub auto_cancel_unconfirmed_after: Option<u32>,
+    /// Connectors to be preferred for cards of an issuer country, keyed by the alpha-2 code of the
+    /// issuer country. The eligible connectors are reordered to try the preferred ones first, in
+    /// the order listed.
+    #[schema(value_type = Option<Object>, example = json!({"GB": ["adyen", "stripe"]}))]
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[nutype::nutype(
@@ -2219,6 +2269,48 @@ pub struct ProfileResponse {
 
     /// Maximum number of auto retries allowed for a payment
     pub max_auto_retries_enabled: Option<i16>,
+
+    /// Indicates if the token reference of the card used should be returned in the confirm response
+    pub is_payment_method_token_response_enabled: Option<bool>,
+
+    /// Maximum amount a customer can be charged, across payments in the same currency, within `customer_spending_limit_window_in_secs`
+    #[schema(value_type = Option<i64>, example = 100000)]
+    pub customer_spending_limit: Option<MinorUnit>,
+
+    /// Duration in seconds over which the captured amount is summed up for the customer spending limit
+    #[schema(example = 2592000)]
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+
+    /// Indicates if the routing rule that selected the connector should be returned in the confirm response
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+
+    /// Fields of the confirm response that are allowed to be revealed, every other field is masked.
+    /// When not set, the confirm response is not masked.
+    #[schema(value_type = Option<ConfirmResponseMaskingPolicy>)]
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+
+    /// Action to be taken when a connector fails a 3DS payment with a soft decline recommending a retry.
+    /// When not set, the payment is not retried for such declines.
+    #[schema(value_type = Option<SoftThreeDsFailurePolicy>, example = "retry_frictionless")]
+    pub soft_three_ds_failure_policy: Option<api_enums::SoftThreeDsFailurePolicy>,
+
+    /// Whether the card number can be stored in the locker when a card is saved. With `token_only`,
+    /// the card is converted to a network token and only the network token is stored, which
+    /// requires network tokenization to be enabled for the profile.
+    /// When not set, the card number is stored.
+    #[schema(value_type = Option<PanStoragePolicy>, example = "token_only")]
+    pub pan_storage_policy: Option<api_enums::PanStoragePolicy>,
+
+    /// Time in seconds after creation, after which a payment that was never confirmed is cancelled.
+    /// Payments for which a confirm was already attempted are not cancelled. When not set,
+    /// payments are not cancelled automatically.
+    #[schema(example = 3600)]
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    /// Connectors to be preferred for cards of an issuer country, keyed by the alpha-2 code of the
+    /// issuer country. The eligible connectors are reordered to try the preferred ones first, in
+    /// the order listed.
+    #[schema(value_type = Option<Object>, example = json!({"GB": ["adyen", "stripe"]}))]
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v2")]
@@ -2454,6 +2546,50 @@ pub struct ProfileUpdate {
 
     /// Maximum number of auto retries allowed for a payment
     pub max_auto_retries_enabled: Option<u8>,
+
+    /// Indicates if the token reference of the card used (Hyperswitch payment method id and network token reference) should be returned in the confirm response.
+    /// The card number is never returned.
+    pub is_payment_method_token_response_enabled: Option<bool>,
+
+    /// Maximum amount a customer can be charged, across payments in the same currency, within `customer_spending_limit_window_in_secs`.
+    /// The limit is checked when a payment is confirmed.
+    #[schema(value_type = Option<i64>, example = 100000)]
+    pub customer_spending_limit: Option<MinorUnit>,
+
+    /// Duration in seconds over which the captured amount is summed up for the customer spending limit, defaults to 30 days
+    #[schema(value_type = Option<u32>, example = 2592000)]
+    pub customer_spending_limit_window_in_secs: Option<u32>,
+
+    /// Indicates if the routing rule that selected the connector should be returned in the confirm response
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+
+    /// Fields of the confirm response that are allowed to be revealed, every other field is masked.
+    /// When not set, the confirm response is not masked.
+    #[schema(value_type = Option<ConfirmResponseMaskingPolicy>)]
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+
+    /// Action to be taken when a connector fails a 3DS payment with a soft decline recommending a retry.
+    /// When not set, the payment is not retried for such declines.
+    #[schema(value_type = Option<SoftThreeDsFailurePolicy>, example = "retry_frictionless")]
+    pub soft_three_ds_failure_policy: Option<api_enums::SoftThreeDsFailurePolicy>,
+
+    /// Whether the card number can be stored in the locker when a card is saved. With `token_only`,
+    /// the card is converted to a network token and only the network token is stored, which
+    /// requires network tokenization to be enabled for the profile.
+    /// When not set, the card number is stored.
+    #[schema(value_type = Option<PanStoragePolicy>, example = "token_only")]
+    pub pan_storage_policy: Option<api_enums::PanStoragePolicy>,
+
+    /// Time in seconds after creation, after which a payment that was never confirmed is cancelled.
+    /// Payments for which a confirm was already attempted are not cancelled. When not set,
+    /// payments are not cancelled automatically.
+    #[schema(example = 3600)]
+    pub auto_cancel_unconfirmed_after: Option<u32>,
+    /// Connectors to be preferred for cards of an issuer country, keyed by the alpha-2 code of the
+    /// issuer country. The eligible connectors are reordered to try the preferred ones first, in
+    /// the order listed.
+    #[schema(value_type = Option<Object>, example = json!({"GB": ["adyen", "stripe"]}))]
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v2")]
@@ -2749,6 +2885,8 @@ impl common_utils::events::ApiEventMetric for ConnectorAgnosticMitChoice {}
 
 impl common_utils::events::ApiEventMetric for payment_methods::PaymentMethodMigrate {}
 
+impl common_utils::events::ApiEventMetric for payment_methods::PaymentMethodMigrateBatchResponse {}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
 pub struct ExtendedCardInfoConfig {
     /// Merchant public key
@@ -76,6 +76,24 @@ pub struct GetPaymentMetricRequest {
     pub distribution: Option<Distribution>,
     #[serde(default)]
     pub delta: bool,
+    /// Chargeback ratio (in percentage) above which a connector is flagged,
+    /// defaults to the card network monitoring threshold
+    pub chargeback_ratio_threshold: Option<f64>,
+    /// Number of standard deviations above the trailing mean decline rate at which a time bucket
+    /// is flagged as a decline spike, defaults to 3
+    pub decline_spike_std_dev_threshold: Option<f64>,
+    /// Whether to also return the sub-totals of the amount and count metrics of each currency.
+    /// The currency dimension is added to the grouping when this is set
+    #[serde(default)]
+    pub currency_totals: bool,
+    /// Whether customers without a saved payment method are counted in the payment methods per
+    /// customer metric
+    #[serde(default)]
+    pub include_customers_without_payment_methods: bool,
+    /// Granularities finer than the one of `time_series` whose buckets are also returned, each
+    /// nested in the bucket of the next coarser granularity that contains it
+    #[serde(default)]
+    pub rollup_granularities: Vec<Granularity>,
 }
 
 #[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
@@ -320,6 +338,20 @@ pub struct MetricsResponse<T> {
     pub meta_data: [AnalyticsMetadata; 1],
 }
 
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentsMetricsResponse<T> {
+    pub query_data: Vec<T>,
+    pub meta_data: [PaymentsAnalyticsMetadata; 1],
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct PaymentsAnalyticsMetadata {
+    pub current_time_range: TimeRange,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency_totals: Option<payments::CurrencyTotals>,
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetApiEventFiltersRequest {
@@ -61,6 +61,7 @@ pub enum PaymentIntentMetrics {
     TotalSmartRetries,
     SmartRetriedAmount,
     PaymentIntentCount,
+    NewVsRepeatSuccessRate,
 }
 
 #[derive(Debug, Default, serde::Serialize)]
@@ -75,6 +76,7 @@ pub mod metric_behaviour {
     pub struct TotalSmartRetries;
     pub struct SmartRetriedAmount;
     pub struct PaymentIntentCount;
+    pub struct NewVsRepeatSuccessRate;
 }
 
 impl From<PaymentIntentMetrics> for NameDescription {
@@ -150,6 +152,8 @@ pub struct PaymentIntentMetricsBucketValue {
     pub total_smart_retries: Option<u64>,
     pub smart_retried_amount: Option<u64>,
     pub payment_intent_count: Option<u64>,
+    pub new_customer_success_rate: Option<f64>,
+    pub repeat_customer_success_rate: Option<f64>,
 }
 
 #[derive(Debug, serde::Serialize)]
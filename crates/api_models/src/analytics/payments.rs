@@ -1,5 +1,5 @@
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
 };
 
@@ -7,8 +7,8 @@ use common_utils::id_type;
 
 use super::{NameDescription, TimeRange};
 use crate::enums::{
-    AttemptStatus, AuthenticationType, CardNetwork, Connector, Currency, PaymentMethod,
-    PaymentMethodType,
+    AttemptStatus, AuthenticationType, CardFundingType, CardNetwork, Connector, Currency,
+    PaymentMethod, PaymentMethodType,
 };
 
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
@@ -33,6 +33,16 @@ pub struct PaymentFilters {
     pub card_network: Vec<CardNetwork>,
     #[serde(default)]
     pub profile_id: Vec<id_type::ProfileId>,
+    #[serde(default)]
+    pub device_fingerprint: Vec<String>,
+    #[serde(default)]
+    pub three_ds_version: Vec<String>,
+    #[serde(default)]
+    pub three_ds_message_version: Vec<String>,
+    #[serde(default)]
+    pub card_funding_type: Vec<CardFundingType>,
+    #[serde(default)]
+    pub card_bin: Vec<String>,
 }
 
 #[derive(
@@ -68,6 +78,11 @@ pub enum PaymentDimensions {
     ClientVersion,
     ProfileId,
     CardNetwork,
+    DeviceFingerprint,
+    ThreeDsVersion,
+    ThreeDsMessageVersion,
+    CardFundingType,
+    CardBin,
 }
 
 #[derive(
@@ -92,6 +107,23 @@ pub enum PaymentMetrics {
     AvgTicketSize,
     RetriesCount,
     ConnectorSuccessRate,
+    ChargebackRatio,
+    SurchargeCollected,
+    PaymentSuccessRateConfidenceInterval,
+    CapturedAmountInReportingCurrency,
+    DeclineSpikes,
+    AuthTypeEffect,
+    TokenizationLatency,
+    ConversionByClientSource,
+    NetRevenue,
+    PaymentMethodsPerCustomer,
+    AverageTimeToSuccess,
+    RetriesPerPayment,
+    FailureClassification,
+    ProcessedAmountInReportingCurrency,
+    DisputeRate,
+    FirstAttemptSuccessRate,
+    ConnectorLatency,
 }
 
 #[derive(Debug, Default, serde::Serialize)]
@@ -127,6 +159,23 @@ pub mod metric_behaviour {
     pub struct PaymentSuccessCount;
     pub struct PaymentProcessedAmount;
     pub struct AvgTicketSize;
+    pub struct ChargebackRatio;
+    pub struct SurchargeCollected;
+    pub struct PaymentSuccessRateConfidenceInterval;
+    pub struct CapturedAmountInReportingCurrency;
+    pub struct DeclineSpikes;
+    pub struct AuthTypeEffect;
+    pub struct TokenizationLatency;
+    pub struct ConversionByClientSource;
+    pub struct NetRevenue;
+    pub struct PaymentMethodsPerCustomer;
+    pub struct AverageTimeToSuccess;
+    pub struct RetriesPerPayment;
+    pub struct FailureClassification;
+    pub struct ProcessedAmountInReportingCurrency;
+    pub struct DisputeRate;
+    pub struct FirstAttemptSuccessRate;
+    pub struct ConnectorLatency;
 }
 
 impl From<PaymentMetrics> for NameDescription {
@@ -147,7 +196,7 @@ impl From<PaymentDimensions> for NameDescription {
     }
 }
 
-#[derive(Debug, serde::Serialize, Eq)]
+#[derive(Debug, Clone, serde::Serialize, Eq)]
 pub struct PaymentMetricsBucketIdentifier {
     pub currency: Option<Currency>,
     pub status: Option<AttemptStatus>,
@@ -159,6 +208,11 @@ pub struct PaymentMetricsBucketIdentifier {
     pub client_source: Option<String>,
     pub client_version: Option<String>,
     pub profile_id: Option<String>,
+    pub card_funding_type: Option<String>,
+    pub card_bin: Option<String>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
     #[serde(rename = "time_range")]
     pub time_bucket: TimeRange,
     // Coz FE sucks
@@ -179,6 +233,11 @@ impl PaymentMetricsBucketIdentifier {
         client_source: Option<String>,
         client_version: Option<String>,
         profile_id: Option<String>,
+        card_funding_type: Option<String>,
+        card_bin: Option<String>,
+        device_fingerprint: Option<String>,
+        three_ds_version: Option<String>,
+        three_ds_message_version: Option<String>,
         normalized_time_range: TimeRange,
     ) -> Self {
         Self {
@@ -191,6 +250,11 @@ impl PaymentMetricsBucketIdentifier {
             client_source,
             client_version,
             profile_id,
+            card_funding_type,
+            card_bin,
+            device_fingerprint,
+            three_ds_version,
+            three_ds_message_version,
             time_bucket: normalized_time_range,
             start_time: normalized_time_range.start_time,
         }
@@ -208,6 +272,11 @@ impl Hash for PaymentMetricsBucketIdentifier {
         self.client_source.hash(state);
         self.client_version.hash(state);
         self.profile_id.hash(state);
+        self.card_funding_type.hash(state);
+        self.card_bin.hash(state);
+        self.device_fingerprint.hash(state);
+        self.three_ds_version.hash(state);
+        self.three_ds_message_version.hash(state);
         self.time_bucket.hash(state);
     }
 }
@@ -222,7 +291,7 @@ impl PartialEq for PaymentMetricsBucketIdentifier {
     }
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Default, serde::Serialize)]
 pub struct PaymentMetricsBucketValue {
     pub payment_success_rate: Option<f64>,
     pub payment_count: Option<u64>,
@@ -233,6 +302,63 @@ pub struct PaymentMetricsBucketValue {
     pub retries_count: Option<u64>,
     pub retries_amount_processed: Option<u64>,
     pub connector_success_rate: Option<f64>,
+    pub chargeback_ratio: Option<f64>,
+    pub chargeback_ratio_threshold_exceeded: Option<bool>,
+    pub surcharge_collected: Option<u64>,
+    pub avg_surcharge_collected: Option<f64>,
+    pub payment_success_rate_lower_bound: Option<f64>,
+    pub payment_success_rate_upper_bound: Option<f64>,
+    pub captured_amount: Option<u64>,
+    pub captured_amount_in_reporting_currency: Option<u64>,
+    pub reporting_currency: Option<Currency>,
+    /// Days with an amount captured in a currency that has no FX rate, these amounts are not part
+    /// of `captured_amount_in_reporting_currency`
+    pub fx_rate_missing_dates: Option<Vec<String>>,
+    pub decline_rate: Option<f64>,
+    /// Whether the decline rate of the time bucket is unusually high compared to the trailing
+    /// time buckets, not set when there are too few trailing time buckets to compare with
+    pub decline_spike: Option<bool>,
+    /// Success rate of the payments of the authentication type of the bucket
+    pub auth_type_success_rate: Option<f64>,
+    /// Number of payments of the authentication type of the bucket
+    pub auth_type_payment_count: Option<u64>,
+    /// Time taken to tokenize the payment method in the locker, in milliseconds
+    pub tokenization_latency_p50: Option<u64>,
+    pub tokenization_latency_p90: Option<u64>,
+    pub tokenization_latency_p99: Option<u64>,
+    /// Success rate of the payments made from the client source of the bucket
+    pub client_source_success_rate: Option<f64>,
+    /// Number of payments made from the client source of the bucket
+    pub client_source_payment_count: Option<u64>,
+    /// Captured amount minus the amount refunded and the connector fees, in the minor unit of
+    /// the currency of the bucket
+    pub net_revenue: Option<i64>,
+    /// Average number of active payment methods saved per customer
+    pub avg_payment_methods_per_customer: Option<f64>,
+    /// Number of customers that have saved each number of active payment methods
+    pub payment_methods_per_customer_distribution: Option<Vec<PaymentMethodCountBucket>>,
+    /// Average time successful payments took to succeed, in seconds
+    pub avg_time_to_success_seconds: Option<f64>,
+    /// Number of payments per number of attempts made, for the payments that succeeded and for
+    /// those that failed or were cancelled. Payments that are still in progress are left out.
+    pub retries_per_payment: Option<RetriesPerPayment>,
+    /// Number of failed payments per failure class, classified by the error code of the connector
+    pub failure_classification: Option<FailureClassCounts>,
+    /// Processed amount of all currencies of the bucket in the reporting currency
+    pub processed_amount_in_reporting_currency: Option<u64>,
+    /// Processed amount per payment currency for which no FX rate was available, these amounts
+    /// are not part of `processed_amount_in_reporting_currency`
+    pub processed_amount_without_fx_rate: Option<Vec<CurrencyAmount>>,
+    /// Disputes raised per successful payment of the connector of the bucket, between 0 and 1
+    pub dispute_rate: Option<f64>,
+    /// Percentage of the first attempts of payments that succeeded, without counting retries
+    pub first_attempt_success_rate: Option<f64>,
+    /// Average time the connector of the bucket took to process payment attempts, in
+    /// milliseconds
+    pub avg_latency_ms: Option<f64>,
+    /// 95th percentile of the time the connector of the bucket took to process payment
+    /// attempts, in milliseconds
+    pub p95_latency_ms: Option<f64>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -241,4 +367,564 @@ pub struct MetricsBucketResponse {
     pub values: PaymentMetricsBucketValue,
     #[serde(flatten)]
     pub dimensions: PaymentMetricsBucketIdentifier,
+    /// Buckets of the next finer rollup granularity that fall within this bucket
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rollup_buckets: Vec<MetricsBucketResponse>,
+}
+
+/// Amounts are in the minor unit of their own currency, so they are never added up across
+/// currencies
+pub const CURRENCY_TOTALS_NOTE: &str =
+    "Sub-totals are per currency, amounts of different currencies are not combined";
+
+/// Sub-totals of the amount and count metrics of the payments of a single currency
+#[derive(Debug, Default, PartialEq, serde::Serialize)]
+pub struct CurrencySubTotal {
+    pub currency: Option<Currency>,
+    pub payment_count: Option<u64>,
+    pub payment_success_count: Option<u64>,
+    pub payment_processed_amount: Option<u64>,
+    pub captured_amount: Option<u64>,
+    pub surcharge_collected: Option<u64>,
+}
+
+/// Amount in `currency`, in its minor unit
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct CurrencyAmount {
+    pub currency: Currency,
+    pub amount: u64,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize)]
+pub struct CurrencyTotals {
+    pub sub_totals: Vec<CurrencySubTotal>,
+    pub note: &'static str,
+}
+
+impl CurrencyTotals {
+    /// Adds up the buckets of each currency, buckets without a currency are added up together
+    pub fn from_buckets(buckets: &[MetricsBucketResponse]) -> Self {
+        fn add(total: &mut Option<u64>, value: Option<u64>) {
+            if let Some(value) = value {
+                *total = Some(total.unwrap_or_default().saturating_add(value));
+            }
+        }
+
+        let mut sub_totals: HashMap<Option<Currency>, CurrencySubTotal> = HashMap::new();
+        for bucket in buckets {
+            let currency = bucket.dimensions.currency;
+            let sub_total = sub_totals
+                .entry(currency)
+                .or_insert_with(|| CurrencySubTotal {
+                    currency,
+                    ..Default::default()
+                });
+            add(&mut sub_total.payment_count, bucket.values.payment_count);
+            add(
+                &mut sub_total.payment_success_count,
+                bucket.values.payment_success_count,
+            );
+            add(
+                &mut sub_total.payment_processed_amount,
+                bucket.values.payment_processed_amount,
+            );
+            add(
+                &mut sub_total.captured_amount,
+                bucket.values.captured_amount,
+            );
+            add(
+                &mut sub_total.surcharge_collected,
+                bucket.values.surcharge_collected,
+            );
+        }
+        let mut sub_totals: Vec<CurrencySubTotal> = sub_totals.into_values().collect();
+        sub_totals.sort_by_key(|sub_total| sub_total.currency.map(|currency| currency.to_string()));
+
+        Self {
+            sub_totals,
+            note: CURRENCY_TOTALS_NOTE,
+        }
+    }
+}
+
+/// Nearest rank percentiles of a latency distribution, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyPercentiles {
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+}
+
+impl LatencyPercentiles {
+    /// `timings` holds each latency along with the number of payments that took that long, in
+    /// any order. Returns `None` when there are no timings.
+    pub fn from_timings(timings: &[(u64, u64)]) -> Option<Self> {
+        Some(Self {
+            p50: nearest_rank_percentile(timings, 50)?,
+            p90: nearest_rank_percentile(timings, 90)?,
+            p99: nearest_rank_percentile(timings, 99)?,
+        })
+    }
+}
+
+/// Nearest rank `percentile`, out of 100, of `timings`, which holds each latency along with the
+/// number of payments that took that long, in any order. Returns `None` when there are no
+/// timings.
+pub fn nearest_rank_percentile(timings: &[(u64, u64)], percentile: u64) -> Option<u64> {
+    let mut timings = timings.to_vec();
+    timings.sort_unstable_by_key(|(latency, _)| *latency);
+    let total = timings
+        .iter()
+        .fold(0u64, |total, (_, count)| total.saturating_add(*count));
+    if total == 0 {
+        return None;
+    }
+
+    let rank = percentile.saturating_mul(total).div_ceil(100).max(1);
+    let mut seen = 0u64;
+    timings
+        .iter()
+        .find(|(_, count)| {
+            seen = seen.saturating_add(*count);
+            seen >= rank
+        })
+        .map(|(latency, _)| *latency)
+}
+
+/// Number of customers that have saved `payment_method_count` active payment methods
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PaymentMethodCountBucket {
+    pub payment_method_count: u64,
+    pub customer_count: u64,
+}
+
+/// Saved payment methods per customer, averaged over the customers of the distribution
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct PaymentMethodsPerCustomer {
+    pub average: f64,
+    pub distribution: Vec<PaymentMethodCountBucket>,
+}
+
+impl PaymentMethodsPerCustomer {
+    /// `counts` holds each number of payment methods along with the number of customers that
+    /// have saved that many, in any order. Returns `None` when there are no customers.
+    pub fn from_counts(counts: &[(u64, u64)]) -> Option<Self> {
+        let mut distribution = Vec::<PaymentMethodCountBucket>::new();
+        for (payment_method_count, customer_count) in counts {
+            match distribution
+                .iter_mut()
+                .find(|bucket| bucket.payment_method_count == *payment_method_count)
+            {
+                Some(bucket) => {
+                    bucket.customer_count = bucket.customer_count.saturating_add(*customer_count)
+                }
+                None => distribution.push(PaymentMethodCountBucket {
+                    payment_method_count: *payment_method_count,
+                    customer_count: *customer_count,
+                }),
+            }
+        }
+        distribution.retain(|bucket| bucket.customer_count > 0);
+        distribution.sort_unstable_by_key(|bucket| bucket.payment_method_count);
+
+        let customers = distribution.iter().fold(0u64, |total, bucket| {
+            total.saturating_add(bucket.customer_count)
+        });
+        if customers == 0 {
+            return None;
+        }
+        let payment_methods = distribution.iter().fold(0u64, |total, bucket| {
+            total.saturating_add(
+                bucket
+                    .payment_method_count
+                    .saturating_mul(bucket.customer_count),
+            )
+        });
+
+        #[allow(clippy::as_conversions)]
+        let average = payment_methods as f64 / customers as f64;
+        Some(Self {
+            average,
+            distribution,
+        })
+    }
+}
+
+/// Number of failed payments declined by the issuer, failed at the connector, blocked as fraud
+/// and with an error code that is not classified
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct FailureClassCounts {
+    pub issuer_decline: u64,
+    pub gateway_error: u64,
+    pub fraud_block: u64,
+    pub unknown: u64,
+}
+
+/// Number of payments that were attempted the number of times in `attempts`. The last bucket,
+/// `4+`, counts the payments attempted four times or more.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct AttemptCountBucket {
+    pub attempts: &'static str,
+    pub payment_count: u64,
+}
+
+/// Payments bucketed by the number of attempts made, with the payments that eventually succeeded
+/// kept apart from those that failed or were cancelled
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RetriesPerPayment {
+    pub succeeded: Vec<AttemptCountBucket>,
+    pub not_succeeded: Vec<AttemptCountBucket>,
+}
+
+impl RetriesPerPayment {
+    const ATTEMPT_BUCKETS: [&'static str; 4] = ["1", "2", "3", "4+"];
+
+    /// `counts` holds each number of attempts, whether the payments succeeded and the number of
+    /// such payments, in any order. Returns `None` when there are no payments.
+    pub fn from_counts(counts: &[(u64, bool, u64)]) -> Option<Self> {
+        let mut succeeded = [0u64; Self::ATTEMPT_BUCKETS.len()];
+        let mut not_succeeded = [0u64; Self::ATTEMPT_BUCKETS.len()];
+        for (attempts, is_succeeded, payment_count) in counts {
+            let Some(bucket) = usize::try_from(*attempts)
+                .ok()
+                .and_then(|attempts| attempts.checked_sub(1))
+                .map(|bucket| bucket.min(Self::ATTEMPT_BUCKETS.len() - 1))
+            else {
+                continue;
+            };
+            let payments = if *is_succeeded {
+                &mut succeeded[bucket]
+            } else {
+                &mut not_succeeded[bucket]
+            };
+            *payments = payments.saturating_add(*payment_count);
+        }
+
+        if succeeded
+            .iter()
+            .chain(not_succeeded.iter())
+            .all(|count| *count == 0)
+        {
+            return None;
+        }
+
+        let buckets = |payment_counts: [u64; 4]| {
+            Self::ATTEMPT_BUCKETS
+                .into_iter()
+                .zip(payment_counts)
+                .map(|(attempts, payment_count)| AttemptCountBucket {
+                    attempts,
+                    payment_count,
+                })
+                .collect()
+        };
+        Some(Self {
+            succeeded: buckets(succeeded),
+            not_succeeded: buckets(not_succeeded),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(
+        currency: Currency,
+        payment_count: u64,
+        payment_processed_amount: u64,
+    ) -> MetricsBucketResponse {
+        MetricsBucketResponse {
+            values: PaymentMetricsBucketValue {
+                payment_count: Some(payment_count),
+                payment_processed_amount: Some(payment_processed_amount),
+                ..Default::default()
+            },
+            dimensions: PaymentMetricsBucketIdentifier::new(
+                Some(currency),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                TimeRange {
+                    start_time: common_utils::date_time::now(),
+                    end_time: None,
+                },
+            ),
+            rollup_buckets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_currency_totals_are_not_summed_across_currencies() {
+        let buckets = vec![
+            bucket(Currency::USD, 2, 1500),
+            bucket(Currency::EUR, 1, 700),
+            bucket(Currency::USD, 3, 2500),
+        ];
+
+        let totals = CurrencyTotals::from_buckets(&buckets);
+
+        assert_eq!(
+            totals.sub_totals,
+            vec![
+                CurrencySubTotal {
+                    currency: Some(Currency::EUR),
+                    payment_count: Some(1),
+                    payment_processed_amount: Some(700),
+                    ..Default::default()
+                },
+                CurrencySubTotal {
+                    currency: Some(Currency::USD),
+                    payment_count: Some(5),
+                    payment_processed_amount: Some(4000),
+                    ..Default::default()
+                },
+            ]
+        );
+        assert_eq!(totals.note, CURRENCY_TOTALS_NOTE);
+    }
+
+    #[test]
+    fn test_tokenization_latency_percentiles_match_fixture() {
+        let timings = [120, 85, 300, 95, 110, 2500, 140, 90, 100, 130].map(|latency| (latency, 1));
+
+        assert_eq!(
+            LatencyPercentiles::from_timings(&timings),
+            Some(LatencyPercentiles {
+                p50: 110,
+                p90: 300,
+                p99: 2500,
+            })
+        );
+    }
+
+    #[test]
+    fn test_tokenization_latency_percentiles_weigh_repeated_timings() {
+        let timings = [(1000, 10), (100, 50), (200, 40)];
+
+        assert_eq!(
+            LatencyPercentiles::from_timings(&timings),
+            Some(LatencyPercentiles {
+                p50: 100,
+                p90: 200,
+                p99: 1000,
+            })
+        );
+        assert_eq!(LatencyPercentiles::from_timings(&[]), None);
+    }
+
+    #[test]
+    fn test_buckets_are_grouped_by_card_funding_type() {
+        let time_range = TimeRange {
+            start_time: common_utils::date_time::now(),
+            end_time: None,
+        };
+        let identifier = |card_funding_type: CardFundingType| {
+            PaymentMetricsBucketIdentifier::new(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(card_funding_type.to_string()),
+                None,
+                None,
+                None,
+                None,
+                time_range,
+            )
+        };
+
+        assert_eq!(
+            PaymentDimensions::CardFundingType.to_string(),
+            "card_funding_type"
+        );
+        assert!(identifier(CardFundingType::Credit) == identifier(CardFundingType::Credit));
+        assert!(identifier(CardFundingType::Credit) != identifier(CardFundingType::Debit));
+        assert_eq!(
+            identifier(CardFundingType::Unknown).card_funding_type,
+            Some("unknown".to_string())
+        );
+    }
+
+    #[test]
+    fn test_buckets_are_grouped_by_card_bin() {
+        let time_range = TimeRange {
+            start_time: common_utils::date_time::now(),
+            end_time: None,
+        };
+        let identifier = |card_bin: Option<&str>| {
+            PaymentMetricsBucketIdentifier::new(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                card_bin.map(ToString::to_string),
+                None,
+                None,
+                None,
+                time_range,
+            )
+        };
+
+        assert_eq!(PaymentDimensions::CardBin.to_string(), "card_bin");
+        assert!(identifier(Some("42424242")) == identifier(Some("42424242")));
+        assert!(identifier(Some("42424242")) != identifier(Some("55555555")));
+        assert!(identifier(Some("42424242")) != identifier(None));
+        assert_eq!(
+            serde_json::to_value(identifier(Some("424242"))).unwrap()["card_bin"],
+            "424242"
+        );
+    }
+
+    #[test]
+    fn test_buckets_are_grouped_by_device_fingerprint() {
+        let time_range = TimeRange {
+            start_time: common_utils::date_time::now(),
+            end_time: None,
+        };
+        let identifier = |device_fingerprint: Option<&str>| {
+            PaymentMetricsBucketIdentifier::new(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                device_fingerprint.map(ToString::to_string),
+                None,
+                None,
+                time_range,
+            )
+        };
+
+        assert!(identifier(Some("fp_1")) == identifier(Some("fp_1")));
+        assert!(identifier(Some("fp_1")) != identifier(Some("fp_2")));
+        assert!(identifier(Some("fp_1")) != identifier(None));
+        assert_eq!(
+            serde_json::to_value(identifier(Some("fp_1"))).unwrap()["device_fingerprint"],
+            "fp_1"
+        );
+    }
+
+    #[test]
+    fn test_buckets_are_grouped_by_three_ds_version() {
+        let time_range = TimeRange {
+            start_time: common_utils::date_time::now(),
+            end_time: None,
+        };
+        let identifier = |three_ds_version: &str, three_ds_message_version: &str| {
+            PaymentMetricsBucketIdentifier::new(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(three_ds_version.to_string()),
+                Some(three_ds_message_version.to_string()),
+                time_range,
+            )
+        };
+
+        assert!(identifier("2.1.0", "2.1.0") == identifier("2.1.0", "2.1.0"));
+        assert!(identifier("2.1.0", "2.1.0") != identifier("2.2.0", "2.1.0"));
+        assert!(identifier("2.2.0", "2.1.0") != identifier("2.2.0", "2.2.0"));
+        assert_eq!(
+            serde_json::to_value(identifier("2.2.0", "2.1.0")).unwrap()["three_ds_version"],
+            "2.2.0"
+        );
+    }
+
+    #[test]
+    fn test_payment_methods_per_customer_match_fixture() {
+        // 3 customers with 1, 2 customers with 2, 1 customer with 5 and 4 customers with none
+        let counts = [(2, 2), (1, 3), (5, 1), (0, 4)];
+
+        let payment_methods_per_customer = PaymentMethodsPerCustomer::from_counts(&counts).unwrap();
+
+        assert_eq!(payment_methods_per_customer.average, 1.2);
+        assert_eq!(
+            payment_methods_per_customer
+                .distribution
+                .iter()
+                .map(|bucket| (bucket.payment_method_count, bucket.customer_count))
+                .collect::<Vec<_>>(),
+            vec![(0, 4), (1, 3), (2, 2), (5, 1)]
+        );
+    }
+
+    #[test]
+    fn test_payment_methods_per_customer_without_customers_is_none() {
+        assert_eq!(PaymentMethodsPerCustomer::from_counts(&[]), None);
+        assert_eq!(PaymentMethodsPerCustomer::from_counts(&[(0, 0)]), None);
+    }
+
+    #[test]
+    fn test_retries_per_payment_buckets_attempt_counts() {
+        let counts = [
+            (1, true, 10),
+            (2, true, 4),
+            (3, true, 2),
+            (4, true, 1),
+            (6, true, 1),
+            (1, false, 3),
+            (5, false, 2),
+            (0, false, 7),
+        ];
+
+        let retries_per_payment = RetriesPerPayment::from_counts(&counts).unwrap();
+
+        let payment_counts = |buckets: &[AttemptCountBucket]| {
+            buckets
+                .iter()
+                .map(|bucket| (bucket.attempts, bucket.payment_count))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            payment_counts(&retries_per_payment.succeeded),
+            vec![("1", 10), ("2", 4), ("3", 2), ("4+", 2)]
+        );
+        assert_eq!(
+            payment_counts(&retries_per_payment.not_succeeded),
+            vec![("1", 3), ("2", 0), ("3", 0), ("4+", 2)]
+        );
+    }
+
+    #[test]
+    fn test_retries_per_payment_without_payments_is_none() {
+        assert_eq!(RetriesPerPayment::from_counts(&[]), None);
+        assert_eq!(RetriesPerPayment::from_counts(&[(2, true, 0)]), None);
+    }
 }
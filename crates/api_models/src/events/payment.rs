@@ -16,7 +16,8 @@ use crate::{
         PaymentMethodResponse, PaymentMethodUpdate,
     },
     payments::{
-        ExtendedCardInfoResponse, PaymentIdType, PaymentListConstraints,
+        ExtendedCardInfoResponse, PaymentAttemptReconciliationExportConstraints,
+        PaymentAttemptStatusTimelineResponse, PaymentIdType, PaymentListConstraints,
         PaymentListFilterConstraints, PaymentListFilters, PaymentListFiltersV2,
         PaymentListResponse, PaymentListResponseV2, PaymentsAggregateResponse,
         PaymentsApproveRequest, PaymentsCancelRequest, PaymentsCaptureRequest,
@@ -226,6 +227,12 @@ impl ApiEventMetric for PaymentListConstraints {
     }
 }
 
+impl ApiEventMetric for PaymentAttemptReconciliationExportConstraints {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ResourceListAPI)
+    }
+}
+
 impl ApiEventMetric for PaymentListResponse {
     fn get_api_event_type(&self) -> Option<ApiEventsType> {
         Some(ApiEventsType::ResourceListAPI)
@@ -265,6 +272,14 @@ impl ApiEventMetric for PaymentsExternalAuthenticationRequest {
 
 impl ApiEventMetric for ExtendedCardInfoResponse {}
 
+impl ApiEventMetric for PaymentAttemptStatusTimelineResponse {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::Payment {
+            payment_id: self.payment_id.clone(),
+        })
+    }
+}
+
 impl ApiEventMetric for PaymentsManualUpdateRequest {
     fn get_api_event_type(&self) -> Option<ApiEventsType> {
         Some(ApiEventsType::Payment {
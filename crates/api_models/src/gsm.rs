@@ -62,6 +62,9 @@ pub enum GsmDecision {
     Requeue,
     #[default]
     DoDefault,
+    /// The connector has failed a 3DS payment with a soft decline, the retry is decided by the
+    /// soft 3DS failure policy of the profile
+    SoftThreeDsFailure,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, ToSchema)]
@@ -310,6 +310,7 @@ impl PaymentMethodCreate {
                     card_network: payment_method_migrate_card.card_network.clone(),
                     card_issuer: payment_method_migrate_card.card_issuer.clone(),
                     card_type: payment_method_migrate_card.card_type.clone(),
+                    preferred_network: None,
                 });
 
         Self {
@@ -450,6 +451,10 @@ pub struct CardDetail {
 
     /// Card Type
     pub card_type: Option<String>,
+
+    /// The network to process a co-badged card on. Must be one of the networks of the card
+    #[schema(value_type = Option<CardNetwork>, example = "CartesBancaires")]
+    pub preferred_network: Option<api_enums::CardNetwork>,
 }
 
 #[cfg(all(feature = "v2", feature = "payment_methods_v2"))]
@@ -498,6 +503,10 @@ pub struct CardDetail {
 
     /// Card Type
     pub card_type: Option<CardType>,
+
+    /// The network to process a co-badged card on. Must be one of the networks of the card
+    #[schema(value_type = Option<CardNetwork>, example = "CartesBancaires")]
+    pub preferred_network: Option<api_enums::CardNetwork>,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -589,6 +598,7 @@ impl CardDetailUpdate {
             card_network: None,
             card_issuer: None,
             card_type: None,
+            preferred_network: None,
         }
     }
 }
@@ -625,6 +635,7 @@ impl CardDetailUpdate {
             card_network: None,
             card_issuer: None,
             card_type: None,
+            preferred_network: None,
         }
     }
 }
@@ -768,6 +779,10 @@ pub struct CardDetailsPaymentMethod {
     pub card_type: Option<String>,
     #[serde(default = "saved_in_locker_default")]
     pub saved_to_locker: bool,
+    /// All the networks of a co-badged card
+    pub card_networks: Option<Vec<api_enums::CardNetwork>>,
+    /// The network the merchant prefers to process the card on
+    pub preferred_network: Option<api_enums::CardNetwork>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -1026,6 +1041,8 @@ impl From<CardDetail> for CardDetailsPaymentMethod {
             card_network: item.card_network,
             card_type: item.card_type.map(|card| card.to_string()),
             saved_to_locker: true,
+            card_networks: None,
+            preferred_network: item.preferred_network,
         }
     }
 }
@@ -1048,6 +1065,8 @@ impl From<CardDetailFromLocker> for CardDetailsPaymentMethod {
             card_network: item.card_network,
             card_type: item.card_type,
             saved_to_locker: item.saved_to_locker,
+            card_networks: None,
+            preferred_network: None,
         }
     }
 }
@@ -1067,6 +1086,8 @@ impl From<CardDetailFromLocker> for CardDetailsPaymentMethod {
             card_network: item.card_network,
             card_type: item.card_type,
             saved_to_locker: item.saved_to_locker,
+            card_networks: None,
+            preferred_network: None,
         }
     }
 }
@@ -1286,7 +1307,7 @@ pub struct RequestPaymentMethodTypes {
     not(feature = "payment_methods_v2")
 ))]
 //List Payment Method
-#[derive(Debug, Clone, serde::Serialize, Default, ToSchema)]
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
 #[serde(deny_unknown_fields)]
 pub struct PaymentMethodListRequest {
     /// This is a 15 minute expiry token which shall be used from the client to authenticate and perform sessions from the SDK
@@ -1320,6 +1341,45 @@ pub struct PaymentMethodListRequest {
     /// Indicates the limit of last used payment methods
     #[schema(example = 1)]
     pub limit: Option<i64>,
+
+    /// Number of last used payment methods to skip before listing the saved payment methods of a customer
+    #[schema(example = 10)]
+    pub offset: Option<i64>,
+
+    /// Filter the saved payment methods of a customer by payment method. Not applied when listing the payment methods enabled for a merchant
+    #[schema(value_type = Option<Vec<PaymentMethod>>, example = json!(["card", "wallet"]))]
+    pub payment_method_types: Option<Vec<api_enums::PaymentMethod>>,
+
+    /// Filter the saved payment methods of a customer by payment method type. Not applied when listing the payment methods enabled for a merchant
+    #[schema(value_type = Option<Vec<PaymentMethodType>>, example = json!(["credit", "apple_pay"]))]
+    pub payment_method_subtypes: Option<Vec<api_enums::PaymentMethodType>>,
+
+    /// Boolean to exclude saved cards that have expired from the saved payment methods of a customer. Default is true.
+    #[schema(default = true, example = false)]
+    pub exclude_expired: bool,
+}
+
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+impl Default for PaymentMethodListRequest {
+    fn default() -> Self {
+        Self {
+            client_secret: None,
+            accepted_countries: None,
+            accepted_currencies: None,
+            amount: None,
+            recurring_enabled: None,
+            installment_payment_enabled: None,
+            card_networks: None,
+            limit: None,
+            offset: None,
+            payment_method_types: None,
+            payment_method_subtypes: None,
+            exclude_expired: true,
+        }
+    }
 }
 
 #[cfg(all(
@@ -1395,6 +1455,24 @@ impl<'de> serde::Deserialize<'de> for PaymentMethodListRequest {
                         "limit" => {
                             set_or_reject_duplicate(&mut output.limit, "limit", map.next_value()?)?;
                         }
+                        "offset" => {
+                            set_or_reject_duplicate(
+                                &mut output.offset,
+                                "offset",
+                                map.next_value()?,
+                            )?;
+                        }
+                        "payment_method_type" => match output.payment_method_types.as_mut() {
+                            Some(inner) => inner.push(map.next_value()?),
+                            None => output.payment_method_types = Some(vec![map.next_value()?]),
+                        },
+                        "payment_method_subtype" => match output.payment_method_subtypes.as_mut() {
+                            Some(inner) => inner.push(map.next_value()?),
+                            None => output.payment_method_subtypes = Some(vec![map.next_value()?]),
+                        },
+                        "exclude_expired" => {
+                            output.exclude_expired = map.next_value()?;
+                        }
                         _ => {}
                     }
                 }
@@ -1622,6 +1700,12 @@ pub struct CustomerPaymentMethodsListResponse {
     pub customer_payment_methods: Vec<CustomerPaymentMethod>,
     /// Returns whether a customer id is not tied to a payment intent (only when the request is made against a client secret)
     pub is_guest_customer: Option<bool>,
+    /// Total number of saved payment methods of the customer that match the request
+    #[schema(example = 25)]
+    pub total_count: Option<i64>,
+    /// Offset to request the next page of saved payment methods with, when there are more to list
+    #[schema(example = 10)]
+    pub next_offset: Option<i64>,
 }
 
 #[cfg(all(feature = "v2", feature = "payment_methods_v2"))]
@@ -2113,6 +2197,30 @@ pub enum MigrationStatus {
     Failed,
 }
 
+#[derive(Debug, Default, serde::Serialize)]
+pub struct PaymentMethodMigrateBatchResponse {
+    /// Number of payment methods that were migrated
+    pub succeeded: usize,
+    /// Number of payment methods that could not be migrated
+    pub failed: usize,
+    /// Outcome of each payment method, in the order they were provided
+    pub results: Vec<PaymentMethodMigrationResponse>,
+}
+
+impl From<Vec<PaymentMethodMigrationResponse>> for PaymentMethodMigrateBatchResponse {
+    fn from(results: Vec<PaymentMethodMigrationResponse>) -> Self {
+        let succeeded = results
+            .iter()
+            .filter(|result| matches!(result.migration_status, MigrationStatus::Success))
+            .count();
+        Self {
+            succeeded,
+            failed: results.len() - succeeded,
+            results,
+        }
+    }
+}
+
 type PaymentMethodMigrationResponseType =
     (Result<PaymentMethodResponse, String>, PaymentMethodRecord);
 #[cfg(all(
@@ -559,6 +559,21 @@ pub struct PaymentsRequest {
     }"#)]
     pub browser_info: Option<serde_json::Value>,
 
+    /// A fingerprint of the customer's device generated on the client, recorded on the payment attempt for risk checks. Only alphanumeric characters, `-` and `_` are allowed
+    #[schema(max_length = 64, example = "d7f3a1c9e2b84f6a9c0d5e1b2a3f4c5d")]
+    #[remove_in(PaymentsUpdateRequest)]
+    pub device_fingerprint: Option<String>,
+
+    /// Explicitly request a 3DS challenge for this payment. When set to `true`, the 3DS requestor challenge indicator sent to the authentication provider is set to "challenge requested", overriding the default preference
+    #[schema(example = true)]
+    #[remove_in(PaymentsUpdateRequest)]
+    pub request_challenge: Option<bool>,
+
+    /// Skip saving the payment method for this payment, even if `setup_future_usage` is set. The connector still receives the `setup_future_usage` of the payment. Cannot be used along with `mandate_data`
+    #[schema(example = true)]
+    #[remove_in(PaymentsUpdateRequest)]
+    pub skip_save_payment_method: Option<bool>,
+
     /// To indicate the type of payment experience that the payment method would go through
     #[schema(value_type = Option<PaymentExperience>, example = "redirect_to_url")]
     pub payment_experience: Option<api_enums::PaymentExperience>,
@@ -645,6 +660,12 @@ pub struct PaymentsRequest {
     #[schema(example = true)]
     pub request_external_three_ds_authentication: Option<bool>,
 
+    /// Identifier of an authentication completed in a prior authentication call, which has to be linked to this payment.
+    /// If passed, the authentication is not performed again during confirm.
+    #[schema(example = "authn_Kp9ZGxvWPX3bHdx7tx0T")]
+    #[remove_in(PaymentsUpdateRequest, PaymentsCreateRequest)]
+    pub authentication_id: Option<String>,
+
     /// Details required for recurring payment
     pub recurring_details: Option<RecurringDetails>,
 
@@ -796,6 +817,23 @@ mod payments_request_test {
             Some(vec!["customer_id and customer.id"])
         );
     }
+
+    #[test]
+    fn test_device_fingerprint_round_trips_through_request() {
+        let payments_request = PaymentsRequest {
+            device_fingerprint: Some("d7f3a1c9e2b84f6a".to_string()),
+            ..Default::default()
+        };
+
+        let deserialized = serde_json::to_string(&payments_request)
+            .and_then(|request| serde_json::from_str::<PaymentsRequest>(&request));
+
+        assert!(matches!(
+            deserialized,
+            Ok(PaymentsRequest { device_fingerprint: Some(ref device_fingerprint), .. })
+                if device_fingerprint == "d7f3a1c9e2b84f6a"
+        ));
+    }
 }
 
 /// Fee information to be charged on the payment being collected
@@ -3962,6 +4000,10 @@ pub struct PaymentsResponse {
     /// Provided mandate information for creating a mandate
     pub mandate_data: Option<MandateData>,
 
+    /// Indicates whether a mandate was created by this payment. This is present only for payments which set up a mandate
+    #[schema(example = true)]
+    pub mandate_created: Option<bool>,
+
     /// Indicates that you intend to make future payments with this Payment’s payment method. Providing this parameter will attach the payment method to the Customer, if present, after the Payment is confirmed and any required actions from the user are complete.
     #[schema(value_type = Option<FutureUsage>, example = "off_session")]
     pub setup_future_usage: Option<api_enums::FutureUsage>,
@@ -4128,6 +4170,11 @@ pub struct PaymentsResponse {
     /// Total number of attempts associated with this payment
     pub attempt_count: i16,
 
+    /// Identifier of the payment attempt that this response describes, the active attempt of the
+    /// payment
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4_1")]
+    pub attempt_id: String,
+
     /// Denotes the action(approve or reject) taken by merchant in case of manual review. Manual review can occur when the transaction is marked as risky by the frm_processor, payment processor or when there is underpayment/over payment incase of crypto payment
     pub merchant_decision: Option<String>,
 
@@ -4196,6 +4243,43 @@ pub struct PaymentsResponse {
 
     /// Connector Identifier for the payment method
     pub connector_mandate_id: Option<String>,
+
+    /// Token reference of the card used for the payment, returned when enabled in the business profile
+    pub payment_method_token_reference: Option<PaymentMethodTokenReference>,
+
+    /// Routing rule that selected the connector, returned when enabled in the business profile
+    pub matched_routing_rule: Option<MatchedRoutingRule>,
+
+    /// Fraud risk score returned by the connector, normalized to a 0-100 scale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<u8>, example = 42)]
+    pub connector_risk_score: Option<i16>,
+
+    /// Fraud risk decision returned by the connector
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "manual_review")]
+    pub connector_risk_decision: Option<String>,
+
+    /// Fee actually charged by the connector for processing the payment, in minor units
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<i64>, example = 59)]
+    pub connector_fee_amount: Option<MinorUnit>,
+
+    /// Currency in which the connector charged the fee
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub connector_fee_currency: Option<api_enums::Currency>,
+
+    /// Funding type of the card used for the payment, as identified from the card's BIN
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<CardFundingType>, example = "credit")]
+    pub card_funding_type: Option<api_enums::CardFundingType>,
+
+    /// Whether the liability for fraudulent chargebacks shifted to the issuer, following a 3DS
+    /// authentication of the payment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = true)]
+    pub liability_shift: Option<bool>,
 }
 
 /// Fee information to be charged on the payment being collected
@@ -4216,6 +4300,32 @@ pub struct PaymentChargeResponse {
     pub transfer_account_id: String,
 }
 
+/// Token reference of the card used for a payment. This never contains the card number.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, ToSchema)]
+pub struct PaymentMethodTokenReference {
+    /// Hyperswitch token (payment method id) of the stored card
+    #[schema(example = "pm_01926c58bc6e77c09e809964e72af8c8")]
+    pub payment_method_id: String,
+
+    /// Network token requestor reference id, present if the card is network tokenized
+    pub network_token_reference_id: Option<String>,
+}
+
+/// Rule of a rule based routing algorithm that matched the payment
+#[derive(Clone, Debug, PartialEq, serde::Serialize, ToSchema)]
+pub struct MatchedRoutingRule {
+    /// Identifier of the routing algorithm the rule belongs to
+    #[schema(value_type = Option<String>, example = "routing_ky4nfj7xq2mrvpr4orbv")]
+    pub routing_algorithm_id: Option<id_type::RoutingId>,
+
+    /// Name of the rule that matched
+    #[schema(example = "high_value_card_payments")]
+    pub rule_name: String,
+
+    /// Conditions of the rule that matched, as configured in the routing algorithm
+    pub statements: Vec<crate::routing::ast::IfStatement>,
+}
+
 /// Details of external authentication
 #[derive(Setter, Clone, Default, Debug, PartialEq, serde::Serialize, ToSchema)]
 pub struct ExternalAuthenticationDetailsResponse {
@@ -4327,6 +4437,80 @@ pub struct IncrementalAuthorizationResponse {
     pub previously_authorized_amount: MinorUnit,
 }
 
+/// Constraints of the reconciliation export of payment attempts
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentAttemptReconciliationExportConstraints {
+    /// The start of the time range in which the payment attempts were created
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the time range in which the payment attempts were created. If not passed, the
+    /// attempts created until now are exported
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+
+    /// The number of payment attempts to export
+    #[serde(default = "default_reconciliation_export_limit")]
+    pub limit: u32,
+
+    /// The number of payment attempts to skip, for fetching the following pages of the export
+    #[serde(default)]
+    pub offset: u32,
+}
+
+fn default_reconciliation_export_limit() -> u32 {
+    100
+}
+
+/// A payment attempt of the reconciliation export. Only the amounts, references and dates of
+/// the attempt are exported, and no payment method, customer or authentication data.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct PaymentAttemptReconciliationRecord {
+    /// The identifier of the payment
+    pub payment_id: id_type::PaymentId,
+    /// The identifier of the payment attempt
+    pub attempt_id: String,
+    /// The identifier of the merchant
+    pub merchant_id: id_type::MerchantId,
+    /// The identifier of the profile of the payment
+    pub profile_id: id_type::ProfileId,
+    /// The status of the payment attempt
+    pub status: enums::AttemptStatus,
+    /// The amount of the payment attempt, in the lowest denomination of the currency
+    pub amount: MinorUnit,
+    /// The amount including surcharge and tax
+    pub net_amount: MinorUnit,
+    /// The amount that was requested to be captured
+    pub amount_to_capture: Option<MinorUnit>,
+    /// The currency of the payment attempt
+    pub currency: Option<enums::Currency>,
+    /// The fee charged by the connector for the payment attempt
+    pub connector_fee_amount: Option<MinorUnit>,
+    /// The currency of the fee charged by the connector
+    pub connector_fee_currency: Option<enums::Currency>,
+    /// The connector that processed the payment attempt
+    pub connector: Option<String>,
+    /// The merchant connector account that processed the payment attempt
+    pub merchant_connector_id: Option<id_type::MerchantConnectorAccountId>,
+    /// The reference of the payment attempt at the connector
+    pub connector_transaction_id: Option<String>,
+    /// The reference of the payment at the connector, as sent to the merchant
+    pub connector_response_reference_id: Option<String>,
+    /// The payment method of the payment attempt
+    pub payment_method: Option<enums::PaymentMethod>,
+    /// The payment method type of the payment attempt
+    pub payment_method_type: Option<enums::PaymentMethodType>,
+    /// The capture method of the payment attempt
+    pub capture_method: Option<enums::CaptureMethod>,
+    /// The time at which the payment attempt was created
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    /// The time at which the payment attempt was last updated, such as when it was captured
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub modified_at: PrimitiveDateTime,
+}
+
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct PaymentListResponseV2 {
     /// The number of payments included in the list for given constraints
@@ -6095,6 +6279,31 @@ pub struct ExtendedCardInfoResponse {
     pub payload: String,
 }
 
+#[derive(Debug, serde::Serialize, Clone, ToSchema)]
+pub struct PaymentAttemptStatusTimelineResponse {
+    /// The identifier for the payment
+    #[schema(value_type = String)]
+    pub payment_id: id_type::PaymentId,
+    /// The identifier for the payment attempt
+    pub attempt_id: String,
+    /// The status transitions of the payment attempt, oldest first
+    pub timeline: Vec<PaymentAttemptStatusTransition>,
+}
+
+#[derive(Debug, serde::Serialize, Clone, ToSchema)]
+pub struct PaymentAttemptStatusTransition {
+    /// The status of the payment attempt before the transition
+    #[schema(value_type = AttemptStatus, example = "authorized")]
+    pub previous_status: api_enums::AttemptStatus,
+    /// The status of the payment attempt after the transition
+    #[schema(value_type = AttemptStatus, example = "charged")]
+    pub status: api_enums::AttemptStatus,
+    /// Time at which the transition happened
+    #[schema(value_type = PrimitiveDateTime, example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
 #[cfg(test)]
 mod payments_request_api_contract {
     #![allow(clippy::unwrap_used)]
@@ -325,6 +325,81 @@ pub enum AuthenticationType {
     NoThreeDs,
 }
 
+/// Action to be taken when a connector fails a 3DS payment with a soft decline and recommends retrying it
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SoftThreeDsFailurePolicy {
+    /// Retry the payment on the same connector without 3DS authentication
+    RetryFrictionless,
+    /// Retry the payment on the next eligible connector, with the same authentication type
+    FallThrough,
+}
+
+/// Whether the card number can be stored in the locker when a card payment method is saved
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PanStoragePolicy {
+    /// Store the card number in the locker
+    #[default]
+    StorePan,
+    /// Convert the card to a network token and store only the network token in the locker
+    TokenOnly,
+}
+
+/// The funding type of a card, as identified from its BIN
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CardFundingType {
+    Credit,
+    Debit,
+    Prepaid,
+    /// The BIN lookup did not return a known funding type for the card
+    Unknown,
+}
+
 /// The status of the capture
 #[derive(
     Clone,
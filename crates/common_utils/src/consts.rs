@@ -36,6 +36,9 @@ pub const STRIPE_ACCOUNT_ONBOARDING_DELAY_IN_SECONDS: i64 = 15;
 /// Maximum limit for payment link list get api
 pub const PAYMENTS_LINK_LIST_LIMIT: u32 = 100;
 
+/// Maximum limit for customer payment methods list api
+pub const CUSTOMER_PAYMENT_METHODS_LIST_MAX_LIMIT: i64 = 100;
+
 /// Maximum limit for payouts list get api
 pub const PAYOUTS_LIST_MAX_LIMIT_GET: u32 = 100;
 /// Maximum limit for payouts list post api
@@ -108,11 +108,24 @@ impl MerchantId {
         format!("max_auto_retries_enabled_{}", self.get_string_repr())
     }
 
+    /// get same connector retry decline codes key
+    pub fn get_same_connector_retry_decline_codes_key(&self) -> String {
+        format!(
+            "same_connector_retry_decline_codes_{}",
+            self.get_string_repr()
+        )
+    }
+
     /// get_requires_cvv_key
     pub fn get_requires_cvv_key(&self) -> String {
         format!("{}_requires_cvv", self.get_string_repr())
     }
 
+    /// get_processing_payment_expiry_key
+    pub fn get_processing_payment_expiry_key(&self) -> String {
+        format!("{}_processing_payment_expiry", self.get_string_repr())
+    }
+
     /// get_pm_filters_cgraph_key
     pub fn get_pm_filters_cgraph_key(&self) -> String {
         format!("pm_filters_cgraph_{}", self.get_string_repr())
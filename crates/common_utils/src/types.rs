@@ -6,6 +6,7 @@ pub mod authentication;
 
 use std::{
     borrow::Cow,
+    collections::HashMap,
     fmt::Display,
     ops::{Add, Sub},
     primitive::i64,
@@ -195,6 +196,10 @@ impl SemanticVersion {
     pub fn get_major(&self) -> u64 {
         self.0.major
     }
+    /// returns minor version number
+    pub fn get_minor(&self) -> u64 {
+        self.0.minor
+    }
     /// Constructs new SemanticVersion instance
     pub fn new(major: u64, minor: u64, patch: u64) -> Self {
         Self(Version::new(major, minor, patch))
@@ -974,6 +979,60 @@ pub struct ChargeRefunds {
 
 crate::impl_to_sql_from_sql_json!(ChargeRefunds);
 
+/// Fields of the payments confirm response that a business profile allows to be revealed.
+/// Every field is masked unless it is explicitly allowed, so the default policy is the most restrictive one.
+#[derive(
+    Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    FromSqlRow,
+    AsExpression,
+    ToSchema,
+)]
+#[diesel(sql_type = Jsonb)]
+pub struct ConfirmResponseMaskingPolicy {
+    /// Reveal the last four digits of the card
+    #[serde(default)]
+    pub reveal_card_last4: bool,
+
+    /// Reveal the card issuer and the card issuing country
+    #[serde(default)]
+    pub reveal_card_issuer: bool,
+
+    /// Reveal the billing address of the payment and of the payment method
+    #[serde(default)]
+    pub reveal_billing: bool,
+}
+
+crate::impl_to_sql_from_sql_json!(ConfirmResponseMaskingPolicy);
+
+/// Connectors that a business profile prefers for cards of an issuer country, keyed by the alpha-2
+/// code of the issuer country. The connectors are listed in the order of preference.
+#[derive(
+    Serialize, serde::Deserialize, Debug, Default, Clone, PartialEq, Eq, FromSqlRow, AsExpression,
+)]
+#[diesel(sql_type = Jsonb)]
+pub struct IssuerCountryConnectorPreferences(
+    pub HashMap<enums::CountryAlpha2, Vec<enums::RoutableConnectors>>,
+);
+
+impl IssuerCountryConnectorPreferences {
+    /// Get the preferred connectors for the issuer country
+    pub fn get_preferred_connectors(
+        &self,
+        issuer_country: enums::CountryAlpha2,
+    ) -> Option<&[enums::RoutableConnectors]> {
+        self.0.get(&issuer_country).map(Vec::as_slice)
+    }
+}
+
+crate::impl_to_sql_from_sql_json!(IssuerCountryConnectorPreferences);
+
 /// A common type of domain type that can be used for fields that contain a string with restriction of length
 #[derive(Debug, Clone, Serialize, Hash, PartialEq, Eq, AsExpression)]
 #[diesel(sql_type = sql_types::Text)]
@@ -1,7 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
 use common_enums::{AuthenticationConnectors, UIWidgetFormLayout};
-use common_utils::{encryption::Encryption, pii};
+use common_utils::{
+    encryption::Encryption,
+    pii,
+    types::{ConfirmResponseMaskingPolicy, IssuerCountryConnectorPreferences, MinorUnit},
+};
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
 use masking::Secret;
 
@@ -57,6 +61,15 @@ pub struct Profile {
     pub is_network_tokenization_enabled: bool,
     pub is_auto_retries_enabled: Option<bool>,
     pub max_auto_retries_enabled: Option<i16>,
+    pub is_payment_method_token_response_enabled: Option<bool>,
+    pub customer_spending_limit: Option<MinorUnit>,
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+    pub soft_three_ds_failure_policy: Option<common_enums::SoftThreeDsFailurePolicy>,
+    pub pan_storage_policy: Option<common_enums::PanStoragePolicy>,
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v1")]
@@ -100,6 +113,15 @@ pub struct ProfileNew {
     pub is_network_tokenization_enabled: bool,
     pub is_auto_retries_enabled: Option<bool>,
     pub max_auto_retries_enabled: Option<i16>,
+    pub is_payment_method_token_response_enabled: Option<bool>,
+    pub customer_spending_limit: Option<MinorUnit>,
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+    pub soft_three_ds_failure_policy: Option<common_enums::SoftThreeDsFailurePolicy>,
+    pub pan_storage_policy: Option<common_enums::PanStoragePolicy>,
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v1")]
@@ -140,6 +162,15 @@ pub struct ProfileUpdateInternal {
     pub is_network_tokenization_enabled: Option<bool>,
     pub is_auto_retries_enabled: Option<bool>,
     pub max_auto_retries_enabled: Option<i16>,
+    pub is_payment_method_token_response_enabled: Option<bool>,
+    pub customer_spending_limit: Option<MinorUnit>,
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+    pub soft_three_ds_failure_policy: Option<common_enums::SoftThreeDsFailurePolicy>,
+    pub pan_storage_policy: Option<common_enums::PanStoragePolicy>,
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v1")]
@@ -179,6 +210,15 @@ impl ProfileUpdateInternal {
             is_network_tokenization_enabled,
             is_auto_retries_enabled,
             max_auto_retries_enabled,
+            is_payment_method_token_response_enabled,
+            customer_spending_limit,
+            customer_spending_limit_window_in_secs,
+            is_matched_routing_rule_response_enabled,
+            confirm_response_masking_policy,
+            soft_three_ds_failure_policy,
+            pan_storage_policy,
+            auto_cancel_unconfirmed_after,
+            issuer_country_connector_preferences,
         } = self;
         Profile {
             profile_id: source.profile_id,
@@ -238,6 +278,22 @@ impl ProfileUpdateInternal {
                 .unwrap_or(source.is_network_tokenization_enabled),
             is_auto_retries_enabled: is_auto_retries_enabled.or(source.is_auto_retries_enabled),
             max_auto_retries_enabled: max_auto_retries_enabled.or(source.max_auto_retries_enabled),
+            is_payment_method_token_response_enabled: is_payment_method_token_response_enabled
+                .or(source.is_payment_method_token_response_enabled),
+            customer_spending_limit: customer_spending_limit.or(source.customer_spending_limit),
+            customer_spending_limit_window_in_secs: customer_spending_limit_window_in_secs
+                .or(source.customer_spending_limit_window_in_secs),
+            is_matched_routing_rule_response_enabled: is_matched_routing_rule_response_enabled
+                .or(source.is_matched_routing_rule_response_enabled),
+            confirm_response_masking_policy: confirm_response_masking_policy
+                .or(source.confirm_response_masking_policy),
+            soft_three_ds_failure_policy: soft_three_ds_failure_policy
+                .or(source.soft_three_ds_failure_policy),
+            pan_storage_policy: pan_storage_policy.or(source.pan_storage_policy),
+            auto_cancel_unconfirmed_after: auto_cancel_unconfirmed_after
+                .or(source.auto_cancel_unconfirmed_after),
+            issuer_country_connector_preferences: issuer_country_connector_preferences
+                .or(source.issuer_country_connector_preferences),
         }
     }
 }
@@ -291,6 +347,15 @@ pub struct Profile {
     pub is_network_tokenization_enabled: bool,
     pub is_auto_retries_enabled: Option<bool>,
     pub max_auto_retries_enabled: Option<i16>,
+    pub is_payment_method_token_response_enabled: Option<bool>,
+    pub customer_spending_limit: Option<MinorUnit>,
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+    pub soft_three_ds_failure_policy: Option<common_enums::SoftThreeDsFailurePolicy>,
+    pub pan_storage_policy: Option<common_enums::PanStoragePolicy>,
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 impl Profile {
@@ -348,6 +413,15 @@ pub struct ProfileNew {
     pub is_network_tokenization_enabled: bool,
     pub is_auto_retries_enabled: Option<bool>,
     pub max_auto_retries_enabled: Option<i16>,
+    pub is_payment_method_token_response_enabled: Option<bool>,
+    pub customer_spending_limit: Option<MinorUnit>,
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+    pub soft_three_ds_failure_policy: Option<common_enums::SoftThreeDsFailurePolicy>,
+    pub pan_storage_policy: Option<common_enums::PanStoragePolicy>,
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v2")]
@@ -389,6 +463,15 @@ pub struct ProfileUpdateInternal {
     pub is_network_tokenization_enabled: Option<bool>,
     pub is_auto_retries_enabled: Option<bool>,
     pub max_auto_retries_enabled: Option<i16>,
+    pub is_payment_method_token_response_enabled: Option<bool>,
+    pub customer_spending_limit: Option<MinorUnit>,
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+    pub soft_three_ds_failure_policy: Option<common_enums::SoftThreeDsFailurePolicy>,
+    pub pan_storage_policy: Option<common_enums::PanStoragePolicy>,
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v2")]
@@ -429,6 +512,15 @@ impl ProfileUpdateInternal {
             is_network_tokenization_enabled,
             is_auto_retries_enabled,
             max_auto_retries_enabled,
+            is_payment_method_token_response_enabled,
+            customer_spending_limit,
+            customer_spending_limit_window_in_secs,
+            is_matched_routing_rule_response_enabled,
+            confirm_response_masking_policy,
+            soft_three_ds_failure_policy,
+            pan_storage_policy,
+            auto_cancel_unconfirmed_after,
+            issuer_country_connector_preferences,
         } = self;
         Profile {
             id: source.id,
@@ -491,6 +583,22 @@ impl ProfileUpdateInternal {
                 .unwrap_or(source.is_network_tokenization_enabled),
             is_auto_retries_enabled: is_auto_retries_enabled.or(source.is_auto_retries_enabled),
             max_auto_retries_enabled: max_auto_retries_enabled.or(source.max_auto_retries_enabled),
+            is_payment_method_token_response_enabled: is_payment_method_token_response_enabled
+                .or(source.is_payment_method_token_response_enabled),
+            customer_spending_limit: customer_spending_limit.or(source.customer_spending_limit),
+            customer_spending_limit_window_in_secs: customer_spending_limit_window_in_secs
+                .or(source.customer_spending_limit_window_in_secs),
+            is_matched_routing_rule_response_enabled: is_matched_routing_rule_response_enabled
+                .or(source.is_matched_routing_rule_response_enabled),
+            confirm_response_masking_policy: confirm_response_masking_policy
+                .or(source.confirm_response_masking_policy),
+            soft_three_ds_failure_policy: soft_three_ds_failure_policy
+                .or(source.soft_three_ds_failure_policy),
+            pan_storage_policy: pan_storage_policy.or(source.pan_storage_policy),
+            auto_cancel_unconfirmed_after: auto_cancel_unconfirmed_after
+                .or(source.auto_cancel_unconfirmed_after),
+            issuer_country_connector_preferences: issuer_country_connector_preferences
+                .or(source.issuer_country_connector_preferences),
         }
     }
 }
@@ -514,6 +622,7 @@ pub struct WebhookDetails {
     pub payment_created_enabled: Option<bool>,
     pub payment_succeeded_enabled: Option<bool>,
     pub payment_failed_enabled: Option<bool>,
+    pub status_mapping: Option<HashMap<String, String>>,
 }
 
 common_utils::impl_to_sql_from_sql_json!(WebhookDetails);
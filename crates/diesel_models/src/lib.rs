@@ -30,6 +30,7 @@ pub mod merchant_connector_account;
 pub mod merchant_key_store;
 pub mod organization;
 pub mod payment_attempt;
+pub mod payment_attempt_status_timeline;
 pub mod payment_intent;
 pub mod payment_link;
 pub mod payment_method;
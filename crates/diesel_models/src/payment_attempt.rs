@@ -61,6 +61,19 @@ pub struct PaymentAttempt {
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
     pub profile_id: id_type::ProfileId,
     pub organization_id: id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
     pub card_network: Option<String>,
     pub payment_method_type_v2: Option<storage_enums::PaymentMethod>,
     pub connector_payment_id: Option<String>,
@@ -145,6 +158,19 @@ pub struct PaymentAttempt {
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
     pub profile_id: id_type::ProfileId,
     pub organization_id: id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
     pub card_network: Option<String>,
     pub shipping_cost: Option<MinorUnit>,
     pub order_tax_amount: Option<MinorUnit>,
@@ -221,6 +247,19 @@ pub struct PaymentAttemptNew {
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
     pub profile_id: id_type::ProfileId,
     pub organization_id: id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
     pub card_network: Option<String>,
     pub shipping_cost: Option<MinorUnit>,
     pub order_tax_amount: Option<MinorUnit>,
@@ -293,6 +332,19 @@ pub struct PaymentAttemptNew {
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
     pub profile_id: id_type::ProfileId,
     pub organization_id: id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
     pub card_network: Option<String>,
     pub shipping_cost: Option<MinorUnit>,
     pub order_tax_amount: Option<MinorUnit>,
@@ -370,6 +422,7 @@ pub enum PaymentAttemptUpdate {
         tax_amount: Option<MinorUnit>,
         updated_by: String,
         merchant_connector_id: Option<id_type::MerchantConnectorAccountId>,
+        tokenization_latency: Option<i64>,
     },
     AuthenticationTypeUpdate {
         authentication_type: storage_enums::AuthenticationType,
@@ -408,6 +461,13 @@ pub enum PaymentAttemptUpdate {
         customer_acceptance: Option<pii::SecretSerdeValue>,
         shipping_cost: Option<MinorUnit>,
         order_tax_amount: Option<MinorUnit>,
+        device_fingerprint: Option<String>,
+        three_ds_version: Option<String>,
+        three_ds_message_version: Option<String>,
+        request_challenge: Option<bool>,
+        tokenization_latency: Option<i64>,
+        card_funding_type: Option<storage_enums::CardFundingType>,
+        liability_shift: Option<bool>,
     },
     VoidUpdate {
         status: storage_enums::AttemptStatus,
@@ -451,6 +511,12 @@ pub enum PaymentAttemptUpdate {
         unified_message: Option<Option<String>>,
         payment_method_data: Option<serde_json::Value>,
         charge_id: Option<String>,
+        connector_risk_score: Option<i16>,
+        connector_risk_decision: Option<String>,
+        connector_fee_amount: Option<MinorUnit>,
+        connector_fee_currency: Option<storage_enums::Currency>,
+        connector_response_received_at: Option<PrimitiveDateTime>,
+        connector_payment_method_token: Option<String>,
     },
     UnresolvedResponseUpdate {
         status: storage_enums::AttemptStatus,
@@ -480,6 +546,7 @@ pub enum PaymentAttemptUpdate {
         connector_transaction_id: Option<String>,
         payment_method_data: Option<serde_json::Value>,
         authentication_type: Option<storage_enums::AuthenticationType>,
+        connector_response_received_at: Option<PrimitiveDateTime>,
     },
     CaptureUpdate {
         amount_to_capture: Option<MinorUnit>,
@@ -816,6 +883,19 @@ pub struct PaymentAttemptUpdateInternal {
     pub card_network: Option<String>,
     pub shipping_cost: Option<MinorUnit>,
     pub order_tax_amount: Option<MinorUnit>,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
 }
 
 #[cfg(feature = "v2")]
@@ -1004,6 +1084,19 @@ impl PaymentAttemptUpdate {
             card_network,
             shipping_cost,
             order_tax_amount,
+            connector_risk_score,
+            connector_risk_decision,
+            connector_fee_amount,
+            connector_fee_currency,
+            device_fingerprint,
+            three_ds_version,
+            three_ds_message_version,
+            request_challenge,
+            tokenization_latency,
+            card_funding_type,
+            liability_shift,
+            connector_payment_method_token,
+            connector_response_received_at,
         } = PaymentAttemptUpdateInternal::from(self).populate_derived_fields(&source);
         PaymentAttempt {
             amount: amount.unwrap_or(source.amount),
@@ -1059,6 +1152,21 @@ impl PaymentAttemptUpdate {
             card_network: card_network.or(source.card_network),
             shipping_cost: shipping_cost.or(source.shipping_cost),
             order_tax_amount: order_tax_amount.or(source.order_tax_amount),
+            connector_risk_score: connector_risk_score.or(source.connector_risk_score),
+            connector_risk_decision: connector_risk_decision.or(source.connector_risk_decision),
+            connector_fee_amount: connector_fee_amount.or(source.connector_fee_amount),
+            connector_fee_currency: connector_fee_currency.or(source.connector_fee_currency),
+            device_fingerprint: device_fingerprint.or(source.device_fingerprint),
+            three_ds_version: three_ds_version.or(source.three_ds_version),
+            three_ds_message_version: three_ds_message_version.or(source.three_ds_message_version),
+            request_challenge: request_challenge.or(source.request_challenge),
+            tokenization_latency: tokenization_latency.or(source.tokenization_latency),
+            card_funding_type: card_funding_type.or(source.card_funding_type),
+            liability_shift: liability_shift.or(source.liability_shift),
+            connector_payment_method_token: connector_payment_method_token
+                .or(source.connector_payment_method_token),
+            connector_response_received_at: connector_response_received_at
+                .or(source.connector_response_received_at),
             ..source
         }
     }
@@ -2053,6 +2161,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::AuthenticationTypeUpdate {
                 authentication_type,
@@ -2107,6 +2228,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::ConfirmUpdate {
                 amount,
@@ -2141,6 +2275,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 customer_acceptance,
                 shipping_cost,
                 order_tax_amount,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint,
+                three_ds_version,
+                three_ds_message_version,
+                request_challenge,
+                tokenization_latency,
+                card_funding_type,
+                liability_shift,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -2191,6 +2338,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost,
                 order_tax_amount,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint,
+                three_ds_version,
+                three_ds_message_version,
+                request_challenge,
+                tokenization_latency,
+                card_funding_type,
+                liability_shift,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::VoidUpdate {
                 status,
@@ -2246,6 +2406,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::RejectUpdate {
                 status,
@@ -2302,6 +2475,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::BlocklistUpdate {
                 status,
@@ -2358,6 +2544,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::PaymentMethodDetailsUpdate {
                 payment_method_id,
@@ -2412,6 +2611,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::ResponseUpdate {
                 status,
@@ -2434,6 +2646,12 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 unified_message,
                 payment_method_data,
                 charge_id,
+                connector_risk_score,
+                connector_risk_decision,
+                connector_fee_amount,
+                connector_fee_currency,
+                connector_response_received_at,
+                connector_payment_method_token,
             } => Self {
                 status: Some(status),
                 connector: connector.map(Some),
@@ -2484,6 +2702,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score,
+                connector_risk_decision,
+                connector_fee_amount,
+                connector_fee_currency,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token,
+                connector_response_received_at,
             },
             PaymentAttemptUpdate::ErrorUpdate {
                 connector,
@@ -2498,6 +2729,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 connector_transaction_id,
                 payment_method_data,
                 authentication_type,
+                connector_response_received_at,
             } => Self {
                 connector: connector.map(Some),
                 status: Some(status),
@@ -2548,6 +2780,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at,
             },
             PaymentAttemptUpdate::StatusUpdate { status, updated_by } => Self {
                 status: Some(status),
@@ -2599,6 +2844,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::UpdateTrackers {
                 payment_token,
@@ -2609,6 +2867,7 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 tax_amount,
                 updated_by,
                 merchant_connector_id,
+                tokenization_latency,
             } => Self {
                 payment_token,
                 modified_at: common_utils::date_time::now(),
@@ -2659,6 +2918,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::UnresolvedResponseUpdate {
                 status,
@@ -2720,6 +2992,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::PreprocessingUpdate {
                 status,
@@ -2779,6 +3064,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::CaptureUpdate {
                 multiple_capture_count,
@@ -2834,6 +3132,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::AmountToCaptureUpdate {
                 status,
@@ -2889,6 +3200,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::ConnectorResponse {
                 authentication_data,
@@ -2947,6 +3271,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::IncrementalAuthorizationAmountUpdate {
                 amount,
@@ -3001,6 +3338,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::AuthenticationUpdate {
                 status,
@@ -3058,6 +3408,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
             PaymentAttemptUpdate::ManualUpdate {
                 status,
@@ -3118,6 +3481,19 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 card_network: None,
                 shipping_cost: None,
                 order_tax_amount: None,
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: None,
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: None,
+                tokenization_latency: None,
+                card_funding_type: None,
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
             },
         }
     }
@@ -0,0 +1,33 @@
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::payment_attempt_status_timeline};
+
+#[derive(Clone, Debug, Eq, Insertable, PartialEq, Serialize, Deserialize)]
+#[diesel(table_name = payment_attempt_status_timeline)]
+pub struct PaymentAttemptStatusTimelineNew {
+    pub merchant_id: common_utils::id_type::MerchantId,
+    pub payment_id: common_utils::id_type::PaymentId,
+    pub attempt_id: String,
+    pub previous_status: storage_enums::AttemptStatus,
+    pub status: storage_enums::AttemptStatus,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(
+    Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Selectable, Serialize, Deserialize,
+)]
+#[diesel(table_name = payment_attempt_status_timeline, check_for_backend(diesel::pg::Pg))]
+pub struct PaymentAttemptStatusTimeline {
+    #[serde(skip)]
+    pub id: i32,
+    pub merchant_id: common_utils::id_type::MerchantId,
+    pub payment_id: common_utils::id_type::PaymentId,
+    pub attempt_id: String,
+    pub previous_status: storage_enums::AttemptStatus,
+    pub status: storage_enums::AttemptStatus,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
@@ -110,6 +110,27 @@ impl PaymentMethod {
     }
 }
 
+/// Payment methods and payment method types to list the payment methods of a customer by. An
+/// empty list does not filter the payment methods.
+#[derive(Clone, Debug, Default)]
+pub struct PaymentMethodListFilters {
+    pub payment_methods: Vec<storage_enums::PaymentMethod>,
+    pub payment_method_types: Vec<storage_enums::PaymentMethodType>,
+}
+
+impl PaymentMethodListFilters {
+    pub fn matches(
+        &self,
+        payment_method: Option<storage_enums::PaymentMethod>,
+        payment_method_type: Option<storage_enums::PaymentMethodType>,
+    ) -> bool {
+        (self.payment_methods.is_empty()
+            || payment_method.is_some_and(|pm| self.payment_methods.contains(&pm)))
+            && (self.payment_method_types.is_empty()
+                || payment_method_type.is_some_and(|pmt| self.payment_method_types.contains(&pmt)))
+    }
+}
+
 #[cfg(all(
     any(feature = "v1", feature = "v2"),
     not(feature = "payment_methods_v2")
@@ -838,3 +859,68 @@ impl From<&PaymentMethodNew> for PaymentMethod {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_filters_match_all_payment_methods() {
+        let filters = PaymentMethodListFilters::default();
+
+        assert!(filters.matches(
+            Some(storage_enums::PaymentMethod::Card),
+            Some(storage_enums::PaymentMethodType::Credit)
+        ));
+        assert!(filters.matches(None, None));
+    }
+
+    #[test]
+    fn test_single_payment_method_filter() {
+        let filters = PaymentMethodListFilters {
+            payment_methods: vec![storage_enums::PaymentMethod::Card],
+            payment_method_types: Vec::new(),
+        };
+
+        assert!(filters.matches(
+            Some(storage_enums::PaymentMethod::Card),
+            Some(storage_enums::PaymentMethodType::Debit)
+        ));
+        assert!(!filters.matches(
+            Some(storage_enums::PaymentMethod::Wallet),
+            Some(storage_enums::PaymentMethodType::ApplePay)
+        ));
+        assert!(!filters.matches(None, None));
+    }
+
+    #[test]
+    fn test_multiple_payment_method_type_filter() {
+        let filters = PaymentMethodListFilters {
+            payment_methods: vec![
+                storage_enums::PaymentMethod::Card,
+                storage_enums::PaymentMethod::Wallet,
+            ],
+            payment_method_types: vec![
+                storage_enums::PaymentMethodType::Credit,
+                storage_enums::PaymentMethodType::ApplePay,
+            ],
+        };
+
+        assert!(filters.matches(
+            Some(storage_enums::PaymentMethod::Card),
+            Some(storage_enums::PaymentMethodType::Credit)
+        ));
+        assert!(filters.matches(
+            Some(storage_enums::PaymentMethod::Wallet),
+            Some(storage_enums::PaymentMethodType::ApplePay)
+        ));
+        assert!(!filters.matches(
+            Some(storage_enums::PaymentMethod::Card),
+            Some(storage_enums::PaymentMethodType::Debit)
+        ));
+        assert!(!filters.matches(
+            Some(storage_enums::PaymentMethod::BankDebit),
+            Some(storage_enums::PaymentMethodType::Ach)
+        ));
+    }
+}
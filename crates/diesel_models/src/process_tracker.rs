@@ -210,6 +210,8 @@ pub enum ProcessTrackerRunner {
     OutgoingWebhookRetryWorkflow,
     AttachPayoutAccountWorkflow,
     PaymentMethodStatusUpdateWorkflow,
+    PaymentIntentAutoCancelWorkflow,
+    PaymentIntentProcessingExpiryWorkflow,
 }
 
 #[cfg(test)]
@@ -407,4 +407,38 @@ impl PaymentAttempt {
         .change_context(DatabaseError::Others)
         .attach_printable("Error filtering count of payments")
     }
+
+    #[cfg(feature = "v1")]
+    pub async fn filter_by_merchant_id_statuses_time_range(
+        conn: &PgPooledConn,
+        merchant_id: &common_utils::id_type::MerchantId,
+        statuses: Vec<enums::AttemptStatus>,
+        time_range: &common_utils::types::TimeRange,
+        limit: i64,
+        offset: i64,
+    ) -> StorageResult<Vec<Self>> {
+        let mut filter = <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::status.eq_any(statuses))
+            .filter(dsl::created_at.ge(time_range.start_time))
+            .into_boxed();
+
+        if let Some(end_time) = time_range.end_time {
+            filter = filter.filter(dsl::created_at.le(end_time));
+        }
+
+        let filter = filter
+            .order((dsl::created_at.asc(), dsl::attempt_id.asc()))
+            .limit(limit)
+            .offset(offset);
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&filter).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            filter.get_results_async(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .change_context(DatabaseError::Others)
+        .attach_printable("Error filtering payment attempts by status and time range")
+    }
 }
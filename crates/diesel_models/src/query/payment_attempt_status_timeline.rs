@@ -0,0 +1,35 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+
+use super::generics;
+use crate::{
+    payment_attempt_status_timeline::{
+        PaymentAttemptStatusTimeline, PaymentAttemptStatusTimelineNew,
+    },
+    schema::payment_attempt_status_timeline::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl PaymentAttemptStatusTimelineNew {
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<PaymentAttemptStatusTimeline> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl PaymentAttemptStatusTimeline {
+    pub async fn find_all_by_merchant_id_attempt_id(
+        conn: &PgPooledConn,
+        merchant_id: &common_utils::id_type::MerchantId,
+        attempt_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::attempt_id.eq(attempt_id.to_owned())),
+            None,
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+}
@@ -1,10 +1,18 @@
+#[cfg(feature = "v1")]
+use async_bb8_diesel::AsyncRunQueryDsl;
 use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+#[cfg(feature = "v1")]
+use diesel::{debug_query, pg::Pg, QueryDsl};
+#[cfg(feature = "v1")]
+use error_stack::ResultExt;
 
 use super::generics;
 #[cfg(feature = "v1")]
 use crate::schema::payment_intent::dsl;
 #[cfg(feature = "v2")]
 use crate::schema_v2::payment_intent::dsl;
+#[cfg(feature = "v1")]
+use crate::{enums, query::generics::db_metrics};
 use crate::{
     errors,
     payment_intent::{
@@ -133,4 +141,42 @@ impl PaymentIntent {
         )
         .await
     }
+
+    /// Sums up the amount captured from the customer in payments of the given currency and
+    /// statuses that were last modified, which is when the amount was captured, within the time
+    /// range
+    #[cfg(feature = "v1")]
+    pub async fn get_customer_captured_amount(
+        conn: &PgPooledConn,
+        merchant_id: &common_utils::id_type::MerchantId,
+        customer_id: &common_utils::id_type::CustomerId,
+        currency: enums::Currency,
+        statuses: Vec<enums::IntentStatus>,
+        time_range: &common_utils::types::TimeRange,
+    ) -> StorageResult<Option<i64>> {
+        let mut query = <Self as HasTable>::table()
+            .select(diesel::dsl::sql::<
+                diesel::sql_types::Nullable<diesel::sql_types::BigInt>,
+            >("CAST(SUM(amount_captured) AS BIGINT)"))
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::customer_id.eq(customer_id.to_owned()))
+            .filter(dsl::currency.eq(currency))
+            .filter(dsl::status.eq_any(statuses))
+            .filter(dsl::modified_at.ge(time_range.start_time))
+            .into_boxed();
+
+        if let Some(end_time) = time_range.end_time {
+            query = query.filter(dsl::modified_at.le(end_time));
+        }
+
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query.get_result_async::<Option<i64>>(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error summing up the amount captured from the customer")
+    }
 }
@@ -1,23 +1,12 @@
-#[cfg(all(
-    any(feature = "v1", feature = "v2"),
-    not(feature = "payment_methods_v2")
-))]
 use async_bb8_diesel::AsyncRunQueryDsl;
 #[cfg(all(
     any(feature = "v1", feature = "v2"),
     not(feature = "payment_methods_v2")
 ))]
 use diesel::Table;
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
-#[cfg(all(
-    any(feature = "v1", feature = "v2"),
-    not(feature = "payment_methods_v2")
-))]
-use diesel::{debug_query, pg::Pg, QueryDsl};
-#[cfg(all(
-    any(feature = "v1", feature = "v2"),
-    not(feature = "payment_methods_v2")
-))]
+use diesel::{
+    associations::HasTable, debug_query, pg::Pg, BoolExpressionMethods, ExpressionMethods, QueryDsl,
+};
 use error_stack::ResultExt;
 
 use super::generics;
@@ -30,7 +19,7 @@ use crate::schema::payment_methods::dsl;
 use crate::schema_v2::payment_methods::dsl::{self, id as pm_id};
 use crate::{
     enums as storage_enums, errors,
-    payment_method::{self, PaymentMethod, PaymentMethodNew},
+    payment_method::{self, PaymentMethod, PaymentMethodListFilters, PaymentMethodNew},
     PgPooledConn, StorageResult,
 };
 
@@ -168,19 +157,43 @@ impl PaymentMethod {
         customer_id: &common_utils::id_type::CustomerId,
         merchant_id: &common_utils::id_type::MerchantId,
         status: storage_enums::PaymentMethodStatus,
+        filters: &PaymentMethodListFilters,
         limit: Option<i64>,
+        offset: Option<i64>,
     ) -> StorageResult<Vec<Self>> {
-        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
-            conn,
-            dsl::customer_id
-                .eq(customer_id.to_owned())
-                .and(dsl::merchant_id.eq(merchant_id.to_owned()))
-                .and(dsl::status.eq(status)),
-            limit,
-            None,
-            Some(dsl::last_used_at.desc()),
+        let mut query = <Self as HasTable>::table()
+            .filter(
+                dsl::customer_id
+                    .eq(customer_id.to_owned())
+                    .and(dsl::merchant_id.eq(merchant_id.to_owned()))
+                    .and(dsl::status.eq(status)),
+            )
+            .order((dsl::last_used_at.desc(), dsl::payment_method_id.desc()))
+            .into_boxed();
+
+        if !filters.payment_methods.is_empty() {
+            query = query.filter(dsl::payment_method.eq_any(filters.payment_methods.clone()));
+        }
+        if !filters.payment_method_types.is_empty() {
+            query =
+                query.filter(dsl::payment_method_type.eq_any(filters.payment_method_types.clone()));
+        }
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+        if let Some(offset) = offset {
+            query = query.offset(offset);
+        }
+
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        generics::db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query.get_results_async(conn),
+            generics::db_metrics::DatabaseOperation::Filter,
         )
         .await
+        .change_context(errors::DatabaseError::NotFound)
+        .attach_printable("Error filtering payment methods by customer")
     }
 
     pub async fn update_with_payment_method_id(
@@ -224,19 +237,43 @@ impl PaymentMethod {
         customer_id: &common_utils::id_type::CustomerId,
         merchant_id: &common_utils::id_type::MerchantId,
         status: storage_enums::PaymentMethodStatus,
+        filters: &PaymentMethodListFilters,
         limit: Option<i64>,
+        offset: Option<i64>,
     ) -> StorageResult<Vec<Self>> {
-        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
-            conn,
-            dsl::customer_id
-                .eq(customer_id.to_owned())
-                .and(dsl::merchant_id.eq(merchant_id.to_owned()))
-                .and(dsl::status.eq(status)),
-            limit,
-            None,
-            Some(dsl::last_used_at.desc()),
+        let mut query = <Self as HasTable>::table()
+            .filter(
+                dsl::customer_id
+                    .eq(customer_id.to_owned())
+                    .and(dsl::merchant_id.eq(merchant_id.to_owned()))
+                    .and(dsl::status.eq(status)),
+            )
+            .order((dsl::last_used_at.desc(), pm_id.desc()))
+            .into_boxed();
+
+        if !filters.payment_methods.is_empty() {
+            query = query.filter(dsl::payment_method.eq_any(filters.payment_methods.clone()));
+        }
+        if !filters.payment_method_types.is_empty() {
+            query =
+                query.filter(dsl::payment_method_type.eq_any(filters.payment_method_types.clone()));
+        }
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+        if let Some(offset) = offset {
+            query = query.offset(offset);
+        }
+
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        generics::db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query.get_results_async(conn),
+            generics::db_metrics::DatabaseOperation::Filter,
         )
         .await
+        .change_context(errors::DatabaseError::NotFound)
+        .attach_printable("Error filtering payment methods by customer")
     }
 
     pub async fn update_with_id(
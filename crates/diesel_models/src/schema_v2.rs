@@ -220,6 +220,17 @@ diesel::table! {
         is_network_tokenization_enabled -> Bool,
         is_auto_retries_enabled -> Nullable<Bool>,
         max_auto_retries_enabled -> Nullable<Int2>,
+        is_payment_method_token_response_enabled -> Nullable<Bool>,
+        customer_spending_limit -> Nullable<Int8>,
+        customer_spending_limit_window_in_secs -> Nullable<Int8>,
+        is_matched_routing_rule_response_enabled -> Nullable<Bool>,
+        confirm_response_masking_policy -> Nullable<Jsonb>,
+        #[max_length = 64]
+        soft_three_ds_failure_policy -> Nullable<Varchar>,
+        #[max_length = 64]
+        pan_storage_policy -> Nullable<Varchar>,
+        auto_cancel_unconfirmed_after -> Nullable<Int8>,
+        issuer_country_connector_preferences -> Nullable<Jsonb>,
     }
 }
 
@@ -801,6 +812,24 @@ diesel::table! {
         profile_id -> Varchar,
         #[max_length = 32]
         organization_id -> Varchar,
+        connector_risk_score -> Nullable<Int2>,
+        #[max_length = 64]
+        connector_risk_decision -> Nullable<Varchar>,
+        connector_fee_amount -> Nullable<Int8>,
+        connector_fee_currency -> Nullable<Currency>,
+        #[max_length = 64]
+        device_fingerprint -> Nullable<Varchar>,
+        #[max_length = 16]
+        three_ds_version -> Nullable<Varchar>,
+        #[max_length = 16]
+        three_ds_message_version -> Nullable<Varchar>,
+        request_challenge -> Nullable<Bool>,
+        tokenization_latency -> Nullable<Int8>,
+        #[max_length = 64]
+        card_funding_type -> Nullable<Varchar>,
+        liability_shift -> Nullable<Bool>,
+        connector_payment_method_token -> Nullable<Text>,
+        connector_response_received_at -> Nullable<Timestamp>,
         #[max_length = 32]
         card_network -> Nullable<Varchar>,
         payment_method_type_v2 -> Nullable<Varchar>,
@@ -820,6 +849,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    payment_attempt_status_timeline (id) {
+        id -> Int4,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        payment_id -> Varchar,
+        #[max_length = 64]
+        attempt_id -> Varchar,
+        previous_status -> AttemptStatus,
+        status -> AttemptStatus,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -890,6 +937,7 @@ diesel::table! {
         payment_link_config -> Nullable<Jsonb>,
         #[max_length = 64]
         id -> Varchar,
+        skip_save_payment_method -> Nullable<Bool>,
     }
 }
 
@@ -1336,6 +1384,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     merchant_key_store,
     organization,
     payment_attempt,
+    payment_attempt_status_timeline,
     payment_intent,
     payment_link,
     payment_methods,
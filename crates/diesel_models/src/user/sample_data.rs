@@ -66,6 +66,19 @@ pub struct PaymentAttemptBatchNew {
     pub customer_acceptance: Option<common_utils::pii::SecretSerdeValue>,
     pub profile_id: common_utils::id_type::ProfileId,
     pub organization_id: common_utils::id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
 }
 
 #[cfg(feature = "v2")]
@@ -127,6 +140,19 @@ impl PaymentAttemptBatchNew {
         //     customer_acceptance: self.customer_acceptance,
         //     profile_id: self.profile_id,
         //     organization_id: self.organization_id,
+        //     connector_risk_score: self.connector_risk_score,
+        //     connector_risk_decision: self.connector_risk_decision,
+        //     connector_fee_amount: self.connector_fee_amount,
+        //     connector_fee_currency: self.connector_fee_currency,
+        //     device_fingerprint: self.device_fingerprint,
+        //     three_ds_version: self.three_ds_version,
+        //     three_ds_message_version: self.three_ds_message_version,
+        //     request_challenge: self.request_challenge,
+        //     tokenization_latency: self.tokenization_latency,
+        //     card_funding_type: self.card_funding_type,
+        //     liability_shift: self.liability_shift,
+        //     connector_payment_method_token: self.connector_payment_method_token,
+        //     connector_response_received_at: self.connector_response_received_at,
         // }
         todo!()
     }
@@ -201,6 +227,19 @@ pub struct PaymentAttemptBatchNew {
     pub customer_acceptance: Option<common_utils::pii::SecretSerdeValue>,
     pub profile_id: common_utils::id_type::ProfileId,
     pub organization_id: common_utils::id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
     pub shipping_cost: Option<MinorUnit>,
     pub order_tax_amount: Option<MinorUnit>,
 }
@@ -279,6 +318,19 @@ impl PaymentAttemptBatchNew {
             customer_acceptance: self.customer_acceptance,
             profile_id: self.profile_id,
             organization_id: self.organization_id,
+            connector_risk_score: self.connector_risk_score,
+            connector_risk_decision: self.connector_risk_decision,
+            connector_fee_amount: self.connector_fee_amount,
+            connector_fee_currency: self.connector_fee_currency,
+            device_fingerprint: self.device_fingerprint,
+            three_ds_version: self.three_ds_version,
+            three_ds_message_version: self.three_ds_message_version,
+            request_challenge: self.request_challenge,
+            tokenization_latency: self.tokenization_latency,
+            card_funding_type: self.card_funding_type,
+            liability_shift: self.liability_shift,
+            connector_payment_method_token: self.connector_payment_method_token,
+            connector_response_received_at: self.connector_response_received_at,
             shipping_cost: self.shipping_cost,
             order_tax_amount: self.order_tax_amount,
         }
@@ -4,7 +4,9 @@ use common_utils::{
     encryption::Encryption,
     errors::{CustomResult, ValidationError},
     pii, type_name,
-    types::keymanager,
+    types::{
+        keymanager, ConfirmResponseMaskingPolicy, IssuerCountryConnectorPreferences, MinorUnit,
+    },
 };
 use diesel_models::business_profile::{
     AuthenticationConnectorDetails, BusinessPaymentLinkConfig, BusinessPayoutLinkConfig,
@@ -58,6 +60,15 @@ pub struct Profile {
     pub is_network_tokenization_enabled: bool,
     pub is_auto_retries_enabled: bool,
     pub max_auto_retries_enabled: Option<i16>,
+    pub is_payment_method_token_response_enabled: Option<bool>,
+    pub customer_spending_limit: Option<MinorUnit>,
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+    pub soft_three_ds_failure_policy: Option<common_enums::SoftThreeDsFailurePolicy>,
+    pub pan_storage_policy: Option<common_enums::PanStoragePolicy>,
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v1")]
@@ -98,6 +109,15 @@ pub struct ProfileSetter {
     pub is_network_tokenization_enabled: bool,
     pub is_auto_retries_enabled: bool,
     pub max_auto_retries_enabled: Option<i16>,
+    pub is_payment_method_token_response_enabled: Option<bool>,
+    pub customer_spending_limit: Option<MinorUnit>,
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+    pub soft_three_ds_failure_policy: Option<common_enums::SoftThreeDsFailurePolicy>,
+    pub pan_storage_policy: Option<common_enums::PanStoragePolicy>,
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v1")]
@@ -145,6 +165,17 @@ impl From<ProfileSetter> for Profile {
             is_network_tokenization_enabled: value.is_network_tokenization_enabled,
             is_auto_retries_enabled: value.is_auto_retries_enabled,
             max_auto_retries_enabled: value.max_auto_retries_enabled,
+            is_payment_method_token_response_enabled: value
+                .is_payment_method_token_response_enabled,
+            customer_spending_limit: value.customer_spending_limit,
+            customer_spending_limit_window_in_secs: value.customer_spending_limit_window_in_secs,
+            is_matched_routing_rule_response_enabled: value
+                .is_matched_routing_rule_response_enabled,
+            confirm_response_masking_policy: value.confirm_response_masking_policy,
+            soft_three_ds_failure_policy: value.soft_three_ds_failure_policy,
+            pan_storage_policy: value.pan_storage_policy,
+            auto_cancel_unconfirmed_after: value.auto_cancel_unconfirmed_after,
+            issuer_country_connector_preferences: value.issuer_country_connector_preferences,
         }
     }
 }
@@ -194,6 +225,15 @@ pub struct ProfileGeneralUpdate {
     pub is_network_tokenization_enabled: Option<bool>,
     pub is_auto_retries_enabled: Option<bool>,
     pub max_auto_retries_enabled: Option<i16>,
+    pub is_payment_method_token_response_enabled: Option<bool>,
+    pub customer_spending_limit: Option<MinorUnit>,
+    pub customer_spending_limit_window_in_secs: Option<i64>,
+    pub is_matched_routing_rule_response_enabled: Option<bool>,
+    pub confirm_response_masking_policy: Option<ConfirmResponseMaskingPolicy>,
+    pub soft_three_ds_failure_policy: Option<common_enums::SoftThreeDsFailurePolicy>,
+    pub pan_storage_policy: Option<common_enums::PanStoragePolicy>,
+    pub auto_cancel_unconfirmed_after: Option<i64>,
+    pub issuer_country_connector_preferences: Option<IssuerCountryConnectorPreferences>,
 }
 
 #[cfg(feature = "v1")]
@@ -256,6 +296,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                     is_network_tokenization_enabled,
                     is_auto_retries_enabled,
                     max_auto_retries_enabled,
+                    is_payment_method_token_response_enabled,
+                    customer_spending_limit,
+                    customer_spending_limit_window_in_secs,
+                    is_matched_routing_rule_response_enabled,
+                    confirm_response_masking_policy,
+                    soft_three_ds_failure_policy,
+                    pan_storage_policy,
+                    auto_cancel_unconfirmed_after,
+                    issuer_country_connector_preferences,
                 } = *update;
 
                 Self {
@@ -293,6 +342,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                     is_network_tokenization_enabled,
                     is_auto_retries_enabled,
                     max_auto_retries_enabled,
+                    is_payment_method_token_response_enabled,
+                    customer_spending_limit,
+                    customer_spending_limit_window_in_secs,
+                    is_matched_routing_rule_response_enabled,
+                    confirm_response_masking_policy,
+                    soft_three_ds_failure_policy,
+                    pan_storage_policy,
+                    auto_cancel_unconfirmed_after,
+                    issuer_country_connector_preferences,
                 }
             }
             ProfileUpdate::RoutingAlgorithmUpdate {
@@ -332,6 +390,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled: None,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
             ProfileUpdate::DynamicRoutingAlgorithmUpdate {
                 dynamic_routing_algorithm,
@@ -369,6 +436,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled: None,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
             ProfileUpdate::ExtendedCardInfoUpdate {
                 is_extended_card_info_enabled,
@@ -406,6 +482,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled: None,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
             ProfileUpdate::ConnectorAgnosticMitUpdate {
                 is_connector_agnostic_mit_enabled,
@@ -443,6 +528,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled: None,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
             ProfileUpdate::NetworkTokenizationUpdate {
                 is_network_tokenization_enabled,
@@ -480,6 +574,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
         }
     }
@@ -536,6 +639,15 @@ impl super::behaviour::Conversion for Profile {
             is_network_tokenization_enabled: self.is_network_tokenization_enabled,
             is_auto_retries_enabled: Some(self.is_auto_retries_enabled),
             max_auto_retries_enabled: self.max_auto_retries_enabled,
+            is_payment_method_token_response_enabled: self.is_payment_method_token_response_enabled,
+            customer_spending_limit: self.customer_spending_limit,
+            customer_spending_limit_window_in_secs: self.customer_spending_limit_window_in_secs,
+            is_matched_routing_rule_response_enabled: self.is_matched_routing_rule_response_enabled,
+            confirm_response_masking_policy: self.confirm_response_masking_policy,
+            soft_three_ds_failure_policy: self.soft_three_ds_failure_policy,
+            pan_storage_policy: self.pan_storage_policy,
+            auto_cancel_unconfirmed_after: self.auto_cancel_unconfirmed_after,
+            issuer_country_connector_preferences: self.issuer_country_connector_preferences,
         })
     }
 
@@ -604,6 +716,17 @@ impl super::behaviour::Conversion for Profile {
                 is_network_tokenization_enabled: item.is_network_tokenization_enabled,
                 is_auto_retries_enabled: item.is_auto_retries_enabled.unwrap_or(false),
                 max_auto_retries_enabled: item.max_auto_retries_enabled,
+                is_payment_method_token_response_enabled: item
+                    .is_payment_method_token_response_enabled,
+                customer_spending_limit: item.customer_spending_limit,
+                customer_spending_limit_window_in_secs: item.customer_spending_limit_window_in_secs,
+                is_matched_routing_rule_response_enabled: item
+                    .is_matched_routing_rule_response_enabled,
+                confirm_response_masking_policy: item.confirm_response_masking_policy,
+                soft_three_ds_failure_policy: item.soft_three_ds_failure_policy,
+                pan_storage_policy: item.pan_storage_policy,
+                auto_cancel_unconfirmed_after: item.auto_cancel_unconfirmed_after,
+                issuer_country_connector_preferences: item.issuer_country_connector_preferences,
             })
         }
         .await
@@ -656,6 +779,15 @@ impl super::behaviour::Conversion for Profile {
             is_network_tokenization_enabled: self.is_network_tokenization_enabled,
             is_auto_retries_enabled: Some(self.is_auto_retries_enabled),
             max_auto_retries_enabled: self.max_auto_retries_enabled,
+            is_payment_method_token_response_enabled: self.is_payment_method_token_response_enabled,
+            customer_spending_limit: self.customer_spending_limit,
+            customer_spending_limit_window_in_secs: self.customer_spending_limit_window_in_secs,
+            is_matched_routing_rule_response_enabled: self.is_matched_routing_rule_response_enabled,
+            confirm_response_masking_policy: self.confirm_response_masking_policy,
+            soft_three_ds_failure_policy: self.soft_three_ds_failure_policy,
+            pan_storage_policy: self.pan_storage_policy,
+            auto_cancel_unconfirmed_after: self.auto_cancel_unconfirmed_after,
+            issuer_country_connector_preferences: self.issuer_country_connector_preferences,
         })
     }
 }
@@ -926,6 +1058,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                     is_network_tokenization_enabled,
                     is_auto_retries_enabled: None,
                     max_auto_retries_enabled: None,
+                    is_payment_method_token_response_enabled: None,
+                    customer_spending_limit: None,
+                    customer_spending_limit_window_in_secs: None,
+                    is_matched_routing_rule_response_enabled: None,
+                    confirm_response_masking_policy: None,
+                    soft_three_ds_failure_policy: None,
+                    pan_storage_policy: None,
+                    auto_cancel_unconfirmed_after: None,
+                    issuer_country_connector_preferences: None,
                 }
             }
             ProfileUpdate::RoutingAlgorithmUpdate {
@@ -966,6 +1107,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled: None,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
             ProfileUpdate::ExtendedCardInfoUpdate {
                 is_extended_card_info_enabled,
@@ -1004,6 +1154,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled: None,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
             ProfileUpdate::ConnectorAgnosticMitUpdate {
                 is_connector_agnostic_mit_enabled,
@@ -1042,6 +1201,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled: None,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
             ProfileUpdate::DefaultRoutingFallbackUpdate {
                 default_fallback_routing,
@@ -1080,6 +1248,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled: None,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
             ProfileUpdate::NetworkTokenizationUpdate {
                 is_network_tokenization_enabled,
@@ -1118,6 +1295,15 @@ impl From<ProfileUpdate> for ProfileUpdateInternal {
                 is_network_tokenization_enabled,
                 is_auto_retries_enabled: None,
                 max_auto_retries_enabled: None,
+                is_payment_method_token_response_enabled: None,
+                customer_spending_limit: None,
+                customer_spending_limit_window_in_secs: None,
+                is_matched_routing_rule_response_enabled: None,
+                confirm_response_masking_policy: None,
+                soft_three_ds_failure_policy: None,
+                pan_storage_policy: None,
+                auto_cancel_unconfirmed_after: None,
+                issuer_country_connector_preferences: None,
             },
         }
     }
@@ -1176,6 +1362,15 @@ impl super::behaviour::Conversion for Profile {
             is_network_tokenization_enabled: self.is_network_tokenization_enabled,
             is_auto_retries_enabled: None,
             max_auto_retries_enabled: None,
+            is_payment_method_token_response_enabled: None,
+            customer_spending_limit: None,
+            customer_spending_limit_window_in_secs: None,
+            is_matched_routing_rule_response_enabled: None,
+            confirm_response_masking_policy: None,
+            soft_three_ds_failure_policy: None,
+            pan_storage_policy: None,
+            auto_cancel_unconfirmed_after: None,
+            issuer_country_connector_preferences: None,
         })
     }
 
@@ -1297,6 +1492,15 @@ impl super::behaviour::Conversion for Profile {
             is_network_tokenization_enabled: self.is_network_tokenization_enabled,
             is_auto_retries_enabled: None,
             max_auto_retries_enabled: None,
+            is_payment_method_token_response_enabled: None,
+            customer_spending_limit: None,
+            customer_spending_limit_window_in_secs: None,
+            is_matched_routing_rule_response_enabled: None,
+            confirm_response_masking_policy: None,
+            soft_three_ds_failure_policy: None,
+            pan_storage_policy: None,
+            auto_cancel_unconfirmed_after: None,
+            issuer_country_connector_preferences: None,
         })
     }
 }
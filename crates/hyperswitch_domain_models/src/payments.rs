@@ -76,6 +76,7 @@ pub struct PaymentIntent {
     pub organization_id: id_type::OrganizationId,
     pub tax_details: Option<TaxDetails>,
     pub skip_external_tax_calculation: Option<bool>,
+    pub skip_save_payment_method: Option<bool>,
 }
 
 impl PaymentIntent {
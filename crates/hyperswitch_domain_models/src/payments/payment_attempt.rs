@@ -160,6 +160,17 @@ pub trait PaymentAttemptInterface {
         profile_id_list: Option<Vec<id_type::ProfileId>>,
         storage_scheme: storage_enums::MerchantStorageScheme,
     ) -> error_stack::Result<i64, errors::StorageError>;
+
+    #[cfg(all(feature = "v1", feature = "olap"))]
+    async fn filter_payment_attempts_by_statuses_time_range(
+        &self,
+        merchant_id: &id_type::MerchantId,
+        statuses: &[storage_enums::AttemptStatus],
+        time_range: &common_utils::types::TimeRange,
+        limit: i64,
+        offset: i64,
+        storage_scheme: storage_enums::MerchantStorageScheme,
+    ) -> error_stack::Result<Vec<PaymentAttempt>, errors::StorageError>;
 }
 
 #[cfg(feature = "v2")]
@@ -213,6 +224,19 @@ pub struct PaymentAttempt {
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
     pub profile_id: id_type::ProfileId,
     pub organization_id: id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
     pub payment_method_type: Option<storage_enums::PaymentMethod>,
     pub payment_method_id: Option<String>,
     pub connector_payment_id: Option<String>,
@@ -334,6 +358,19 @@ pub struct PaymentAttempt {
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
     pub profile_id: id_type::ProfileId,
     pub organization_id: id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
     pub shipping_cost: Option<MinorUnit>,
     pub order_tax_amount: Option<MinorUnit>,
 }
@@ -419,6 +456,19 @@ pub struct PaymentAttemptNew {
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
     pub profile_id: id_type::ProfileId,
     pub organization_id: id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
     pub card_network: Option<String>,
 }
 
@@ -490,6 +540,19 @@ pub struct PaymentAttemptNew {
     pub customer_acceptance: Option<pii::SecretSerdeValue>,
     pub profile_id: id_type::ProfileId,
     pub organization_id: id_type::OrganizationId,
+    pub connector_risk_score: Option<i16>,
+    pub connector_risk_decision: Option<String>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+    pub device_fingerprint: Option<String>,
+    pub three_ds_version: Option<String>,
+    pub three_ds_message_version: Option<String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub liability_shift: Option<bool>,
+    pub connector_payment_method_token: Option<String>,
+    pub connector_response_received_at: Option<PrimitiveDateTime>,
     pub shipping_cost: Option<MinorUnit>,
     pub order_tax_amount: Option<MinorUnit>,
 }
@@ -555,6 +618,7 @@ pub enum PaymentAttemptUpdate {
         tax_amount: Option<MinorUnit>,
         updated_by: String,
         merchant_connector_id: Option<id_type::MerchantConnectorAccountId>,
+        tokenization_latency: Option<i64>,
     },
     AuthenticationTypeUpdate {
         authentication_type: storage_enums::AuthenticationType,
@@ -593,6 +657,13 @@ pub enum PaymentAttemptUpdate {
         customer_acceptance: Option<pii::SecretSerdeValue>,
         shipping_cost: Option<MinorUnit>,
         order_tax_amount: Option<MinorUnit>,
+        device_fingerprint: Option<String>,
+        three_ds_version: Option<String>,
+        three_ds_message_version: Option<String>,
+        request_challenge: Option<bool>,
+        tokenization_latency: Option<i64>,
+        card_funding_type: Option<storage_enums::CardFundingType>,
+        liability_shift: Option<bool>,
     },
     RejectUpdate {
         status: storage_enums::AttemptStatus,
@@ -636,6 +707,12 @@ pub enum PaymentAttemptUpdate {
         unified_message: Option<Option<String>>,
         payment_method_data: Option<serde_json::Value>,
         charge_id: Option<String>,
+        connector_risk_score: Option<i16>,
+        connector_risk_decision: Option<String>,
+        connector_fee_amount: Option<MinorUnit>,
+        connector_fee_currency: Option<storage_enums::Currency>,
+        connector_response_received_at: Option<PrimitiveDateTime>,
+        connector_payment_method_token: Option<String>,
     },
     UnresolvedResponseUpdate {
         status: storage_enums::AttemptStatus,
@@ -665,6 +742,7 @@ pub enum PaymentAttemptUpdate {
         connector_transaction_id: Option<String>,
         payment_method_data: Option<serde_json::Value>,
         authentication_type: Option<storage_enums::AuthenticationType>,
+        connector_response_received_at: Option<PrimitiveDateTime>,
     },
     CaptureUpdate {
         amount_to_capture: Option<MinorUnit>,
@@ -812,6 +890,19 @@ impl behaviour::Conversion for PaymentAttempt {
             customer_acceptance: self.customer_acceptance,
             profile_id: self.profile_id,
             organization_id: self.organization_id,
+            connector_risk_score: self.connector_risk_score,
+            connector_risk_decision: self.connector_risk_decision,
+            connector_fee_amount: self.connector_fee_amount,
+            connector_fee_currency: self.connector_fee_currency,
+            device_fingerprint: self.device_fingerprint,
+            three_ds_version: self.three_ds_version,
+            three_ds_message_version: self.three_ds_message_version,
+            request_challenge: self.request_challenge,
+            tokenization_latency: self.tokenization_latency,
+            card_funding_type: self.card_funding_type,
+            liability_shift: self.liability_shift,
+            connector_payment_method_token: self.connector_payment_method_token,
+            connector_response_received_at: self.connector_response_received_at,
             card_network,
             order_tax_amount: self.order_tax_amount,
             shipping_cost: self.shipping_cost,
@@ -890,6 +981,19 @@ impl behaviour::Conversion for PaymentAttempt {
                 customer_acceptance: storage_model.customer_acceptance,
                 profile_id: storage_model.profile_id,
                 organization_id: storage_model.organization_id,
+                connector_risk_score: storage_model.connector_risk_score,
+                connector_risk_decision: storage_model.connector_risk_decision,
+                connector_fee_amount: storage_model.connector_fee_amount,
+                connector_fee_currency: storage_model.connector_fee_currency,
+                device_fingerprint: storage_model.device_fingerprint,
+                three_ds_version: storage_model.three_ds_version,
+                three_ds_message_version: storage_model.three_ds_message_version,
+                request_challenge: storage_model.request_challenge,
+                tokenization_latency: storage_model.tokenization_latency,
+                card_funding_type: storage_model.card_funding_type,
+                liability_shift: storage_model.liability_shift,
+                connector_payment_method_token: storage_model.connector_payment_method_token,
+                connector_response_received_at: storage_model.connector_response_received_at,
                 order_tax_amount: storage_model.order_tax_amount,
                 shipping_cost: storage_model.shipping_cost,
             })
@@ -970,6 +1074,19 @@ impl behaviour::Conversion for PaymentAttempt {
             customer_acceptance: self.customer_acceptance,
             profile_id: self.profile_id,
             organization_id: self.organization_id,
+            connector_risk_score: self.connector_risk_score,
+            connector_risk_decision: self.connector_risk_decision,
+            connector_fee_amount: self.connector_fee_amount,
+            connector_fee_currency: self.connector_fee_currency,
+            device_fingerprint: self.device_fingerprint,
+            three_ds_version: self.three_ds_version,
+            three_ds_message_version: self.three_ds_message_version,
+            request_challenge: self.request_challenge,
+            tokenization_latency: self.tokenization_latency,
+            card_funding_type: self.card_funding_type,
+            liability_shift: self.liability_shift,
+            connector_payment_method_token: self.connector_payment_method_token,
+            connector_response_received_at: self.connector_response_received_at,
             card_network,
             order_tax_amount: self.order_tax_amount,
             shipping_cost: self.shipping_cost,
@@ -1037,6 +1154,19 @@ impl behaviour::Conversion for PaymentAttempt {
             customer_acceptance,
             profile_id,
             organization_id,
+            connector_risk_score,
+            connector_risk_decision,
+            connector_fee_amount,
+            connector_fee_currency,
+            device_fingerprint,
+            three_ds_version,
+            three_ds_message_version,
+            request_challenge,
+            tokenization_latency,
+            card_funding_type,
+            liability_shift,
+            connector_payment_method_token,
+            connector_response_received_at,
             payment_method_type,
             connector_payment_id,
             payment_method_subtype,
@@ -1098,6 +1228,19 @@ impl behaviour::Conversion for PaymentAttempt {
             customer_acceptance,
             profile_id,
             organization_id,
+            connector_risk_score,
+            connector_risk_decision,
+            connector_fee_amount,
+            connector_fee_currency,
+            device_fingerprint,
+            three_ds_version,
+            three_ds_message_version,
+            request_challenge,
+            tokenization_latency,
+            card_funding_type,
+            liability_shift,
+            connector_payment_method_token,
+            connector_response_received_at,
             card_network,
             order_tax_amount,
             shipping_cost,
@@ -1167,6 +1310,19 @@ impl behaviour::Conversion for PaymentAttempt {
                 customer_acceptance: storage_model.customer_acceptance,
                 profile_id: storage_model.profile_id,
                 organization_id: storage_model.organization_id,
+                connector_risk_score: storage_model.connector_risk_score,
+                connector_risk_decision: storage_model.connector_risk_decision,
+                connector_fee_amount: storage_model.connector_fee_amount,
+                connector_fee_currency: storage_model.connector_fee_currency,
+                device_fingerprint: storage_model.device_fingerprint,
+                three_ds_version: storage_model.three_ds_version,
+                three_ds_message_version: storage_model.three_ds_message_version,
+                request_challenge: storage_model.request_challenge,
+                tokenization_latency: storage_model.tokenization_latency,
+                card_funding_type: storage_model.card_funding_type,
+                liability_shift: storage_model.liability_shift,
+                connector_payment_method_token: storage_model.connector_payment_method_token,
+                connector_response_received_at: storage_model.connector_response_received_at,
                 order_tax_amount: storage_model.order_tax_amount,
                 shipping_cost: storage_model.shipping_cost,
                 payment_method_subtype: storage_model.payment_method_subtype,
@@ -1236,6 +1392,19 @@ impl behaviour::Conversion for PaymentAttempt {
             customer_acceptance: self.customer_acceptance,
             profile_id: self.profile_id,
             organization_id: self.organization_id,
+            connector_risk_score: self.connector_risk_score,
+            connector_risk_decision: self.connector_risk_decision,
+            connector_fee_amount: self.connector_fee_amount,
+            connector_fee_currency: self.connector_fee_currency,
+            device_fingerprint: self.device_fingerprint,
+            three_ds_version: self.three_ds_version,
+            three_ds_message_version: self.three_ds_message_version,
+            request_challenge: self.request_challenge,
+            tokenization_latency: self.tokenization_latency,
+            card_funding_type: self.card_funding_type,
+            liability_shift: self.liability_shift,
+            connector_payment_method_token: self.connector_payment_method_token,
+            connector_response_received_at: self.connector_response_received_at,
             card_network,
             order_tax_amount: self.order_tax_amount,
             shipping_cost: self.shipping_cost,
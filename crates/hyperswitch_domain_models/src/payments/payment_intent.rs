@@ -68,6 +68,17 @@ pub trait PaymentIntentInterface {
         storage_scheme: storage_enums::MerchantStorageScheme,
     ) -> error_stack::Result<PaymentIntent, errors::StorageError>;
 
+    #[cfg(feature = "v1")]
+    async fn get_customer_captured_amount(
+        &self,
+        merchant_id: &id_type::MerchantId,
+        customer_id: &id_type::CustomerId,
+        currency: storage_enums::Currency,
+        statuses: Vec<storage_enums::IntentStatus>,
+        time_range: &common_utils::types::TimeRange,
+        storage_scheme: storage_enums::MerchantStorageScheme,
+    ) -> error_stack::Result<MinorUnit, errors::StorageError>;
+
     #[cfg(all(feature = "v1", feature = "olap"))]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -178,6 +189,7 @@ pub struct PaymentIntentUpdateFields {
     pub shipping_details: Option<Encryptable<Secret<serde_json::Value>>>,
     pub is_payment_processor_token_flow: Option<bool>,
     pub tax_details: Option<diesel_models::TaxDetails>,
+    pub skip_save_payment_method: Option<bool>,
 }
 
 #[cfg(feature = "v1")]
@@ -411,6 +423,7 @@ pub struct PaymentIntentUpdateInternal {
     pub shipping_details: Option<Encryptable<Secret<serde_json::Value>>>,
     pub is_payment_processor_token_flow: Option<bool>,
     pub tax_details: Option<diesel_models::TaxDetails>,
+    pub skip_save_payment_method: Option<bool>,
 }
 
 #[cfg(feature = "v2")]
@@ -632,6 +645,7 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 merchant_order_reference_id: value.merchant_order_reference_id,
                 shipping_details: value.shipping_details,
                 is_payment_processor_token_flow: value.is_payment_processor_token_flow,
+                skip_save_payment_method: value.skip_save_payment_method,
                 ..Default::default()
             },
             PaymentIntentUpdate::PaymentCreateUpdate {
@@ -992,6 +1006,7 @@ impl From<PaymentIntentUpdate> for DieselPaymentIntentUpdate {
                     shipping_details: value.shipping_details.map(Encryption::from),
                     is_payment_processor_token_flow: value.is_payment_processor_token_flow,
                     tax_details: value.tax_details,
+                    skip_save_payment_method: value.skip_save_payment_method,
                 }))
             }
             PaymentIntentUpdate::PaymentCreateUpdate {
@@ -1217,6 +1232,7 @@ impl From<PaymentIntentUpdateInternal> for diesel_models::PaymentIntentUpdateInt
             shipping_details,
             is_payment_processor_token_flow,
             tax_details,
+            skip_save_payment_method,
         } = value;
         Self {
             amount,
@@ -1255,6 +1271,7 @@ impl From<PaymentIntentUpdateInternal> for diesel_models::PaymentIntentUpdateInt
             shipping_details: shipping_details.map(Encryption::from),
             is_payment_processor_token_flow,
             tax_details,
+            skip_save_payment_method,
         }
     }
 }
@@ -1505,6 +1522,7 @@ impl behaviour::Conversion for PaymentIntent {
         } = self;
         Ok(DieselPaymentIntent {
             skip_external_tax_calculation: Some(amount_details.get_external_tax_action_as_bool()),
+            skip_save_payment_method: None,
             surcharge_applicable: Some(amount_details.get_surcharge_action_as_bool()),
             merchant_id,
             status,
@@ -1783,6 +1801,7 @@ impl behaviour::Conversion for PaymentIntent {
             shipping_cost: self.shipping_cost,
             tax_details: self.tax_details,
             skip_external_tax_calculation: self.skip_external_tax_calculation,
+            skip_save_payment_method: self.skip_save_payment_method,
         })
     }
 
@@ -1870,6 +1889,7 @@ impl behaviour::Conversion for PaymentIntent {
                 is_payment_processor_token_flow: storage_model.is_payment_processor_token_flow,
                 organization_id: storage_model.organization_id,
                 skip_external_tax_calculation: storage_model.skip_external_tax_calculation,
+                skip_save_payment_method: storage_model.skip_save_payment_method,
             })
         }
         .await
@@ -1932,6 +1952,7 @@ impl behaviour::Conversion for PaymentIntent {
             shipping_cost: self.shipping_cost,
             tax_details: self.tax_details,
             skip_external_tax_calculation: self.skip_external_tax_calculation,
+            skip_save_payment_method: self.skip_save_payment_method,
         })
     }
 }
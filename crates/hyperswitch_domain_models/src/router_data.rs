@@ -322,6 +322,8 @@ pub struct PaymentMethodBalance {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ConnectorResponseData {
     pub additional_payment_method_data: Option<AdditionalPaymentMethodConnectorResponse>,
+    pub risk_data: Option<ConnectorRiskData>,
+    pub fee_data: Option<ConnectorFeeData>,
 }
 
 impl ConnectorResponseData {
@@ -330,8 +332,38 @@ impl ConnectorResponseData {
     ) -> Self {
         Self {
             additional_payment_method_data: Some(additional_payment_method_data),
+            risk_data: None,
+            fee_data: None,
         }
     }
+
+    pub fn with_risk_data(risk_data: ConnectorRiskData) -> Self {
+        Self {
+            additional_payment_method_data: None,
+            risk_data: Some(risk_data),
+            fee_data: None,
+        }
+    }
+}
+
+/// Fraud / risk assessment returned by the connector for a payment
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConnectorRiskData {
+    /// Risk score as reported by the connector
+    pub score: Option<f64>,
+    /// Highest score on the connector's scale, if the connector does not score on a 0-100 scale
+    pub max_score: Option<f64>,
+    /// Risk decision as reported by the connector, e.g. `ACCEPT` or `REVIEW`
+    pub decision: Option<String>,
+}
+
+/// Fee actually charged by the connector for processing a payment
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConnectorFeeData {
+    /// Fee amount as reported by the connector
+    pub amount: MinorUnit,
+    /// Currency in which the connector charged the fee
+    pub currency: common_enums::Currency,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
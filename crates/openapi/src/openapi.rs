@@ -227,6 +227,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::admin::BusinessGenericLinkConfig,
         api_models::admin::BusinessCollectLinkConfig,
         api_models::admin::BusinessPayoutLinkConfig,
+        api_models::admin::ConfirmResponseMaskingPolicy,
         api_models::customers::CustomerRequest,
         api_models::customers::CustomerDeleteResponse,
         api_models::payment_methods::PaymentMethodCreate,
@@ -259,6 +260,9 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::CaptureMethod,
         api_models::enums::FutureUsage,
         api_models::enums::AuthenticationType,
+        api_models::enums::SoftThreeDsFailurePolicy,
+        api_models::enums::PanStoragePolicy,
+        api_models::enums::CardFundingType,
         api_models::enums::Connector,
         api_models::enums::PaymentMethod,
         api_models::enums::PaymentMethodIssuerCode,
@@ -477,6 +481,8 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::ThreeDsMethodData,
         api_models::payments::PollConfigResponse,
         api_models::payments::ExternalAuthenticationDetailsResponse,
+        api_models::payments::PaymentMethodTokenReference,
+        api_models::payments::MatchedRoutingRule,
         api_models::payments::ExtendedCardInfo,
         api_models::payment_methods::RequiredFieldInfo,
         api_models::payment_methods::DefaultPaymentMethod,
@@ -556,6 +562,8 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::PaymentLinkInitiateRequest,
         api_models::payouts::PayoutLinkInitiateRequest,
         api_models::payments::ExtendedCardInfoResponse,
+        api_models::payments::PaymentAttemptStatusTimelineResponse,
+        api_models::payments::PaymentAttemptStatusTransition,
         api_models::payments::GooglePayAssuranceDetails,
         api_models::routing::RoutingConfigRequest,
         api_models::routing::RoutingDictionaryRecord,
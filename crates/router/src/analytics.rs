@@ -352,6 +352,30 @@ pub mod routes {
         .await
     }
 
+    /// Reporting currency of the payment metrics along with the FX rates of the forex service.
+    /// The metrics are still served when the rates cannot be fetched, amounts of other currencies
+    /// are then reported as not converted.
+    async fn get_reporting_currency(state: &crate::SessionState) -> analytics::ReportingCurrency {
+        let forex_api = state.conf.forex_api.get_inner();
+        let exchange_rates = crate::utils::currency::get_forex_rates(
+            state,
+            forex_api.call_delay,
+            forex_api.local_fetch_retry_delay,
+            forex_api.local_fetch_retry_count,
+        )
+        .await
+        .map_err(|error| {
+            router_env::logger::error!(?error, "Failed to fetch the forex rates for analytics")
+        })
+        .ok()
+        .map(|rates| rates.get_exchange_rates());
+
+        analytics::ReportingCurrency {
+            currency: state.conf.analytics_reporting_currency.currency,
+            exchange_rates,
+        }
+    }
+
     /// # Panics
     ///
     /// Panics if `json_payload` array does not contain one `GetPaymentMetricRequest` element.
@@ -380,9 +404,18 @@ pub mod routes {
                     org_id: org_id.clone(),
                     merchant_ids: vec![merchant_id.clone()],
                 };
-                analytics::payments::get_metrics(&state.pool, &auth, req)
-                    .await
-                    .map(ApplicationResponse::Json)
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
+                analytics::payments::get_metrics(
+                    &state.pool,
+                    &auth,
+                    req,
+                    &get_reporting_currency(&state).await,
+                )
+                .await
+                .map(ApplicationResponse::Json)
             },
             &auth::JWTAuth {
                 permission: Permission::Analytics,
@@ -419,9 +452,18 @@ pub mod routes {
                 let auth: AuthInfo = AuthInfo::OrgLevel {
                     org_id: org_id.clone(),
                 };
-                analytics::payments::get_metrics(&state.pool, &auth, req)
-                    .await
-                    .map(ApplicationResponse::Json)
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
+                analytics::payments::get_metrics(
+                    &state.pool,
+                    &auth,
+                    req,
+                    &get_reporting_currency(&state).await,
+                )
+                .await
+                .map(ApplicationResponse::Json)
             },
             &auth::JWTAuth {
                 permission: Permission::Analytics,
@@ -465,9 +507,18 @@ pub mod routes {
                     merchant_id: merchant_id.clone(),
                     profile_ids: vec![profile_id.clone()],
                 };
-                analytics::payments::get_metrics(&state.pool, &auth, req)
-                    .await
-                    .map(ApplicationResponse::Json)
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
+                analytics::payments::get_metrics(
+                    &state.pool,
+                    &auth,
+                    req,
+                    &get_reporting_currency(&state).await,
+                )
+                .await
+                .map(ApplicationResponse::Json)
             },
             &auth::JWTAuth {
                 permission: Permission::Analytics,
@@ -506,6 +557,10 @@ pub mod routes {
                     org_id: org_id.clone(),
                     merchant_ids: vec![merchant_id.clone()],
                 };
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
                 analytics::payment_intents::get_metrics(&state.pool, &auth, req)
                     .await
                     .map(ApplicationResponse::Json)
@@ -545,6 +600,10 @@ pub mod routes {
                 let auth: AuthInfo = AuthInfo::OrgLevel {
                     org_id: org_id.clone(),
                 };
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
                 analytics::payment_intents::get_metrics(&state.pool, &auth, req)
                     .await
                     .map(ApplicationResponse::Json)
@@ -591,6 +650,10 @@ pub mod routes {
                     merchant_id: merchant_id.clone(),
                     profile_ids: vec![profile_id.clone()],
                 };
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
                 analytics::payment_intents::get_metrics(&state.pool, &auth, req)
                     .await
                     .map(ApplicationResponse::Json)
@@ -632,6 +695,10 @@ pub mod routes {
                     org_id: org_id.clone(),
                     merchant_ids: vec![merchant_id.clone()],
                 };
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
                 analytics::refunds::get_metrics(&state.pool, &auth, req)
                     .await
                     .map(ApplicationResponse::Json)
@@ -671,6 +738,10 @@ pub mod routes {
                 let auth: AuthInfo = AuthInfo::OrgLevel {
                     org_id: org_id.clone(),
                 };
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
                 analytics::refunds::get_metrics(&state.pool, &auth, req)
                     .await
                     .map(ApplicationResponse::Json)
@@ -717,6 +788,10 @@ pub mod routes {
                     merchant_id: merchant_id.clone(),
                     profile_ids: vec![profile_id.clone()],
                 };
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
                 analytics::refunds::get_metrics(&state.pool, &auth, req)
                     .await
                     .map(ApplicationResponse::Json)
@@ -2180,6 +2255,10 @@ pub mod routes {
                     org_id: org_id.clone(),
                     merchant_ids: vec![merchant_id.clone()],
                 };
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
                 analytics::disputes::get_metrics(&state.pool, &auth, req)
                     .await
                     .map(ApplicationResponse::Json)
@@ -2226,6 +2305,10 @@ pub mod routes {
                     merchant_id: merchant_id.clone(),
                     profile_ids: vec![profile_id.clone()],
                 };
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
                 analytics::disputes::get_metrics(&state.pool, &auth, req)
                     .await
                     .map(ApplicationResponse::Json)
@@ -2265,6 +2348,10 @@ pub mod routes {
                 let auth: AuthInfo = AuthInfo::OrgLevel {
                     org_id: org_id.clone(),
                 };
+                state
+                    .conf
+                    .analytics_dimensions
+                    .validate(&req.group_by_names)?;
                 analytics::disputes::get_metrics(&state.pool, &auth, req)
                     .await
                     .map(ApplicationResponse::Json)
@@ -329,6 +329,14 @@ impl ProcessTrackerWorkflows<routes::SessionState> for WorkflowRunner {
                 storage::ProcessTrackerRunner::PaymentMethodStatusUpdateWorkflow => Ok(Box::new(
                     workflows::payment_method_status_update::PaymentMethodStatusUpdateWorkflow,
                 )),
+                storage::ProcessTrackerRunner::PaymentIntentAutoCancelWorkflow => Ok(Box::new(
+                    workflows::payment_intent_auto_cancel::PaymentIntentAutoCancelWorkflow,
+                )),
+                storage::ProcessTrackerRunner::PaymentIntentProcessingExpiryWorkflow => {
+                    Ok(Box::new(
+                        workflows::payment_intent_processing_expiry::PaymentIntentProcessingExpiryWorkflow,
+                    ))
+                }
             }
         };
 
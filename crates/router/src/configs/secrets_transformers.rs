@@ -489,6 +489,7 @@ pub(crate) async fn fetch_raw_secrets(
         jwekey,
         webhooks: conf.webhooks,
         pm_filters: conf.pm_filters,
+        connector_amount_limits: conf.connector_amount_limits,
         payout_method_filters: conf.payout_method_filters,
         bank_config: conf.bank_config,
         api_keys,
@@ -522,6 +523,10 @@ pub(crate) async fn fetch_raw_secrets(
         analytics,
         #[cfg(feature = "olap")]
         opensearch: conf.opensearch,
+        #[cfg(feature = "olap")]
+        analytics_dimensions: conf.analytics_dimensions,
+        #[cfg(feature = "olap")]
+        analytics_reporting_currency: conf.analytics_reporting_currency,
         #[cfg(feature = "kv_store")]
         kv_config: conf.kv_config,
         #[cfg(feature = "frm")]
@@ -4,9 +4,11 @@ use std::{
 };
 
 #[cfg(feature = "olap")]
-use analytics::{opensearch::OpenSearchConfig, ReportConfig};
+use analytics::{
+    opensearch::OpenSearchConfig, DimensionAllowListConfig, ReportConfig, ReportingCurrencyConfig,
+};
 use api_models::{enums, payment_methods::RequiredFieldInfo};
-use common_utils::ext_traits::ConfigExt;
+use common_utils::{ext_traits::ConfigExt, types::MinorUnit};
 use config::{Environment, File};
 use error_stack::ResultExt;
 #[cfg(feature = "email")]
@@ -72,6 +74,7 @@ pub struct Settings<S: SecretState> {
     pub jwekey: SecretStateContainer<Jwekey, S>,
     pub webhooks: WebhooksSettings,
     pub pm_filters: ConnectorFilters,
+    pub connector_amount_limits: ConnectorAmountLimits,
     pub bank_config: BankRedirectConfig,
     pub api_keys: SecretStateContainer<ApiKeys, S>,
     pub file_storage: FileStorageConfig,
@@ -113,6 +116,10 @@ pub struct Settings<S: SecretState> {
     pub report_download_config: ReportConfig,
     #[cfg(feature = "olap")]
     pub opensearch: OpenSearchConfig,
+    #[cfg(feature = "olap")]
+    pub analytics_dimensions: DimensionAllowListConfig,
+    #[cfg(feature = "olap")]
+    pub analytics_reporting_currency: ReportingCurrencyConfig,
     pub events: EventsConfig,
     #[cfg(feature = "olap")]
     pub connector_onboarding: SecretStateContainer<ConnectorOnboarding, S>,
@@ -475,6 +482,57 @@ pub enum PaymentMethodTypeTokenFilter {
     AllAccepted,
 }
 
+/// Minimum and maximum amounts supported by each connector, per currency. Connectors whose limits
+/// exclude the amount of the payment are not considered while routing the payment.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct ConnectorAmountLimits(pub HashMap<enums::RoutableConnectors, Vec<AmountLimits>>);
+
+impl ConnectorAmountLimits {
+    pub fn get_amount_limits(
+        &self,
+        connector: &enums::RoutableConnectors,
+        currency: &enums::Currency,
+    ) -> Option<&AmountLimits> {
+        self.0.get(connector).and_then(|amount_limits| {
+            amount_limits
+                .iter()
+                .find(|limits| &limits.currency == currency)
+        })
+    }
+
+    pub fn is_amount_supported(
+        &self,
+        connector: &enums::RoutableConnectors,
+        currency: &enums::Currency,
+        amount: MinorUnit,
+    ) -> bool {
+        self.get_amount_limits(connector, currency)
+            .map_or(true, |limits| {
+                !limits.is_below_minimum(amount) && !limits.is_above_maximum(amount)
+            })
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct AmountLimits {
+    pub currency: enums::Currency,
+    pub minimum_amount: Option<MinorUnit>,
+    pub maximum_amount: Option<MinorUnit>,
+}
+
+impl AmountLimits {
+    pub fn is_below_minimum(&self, amount: MinorUnit) -> bool {
+        self.minimum_amount
+            .is_some_and(|minimum_amount| amount < minimum_amount)
+    }
+
+    pub fn is_above_maximum(&self, amount: MinorUnit) -> bool {
+        self.maximum_amount
+            .is_some_and(|maximum_amount| amount > maximum_amount)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct BankRedirectConfig(pub HashMap<enums::PaymentMethodType, ConnectorBankNames>);
 #[derive(Debug, Deserialize, Clone)]
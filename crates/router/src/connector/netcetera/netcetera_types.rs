@@ -253,6 +253,25 @@ pub enum ThreeDSRequestorChallengeIndicator {
     ChallengeRequestedWhitelistPrompt,
 }
 
+impl ThreeDSRequestorChallengeIndicator {
+    /// Builds the challenge indicator for an explicit challenge request from the merchant.
+    /// When no challenge is requested the field is left out, so the ACS applies its default of `01`.
+    pub fn from_request_challenge(
+        request_challenge: Option<bool>,
+        message_version: &common_utils::types::SemanticVersion,
+    ) -> Option<SingleOrListElement<Self>> {
+        request_challenge
+            .filter(|request_challenge| *request_challenge)
+            .map(|_| {
+                if *message_version >= common_utils::types::SemanticVersion::new(2, 3, 1) {
+                    SingleOrListElement::new_list(vec![Self::ChallengeRequestedMandate])
+                } else {
+                    SingleOrListElement::new_single(Self::ChallengeRequestedMandate)
+                }
+            })
+    }
+}
+
 /// This field contains information about how the 3DS Requestor authenticated the cardholder as part of a previous 3DS transaction.
 /// Format of this field was changed with EMV 3DS 2.3.1 version:
 /// In versions prior to 2.3.1, this field is a single object.
@@ -1659,3 +1678,43 @@ pub enum SdkUiType {
     #[serde(rename = "05")]
     HtmlOther,
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use common_utils::types::SemanticVersion;
+
+    use super::*;
+
+    #[test]
+    fn test_request_challenge_forces_challenge_indicator() {
+        let indicator = ThreeDSRequestorChallengeIndicator::from_request_challenge(
+            Some(true),
+            &SemanticVersion::new(2, 2, 0),
+        );
+        assert_eq!(
+            serde_json::to_value(indicator).unwrap(),
+            serde_json::json!("04")
+        );
+
+        let indicator = ThreeDSRequestorChallengeIndicator::from_request_challenge(
+            Some(true),
+            &SemanticVersion::new(2, 3, 1),
+        );
+        assert_eq!(
+            serde_json::to_value(indicator).unwrap(),
+            serde_json::json!(["04"])
+        );
+    }
+
+    #[test]
+    fn test_challenge_indicator_defaults_without_request_challenge() {
+        for request_challenge in [None, Some(false)] {
+            assert!(ThreeDSRequestorChallengeIndicator::from_request_challenge(
+                request_challenge,
+                &SemanticVersion::new(2, 2, 0),
+            )
+            .is_none());
+        }
+    }
+}
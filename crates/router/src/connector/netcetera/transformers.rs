@@ -460,7 +460,11 @@ impl TryFrom<&NetceteraRouterData<&types::authentication::ConnectorAuthenticatio
             three_ds_requestor_authentication_ind:
                 netcetera_types::ThreeDSRequestorAuthenticationIndicator::Payment,
             three_ds_requestor_authentication_info: None,
-            three_ds_requestor_challenge_ind: None,
+            three_ds_requestor_challenge_ind:
+                netcetera_types::ThreeDSRequestorChallengeIndicator::from_request_challenge(
+                    request.request_challenge,
+                    &pre_authn_data.message_version,
+                ),
             three_ds_requestor_prior_authentication_info: None,
             three_ds_requestor_dec_req_ind: None,
             three_ds_requestor_dec_max_time: None,
@@ -794,7 +794,7 @@ impl
                 self.base_url(connectors),
                 "v1/payment_intents",
                 x,
-                "?expand[0]=latest_charge" //updated payment_id(if present) reside inside latest_charge field
+                "?expand[0]=latest_charge&expand[1]=latest_charge.balance_transaction" //updated payment_id(if present) reside inside latest_charge field
             )),
             x => x.change_context(errors::ConnectorError::MissingConnectorTransactionID),
         }
@@ -931,6 +931,10 @@ impl
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.push((
+            headers::IDEMPOTENCY_KEY.to_string(),
+            connector_utils::get_idempotency_key(&req.merchant_id, &req.attempt_id).into_masked(),
+        ));
 
         req.request
             .charges
@@ -2901,3 +2905,131 @@ impl
         self.build_error_response(res, event_builder)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn get_authorize_router_data(attempt_id: &str) -> types::PaymentsAuthorizeRouterData {
+        types::RouterData {
+            flow: std::marker::PhantomData,
+            status: enums::AttemptStatus::Started,
+            request: types::PaymentsAuthorizeData {
+                payment_method_data: domain::PaymentMethodData::Card(domain::Card::default()),
+                email: None,
+                customer_name: None,
+                amount: 1000,
+                minor_amount: common_utils::types::MinorUnit::new(1000),
+                confirm: true,
+                currency: enums::Currency::USD,
+                metadata: None,
+                mandate_id: None,
+                webhook_url: None,
+                customer_id: None,
+                off_session: None,
+                browser_info: None,
+                session_token: None,
+                order_details: None,
+                order_category: None,
+                capture_method: None,
+                enrolled_for_3ds: false,
+                router_return_url: None,
+                surcharge_details: None,
+                setup_future_usage: None,
+                payment_experience: None,
+                payment_method_type: None,
+                statement_descriptor: None,
+                setup_mandate_details: None,
+                complete_authorize_url: None,
+                related_transaction_id: None,
+                statement_descriptor_suffix: None,
+                request_incremental_authorization: false,
+                authentication_data: None,
+                customer_acceptance: None,
+                charges: None,
+                merchant_order_reference_id: None,
+                integrity_object: None,
+            },
+            response: Err(types::ErrorResponse::default()),
+            connector: "stripe".to_string(),
+            auth_type: enums::AuthenticationType::NoThreeDs,
+            test_mode: None,
+            return_url: None,
+            attempt_id: attempt_id.to_string(),
+            description: None,
+            customer_id: None,
+            merchant_id: common_utils::id_type::MerchantId::default(),
+            reference_id: None,
+            access_token: None,
+            session_token: None,
+            payment_method: enums::PaymentMethod::Card,
+            amount_captured: None,
+            minor_amount_captured: None,
+            preprocessing_id: None,
+            connector_customer: None,
+            connector_auth_type: types::ConnectorAuthType::HeaderKey {
+                api_key: masking::Secret::new("sk_test_key".to_string()),
+            },
+            connector_meta_data: None,
+            connector_wallets_details: None,
+            payment_method_token: None,
+            connector_api_version: None,
+            recurring_mandate_payment_data: None,
+            payment_method_status: None,
+            connector_request_reference_id: attempt_id.to_string(),
+            address: types::PaymentAddress::new(None, None, None, None),
+            payment_id: "pay_idempotency".to_string(),
+            #[cfg(feature = "payouts")]
+            payout_method_data: None,
+            #[cfg(feature = "payouts")]
+            quote_id: None,
+            payment_method_balance: None,
+            connector_http_status_code: None,
+            external_latency: None,
+            apple_pay_flow: None,
+            frm_metadata: None,
+            refund_id: None,
+            dispute_id: None,
+            connector_response: None,
+            integrity_check: Ok(()),
+            additional_merchant_data: None,
+            header_payload: None,
+        }
+    }
+
+    fn get_idempotency_key_header(router_data: &types::PaymentsAuthorizeRouterData) -> String {
+        let request_headers = types::PaymentsAuthorizeType::get_headers(
+            Stripe::new(),
+            router_data,
+            &Default::default(),
+        )
+        .unwrap();
+        request_headers
+            .into_iter()
+            .find(|(name, _)| name == headers::IDEMPOTENCY_KEY)
+            .map(|(_, value)| value.into_inner())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_same_attempt_produces_same_idempotency_key_across_retries() {
+        let first_request = get_idempotency_key_header(&get_authorize_router_data("pay_1_1"));
+        let resent_request = get_idempotency_key_header(&get_authorize_router_data("pay_1_1"));
+        let next_attempt = get_idempotency_key_header(&get_authorize_router_data("pay_1_2"));
+
+        assert_eq!(first_request, resent_request);
+        assert_ne!(first_request, next_attempt);
+    }
+
+    #[test]
+    fn test_authorize_request_carries_idempotency_key() {
+        let router_data = get_authorize_router_data("pay_1_1");
+
+        assert_eq!(
+            get_idempotency_key_header(&router_data),
+            connector_utils::get_idempotency_key(&router_data.merchant_id, &router_data.attempt_id)
+        );
+    }
+}
@@ -2206,6 +2206,32 @@ pub enum StripeChargeEnum {
 pub struct StripeCharge {
     pub id: String,
     pub payment_method_details: Option<StripePaymentMethodDetailsResponse>,
+    pub outcome: Option<StripeChargeOutcome>,
+    pub balance_transaction: Option<StripeBalanceTransactionEnum>,
+}
+
+#[derive(Debug, Eq, PartialEq, Deserialize, Clone, Serialize)]
+#[serde(untagged)]
+pub enum StripeBalanceTransactionEnum {
+    BalanceTransactionId(String),
+    BalanceTransactionObject(StripeBalanceTransaction),
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StripeBalanceTransaction {
+    pub id: String,
+    /// Fees paid for this transaction, in the currency of the balance transaction
+    pub fee: MinorUnit,
+    /// Three-letter ISO currency code, in lowercase
+    pub currency: String,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StripeChargeOutcome {
+    /// Radar risk score between 0 and 100, only returned for Radar for Fraud Teams accounts
+    pub risk_score: Option<u8>,
+    #[serde(rename = "type")]
+    pub outcome_type: Option<String>,
 }
 
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq, Serialize)]
@@ -2389,15 +2415,45 @@ fn extract_payment_method_connector_response_from_latest_charge(
     stripe_charge_enum: &StripeChargeEnum,
 ) -> Option<types::ConnectorResponseData> {
     if let StripeChargeEnum::ChargeObject(charge_object) = stripe_charge_enum {
-        charge_object
+        let additional_payment_method_data = charge_object
             .payment_method_details
             .as_ref()
             .and_then(StripePaymentMethodDetailsResponse::get_additional_payment_method_data)
+            .map(types::AdditionalPaymentMethodConnectorResponse::from);
+        let risk_data = charge_object
+            .outcome
+            .as_ref()
+            .map(|outcome| types::ConnectorRiskData {
+                score: outcome.risk_score.map(f64::from),
+                max_score: None,
+                decision: outcome.outcome_type.clone(),
+            });
+        // The balance transaction is only created once the charge settles into the balance, so
+        // the fee is not known for payments that are yet to be captured
+        let fee_data = match charge_object.balance_transaction.as_ref() {
+            Some(StripeBalanceTransactionEnum::BalanceTransactionObject(balance_transaction)) => {
+                balance_transaction
+                    .currency
+                    .to_uppercase()
+                    .parse::<storage_enums::Currency>()
+                    .ok()
+                    .map(|currency| types::ConnectorFeeData {
+                        amount: balance_transaction.fee,
+                        currency,
+                    })
+            }
+            Some(StripeBalanceTransactionEnum::BalanceTransactionId(_)) | None => None,
+        };
+
+        (additional_payment_method_data.is_some() || risk_data.is_some() || fee_data.is_some())
+            .then_some(types::ConnectorResponseData {
+                additional_payment_method_data,
+                risk_data,
+                fee_data,
+            })
     } else {
         None
     }
-    .map(types::AdditionalPaymentMethodConnectorResponse::from)
-    .map(types::ConnectorResponseData::with_additional_payment_method_data)
 }
 
 fn extract_payment_method_connector_response_from_latest_attempt(
@@ -78,6 +78,16 @@ impl AccessTokenRequestInfo for types::RefreshTokenRouterData {
     }
 }
 
+/// Idempotency key for connectors that support one. It is derived from the payment attempt, so a
+/// request that is sent again for the same attempt carries the same key and is not processed twice
+pub fn get_idempotency_key(merchant_id: &id_type::MerchantId, attempt_id: &str) -> String {
+    let attempt_reference = format!("{}_{attempt_id}", merchant_id.get_string_repr());
+    hex::encode(ring::digest::digest(
+        &ring::digest::SHA256,
+        attempt_reference.as_bytes(),
+    ))
+}
+
 pub trait RouterData {
     fn get_billing(&self) -> Result<&api::Address, Error>;
     fn get_billing_country(&self) -> Result<api_models::enums::CountryAlpha2, Error>;
@@ -1513,7 +1523,7 @@ impl CardData for domain::Card {
 }
 
 #[track_caller]
-fn get_card_issuer(card_number: &str) -> Result<CardIssuer, Error> {
+pub(crate) fn get_card_issuer(card_number: &str) -> Result<CardIssuer, Error> {
     for (k, v) in CARD_REGEX.iter() {
         let regex: Regex = v
             .clone()
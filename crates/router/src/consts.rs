@@ -24,12 +24,26 @@ pub const REQUEST_TIMEOUT_PAYMENT_NOT_FOUND: &str = "Timed out ,payment not foun
 pub const REQUEST_TIMEOUT_ERROR_MESSAGE_FROM_PSYNC: &str =
     "This Payment has been moved to failed as there is no response from the connector";
 
+/// Cancellation reason set on payments that are auto cancelled for not being confirmed in time
+pub const AUTO_CANCEL_UNCONFIRMED_PAYMENT_REASON: &str =
+    "Payment was not confirmed within the auto cancel window";
+
+/// Default time after which a payment that is still processing is moved to failed (in seconds)
+pub const DEFAULT_PROCESSING_PAYMENT_EXPIRY: i64 = 7 * 24 * 60 * 60;
+
+/// Error reason set on payments that are moved to failed for processing for too long
+pub const PROCESSING_PAYMENT_EXPIRED_REASON: &str =
+    "This Payment has been moved to failed as it did not complete processing in time";
+
 ///Payment intent fulfillment default timeout (in seconds)
 pub const DEFAULT_FULFILLMENT_TIME: i64 = 15 * 60;
 
 /// Payment intent default client secret expiry (in seconds)
 pub const DEFAULT_SESSION_EXPIRY: i64 = 15 * 60;
 
+/// Default duration over which a customer's captured amount is summed up for the spending limit (in seconds)
+pub const DEFAULT_CUSTOMER_SPENDING_LIMIT_WINDOW: i64 = 30 * 24 * 60 * 60;
+
 /// The length of a merchant fingerprint secret
 pub const FINGERPRINT_SECRET_LENGTH: usize = 64;
 
@@ -130,6 +144,9 @@ pub const CONNECTOR_CREDS_TOKEN_TTL: i64 = 900;
 //max_amount allowed is 999999999 in minor units
 pub const MAX_ALLOWED_AMOUNT: i64 = 999999999;
 
+// Maximum length of the device fingerprint accepted in the payments request
+pub const MAX_DEVICE_FINGERPRINT_LENGTH: usize = 64;
+
 //payment attempt default unified error code and unified error message
 pub const DEFAULT_UNIFIED_ERROR_CODE: &str = "UE_000";
 pub const DEFAULT_UNIFIED_ERROR_MESSAGE: &str = "Something went wrong";
@@ -3539,6 +3539,17 @@ impl ProfileCreateBridge for api::ProfileCreate {
             is_network_tokenization_enabled: self.is_network_tokenization_enabled,
             is_auto_retries_enabled: self.is_auto_retries_enabled.unwrap_or_default(),
             max_auto_retries_enabled: self.max_auto_retries_enabled.map(i16::from),
+            is_payment_method_token_response_enabled: self.is_payment_method_token_response_enabled,
+            customer_spending_limit: self.customer_spending_limit,
+            customer_spending_limit_window_in_secs: self
+                .customer_spending_limit_window_in_secs
+                .map(i64::from),
+            is_matched_routing_rule_response_enabled: self.is_matched_routing_rule_response_enabled,
+            confirm_response_masking_policy: self.confirm_response_masking_policy,
+            soft_three_ds_failure_policy: self.soft_three_ds_failure_policy,
+            pan_storage_policy: self.pan_storage_policy,
+            auto_cancel_unconfirmed_after: self.auto_cancel_unconfirmed_after.map(i64::from),
+            issuer_country_connector_preferences: self.issuer_country_connector_preferences,
         }))
     }
 
@@ -3888,6 +3899,19 @@ impl ProfileUpdateBridge for api::ProfileUpdate {
                 is_network_tokenization_enabled: self.is_network_tokenization_enabled,
                 is_auto_retries_enabled: self.is_auto_retries_enabled,
                 max_auto_retries_enabled: self.max_auto_retries_enabled.map(i16::from),
+                is_payment_method_token_response_enabled: self
+                    .is_payment_method_token_response_enabled,
+                customer_spending_limit: self.customer_spending_limit,
+                customer_spending_limit_window_in_secs: self
+                    .customer_spending_limit_window_in_secs
+                    .map(i64::from),
+                is_matched_routing_rule_response_enabled: self
+                    .is_matched_routing_rule_response_enabled,
+                confirm_response_masking_policy: self.confirm_response_masking_policy,
+                soft_three_ds_failure_policy: self.soft_three_ds_failure_policy,
+                pan_storage_policy: self.pan_storage_policy,
+                auto_cancel_unconfirmed_after: self.auto_cancel_unconfirmed_after.map(i64::from),
+                issuer_country_connector_preferences: self.issuer_country_connector_preferences,
             },
         )))
     }
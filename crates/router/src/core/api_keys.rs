@@ -57,6 +57,8 @@ impl PlaintextApiKey {
 
     const PREFIX_LEN: usize = 12;
 
+    const ENVIRONMENT_PREFIXES: [&'static str; 3] = ["dev", "snd", "prd"];
+
     pub fn new(length: usize) -> Self {
         let env = router_env::env::prefix_for_env();
         let key = common_utils::crypto::generate_cryptographically_secure_random_string(length);
@@ -76,6 +78,28 @@ impl PlaintextApiKey {
         self.0.peek()
     }
 
+    /// Prefix of the environment the API key was generated in, either `dev`, `snd` or `prd`
+    pub fn environment_prefix(&self) -> Option<&str> {
+        self.0
+            .peek()
+            .split_once('_')
+            .map(|(prefix, _)| prefix)
+            .filter(|prefix| Self::ENVIRONMENT_PREFIXES.contains(prefix))
+    }
+
+    /// Rejects API keys generated in a different environment than the given one, such as a
+    /// sandbox API key used against production
+    pub fn validate_environment(&self, environment_prefix: &str) -> errors::RouterResult<()> {
+        match self.environment_prefix() {
+            Some(key_environment_prefix) if key_environment_prefix != environment_prefix => {
+                Err(report!(errors::ApiErrorResponse::Unauthorized)).attach_printable(
+                    "API key was generated for a different environment than the current one",
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub fn keyed_hash(&self, key: &[u8; Self::HASH_KEY_LEN]) -> HashedApiKey {
         /*
         Decisions regarding API key hashing algorithm chosen:
@@ -584,4 +608,28 @@ mod tests {
         let new_hashed_api_key = plaintext_api_key.keyed_hash(hash_key.peek());
         assert_eq!(hashed_api_key, new_hashed_api_key)
     }
+
+    #[test]
+    fn test_api_key_environment_matches() {
+        let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
+
+        assert_eq!(
+            plaintext_api_key.environment_prefix(),
+            Some(router_env::env::prefix_for_env())
+        );
+        assert!(plaintext_api_key
+            .validate_environment(router_env::env::prefix_for_env())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_sandbox_api_key_rejected_in_production() {
+        let plaintext_api_key = PlaintextApiKey::from("snd_Qx2Rb1ZtJ0mK8vW3yH7nLc4pT6sE9aDf");
+
+        let error = plaintext_api_key.validate_environment("prd").unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::Unauthorized
+        ));
+    }
 }
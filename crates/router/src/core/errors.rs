@@ -1,5 +1,6 @@
 pub mod customers_error_response;
 pub mod error_handlers;
+pub mod localized_error_response;
 pub mod transformers;
 #[cfg(feature = "olap")]
 pub mod user;
@@ -23,6 +24,7 @@ pub use user::*;
 
 pub use self::{
     customers_error_response::CustomersErrorResponse,
+    localized_error_response::LocalizedApiErrorResponse,
     sch_errors::*,
     storage_errors::*,
     storage_impl_errors::*,
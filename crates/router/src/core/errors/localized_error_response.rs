@@ -0,0 +1,127 @@
+use common_utils::errors::ErrorSwitch;
+use rust_i18n::t;
+
+use super::ApiErrorResponse;
+
+/// Locales that error messages are translated to. Errors of requests in other locales keep their
+/// English message.
+const ERROR_MESSAGE_LOCALES: [&str; 3] = ["de", "es", "fr"];
+
+/// Error response whose message is translated to the locale the request was made in
+#[derive(Debug, thiserror::Error)]
+#[error("{error}")]
+pub struct LocalizedApiErrorResponse {
+    pub error: ApiErrorResponse,
+    pub locale: Option<String>,
+}
+
+impl LocalizedApiErrorResponse {
+    pub fn from_report(
+        report: error_stack::Report<ApiErrorResponse>,
+        locale: Option<String>,
+    ) -> error_stack::Report<Self> {
+        let error = report.current_context().clone();
+        report.change_context(Self { error, locale })
+    }
+}
+
+/// Supported locale of an `Accept-Language` header value such as `de-DE,de;q=0.9,en;q=0.8`,
+/// matched by the primary language of the most preferred language range
+fn get_error_message_locale(accept_language: &str) -> Option<&'static str> {
+    let language = accept_language
+        .split([',', ';', '-', '_'])
+        .next()?
+        .trim()
+        .to_lowercase();
+    ERROR_MESSAGE_LOCALES
+        .into_iter()
+        .find(|locale| *locale == language)
+}
+
+/// Message of the error in the message catalog, keyed by the error code, for errors that have a
+/// translated message
+pub fn get_localized_error_message(error: &ApiErrorResponse, locale: &str) -> Option<String> {
+    let locale = get_error_message_locale(locale)?;
+    let message = match error {
+        ApiErrorResponse::MissingRequiredField { field_name } => {
+            t!("errors.IR_04", locale = locale, field_name = field_name)
+        }
+        ApiErrorResponse::InvalidDataValue { field_name } => {
+            t!("errors.IR_07", locale = locale, field_name = field_name)
+        }
+        ApiErrorResponse::ClientSecretInvalid => t!("errors.IR_09", locale = locale),
+        ApiErrorResponse::MissingRequiredFields { .. } => t!("errors.IR_21", locale = locale),
+        ApiErrorResponse::PaymentNotFound => t!("errors.HE_02", locale = locale),
+        _ => return None,
+    };
+    Some(message.to_string())
+}
+
+impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for LocalizedApiErrorResponse {
+    fn switch(&self) -> api_models::errors::types::ApiErrorResponse {
+        let mut response: api_models::errors::types::ApiErrorResponse = self.error.switch();
+        if let Some(message) = self
+            .locale
+            .as_deref()
+            .and_then(|locale| get_localized_error_message(&self.error, locale))
+        {
+            response.get_internal_error_mut().error_message = message;
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_message(error: ApiErrorResponse, locale: Option<&str>) -> String {
+        let mut response = LocalizedApiErrorResponse {
+            error,
+            locale: locale.map(ToString::to_string),
+        }
+        .switch();
+        response.get_internal_error_mut().error_message.clone()
+    }
+
+    #[test]
+    fn test_invalid_request_error_is_localized() {
+        let error = || ApiErrorResponse::MissingRequiredField {
+            field_name: "payment_method",
+        };
+
+        assert_eq!(
+            error_message(error(), Some("de-DE,de;q=0.9,en;q=0.8")),
+            "Erforderlicher Parameter fehlt: payment_method"
+        );
+        assert_eq!(
+            error_message(error(), Some("fr")),
+            "Paramètre obligatoire manquant : payment_method"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_locales_fall_back_to_english() {
+        let error = || ApiErrorResponse::MissingRequiredField {
+            field_name: "payment_method",
+        };
+
+        assert_eq!(
+            error_message(error(), Some("ja-JP")),
+            "Missing required param: payment_method"
+        );
+        assert_eq!(
+            error_message(error(), None),
+            "Missing required param: payment_method"
+        );
+        assert_eq!(
+            error_message(
+                ApiErrorResponse::InvalidRequestData {
+                    message: "amount must be positive".to_string(),
+                },
+                Some("de")
+            ),
+            "amount must be positive"
+        );
+    }
+}
@@ -168,6 +168,7 @@ pub async fn call_to_locker(
             card_network: None,
             card_issuer: None,
             card_type: None,
+            preferred_network: None,
         };
 
         let pm_create = api::PaymentMethodCreate {
@@ -41,7 +41,7 @@ use hyperswitch_domain_models::payments::{payment_attempt::PaymentAttempt, Payme
 #[cfg(all(feature = "v2", feature = "payment_methods_v2"))]
 use masking::ExposeInterface;
 use masking::{PeekInterface, Secret};
-use router_env::{instrument, tracing};
+use router_env::{instrument, metrics::add_attributes, tracing};
 use time::Duration;
 
 use super::{
@@ -68,7 +68,7 @@ use crate::{
         errors::{self, RouterResult},
         payments::helpers as payment_helpers,
     },
-    routes::{app::StorageInterface, SessionState},
+    routes::{app::StorageInterface, metrics, SessionState},
     services,
     types::{
         domain,
@@ -79,6 +79,50 @@ use crate::{
 const PAYMENT_METHOD_STATUS_UPDATE_TASK: &str = "PAYMENT_METHOD_STATUS_UPDATE";
 const PAYMENT_METHOD_STATUS_TAG: &str = "PAYMENT_METHOD_STATUS";
 
+/// Outcome of a payment method create or migrate request, as reported in the
+/// `PAYMENT_METHOD_CREATION_OUTCOME` metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentMethodCreationOutcome {
+    Success,
+    ValidationFailure,
+    LockerFailure,
+    Duplicate,
+    OtherFailure,
+}
+
+impl<T> From<&RouterResult<T>> for PaymentMethodCreationOutcome {
+    fn from(result: &RouterResult<T>) -> Self {
+        let Err(error) = result else {
+            return Self::Success;
+        };
+        // Locker errors are converted into an internal server error by the callers, so the
+        // original vault error is looked up in the report chain
+        if error.contains::<errors::VaultError>() {
+            return Self::LockerFailure;
+        }
+        match error.current_context() {
+            errors::ApiErrorResponse::DuplicatePaymentMethod => Self::Duplicate,
+            errors::ApiErrorResponse::InvalidRequestData { .. }
+            | errors::ApiErrorResponse::MissingRequiredField { .. }
+            | errors::ApiErrorResponse::MissingRequiredFields { .. }
+            | errors::ApiErrorResponse::InvalidDataValue { .. }
+            | errors::ApiErrorResponse::InvalidDataFormat { .. }
+            | errors::ApiErrorResponse::PreconditionFailed { .. } => Self::ValidationFailure,
+            _ => Self::OtherFailure,
+        }
+    }
+}
+
+pub fn record_payment_method_creation_outcome<T>(flow: &'static str, result: &RouterResult<T>) {
+    let outcome = PaymentMethodCreationOutcome::from(result);
+    metrics::PAYMENT_METHOD_CREATION_OUTCOME.add(
+        &metrics::CONTEXT,
+        1,
+        &add_attributes([("flow", flow), ("outcome", outcome.into())]),
+    );
+}
+
 #[instrument(skip_all)]
 pub async fn retrieve_payment_method_core(
     pm_data: &Option<domain::PaymentMethodData>,
@@ -700,6 +744,7 @@ pub(crate) async fn get_payment_method_create_request(
                             .transpose()
                             .ok()
                             .flatten(),
+                        preferred_network: None,
                     };
                     let payment_method_request = payment_methods::PaymentMethodCreate {
                         payment_method,
@@ -765,6 +810,7 @@ pub(crate) async fn get_payment_method_create_request(
                         card_network: card.card_network.clone(),
                         card_issuer: card.card_issuer.clone(),
                         card_type: card.card_type.clone(),
+                        preferred_network: None,
                     };
                     let payment_method_request = payment_methods::PaymentMethodCreate {
                         payment_method: Some(payment_method),
@@ -1245,7 +1291,16 @@ pub async fn create_pm_additional_data_update(
 ) -> RouterResult<storage::PaymentMethodUpdate> {
     let card = match pmd {
         pm_types::PaymentMethodVaultingData::Card(card) => {
-            api::PaymentMethodsData::Card(card.clone().into())
+            api::PaymentMethodsData::Card(api::CardDetailsPaymentMethod {
+                card_network: card.card_network.clone().or_else(|| {
+                    api::payment_methods::get_card_network_from_card_number(card.card_number.peek())
+                }),
+                card_networks: Some(api::payment_methods::get_card_networks(
+                    card,
+                    card.card_network.as_ref(),
+                )),
+                ..card.clone().into()
+            })
         }
     };
 
@@ -1492,7 +1547,9 @@ pub async fn list_customer_payment_method(
             customer_id,
             merchant_account.get_id(),
             common_enums::PaymentMethodStatus::Active,
+            &Default::default(),
             limit,
+            None,
             merchant_account.storage_scheme,
         )
         .await
@@ -1630,13 +1687,13 @@ async fn generate_saved_pm_response(
     )
     .await?;
 
-    let requires_cvv = if is_connector_agnostic_mit_enabled {
-        requires_cvv
-            && !(off_session_payment_flag
-                && (pm.connector_mandate_details.is_some() || pm.network_transaction_id.is_some()))
-    } else {
-        requires_cvv && !(off_session_payment_flag && pm.connector_mandate_details.is_some())
-    };
+    let requires_cvv = cards::saved_payment_method_requires_cvv(
+        requires_cvv,
+        off_session_payment_flag,
+        is_connector_agnostic_mit_enabled,
+        pm.connector_mandate_details.is_some(),
+        pm.network_transaction_id.is_some(),
+    );
 
     let pmd = if let Some(card) = pm_list_context.card_details.as_ref() {
         Some(api::PaymentMethodListData::Card(card.clone()))
@@ -1661,8 +1718,7 @@ async fn generate_saved_pm_response(
         created: Some(pm.created_at),
         bank: bank_details,
         surcharge_details: None,
-        requires_cvv: requires_cvv
-            && !(off_session_payment_flag && pm.connector_mandate_details.is_some()),
+        requires_cvv,
         last_used_at: Some(pm.last_used_at),
         is_default: customer.default_payment_method_id.is_some()
             && customer.default_payment_method_id.as_deref() == Some(pm.get_id().get_string_repr()),
@@ -1935,3 +1991,306 @@ impl pm_types::SavedPMLPaymentsInfo {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::HashMap;
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(feature = "payment_methods_v2")
+    ))]
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_payment_method_creation_outcome_counts() {
+        let fixture: Vec<RouterResult<()>> = vec![
+            Ok(()),
+            Ok(()),
+            Err(report!(errors::ApiErrorResponse::DuplicatePaymentMethod)),
+            Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "card_number"
+            })),
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "Invalid card expiry".to_string()
+            })),
+            Err(report!(errors::VaultError::SaveCardFailed)
+                .change_context(errors::ApiErrorResponse::InternalServerError)),
+            Err(report!(errors::ApiErrorResponse::InternalServerError)),
+        ];
+
+        let mut counts: HashMap<PaymentMethodCreationOutcome, usize> = HashMap::new();
+        for result in &fixture {
+            *counts
+                .entry(PaymentMethodCreationOutcome::from(result))
+                .or_default() += 1;
+        }
+
+        assert_eq!(counts.get(&PaymentMethodCreationOutcome::Success), Some(&2));
+        assert_eq!(
+            counts.get(&PaymentMethodCreationOutcome::Duplicate),
+            Some(&1)
+        );
+        assert_eq!(
+            counts.get(&PaymentMethodCreationOutcome::ValidationFailure),
+            Some(&2)
+        );
+        assert_eq!(
+            counts.get(&PaymentMethodCreationOutcome::LockerFailure),
+            Some(&1)
+        );
+        assert_eq!(
+            counts.get(&PaymentMethodCreationOutcome::OtherFailure),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn test_payment_method_creation_outcome_label() {
+        let label: &'static str = PaymentMethodCreationOutcome::LockerFailure.into();
+        assert_eq!(label, "locker_failure");
+    }
+
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(feature = "payment_methods_v2")
+    ))]
+    fn get_card_detail() -> api_models::payment_methods::CardDetail {
+        api_models::payment_methods::CardDetail {
+            card_number: ::cards::CardNumber::from_str("4242424242424242").unwrap(),
+            card_exp_month: Secret::new("10".to_string()),
+            card_exp_year: Secret::new("2030".to_string()),
+            card_holder_name: None,
+            nick_name: None,
+            card_issuing_country: None,
+            card_network: Some(common_enums::CardNetwork::Visa),
+            card_issuer: None,
+            card_type: None,
+            preferred_network: None,
+        }
+    }
+
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(feature = "payment_methods_v2")
+    ))]
+    #[test]
+    fn test_token_only_pan_storage_policy_stores_no_pan() {
+        let card_details = transformers::mk_token_only_card_details(get_card_detail());
+        assert!(card_details.card_number.is_none());
+        assert!(!card_details.saved_to_locker);
+        assert_eq!(card_details.last4_digits.as_deref(), Some("4242"));
+
+        let payment_method_data =
+            serde_json::to_string(&payment_methods::PaymentMethodsData::Card(
+                payment_methods::CardDetailsPaymentMethod::from(card_details),
+            ))
+            .unwrap();
+        assert!(!payment_method_data.contains("4242424242424242"));
+    }
+
+    #[test]
+    fn test_token_only_pan_storage_policy_requires_network_tokenization() {
+        assert!(cards::validate_pan_storage_policy(
+            common_enums::PanStoragePolicy::TokenOnly,
+            true
+        )
+        .is_ok());
+        match cards::validate_pan_storage_policy(common_enums::PanStoragePolicy::TokenOnly, false) {
+            Err(error) => assert!(matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::PreconditionFailed { .. }
+            )),
+            Ok(()) => panic!("token_only must be rejected without network tokenization"),
+        }
+        assert!(cards::validate_pan_storage_policy(
+            common_enums::PanStoragePolicy::StorePan,
+            false
+        )
+        .is_ok());
+    }
+
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(feature = "payment_methods_v2")
+    ))]
+    #[test]
+    fn test_store_pan_policy_stores_card_in_locker() {
+        assert_eq!(
+            common_enums::PanStoragePolicy::default(),
+            common_enums::PanStoragePolicy::StorePan
+        );
+
+        let req: api_models::payment_methods::PaymentMethodCreate =
+            serde_json::from_value(serde_json::json!({
+                "payment_method": "card",
+                "customer_id": "cus_123"
+            }))
+            .unwrap();
+        let resp = transformers::mk_add_card_response_hs(
+            get_card_detail(),
+            "card_reference".to_string(),
+            req,
+            &id_type::MerchantId::default(),
+        );
+        let card_details = resp.card.unwrap();
+        assert_eq!(resp.payment_method_id, "card_reference");
+        assert!(card_details.saved_to_locker);
+        assert_eq!(
+            card_details
+                .card_number
+                .map(|card_number| card_number.get_card_no()),
+            Some("4242424242424242".to_string())
+        );
+    }
+
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(feature = "payment_methods_v2")
+    ))]
+    #[test]
+    fn test_card_create_response_includes_card_metadata() {
+        let req: api_models::payment_methods::PaymentMethodCreate =
+            serde_json::from_value(serde_json::json!({
+                "payment_method": "card",
+                "customer_id": "cus_123"
+            }))
+            .unwrap();
+        let card_detail = api_models::payment_methods::CardDetail {
+            card_issuing_country: Some("US".to_string()),
+            card_network: None,
+            card_issuer: Some("Stripe Test Bank".to_string()),
+            ..get_card_detail()
+        };
+
+        let resp = transformers::mk_add_card_response_hs(
+            card_detail.clone(),
+            "card_reference".to_string(),
+            req,
+            &id_type::MerchantId::default(),
+        );
+        let token_only_card_details = transformers::mk_token_only_card_details(card_detail);
+
+        for card_details in [resp.card.unwrap(), token_only_card_details] {
+            assert_eq!(
+                card_details.card_issuer.as_deref(),
+                Some("Stripe Test Bank")
+            );
+            assert_eq!(card_details.issuer_country.as_deref(), Some("US"));
+            assert_eq!(
+                card_details.card_network,
+                Some(common_enums::CardNetwork::Visa)
+            );
+            assert_eq!(card_details.last4_digits.as_deref(), Some("4242"));
+        }
+    }
+
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(feature = "payment_methods_v2"),
+        not(feature = "customer_v2")
+    ))]
+    #[test]
+    fn test_saved_card_is_valid_through_end_of_expiry_month() {
+        let today = time::macros::date!(2024 - 05 - 31);
+        let is_expired = |month: &str, year: &str| {
+            cards::is_saved_card_expired(
+                Some(&Secret::new(month.to_string())),
+                Some(&Secret::new(year.to_string())),
+                today,
+            )
+        };
+
+        assert!(!is_expired("05", "2024"));
+        assert!(!is_expired("5", "24"));
+        assert!(is_expired("04", "2024"));
+        assert!(is_expired("12", "2023"));
+        assert!(!is_expired("01", "2025"));
+        assert!(!cards::is_saved_card_expired(None, None, today));
+    }
+
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(feature = "payment_methods_v2"),
+        not(feature = "customer_v2")
+    ))]
+    #[test]
+    fn test_customer_payment_method_list_pagination_bounds() {
+        use common_utils::consts::CUSTOMER_PAYMENT_METHODS_LIST_MAX_LIMIT;
+
+        let is_valid = |limit, offset| {
+            cards::validate_customer_payment_method_list_pagination(limit, offset).is_ok()
+        };
+
+        assert!(is_valid(None, None));
+        assert!(is_valid(Some(1), Some(0)));
+        assert!(is_valid(
+            Some(CUSTOMER_PAYMENT_METHODS_LIST_MAX_LIMIT),
+            Some(10)
+        ));
+        assert!(!is_valid(Some(0), None));
+        assert!(!is_valid(
+            Some(CUSTOMER_PAYMENT_METHODS_LIST_MAX_LIMIT + 1),
+            None
+        ));
+        assert!(!is_valid(Some(10), Some(-1)));
+    }
+
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(feature = "payment_methods_v2"),
+        not(feature = "customer_v2")
+    ))]
+    #[test]
+    fn test_customer_payment_methods_are_counted_before_paging() {
+        let listable = vec!["pm_5", "pm_4", "pm_3", "pm_2", "pm_1"];
+
+        let (page, total_count, next_offset) =
+            cards::paginate_customer_payment_methods(listable.clone(), Some(2), None).unwrap();
+        assert_eq!(page, vec!["pm_5", "pm_4"]);
+        assert_eq!(total_count, 5);
+        assert_eq!(next_offset, Some(2));
+
+        let (page, total_count, next_offset) =
+            cards::paginate_customer_payment_methods(listable.clone(), Some(2), Some(4)).unwrap();
+        assert_eq!(page, vec!["pm_1"]);
+        assert_eq!(total_count, 5);
+        assert_eq!(next_offset, None);
+
+        let (page, total_count, next_offset) =
+            cards::paginate_customer_payment_methods(listable, None, Some(6)).unwrap();
+        assert!(page.is_empty());
+        assert_eq!(total_count, 5);
+        assert_eq!(next_offset, None);
+    }
+
+    #[test]
+    fn test_saved_payment_method_requires_cvv() {
+        // On session use of a saved card
+        assert!(cards::saved_payment_method_requires_cvv(
+            true, false, false, true, false
+        ));
+        // Off session use of a saved card without a mandate
+        assert!(cards::saved_payment_method_requires_cvv(
+            true, true, false, false, false
+        ));
+        // Off session use of a saved card with a connector mandate
+        assert!(!cards::saved_payment_method_requires_cvv(
+            true, true, false, true, false
+        ));
+        // Network transaction ids are only used with connector agnostic MIT
+        assert!(cards::saved_payment_method_requires_cvv(
+            true, true, false, false, true
+        ));
+        assert!(!cards::saved_payment_method_requires_cvv(
+            true, true, true, false, true
+        ));
+        // Merchant has opted out of collecting the CVV
+        assert!(!cards::saved_payment_method_requires_cvv(
+            false, false, false, false, false
+        ));
+    }
+}
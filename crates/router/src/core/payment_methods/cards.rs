@@ -1075,6 +1075,8 @@ pub async fn add_payment_method_data(
                             card_issuer: card_info.as_ref().and_then(|ci| ci.card_issuer.clone()),
                             card_type: card_info.as_ref().and_then(|ci| ci.card_type.clone()),
                             saved_to_locker: true,
+                            card_networks: None,
+                            preferred_network: None,
                         };
 
                         let pm_data_encrypted: Encryptable<Secret<serde_json::Value>> =
@@ -1186,6 +1188,7 @@ pub async fn add_payment_method(
         .transpose()
         .change_context(errors::ApiErrorResponse::InternalServerError)?;
 
+    let mut network_token_details = None;
     let response = match payment_method {
         #[cfg(feature = "payouts")]
         api_enums::PaymentMethod::BankTransfer => match req.bank_transfer.clone() {
@@ -1218,17 +1221,36 @@ pub async fn add_payment_method(
                     &card_details.card_exp_month,
                     &card_details.card_exp_year,
                 )?;
-                Box::pin(add_card_to_locker(
-                    state,
-                    req.clone(),
-                    &card_details,
-                    &customer_id,
-                    merchant_account,
-                    None,
-                ))
-                .await
-                .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Add Card Failed")
+                match get_pan_storage_policy(state, merchant_account, key_store).await? {
+                    api_enums::PanStoragePolicy::StorePan => Box::pin(add_card_to_locker(
+                        state,
+                        req.clone(),
+                        &card_details,
+                        &customer_id,
+                        merchant_account,
+                        None,
+                    ))
+                    .await
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Add Card Failed"),
+                    api_enums::PanStoragePolicy::TokenOnly => {
+                        let (network_token_resp, network_token_requestor_ref_id) =
+                            Box::pin(add_network_token_to_locker(
+                                state,
+                                req.clone(),
+                                &card_details,
+                                &customer_id,
+                                merchant_account,
+                            ))
+                            .await?;
+                        let mut resp = network_token_resp.clone();
+                        resp.card = Some(payment_methods::mk_token_only_card_details(card_details));
+                        network_token_details =
+                            Some((network_token_resp, network_token_requestor_ref_id));
+                        // The duplication check is discarded, since the card itself is not stored in the locker
+                        Ok((resp, None))
+                    }
+                }
             }
             _ => Ok(store_default_payment_method(
                 &req,
@@ -1376,13 +1398,36 @@ pub async fn add_payment_method(
         None => {
             let pm_metadata = resp.metadata.as_ref().map(|data| data.peek());
 
-            let locker_id = if resp.payment_method == Some(api_enums::PaymentMethod::Card)
-                || resp.payment_method == Some(api_enums::PaymentMethod::BankTransfer)
+            let locker_id = if network_token_details.is_none()
+                && (resp.payment_method == Some(api_enums::PaymentMethod::Card)
+                    || resp.payment_method == Some(api_enums::PaymentMethod::BankTransfer))
             {
                 Some(resp.payment_method_id)
             } else {
                 None
             };
+            let (
+                network_token_requestor_reference_id,
+                network_token_locker_id,
+                network_token_payment_method_data,
+            ) = match network_token_details {
+                Some((network_token_resp, network_token_requestor_ref_id)) => {
+                    let network_token_payment_method_data = network_token_resp
+                        .card
+                        .map(|card| PaymentMethodsData::Card(CardDetailsPaymentMethod::from(card)))
+                        .async_map(|pm_card| create_encrypted_data(state, key_store, pm_card))
+                        .await
+                        .transpose()
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("Unable to encrypt network token data")?;
+                    (
+                        network_token_requestor_ref_id,
+                        Some(network_token_resp.payment_method_id),
+                        network_token_payment_method_data,
+                    )
+                }
+                None => (None, None, None),
+            };
             resp.payment_method_id = generate_id(consts::ID_LENGTH, "pm");
             let pm = insert_payment_method(
                 state,
@@ -1398,9 +1443,9 @@ pub async fn add_payment_method(
                 req.network_transaction_id.clone(),
                 merchant_account.storage_scheme,
                 payment_method_billing_address.map(Into::into),
-                None,
-                None,
-                None,
+                network_token_requestor_reference_id,
+                network_token_locker_id,
+                network_token_payment_method_data,
             )
             .await?;
 
@@ -1411,6 +1456,64 @@ pub async fn add_payment_method(
     Ok(services::ApplicationResponse::Json(resp))
 }
 
+/// Returns the PAN storage policy of the default profile of the merchant
+///
+/// With `token_only` the network token is the only credential stored for the card, so the policy
+/// is rejected when network tokenization is not enabled for the profile.
+#[cfg(feature = "v1")]
+async fn get_pan_storage_policy(
+    state: &routes::SessionState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+) -> errors::RouterResult<api_enums::PanStoragePolicy> {
+    let business_profile = core_utils::validate_and_get_business_profile(
+        &*state.store,
+        &state.into(),
+        key_store,
+        merchant_account.default_profile.as_ref(),
+        merchant_account.get_id(),
+    )
+    .await?;
+
+    let pan_storage_policy = business_profile
+        .as_ref()
+        .and_then(|business_profile| business_profile.pan_storage_policy)
+        .unwrap_or_default();
+    let is_network_tokenization_enabled = state.conf.network_tokenization_service.is_some()
+        && business_profile
+            .as_ref()
+            .is_some_and(|business_profile| business_profile.is_network_tokenization_enabled);
+    validate_pan_storage_policy(pan_storage_policy, is_network_tokenization_enabled)?;
+
+    Ok(pan_storage_policy)
+}
+
+/// Merchant accounts have no default profile in v2, so cards are saved with the default policy
+#[cfg(feature = "v2")]
+async fn get_pan_storage_policy(
+    _state: &routes::SessionState,
+    _merchant_account: &domain::MerchantAccount,
+    _key_store: &domain::MerchantKeyStore,
+) -> errors::RouterResult<api_enums::PanStoragePolicy> {
+    Ok(api_enums::PanStoragePolicy::default())
+}
+
+pub fn validate_pan_storage_policy(
+    pan_storage_policy: api_enums::PanStoragePolicy,
+    is_network_tokenization_enabled: bool,
+) -> errors::RouterResult<()> {
+    match pan_storage_policy {
+        api_enums::PanStoragePolicy::StorePan => Ok(()),
+        api_enums::PanStoragePolicy::TokenOnly => {
+            is_network_tokenization_enabled.then_some(()).ok_or_else(|| {
+                report!(errors::ApiErrorResponse::PreconditionFailed {
+                    message: "network tokenization must be enabled for the profile to save cards with the token_only pan_storage_policy".to_string(),
+                })
+            })
+        }
+    }
+}
+
 #[cfg(all(feature = "v2", feature = "payment_methods_v2"))]
 #[instrument(skip_all)]
 pub async fn get_fingerprint_id_from_locker<
@@ -1535,10 +1638,21 @@ pub async fn insert_payment_method(
     network_token_locker_id: Option<String>,
     network_token_payment_method_data: crypto::OptionalEncryptableValue,
 ) -> errors::RouterResult<domain::PaymentMethod> {
-    let pm_card_details = resp
-        .card
-        .clone()
-        .map(|card| PaymentMethodsData::Card(CardDetailsPaymentMethod::from(card.clone())));
+    let pm_card_details = resp.card.clone().map(|card| {
+        PaymentMethodsData::Card(CardDetailsPaymentMethod {
+            card_networks: req.card.as_ref().map(|card| {
+                api::payment_methods::get_card_networks(
+                    card,
+                    api::payment_methods::get_provided_card_network(req).as_ref(),
+                )
+            }),
+            preferred_network: req
+                .card
+                .as_ref()
+                .and_then(|card| card.preferred_network.clone()),
+            ..CardDetailsPaymentMethod::from(card)
+        })
+    });
 
     let pm_data_encrypted: crypto::OptionalEncryptableValue = pm_card_details
         .clone()
@@ -1642,7 +1756,7 @@ pub async fn update_customer_payment_method(
         }
 
         // Fetch the existing payment method data from db
-        let existing_card_data =
+        let existing_card_details =
             pm.payment_method_data
                 .clone()
                 .map(|x| x.into_inner().expose())
@@ -1659,11 +1773,12 @@ pub async fn update_customer_payment_method(
                 )
                 .transpose()?
                 .and_then(|pmd| match pmd {
-                    PaymentMethodsData::Card(crd) => Some(api::CardDetailFromLocker::from(crd)),
+                    PaymentMethodsData::Card(crd) => Some(crd),
                     _ => None,
                 })
                 .ok_or(errors::ApiErrorResponse::InternalServerError)
                 .attach_printable("Failed to obtain decrypted card object from db")?;
+        let existing_card_data = api::CardDetailFromLocker::from(existing_card_details.clone());
 
         let is_card_updation_required =
             validate_payment_method_update(card_update.clone(), existing_card_data.clone());
@@ -1762,9 +1877,14 @@ pub async fn update_customer_payment_method(
                 saved_to_locker: true,
             });
 
-            let updated_pmd = updated_card
-                .as_ref()
-                .map(|card| PaymentMethodsData::Card(CardDetailsPaymentMethod::from(card.clone())));
+            // The networks of the card and the preferred network are not updated
+            let updated_pmd = updated_card.as_ref().map(|card| {
+                PaymentMethodsData::Card(CardDetailsPaymentMethod {
+                    card_networks: existing_card_details.card_networks.clone(),
+                    preferred_network: existing_card_details.preferred_network.clone(),
+                    ..CardDetailsPaymentMethod::from(card.clone())
+                })
+            });
 
             let pm_data_encrypted: Option<Encryptable<Secret<serde_json::Value>>> = updated_pmd
                 .async_map(|updated_pmd| create_encrypted_data(&state, &key_store, updated_pmd))
@@ -1962,6 +2082,61 @@ pub async fn add_bank_to_locker(
     Ok((payment_method_resp, store_resp.duplication_check))
 }
 
+/// Converts the card to a network token and stores only the network token in the locker.
+///
+/// The response will be the tuple of PaymentMethodResponse of the stored network token and the
+/// network token requestor reference id. Unlike saving the network token alongside the card in a
+/// payment, a failure to tokenize the card is returned as an error, so that the card number is
+/// never stored instead.
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+pub async fn add_network_token_to_locker(
+    state: &routes::SessionState,
+    req: api::PaymentMethodCreate,
+    card: &api::CardDetail,
+    customer_id: &id_type::CustomerId,
+    merchant_account: &domain::MerchantAccount,
+) -> errors::RouterResult<(api::PaymentMethodResponse, Option<String>)> {
+    let (token_response, network_token_requestor_ref_id) =
+        network_tokenization::make_card_detail_network_tokenization_request(
+            state,
+            card,
+            customer_id,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to tokenize card for token only PAN storage policy")?;
+
+    let network_token = api::CardDetail {
+        card_number: token_response.token,
+        card_exp_month: token_response.token_expiry_month,
+        card_exp_year: token_response.token_expiry_year,
+        card_holder_name: None,
+        nick_name: None,
+        card_issuing_country: None,
+        card_network: Some(token_response.card_brand),
+        card_issuer: None,
+        card_type: None,
+        preferred_network: None,
+    };
+
+    let (resp, _network_token_duplication_check) = Box::pin(add_card_to_locker(
+        state,
+        req,
+        &network_token,
+        customer_id,
+        merchant_account,
+        None,
+    ))
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Add Network Token Failed")?;
+
+    Ok((resp, network_token_requestor_ref_id))
+}
+
 /// The response will be the tuple of PaymentMethodResponse and the duplication check of payment_method
 pub async fn add_card_to_locker(
     state: &routes::SessionState,
@@ -3339,6 +3514,7 @@ pub async fn list_payment_methods(
             merchant_connector_id: None,
             surcharge_amount: None,
             tax_amount: None,
+            tokenization_latency: None,
         };
 
         state
@@ -4316,6 +4492,135 @@ fn filter_recurring_based(
     recurring_enabled.map_or(true, |enabled| payment_method.recurring_enabled == enabled)
 }
 
+#[cfg(all(
+    any(feature = "v2", feature = "v1"),
+    not(feature = "payment_methods_v2"),
+    not(feature = "customer_v2")
+))]
+pub(crate) fn validate_customer_payment_method_list_pagination(
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> errors::RouterResult<()> {
+    use common_utils::consts::CUSTOMER_PAYMENT_METHODS_LIST_MAX_LIMIT;
+
+    utils::when(
+        limit.is_some_and(|limit| !(1..=CUSTOMER_PAYMENT_METHODS_LIST_MAX_LIMIT).contains(&limit)),
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "limit should be in between 1 and {CUSTOMER_PAYMENT_METHODS_LIST_MAX_LIMIT}"
+                ),
+            })
+        },
+    )?;
+    utils::when(offset.is_some_and(|offset| offset < 0), || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "offset should not be negative".to_string(),
+        })
+    })?;
+    Ok(())
+}
+
+/// Takes the requested page out of the customer payment methods that can be listed.
+///
+/// Returns the page along with the number of payment methods that can be listed and the offset
+/// of the next page, if there is one.
+pub(crate) fn paginate_customer_payment_methods<T>(
+    payment_methods: Vec<T>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> errors::RouterResult<(Vec<T>, i64, Option<i64>)> {
+    let total_count = i64::try_from(payment_methods.len())
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let offset = offset.unwrap_or(0);
+    let page = payment_methods
+        .into_iter()
+        .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+        .take(
+            limit
+                .and_then(|limit| usize::try_from(limit).ok())
+                .unwrap_or(usize::MAX),
+        )
+        .collect::<Vec<_>>();
+    let listed_count = offset.saturating_add(
+        i64::try_from(page.len()).change_context(errors::ApiErrorResponse::InternalServerError)?,
+    );
+    let next_offset = (listed_count < total_count).then_some(listed_count);
+
+    Ok((page, total_count, next_offset))
+}
+
+/// Whether the CVV of a saved payment method has to be collected again when it is used.
+///
+/// Payments made off session with a mandate on the payment method do not need the CVV, nor do
+/// those with a network transaction id when connector agnostic MIT is enabled. Every other use
+/// needs it unless the merchant has opted out of collecting the CVV.
+pub(crate) fn saved_payment_method_requires_cvv(
+    merchant_requires_cvv: bool,
+    off_session_payment: bool,
+    is_connector_agnostic_mit_enabled: bool,
+    has_connector_mandate: bool,
+    has_network_transaction_id: bool,
+) -> bool {
+    let has_mandate =
+        has_connector_mandate || (is_connector_agnostic_mit_enabled && has_network_transaction_id);
+    merchant_requires_cvv && !(off_session_payment && has_mandate)
+}
+
+/// Whether a saved card expired before the month of `today`. Cards remain valid through the end
+/// of their expiry month, and cards whose expiry cannot be read are not treated as expired.
+#[cfg(all(
+    any(feature = "v2", feature = "v1"),
+    not(feature = "payment_methods_v2"),
+    not(feature = "customer_v2")
+))]
+pub(crate) fn is_saved_card_expired(
+    expiry_month: Option<&Secret<String>>,
+    expiry_year: Option<&Secret<String>>,
+    today: time::Date,
+) -> bool {
+    let expiry_month = expiry_month.and_then(|month| month.peek().trim().parse::<u8>().ok());
+    let expiry_year = expiry_year.and_then(|year| {
+        let year = year.peek().trim();
+        match year.len() {
+            2 => format!("20{year}"),
+            _ => year.to_string(),
+        }
+        .parse::<i32>()
+        .ok()
+    });
+
+    match (expiry_month, expiry_year) {
+        (Some(expiry_month), Some(expiry_year)) => {
+            (expiry_year, expiry_month) < (today.year(), u8::from(today.month()))
+        }
+        _ => false,
+    }
+}
+
+/// Whether the saved payment method is a card that expired before the month of `today`, going by
+/// the card details stored with the payment method
+#[cfg(all(
+    any(feature = "v2", feature = "v1"),
+    not(feature = "payment_methods_v2"),
+    not(feature = "customer_v2")
+))]
+pub(crate) fn is_saved_payment_method_expired(
+    pm: &domain::PaymentMethod,
+    today: time::Date,
+) -> bool {
+    pm.payment_method_data
+        .clone()
+        .map(|payment_method_data| payment_method_data.into_inner().expose())
+        .and_then(|value| serde_json::from_value::<PaymentMethodsData>(value).ok())
+        .is_some_and(|payment_method_data| match payment_method_data {
+            PaymentMethodsData::Card(card) => {
+                is_saved_card_expired(card.expiry_month.as_ref(), card.expiry_year.as_ref(), today)
+            }
+            _ => false,
+        })
+}
+
 #[cfg(all(
     any(feature = "v2", feature = "v1"),
     not(feature = "payment_methods_v2"),
@@ -4330,6 +4635,16 @@ pub async fn do_list_customer_pm_fetch_customer_if_not_passed(
     ephemeral_api_key: Option<&str>,
 ) -> errors::RouterResponse<api::CustomerPaymentMethodsListResponse> {
     let limit = req.clone().and_then(|pml_req| pml_req.limit);
+    let offset = req.as_ref().and_then(|pml_req| pml_req.offset);
+    validate_customer_payment_method_list_pagination(limit, offset)?;
+    let exclude_expired = req.as_ref().map_or(true, |pml_req| pml_req.exclude_expired);
+    let filters = req
+        .as_ref()
+        .map(|pml_req| payment_method::PaymentMethodListFilters {
+            payment_methods: pml_req.payment_method_types.clone().unwrap_or_default(),
+            payment_method_types: pml_req.payment_method_subtypes.clone().unwrap_or_default(),
+        })
+        .unwrap_or_default();
 
     let auth_cust = if let Some(key) = ephemeral_api_key {
         let key = state
@@ -4353,6 +4668,9 @@ pub async fn do_list_customer_pm_fetch_customer_if_not_passed(
             None,
             customer_id,
             limit,
+            offset,
+            &filters,
+            exclude_expired,
         ))
         .await
     } else {
@@ -4378,6 +4696,9 @@ pub async fn do_list_customer_pm_fetch_customer_if_not_passed(
                     payment_intent,
                     &customer_id,
                     limit,
+                    offset,
+                    &filters,
+                    exclude_expired,
                 ))
                 .await
             }
@@ -4385,6 +4706,8 @@ pub async fn do_list_customer_pm_fetch_customer_if_not_passed(
                 let response = api::CustomerPaymentMethodsListResponse {
                     customer_payment_methods: Vec::new(),
                     is_guest_customer: Some(true),
+                    total_count: None,
+                    next_offset: None,
                 };
                 Ok(services::ApplicationResponse::Json(response))
             }
@@ -4404,6 +4727,9 @@ pub async fn list_customer_payment_method(
     payment_intent: Option<storage::PaymentIntent>,
     customer_id: &id_type::CustomerId,
     limit: Option<i64>,
+    offset: Option<i64>,
+    filters: &payment_method::PaymentMethodListFilters,
+    exclude_expired: bool,
 ) -> errors::RouterResponse<api::CustomerPaymentMethodsListResponse> {
     let db = &*state.store;
     let key_manager_state = &state.into();
@@ -4439,18 +4765,31 @@ pub async fn list_customer_payment_method(
 
     let requires_cvv = is_requires_cvv.config != "false";
 
-    let resp = db
+    let today = common_utils::date_time::now().date();
+
+    // The expiry of saved cards is encrypted, so expired cards are excluded after the payment
+    // methods are read, and the page and the count are taken from what remains
+    let listable_payment_methods = db
         .find_payment_method_by_customer_id_merchant_id_status(
             &(state.into()),
             &key_store,
             customer_id,
             merchant_account.get_id(),
             common_enums::PaymentMethodStatus::Active,
-            limit,
+            filters,
+            None,
+            None,
             merchant_account.storage_scheme,
         )
         .await
-        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?
+        .into_iter()
+        .filter(|pm| !(exclude_expired && is_saved_payment_method_expired(pm, today)))
+        .collect::<Vec<_>>();
+
+    let (resp, total_count, next_offset) =
+        paginate_customer_payment_methods(listable_payment_methods, limit, offset)?;
+
     let mut customer_pms = Vec::new();
 
     let profile_id = payment_intent
@@ -4538,14 +4877,13 @@ pub async fn list_customer_payment_method(
         )
         .await?;
 
-        let requires_cvv = if is_connector_agnostic_mit_enabled {
-            requires_cvv
-                && !(off_session_payment_flag
-                    && (pm.connector_mandate_details.is_some()
-                        || pm.network_transaction_id.is_some()))
-        } else {
-            requires_cvv && !(off_session_payment_flag && pm.connector_mandate_details.is_some())
-        };
+        let requires_cvv = saved_payment_method_requires_cvv(
+            requires_cvv,
+            off_session_payment_flag,
+            is_connector_agnostic_mit_enabled,
+            pm.connector_mandate_details.is_some(),
+            pm.network_transaction_id.is_some(),
+        );
         // Need validation for enabled payment method ,querying MCA
         let pma = api::CustomerPaymentMethod {
             payment_token: parent_payment_method_token.to_owned(),
@@ -4624,6 +4962,8 @@ pub async fn list_customer_payment_method(
     let mut response = api::CustomerPaymentMethodsListResponse {
         customer_payment_methods: customer_pms,
         is_guest_customer: payment_intent.as_ref().map(|_| false), //to return this key only when the request is tied to a payment intent
+        total_count: Some(total_count),
+        next_offset,
     };
 
     Box::pin(perform_surcharge_ops(
@@ -1,8 +1,29 @@
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+use std::str::FromStr;
+
 use actix_multipart::form::{bytes::Bytes, MultipartForm};
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+use api_models::payment_methods::{MigrationStatus, PaymentMethodMigrateBatchResponse};
 use api_models::payment_methods::{PaymentMethodMigrationResponse, PaymentMethodRecord};
 use csv::Reader;
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+use masking::PeekInterface;
 use rdkafka::message::ToBytes;
 
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+use crate::types::api::PaymentMethodCreateExt;
 use crate::{
     core::{errors, payment_methods::cards::migrate_payment_method},
     routes, services,
@@ -26,6 +47,7 @@ pub async fn migrate_payment_methods(
             key_store,
         )
         .await;
+        super::record_payment_method_creation_outcome("migrate", &res);
         result.push(PaymentMethodMigrationResponse::from((
             match res {
                 Ok(services::api::ApplicationResponse::Json(response)) => Ok(response),
@@ -38,6 +60,113 @@ pub async fn migrate_payment_methods(
     Ok(services::api::ApplicationResponse::Json(result))
 }
 
+/// Validate the payment method the way it would be validated if it were created. Card numbers
+/// that cannot be parsed, such as masked card numbers, are migrated without being saved in the
+/// locker, so the card details of such payment methods are not validated.
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+fn validate_payment_method_migrate(req: &api::PaymentMethodMigrate) -> errors::RouterResult<()> {
+    let card_number = req
+        .card
+        .as_ref()
+        .and_then(|card| cards::CardNumber::from_str(card.card_number.peek()).ok());
+    match card_number {
+        Some(card_number) => {
+            api::PaymentMethodCreate::get_payment_method_create_from_payment_method_migrate(
+                card_number,
+                req,
+            )
+            .validate()
+        }
+        None => Ok(()),
+    }
+}
+
+/// Validate the payment method and migrate it
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+async fn validate_and_migrate_payment_method(
+    state: routes::SessionState,
+    req: api::PaymentMethodMigrate,
+    merchant_id: &common_utils::id_type::MerchantId,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+) -> errors::RouterResponse<api::PaymentMethodResponse> {
+    validate_payment_method_migrate(&req)?;
+    migrate_payment_method(state, req, merchant_id, merchant_account, key_store).await
+}
+
+/// Outcome of the migration of a payment method of a batch
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+fn get_batch_migration_response(
+    line_number: i64,
+    customer_id: Option<common_utils::id_type::CustomerId>,
+    res: errors::RouterResponse<api::PaymentMethodResponse>,
+) -> PaymentMethodMigrationResponse {
+    match res {
+        Ok(services::api::ApplicationResponse::Json(response)) => PaymentMethodMigrationResponse {
+            line_number: Some(line_number),
+            payment_method_id: Some(response.payment_method_id),
+            payment_method: response.payment_method,
+            payment_method_type: response.payment_method_type,
+            customer_id: response.customer_id,
+            migration_status: MigrationStatus::Success,
+            migration_error: None,
+            card_number_masked: None,
+        },
+        res => PaymentMethodMigrationResponse {
+            line_number: Some(line_number),
+            customer_id,
+            migration_status: MigrationStatus::Failed,
+            migration_error: Some(match res {
+                Err(e) => e.to_string(),
+                _ => "Failed to migrate payment method".to_string(),
+            }),
+            ..PaymentMethodMigrationResponse::default()
+        },
+    }
+}
+
+/// Migrate a batch of payment methods, reporting the outcome of each payment method separately.
+/// A payment method that fails validation or storage does not stop the rest of the batch from
+/// being migrated.
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+pub async fn migrate_payment_methods_batch(
+    state: routes::SessionState,
+    payment_methods: Vec<api::PaymentMethodMigrate>,
+    merchant_id: &common_utils::id_type::MerchantId,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+) -> errors::RouterResponse<PaymentMethodMigrateBatchResponse> {
+    let mut result = Vec::with_capacity(payment_methods.len());
+    for (line_number, req) in (1..).zip(payment_methods) {
+        let customer_id = req.customer_id.clone();
+        let res = validate_and_migrate_payment_method(
+            state.clone(),
+            req,
+            merchant_id,
+            merchant_account,
+            key_store,
+        )
+        .await;
+        super::record_payment_method_creation_outcome("migrate", &res);
+        result.push(get_batch_migration_response(line_number, customer_id, res));
+    }
+    Ok(services::api::ApplicationResponse::Json(
+        PaymentMethodMigrateBatchResponse::from(result),
+    ))
+}
+
 #[derive(Debug, MultipartForm)]
 pub struct PaymentMethodsMigrateForm {
     #[multipart(limit = "1MB")]
@@ -62,25 +191,167 @@ pub fn get_payment_method_records(
 {
     match parse_csv(form.file.data.to_bytes()) {
         Ok(records) => {
-            if let Some(first_record) = records.first() {
-                if records
-                    .iter()
-                    .all(|merchant_id| merchant_id.merchant_id == first_record.merchant_id)
-                {
-                    Ok((first_record.merchant_id.clone(), records))
-                } else {
-                    Err(errors::ApiErrorResponse::PreconditionFailed {
-                        message: "Only one merchant id can be updated at a time".to_string(),
-                    })
-                }
-            } else {
-                Err(errors::ApiErrorResponse::PreconditionFailed {
-                    message: "No records found".to_string(),
-                })
-            }
+            let merchant_id =
+                get_batch_merchant_id(records.iter().map(|record| &record.merchant_id))?;
+            Ok((merchant_id, records))
         }
         Err(e) => Err(errors::ApiErrorResponse::PreconditionFailed {
             message: e.to_string(),
         }),
     }
 }
+
+/// Merchant of a batch of payment methods to be migrated, since all of them have to belong to the
+/// same merchant
+pub fn get_batch_merchant_id<'a>(
+    mut merchant_ids: impl Iterator<Item = &'a common_utils::id_type::MerchantId>,
+) -> Result<common_utils::id_type::MerchantId, errors::ApiErrorResponse> {
+    let first_merchant_id =
+        merchant_ids
+            .next()
+            .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+                message: "No records found".to_string(),
+            })?;
+    if merchant_ids.all(|merchant_id| merchant_id == first_merchant_id) {
+        Ok(first_merchant_id.clone())
+    } else {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Only one merchant id can be updated at a time".to_string(),
+        })
+    }
+}
+
+#[cfg(all(
+    test,
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::borrow::Cow;
+
+    use error_stack::report;
+
+    use super::*;
+
+    fn get_payment_method_migrate(
+        card_number: &str,
+        card_exp_month: &str,
+    ) -> api::PaymentMethodMigrate {
+        serde_json::from_value(serde_json::json!({
+            "merchant_id": "merchant_1",
+            "payment_method": "card",
+            "customer_id": "cus_migrate",
+            "card": {
+                "card_number": card_number,
+                "card_exp_month": card_exp_month,
+                "card_exp_year": "2099",
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_payment_method_migrate_validated_as_created() {
+        assert!(validate_payment_method_migrate(&get_payment_method_migrate(
+            "4242424242424242",
+            "12"
+        ))
+        .is_ok());
+
+        let error =
+            validate_payment_method_migrate(&get_payment_method_migrate("4242424242424242", "13"))
+                .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { message }
+                if message == "Invalid card expiry month"
+        ));
+    }
+
+    #[test]
+    fn test_masked_card_payment_method_migrate_not_validated() {
+        assert!(validate_payment_method_migrate(&get_payment_method_migrate(
+            "424242XXXXXX4242",
+            "13"
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_batch_migration_reports_each_payment_method() {
+        let customer_id =
+            common_utils::id_type::CustomerId::try_from(Cow::from("cus_migrate")).unwrap();
+        let response: api::PaymentMethodResponse = serde_json::from_value(serde_json::json!({
+            "merchant_id": "merchant_1",
+            "customer_id": "cus_migrate",
+            "payment_method_id": "pm_migrated",
+            "payment_method": "card",
+            "recurring_enabled": false,
+            "installment_payment_enabled": false,
+        }))
+        .unwrap();
+
+        let batch_response = PaymentMethodMigrateBatchResponse::from(vec![
+            get_batch_migration_response(
+                1,
+                Some(customer_id.clone()),
+                Ok(services::api::ApplicationResponse::Json(response)),
+            ),
+            get_batch_migration_response(
+                2,
+                Some(customer_id.clone()),
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: "Invalid card expiry month".to_string(),
+                })),
+            ),
+            get_batch_migration_response(
+                3,
+                Some(customer_id.clone()),
+                Err(report!(errors::ApiErrorResponse::InternalServerError)),
+            ),
+        ]);
+
+        assert_eq!(batch_response.succeeded, 1);
+        assert_eq!(batch_response.failed, 2);
+
+        let migrated = &batch_response.results[0];
+        assert_eq!(migrated.line_number, Some(1));
+        assert!(matches!(
+            migrated.migration_status,
+            MigrationStatus::Success
+        ));
+        assert_eq!(migrated.payment_method_id.as_deref(), Some("pm_migrated"));
+        assert!(migrated.migration_error.is_none());
+
+        for (failed, line_number) in batch_response.results[1..].iter().zip(2..) {
+            assert_eq!(failed.line_number, Some(line_number));
+            assert!(matches!(failed.migration_status, MigrationStatus::Failed));
+            assert_eq!(failed.customer_id.as_ref(), Some(&customer_id));
+            assert!(failed.payment_method_id.is_none());
+            assert!(failed.migration_error.is_some());
+        }
+    }
+
+    #[test]
+    fn test_batch_merchant_id() {
+        let merchant_id =
+            common_utils::id_type::MerchantId::try_from(Cow::from("merchant_1")).unwrap();
+        let other_merchant_id =
+            common_utils::id_type::MerchantId::try_from(Cow::from("merchant_2")).unwrap();
+
+        assert_eq!(
+            get_batch_merchant_id([&merchant_id, &merchant_id].into_iter()).unwrap(),
+            merchant_id
+        );
+        assert!(matches!(
+            get_batch_merchant_id([&merchant_id, &other_merchant_id].into_iter()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+        assert!(matches!(
+            get_batch_merchant_id(std::iter::empty()),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+    }
+}
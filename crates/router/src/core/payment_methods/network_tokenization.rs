@@ -30,7 +30,8 @@ pub struct CardData {
     card_number: CardNumber,
     exp_month: Secret<String>,
     exp_year: Secret<String>,
-    card_security_code: Secret<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    card_security_code: Option<Secret<String>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -272,9 +273,36 @@ pub async fn make_card_network_tokenization_request(
         card_number: card.card_number.clone(),
         exp_month: card.card_exp_month.clone(),
         exp_year: card.card_exp_year.clone(),
-        card_security_code: card.card_cvc.clone(),
+        card_security_code: Some(card.card_cvc.clone()),
     };
 
+    tokenize_card_data(state, &card_data, customer_id).await
+}
+
+/// Generates a network token for a card being saved outside of a payment, where the card
+/// security code is not available
+pub async fn make_card_detail_network_tokenization_request(
+    state: &routes::SessionState,
+    card: &api::CardDetail,
+    customer_id: &id_type::CustomerId,
+) -> CustomResult<(CardNetworkTokenResponsePayload, Option<String>), errors::NetworkTokenizationError>
+{
+    let card_data = CardData {
+        card_number: card.card_number.clone(),
+        exp_month: card.card_exp_month.clone(),
+        exp_year: card.card_exp_year.clone(),
+        card_security_code: None,
+    };
+
+    tokenize_card_data(state, &card_data, customer_id).await
+}
+
+async fn tokenize_card_data(
+    state: &routes::SessionState,
+    card_data: &CardData,
+    customer_id: &id_type::CustomerId,
+) -> CustomResult<(CardNetworkTokenResponsePayload, Option<String>), errors::NetworkTokenizationError>
+{
     let payload = card_data
         .encode_to_string_of_json()
         .and_then(|x| x.encode_to_string_of_json())
@@ -459,6 +459,18 @@ pub fn mk_add_bank_response_hs(
     todo!()
 }
 
+/// Network of the card, detected from the card number if it was neither provided nor found from
+/// the BIN of the card
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+fn get_card_network(card: &api::CardDetail) -> Option<api_enums::CardNetwork> {
+    card.card_network.clone().or_else(|| {
+        api::payment_methods::get_card_network_from_card_number(card.card_number.peek())
+    })
+}
+
 #[cfg(all(
     any(feature = "v1", feature = "v2"),
     not(feature = "payment_methods_v2")
@@ -472,10 +484,10 @@ pub fn mk_add_card_response_hs(
     let card_number = card.card_number.clone();
     let last4_digits = card_number.get_last4();
     let card_isin = card_number.get_card_isin();
+    let card_network = get_card_network(&card);
 
     let card = api::CardDetailFromLocker {
-        scheme: card
-            .card_network
+        scheme: card_network
             .clone()
             .map(|card_network| card_network.to_string()),
         last4_digits: Some(last4_digits),
@@ -489,7 +501,7 @@ pub fn mk_add_card_response_hs(
         nick_name: card.nick_name.clone(),
         card_isin: Some(card_isin),
         card_issuer: card.card_issuer,
-        card_network: card.card_network,
+        card_network,
         card_type: card.card_type,
         saved_to_locker: true,
     };
@@ -512,6 +524,35 @@ pub fn mk_add_card_response_hs(
     }
 }
 
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+/// Card details of a card saved with the `token_only` PAN storage policy. Only the network token
+/// of the card is stored in the locker, so the card number is not retained.
+pub fn mk_token_only_card_details(card: api::CardDetail) -> api::CardDetailFromLocker {
+    let card_network = get_card_network(&card);
+    api::CardDetailFromLocker {
+        scheme: card_network
+            .clone()
+            .map(|card_network| card_network.to_string()),
+        last4_digits: Some(card.card_number.get_last4()),
+        issuer_country: card.card_issuing_country,
+        card_number: None,
+        expiry_month: Some(card.card_exp_month),
+        expiry_year: Some(card.card_exp_year),
+        card_token: None,
+        card_fingerprint: None,
+        card_holder_name: card.card_holder_name,
+        nick_name: card.nick_name,
+        card_isin: Some(card.card_number.get_card_isin()),
+        card_issuer: card.card_issuer,
+        card_network,
+        card_type: card.card_type,
+        saved_to_locker: false,
+    }
+}
+
 #[cfg(all(feature = "v2", feature = "payment_methods_v2"))]
 pub fn mk_add_card_response_hs(
     card: api::CardDetail,
@@ -541,6 +582,7 @@ pub fn generate_pm_vaulting_req_from_update_request(
             card_type: card_create.card_type,
             card_holder_name: update_card.card_holder_name,
             nick_name: update_card.nick_name,
+            preferred_network: card_create.preferred_network,
         }),
     }
 }
@@ -5,6 +5,8 @@ pub mod customers;
 pub mod flows;
 pub mod helpers;
 pub mod operations;
+#[cfg(all(feature = "olap", feature = "v1"))]
+pub mod reconciliation;
 #[cfg(feature = "retry")]
 pub mod retry;
 pub mod routing;
@@ -334,7 +336,6 @@ where
                         #[cfg(not(feature = "frm"))]
                         None,
                         &business_profile,
-                        false,
                     )
                     .await?;
 
@@ -437,7 +438,6 @@ where
                         #[cfg(not(feature = "frm"))]
                         None,
                         &business_profile,
-                        false,
                     )
                     .await?;
 
@@ -1589,7 +1589,6 @@ pub async fn call_connector_service<F, RouterDReq, ApiRequest, D>(
     header_payload: HeaderPayload,
     frm_suggestion: Option<storage_enums::FrmSuggestion>,
     business_profile: &domain::Profile,
-    is_retry_payment: bool,
 ) -> RouterResult<(
     RouterData<F, RouterDReq, router_types::PaymentsResponseData>,
     helpers::MerchantConnectorAccountType,
@@ -1619,13 +1618,14 @@ where
     )
     .await?;
 
-    if payment_data
-        .get_payment_attempt()
-        .merchant_connector_id
-        .is_none()
-    {
-        payment_data.set_merchant_connector_id_in_attempt(merchant_connector_account.get_mca_id());
-    }
+    let merchant_connector_id = helpers::get_merchant_connector_id_for_attempt(
+        &merchant_connector_account,
+        payment_data
+            .get_payment_attempt()
+            .merchant_connector_id
+            .clone(),
+    );
+    payment_data.set_merchant_connector_id_in_attempt(merchant_connector_id);
 
     operation
         .to_domain()?
@@ -1731,7 +1731,6 @@ where
         tokenization::update_router_data_with_payment_method_token_result(
             payment_method_token_response,
             &mut router_data,
-            is_retry_payment,
             should_continue_further,
         );
 
@@ -2873,6 +2872,7 @@ where
 
             let connector_tokenization_action = match payment_method_action {
                 TokenizationAction::TokenizeInRouter => {
+                    let tokenization_start_time = Instant::now();
                     let (_operation, payment_method_data, pm_id) = operation
                         .to_domain()?
                         .make_pm_data(
@@ -2884,6 +2884,8 @@ where
                             business_profile,
                         )
                         .await?;
+                    payment_data
+                        .set_tokenization_latency_in_attempt(tokenization_start_time.elapsed());
                     payment_data.set_payment_method_data(payment_method_data);
                     payment_data.set_payment_method_id_in_attempt(pm_id);
 
@@ -2892,6 +2894,7 @@ where
 
                 TokenizationAction::TokenizeInConnector => TokenizationAction::TokenizeInConnector,
                 TokenizationAction::TokenizeInConnectorAndRouter => {
+                    let tokenization_start_time = Instant::now();
                     let (_operation, payment_method_data, pm_id) = operation
                         .to_domain()?
                         .make_pm_data(
@@ -2903,6 +2906,8 @@ where
                             business_profile,
                         )
                         .await?;
+                    payment_data
+                        .set_tokenization_latency_in_attempt(tokenization_start_time.elapsed());
 
                     payment_data.set_payment_method_data(payment_method_data);
                     payment_data.set_payment_method_id_in_attempt(pm_id);
@@ -2975,6 +2980,7 @@ where
         .request_external_three_ds_authentication;
     let payment_data =
         if !is_operation_confirm(operation) || is_external_authentication_requested == Some(true) {
+            let tokenization_start_time = Instant::now();
             let (_operation, payment_method_data, pm_id) = operation
                 .to_domain()?
                 .make_pm_data(
@@ -2986,6 +2992,7 @@ where
                     business_profile,
                 )
                 .await?;
+            payment_data.set_tokenization_latency_in_attempt(tokenization_start_time.elapsed());
             payment_data.set_payment_method_data(payment_method_data);
             if let Some(payment_method_id) = pm_id {
                 payment_data.set_payment_method_id_in_attempt(Some(payment_method_id));
@@ -3047,6 +3054,13 @@ where
     pub recurring_details: Option<RecurringDetails>,
     pub poll_config: Option<router_types::PollConfig>,
     pub tax_data: Option<TaxData>,
+    pub should_return_payment_method_token: bool,
+    pub matched_routing_rule: Option<api_models::payments::MatchedRoutingRule>,
+    /// Masking policy of the business profile applied to the response of a confirmed payment,
+    /// set for confirm, create with confirm and retrieve
+    pub confirm_response_masking_policy: Option<common_utils::types::ConfirmResponseMaskingPolicy>,
+    /// Whether the merchant asked not to save the payment method of this payment
+    pub skip_save_payment_method: bool,
 }
 
 #[derive(Clone, serde::Serialize, Debug)]
@@ -3212,6 +3226,45 @@ pub fn is_operation_complete_authorize<Op: Debug>(operation: &Op) -> bool {
     matches!(format!("{operation:?}").as_str(), "CompleteAuthorize")
 }
 
+/// Retrieves a single payment attempt by its id. The attempt is decrypted with the key store of
+/// the merchant and is returned only if it belongs to the merchant. An attempt of another merchant
+/// cannot be decrypted with this key store, that failure is reported as not found as well.
+#[cfg(feature = "v2")]
+pub async fn get_payment_attempt(
+    state: &SessionState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    attempt_id: &str,
+) -> RouterResult<storage::PaymentAttempt> {
+    let payment_attempt = match state
+        .store
+        .find_payment_attempt_by_id(
+            &state.into(),
+            key_store,
+            attempt_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+    {
+        Err(error)
+            if matches!(
+                error.current_context(),
+                errors::DataStorageError::DecryptionError
+            ) =>
+        {
+            Err(error.change_context(errors::ApiErrorResponse::PaymentNotFound))
+        }
+        result => result.to_not_found_response(errors::ApiErrorResponse::PaymentNotFound),
+    }?;
+
+    helpers::validate_payment_attempt_ownership(
+        merchant_account.get_id(),
+        &payment_attempt.merchant_id,
+    )?;
+
+    Ok(payment_attempt)
+}
+
 #[cfg(all(feature = "olap", feature = "v1"))]
 pub async fn list_payments(
     state: SessionState,
@@ -3525,6 +3578,75 @@ pub async fn add_process_sync_task(
     Ok(())
 }
 
+#[cfg(feature = "v1")]
+pub async fn add_auto_cancel_unconfirmed_payment_task(
+    db: &dyn StorageInterface,
+    payment_intent: &storage::PaymentIntent,
+    auto_cancel_after: i64,
+) -> CustomResult<(), errors::StorageError> {
+    let tracking_data = storage::PaymentIntentAutoCancelTrackingData {
+        payment_id: payment_intent.payment_id.clone(),
+        merchant_id: payment_intent.merchant_id.clone(),
+    };
+    let runner = storage::ProcessTrackerRunner::PaymentIntentAutoCancelWorkflow;
+    let task = "PAYMENT_INTENT_AUTO_CANCEL";
+    let tag = ["AUTO_CANCEL", "PAYMENT"];
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        runner,
+        task,
+        payment_intent.payment_id.get_string_repr(),
+        &payment_intent.merchant_id,
+    );
+    let schedule_time =
+        helpers::get_auto_cancel_schedule_time(payment_intent.created_at, auto_cancel_after);
+    let process_tracker_entry = storage::ProcessTrackerNew::new(
+        process_tracker_id,
+        task,
+        runner,
+        tag,
+        tracking_data,
+        schedule_time,
+    )
+    .map_err(errors::StorageError::from)?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
+#[cfg(feature = "v1")]
+pub async fn add_processing_payment_expiry_task(
+    db: &dyn StorageInterface,
+    payment_attempt: &storage::PaymentAttempt,
+    schedule_time: time::PrimitiveDateTime,
+) -> CustomResult<(), errors::StorageError> {
+    let tracking_data = storage::PaymentIntentProcessingExpiryTrackingData {
+        payment_id: payment_attempt.payment_id.clone(),
+        merchant_id: payment_attempt.merchant_id.clone(),
+        attempt_id: payment_attempt.get_id().to_owned(),
+    };
+    let runner = storage::ProcessTrackerRunner::PaymentIntentProcessingExpiryWorkflow;
+    let task = "PAYMENT_INTENT_PROCESSING_EXPIRY";
+    let tag = ["PROCESSING_EXPIRY", "PAYMENT"];
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        runner,
+        task,
+        payment_attempt.get_id(),
+        &payment_attempt.merchant_id,
+    );
+    let process_tracker_entry = storage::ProcessTrackerNew::new(
+        process_tracker_id,
+        task,
+        runner,
+        tag,
+        tracking_data,
+        schedule_time,
+    )
+    .map_err(errors::StorageError::from)?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
 pub async fn reset_process_sync_task(
     db: &dyn StorageInterface,
     payment_attempt: &storage::PaymentAttempt,
@@ -3646,6 +3768,36 @@ where
     } else {
         None
     };
+
+    // Routed connectors are already filtered by their amount limits, connectors passed by the
+    // merchant or picked from a mandate are validated before they are called
+    if let Some(ConnectorCallType::PreDetermined(connector_data)) = connector.as_ref() {
+        if is_operation_confirm(operation) {
+            helpers::validate_connector_amount_limits(
+                &state.conf.connector_amount_limits,
+                connector_data.connector_name,
+                payment_data.get_payment_attempt().get_total_amount(),
+                payment_data.get_payment_attempt().currency,
+            )?;
+            helpers::validate_connector_metadata_for_connector(
+                connector_data.connector_name,
+                payment_data
+                    .get_payment_intent()
+                    .connector_metadata
+                    .as_ref(),
+                payment_data
+                    .get_mandate_id()
+                    .and_then(|mandate_ids| mandate_ids.mandate_reference_id.as_ref())
+                    .is_some_and(|mandate_reference_id| {
+                        matches!(
+                            mandate_reference_id,
+                            api_models::payments::MandateReferenceId::ConnectorMandateId(_)
+                        )
+                    }),
+            )?;
+        }
+    }
+
     Ok(connector)
 }
 
@@ -4649,7 +4801,7 @@ where
         algorithm_ref.algorithm_id
     };
 
-    let connectors = routing::perform_static_routing_v1(
+    let (connectors, matched_rule) = routing::perform_static_routing_v1(
         state,
         merchant_account.get_id(),
         routing_algorithm_id.as_ref(),
@@ -4659,6 +4811,14 @@ where
     .await
     .change_context(errors::ApiErrorResponse::InternalServerError)?;
 
+    payment_data.set_matched_routing_rule(helpers::get_matched_routing_rule(
+        business_profile
+            .is_matched_routing_rule_response_enabled
+            .unwrap_or(false),
+        routing_algorithm_id,
+        matched_rule,
+    ));
+
     let connectors = routing::perform_eligibility_analysis_with_fallback(
         &state.clone(),
         key_store,
@@ -4671,6 +4831,29 @@ where
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("failed eligibility analysis and fallback")?;
 
+    let connectors = helpers::filter_connectors_by_amount_limits(
+        &state.conf.connector_amount_limits,
+        connectors,
+        payment_data.get_payment_attempt().get_total_amount(),
+        payment_data.get_payment_attempt().currency,
+    )?;
+
+    let connectors = helpers::reorder_connectors_by_issuer_country(
+        business_profile
+            .issuer_country_connector_preferences
+            .as_ref(),
+        helpers::get_card_issuing_country(
+            payment_data.get_payment_method_data(),
+            payment_data
+                .get_payment_attempt()
+                .payment_method_data
+                .as_ref(),
+        )
+        .as_deref()
+        .and_then(helpers::get_issuer_country_code),
+        connectors,
+    );
+
     let connector_data = connectors
         .into_iter()
         .map(|conn| {
@@ -4736,7 +4919,7 @@ pub async fn route_connector_v1_for_payouts(
         algorithm_ref.algorithm_id
     };
 
-    let connectors = routing::perform_static_routing_v1(
+    let (connectors, _) = routing::perform_static_routing_v1(
         state,
         merchant_account.get_id(),
         routing_algorithm_id.as_ref(),
@@ -4990,6 +5173,7 @@ pub async fn payment_external_authentication(
         optional_customer.and_then(|customer| customer.email.map(pii::Email::from)),
         webhook_url,
         authentication_details.three_ds_requestor_url.clone(),
+        payment_attempt.request_challenge,
     ))
     .await?;
     Ok(services::ApplicationResponse::Json(
@@ -5032,6 +5216,40 @@ pub async fn get_extended_card_info(
     ))
 }
 
+#[cfg(feature = "v1")]
+#[instrument(skip_all)]
+pub async fn get_payment_attempt_status_timeline(
+    state: SessionState,
+    merchant_id: id_type::MerchantId,
+    payment_id: id_type::PaymentId,
+    attempt_id: String,
+) -> RouterResponse<payments_api::PaymentAttemptStatusTimelineResponse> {
+    let timeline = state
+        .store
+        .find_payment_attempt_status_timeline_by_merchant_id_attempt_id(&merchant_id, &attempt_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the payment attempt status timeline")?
+        .into_iter()
+        .filter(|timeline_entry| timeline_entry.payment_id == payment_id)
+        .map(
+            |timeline_entry| payments_api::PaymentAttemptStatusTransition {
+                previous_status: timeline_entry.previous_status,
+                status: timeline_entry.status,
+                created_at: timeline_entry.created_at,
+            },
+        )
+        .collect();
+
+    Ok(services::ApplicationResponse::Json(
+        payments_api::PaymentAttemptStatusTimelineResponse {
+            payment_id,
+            attempt_id,
+            timeline,
+        },
+    ))
+}
+
 #[cfg(all(feature = "olap", feature = "v1"))]
 pub async fn payments_manual_update(
     state: SessionState,
@@ -5195,6 +5413,11 @@ pub trait OperationSessionGetters<F> {
     fn get_mandate_connector(&self) -> Option<&MandateConnectorDetails>;
     fn get_force_sync(&self) -> Option<bool>;
     fn get_capture_method(&self) -> Option<enums::CaptureMethod>;
+    fn get_should_return_payment_method_token(&self) -> bool;
+    fn get_matched_routing_rule(&self) -> Option<&api_models::payments::MatchedRoutingRule>;
+    fn get_confirm_response_masking_policy(
+        &self,
+    ) -> Option<common_utils::types::ConfirmResponseMaskingPolicy>;
 }
 
 pub trait OperationSessionSetters<F> {
@@ -5204,6 +5427,7 @@ pub trait OperationSessionSetters<F> {
     fn set_payment_method_data(&mut self, payment_method_data: Option<domain::PaymentMethodData>);
     fn set_email_if_not_present(&mut self, email: pii::Email);
     fn set_payment_method_id_in_attempt(&mut self, payment_method_id: Option<String>);
+    fn set_tokenization_latency_in_attempt(&mut self, tokenization_latency: std::time::Duration);
     fn set_pm_token(&mut self, token: String);
     fn set_connector_customer_id(&mut self, customer_id: Option<String>);
     fn push_sessions_token(&mut self, token: api::SessionToken);
@@ -5237,6 +5461,10 @@ pub trait OperationSessionSetters<F> {
         straight_through_algorithm: serde_json::Value,
     );
     fn set_connector_in_payment_attempt(&mut self, connector: Option<String>);
+    fn set_matched_routing_rule(
+        &mut self,
+        matched_routing_rule: Option<api_models::payments::MatchedRoutingRule>,
+    );
 }
 
 impl<F: Clone> OperationSessionGetters<F> for PaymentData<F> {
@@ -5372,6 +5600,19 @@ impl<F: Clone> OperationSessionGetters<F> for PaymentData<F> {
     fn get_capture_method(&self) -> Option<enums::CaptureMethod> {
         self.payment_intent.capture_method
     }
+    fn get_should_return_payment_method_token(&self) -> bool {
+        self.should_return_payment_method_token
+    }
+
+    fn get_matched_routing_rule(&self) -> Option<&api_models::payments::MatchedRoutingRule> {
+        self.matched_routing_rule.as_ref()
+    }
+
+    fn get_confirm_response_masking_policy(
+        &self,
+    ) -> Option<common_utils::types::ConfirmResponseMaskingPolicy> {
+        self.confirm_response_masking_policy
+    }
 }
 
 impl<F: Clone> OperationSessionSetters<F> for PaymentData<F> {
@@ -5392,6 +5633,11 @@ impl<F: Clone> OperationSessionSetters<F> for PaymentData<F> {
         self.payment_attempt.payment_method_id = payment_method_id;
     }
 
+    fn set_tokenization_latency_in_attempt(&mut self, tokenization_latency: std::time::Duration) {
+        self.payment_attempt.tokenization_latency =
+            Some(i64::try_from(tokenization_latency.as_millis()).unwrap_or(i64::MAX));
+    }
+
     fn set_email_if_not_present(&mut self, email: pii::Email) {
         self.email = self.email.clone().or(Some(email));
     }
@@ -5469,6 +5715,13 @@ impl<F: Clone> OperationSessionSetters<F> for PaymentData<F> {
     fn set_connector_in_payment_attempt(&mut self, connector: Option<String>) {
         self.payment_attempt.connector = connector;
     }
+
+    fn set_matched_routing_rule(
+        &mut self,
+        matched_routing_rule: Option<api_models::payments::MatchedRoutingRule>,
+    ) {
+        self.matched_routing_rule = matched_routing_rule;
+    }
 }
 
 #[cfg(feature = "v2")]
@@ -5593,6 +5846,19 @@ impl<F: Clone> OperationSessionGetters<F> for PaymentIntentData<F> {
     fn get_capture_method(&self) -> Option<enums::CaptureMethod> {
         todo!()
     }
+    fn get_should_return_payment_method_token(&self) -> bool {
+        todo!()
+    }
+
+    fn get_matched_routing_rule(&self) -> Option<&api_models::payments::MatchedRoutingRule> {
+        todo!()
+    }
+
+    fn get_confirm_response_masking_policy(
+        &self,
+    ) -> Option<common_utils::types::ConfirmResponseMaskingPolicy> {
+        todo!()
+    }
 }
 
 #[cfg(feature = "v2")]
@@ -5614,6 +5880,10 @@ impl<F: Clone> OperationSessionSetters<F> for PaymentIntentData<F> {
         todo!()
     }
 
+    fn set_tokenization_latency_in_attempt(&mut self, _tokenization_latency: std::time::Duration) {
+        todo!()
+    }
+
     fn set_email_if_not_present(&mut self, _email: pii::Email) {
         todo!()
     }
@@ -5678,4 +5948,11 @@ impl<F: Clone> OperationSessionSetters<F> for PaymentIntentData<F> {
     fn set_connector_in_payment_attempt(&mut self, _connector: Option<String>) {
         todo!()
     }
+
+    fn set_matched_routing_rule(
+        &mut self,
+        _matched_routing_rule: Option<api_models::payments::MatchedRoutingRule>,
+    ) {
+        todo!()
+    }
 }
@@ -56,7 +56,9 @@ use super::{
 #[cfg(feature = "v2")]
 use crate::core::admin as core_admin;
 use crate::{
-    configs::settings::{ConnectorRequestReferenceIdConfig, TempLockerEnableConfig},
+    configs::settings::{
+        ConnectorAmountLimits, ConnectorRequestReferenceIdConfig, TempLockerEnableConfig,
+    },
     connector,
     consts::{self, BASE64_ENGINE},
     core::{
@@ -79,9 +81,10 @@ use crate::{
         domain::{self, types},
         storage::{self, enums as storage_enums, ephemeral_key, CardTokenData},
         transformers::{ForeignFrom, ForeignTryFrom},
-        AdditionalMerchantData, AdditionalPaymentMethodConnectorResponse, ErrorResponse,
-        MandateReference, MerchantAccountData, MerchantRecipientData, PaymentsResponseData,
-        RecipientIdType, RecurringMandatePaymentData, RouterData,
+        AdditionalMerchantData, AdditionalPaymentMethodConnectorResponse, ConnectorFeeData,
+        ConnectorRiskData, ErrorResponse, MandateReference, MerchantAccountData,
+        MerchantRecipientData, PaymentsResponseData, RecipientIdType, RecurringMandatePaymentData,
+        RouterData,
     },
     utils::{
         self,
@@ -824,6 +827,20 @@ pub async fn get_token_for_recurring_mandate(
     }
 }
 
+/// Ensures that the payment attempt belongs to the merchant making the request. Attempts of
+/// other merchants are reported as not found, so that their existence is not revealed.
+pub fn validate_payment_attempt_ownership(
+    merchant_id: &id_type::MerchantId,
+    attempt_merchant_id: &id_type::MerchantId,
+) -> RouterResult<()> {
+    utils::when(merchant_id.ne(attempt_merchant_id), || {
+        Err(report!(errors::ApiErrorResponse::PaymentNotFound)).attach_printable(format!(
+            "Payment attempt belongs to merchant {}",
+            attempt_merchant_id.get_string_repr()
+        ))
+    })
+}
+
 #[instrument(skip_all)]
 /// Check weather the merchant id in the request
 /// and merchant id in the merchant account are same.
@@ -1181,6 +1198,207 @@ pub fn create_authentication_url(
     )
 }
 
+/// Removes the routed connectors whose configured amount limits exclude the amount of the payment.
+/// Fails only when none of the routed connectors support the amount.
+pub fn filter_connectors_by_amount_limits(
+    connector_amount_limits: &ConnectorAmountLimits,
+    connectors: Vec<api_models::routing::RoutableConnectorChoice>,
+    amount: MinorUnit,
+    currency: Option<storage_enums::Currency>,
+) -> RouterResult<Vec<api_models::routing::RoutableConnectorChoice>> {
+    let Some(currency) = currency else {
+        return Ok(connectors);
+    };
+    let has_routed_connectors = !connectors.is_empty();
+    let supported_connectors = connectors
+        .into_iter()
+        .filter(|connector_choice| {
+            connector_amount_limits.is_amount_supported(
+                &connector_choice.connector,
+                &currency,
+                amount,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if has_routed_connectors && supported_connectors.is_empty() {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "amount {} {currency} is outside the limits supported by the eligible connectors",
+                amount.get_amount_as_i64()
+            ),
+        }));
+    }
+
+    Ok(supported_connectors)
+}
+
+/// Rejects the payment before calling the connector when its amount is outside the limits
+/// configured for the connector, instead of letting the connector decline it. Connectors without
+/// limits for the currency accept any amount.
+pub fn validate_connector_amount_limits(
+    connector_amount_limits: &ConnectorAmountLimits,
+    connector: api_enums::Connector,
+    amount: MinorUnit,
+    currency: Option<storage_enums::Currency>,
+) -> RouterResult<()> {
+    let Some(currency) = currency else {
+        return Ok(());
+    };
+    let Some(limits) = api_enums::RoutableConnectors::from_str(&connector.to_string())
+        .ok()
+        .and_then(|connector| {
+            connector_amount_limits
+                .get_amount_limits(&connector, &currency)
+                .copied()
+        })
+    else {
+        return Ok(());
+    };
+
+    if limits.is_below_minimum(amount) {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "amount {} {currency} is below the minimum amount {} supported by {connector}",
+                amount.get_amount_as_i64(),
+                limits
+                    .minimum_amount
+                    .unwrap_or_default()
+                    .get_amount_as_i64(),
+            ),
+        }))
+    } else if limits.is_above_maximum(amount) {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "amount {} {currency} is above the maximum amount {} supported by {connector}",
+                amount.get_amount_as_i64(),
+                limits
+                    .maximum_amount
+                    .unwrap_or_default()
+                    .get_amount_as_i64(),
+            ),
+        }))
+    } else {
+        Ok(())
+    }
+}
+
+/// Gets the issuer country of the card found by the BIN lookup, from the payment method data of the
+/// payment, or else from the additional payment method data stored in the payment attempt
+pub fn get_card_issuing_country(
+    payment_method_data: Option<&domain::PaymentMethodData>,
+    additional_payment_method_data: Option<&serde_json::Value>,
+) -> Option<String> {
+    match payment_method_data {
+        Some(domain::PaymentMethodData::Card(card)) if card.card_issuing_country.is_some() => {
+            card.card_issuing_country.clone()
+        }
+        _ => additional_payment_method_data
+            .cloned()
+            .and_then(|additional_payment_method_data| {
+                additional_payment_method_data
+                    .parse_value::<api_models::payments::AdditionalPaymentData>(
+                        "additional_payment_method_data",
+                    )
+                    .ok()
+            })
+            .and_then(|additional_payment_data| match additional_payment_data {
+                api_models::payments::AdditionalPaymentData::Card(card) => {
+                    card.card_issuing_country
+                }
+                _ => None,
+            }),
+    }
+}
+
+/// Resolves the issuer country of the card found by the BIN lookup to its alpha-2 code. The issuer
+/// country may be an alpha-2 code, or a country name matched ignoring case, whitespace and
+/// punctuation.
+pub fn get_issuer_country_code(issuer_country: &str) -> Option<common_enums::CountryAlpha2> {
+    use strum::IntoEnumIterator;
+
+    let normalize = |country: &str| {
+        country
+            .chars()
+            .filter(|character| character.is_alphanumeric())
+            .collect::<String>()
+            .to_uppercase()
+    };
+    let issuer_country = normalize(issuer_country);
+
+    common_enums::CountryAlpha2::from_str(&issuer_country)
+        .ok()
+        .or_else(|| {
+            common_enums::Country::iter()
+                .find(|country| normalize(&country.to_string()) == issuer_country)
+                .map(|country| country.to_alpha2())
+        })
+}
+
+/// Moves the routed connectors that the business profile prefers for the issuer country of the
+/// card to the front, in the order of preference. Connectors that were not routed are not added,
+/// and the others keep their order.
+pub fn reorder_connectors_by_issuer_country(
+    issuer_country_connector_preferences: Option<
+        &common_utils::types::IssuerCountryConnectorPreferences,
+    >,
+    issuer_country: Option<common_enums::CountryAlpha2>,
+    connectors: Vec<api_models::routing::RoutableConnectorChoice>,
+) -> Vec<api_models::routing::RoutableConnectorChoice> {
+    let Some(preferred_connectors) = issuer_country_connector_preferences
+        .zip(issuer_country)
+        .and_then(|(preferences, issuer_country)| {
+            preferences.get_preferred_connectors(issuer_country)
+        })
+    else {
+        return connectors;
+    };
+
+    let mut connectors = connectors;
+    connectors.sort_by_key(|connector_choice| {
+        preferred_connectors
+            .iter()
+            .position(|preferred_connector| *preferred_connector == connector_choice.connector)
+            .unwrap_or(preferred_connectors.len())
+    });
+    connectors
+}
+
+/// Masks the fields of the confirm response that the masking policy of the business profile does
+/// not allow to be revealed. Returns the payment method data and the billing address to be sent.
+pub fn apply_confirm_response_masking_policy(
+    masking_policy: common_utils::types::ConfirmResponseMaskingPolicy,
+    payment_method_data_response: Option<
+        api_models::payments::PaymentMethodDataResponseWithBilling,
+    >,
+    billing: Option<api_models::payments::Address>,
+) -> (
+    Option<api_models::payments::PaymentMethodDataResponseWithBilling>,
+    Option<api_models::payments::Address>,
+) {
+    let payment_method_data_response =
+        payment_method_data_response.map(|mut payment_method_data_response| {
+            if let Some(api_models::payments::PaymentMethodDataResponse::Card(card)) =
+                payment_method_data_response.payment_method_data.as_mut()
+            {
+                if !masking_policy.reveal_card_last4 {
+                    card.last4 = None;
+                }
+                if !masking_policy.reveal_card_issuer {
+                    card.card_issuer = None;
+                    card.card_issuing_country = None;
+                }
+            }
+            if !masking_policy.reveal_billing {
+                payment_method_data_response.billing = None;
+            }
+            payment_method_data_response
+        });
+    let billing = billing.filter(|_| masking_policy.reveal_billing);
+
+    (payment_method_data_response, billing)
+}
+
 pub fn create_authorize_url(
     router_base_url: &str,
     payment_attempt: &PaymentAttempt,
@@ -1408,6 +1626,118 @@ pub fn validate_max_amount(
     }
 }
 
+/// Validate the device fingerprint sent in the payments request, it should not be empty, should
+/// not exceed the maximum length and should only contain alphanumeric characters, `-` and `_`
+pub fn validate_device_fingerprint(
+    device_fingerprint: Option<&str>,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    device_fingerprint.map_or(Ok(()), |device_fingerprint| {
+        let is_valid = !device_fingerprint.is_empty()
+            && device_fingerprint.len() <= consts::MAX_DEVICE_FINGERPRINT_LENGTH
+            && device_fingerprint
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        utils::when(!is_valid, || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "device_fingerprint should be between 1 and {} characters long and should only contain alphanumeric characters, '-' and '_'",
+                    consts::MAX_DEVICE_FINGERPRINT_LENGTH
+                )
+            }))
+        })
+    })
+}
+
+/// Saving the payment method can't be skipped for payments that create a mandate, since the
+/// mandate is stored with the saved payment method
+pub fn validate_skip_save_payment_method(
+    request: &api_models::payments::PaymentsRequest,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    utils::when(
+        request.skip_save_payment_method == Some(true) && request.mandate_data.is_some(),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "skip_save_payment_method cannot be used along with mandate_data".into()
+            }))
+        },
+    )
+}
+
+/// The skip flag sent at confirm takes precedence over the one stored on the intent at create
+pub fn get_skip_save_payment_method_on_confirm(
+    current: Option<bool>,
+    requested: Option<bool>,
+) -> Option<bool> {
+    requested.or(current)
+}
+
+/// How the payment method is persisted once the connector responds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavePaymentMethodAction {
+    /// The mandate is created on the application side and at the connector
+    LegacyMandate,
+    /// The mandate is created on the connector's end, the payment method is saved only if `save`
+    /// is set
+    ConnectorMandate { save: bool },
+    /// The payment was made with an already saved payment method
+    UseSavedPaymentMethod,
+    /// The merchant asked not to save the payment method
+    Skip,
+    /// The payment method is saved in the background
+    Save,
+}
+
+/// Merchants can skip saving the payment method for a one off payment that was set up for future
+/// usage, without changing the future usage sent to the connector. Skipping only applies to the
+/// saved payment method: the connector mandate reference is still recorded on the payment, and
+/// mandates created by the application are stored with the saved payment method, so it is always
+/// saved for them.
+pub fn get_save_payment_method_action(
+    is_legacy_mandate: bool,
+    is_connector_mandate: bool,
+    is_saved_payment_method_reused: bool,
+    skip_save_payment_method: bool,
+) -> SavePaymentMethodAction {
+    if is_legacy_mandate {
+        SavePaymentMethodAction::LegacyMandate
+    } else if is_connector_mandate {
+        SavePaymentMethodAction::ConnectorMandate {
+            save: !skip_save_payment_method,
+        }
+    } else if is_saved_payment_method_reused {
+        SavePaymentMethodAction::UseSavedPaymentMethod
+    } else if skip_save_payment_method {
+        SavePaymentMethodAction::Skip
+    } else {
+        SavePaymentMethodAction::Save
+    }
+}
+
+/// Prepend the statement descriptor prefix of the merchant connector account to the statement
+/// descriptor of the payment, in the `PREFIX* DESCRIPTOR` format used by card networks.
+/// The combined descriptor should not exceed the maximum statement descriptor length
+pub fn get_statement_descriptor_with_prefix(
+    statement_descriptor_prefix: Option<&str>,
+    statement_descriptor: Option<String>,
+) -> CustomResult<Option<String>, errors::ApiErrorResponse> {
+    let combined_statement_descriptor = match (statement_descriptor_prefix, statement_descriptor) {
+        (Some(prefix), Some(descriptor)) => format!("{prefix}* {descriptor}"),
+        (Some(prefix), None) => prefix.to_string(),
+        (None, statement_descriptor) => return Ok(statement_descriptor),
+    };
+
+    let max_length = usize::from(common_utils::consts::MAX_STATEMENT_DESCRIPTOR_LENGTH);
+    if combined_statement_descriptor.chars().count() > max_length {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "statement_descriptor_name combined with the connector's statement descriptor prefix should not exceed {max_length} characters"
+            )
+        }));
+    }
+
+    Ok(Some(combined_statement_descriptor))
+}
+
 /// Check whether the customer information that is sent in the root of payments request
 /// and in the customer object are same, if the values mismatch return an error
 pub fn validate_customer_information(
@@ -2411,6 +2741,31 @@ pub(crate) fn validate_amount_to_capture(
     )
 }
 
+#[instrument(skip_all)]
+pub(crate) fn validate_attempt_status_for_capture(
+    attempt_status: storage_enums::AttemptStatus,
+    capture_method: storage_enums::CaptureMethod,
+) -> RouterResult<()> {
+    let is_capturable = match attempt_status {
+        storage_enums::AttemptStatus::Authorized
+        | storage_enums::AttemptStatus::PartialChargedAndChargeable => true,
+        // Further captures can be created while a previous one is still pending only for
+        // manual_multiple, in line with `validate_status_with_capture_method`
+        storage_enums::AttemptStatus::CaptureInitiated => {
+            capture_method == storage_enums::CaptureMethod::ManualMultiple
+        }
+        _ => false,
+    };
+    utils::when(!is_capturable, || {
+        Err(report!(errors::ApiErrorResponse::PaymentUnexpectedState {
+            field_name: "payment_attempt.status".to_string(),
+            current_flow: "captured".to_string(),
+            current_value: attempt_status.to_string(),
+            states: "authorized, partial_charged_and_chargeable".to_string()
+        }))
+    })
+}
+
 #[instrument(skip_all)]
 pub(crate) fn validate_payment_method_fields_present(
     req: &api::PaymentsRequest,
@@ -2725,6 +3080,50 @@ pub(super) fn validate_payment_list_request_for_joins(
     Ok(())
 }
 
+/// Returns the billing address saved with the payment method used for the payment, which is to be
+/// stored as the payment method billing of the attempt. A billing address sent in the request takes
+/// precedence, in which case the saved address is not used.
+pub fn get_saved_payment_method_billing(
+    request_payment_method_billing: Option<&api::Address>,
+    saved_payment_method_billing: Option<serde_json::Value>,
+) -> RouterResult<Option<api::Address>> {
+    if request_payment_method_billing.is_some() {
+        return Ok(None);
+    }
+
+    saved_payment_method_billing
+        .map(|billing| billing.parse_value("payment_method_billing_address"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("unable to parse payment_method_billing_address")
+}
+
+/// Validates the `setup_future_usage` sent when confirming a payment. It can be set on confirm
+/// when the intent does not have one yet, but a value already on the intent cannot be changed.
+pub fn validate_setup_future_usage_update(
+    current: Option<storage_enums::FutureUsage>,
+    requested: Option<storage_enums::FutureUsage>,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    utils::when(
+        current.is_some_and(|current| requested.is_some_and(|requested| requested != current)),
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "setup_future_usage cannot be changed once it is set on the payment"
+                    .to_string(),
+            })
+        },
+    )
+}
+
+/// The `setup_future_usage` to be stored on the intent when it is confirmed.
+pub fn get_setup_future_usage_on_confirm(
+    current: Option<storage_enums::FutureUsage>,
+    requested: Option<storage_enums::FutureUsage>,
+) -> CustomResult<Option<storage_enums::FutureUsage>, errors::ApiErrorResponse> {
+    validate_setup_future_usage_update(current, requested)?;
+    Ok(requested.or(current))
+}
+
 pub fn get_handle_response_url(
     payment_id: id_type::PaymentId,
     business_profile: &domain::Profile,
@@ -3224,91 +3623,561 @@ pub(crate) fn get_payment_id_from_client_secret(cs: &str) -> RouterResult<String
     Ok(payment_id.to_string())
 }
 
-#[cfg(feature = "v1")]
-#[cfg(test)]
-mod tests {
-    #![allow(clippy::unwrap_used)]
+/// Returns whether a mandate was created by the payment, this is `None` for payments which do not
+/// set up a mandate
+pub fn get_mandate_created(is_mandate_setup: bool, mandate_id: Option<&String>) -> Option<bool> {
+    is_mandate_setup.then_some(mandate_id.is_some())
+}
 
-    use super::*;
+/// Validates the connector specific sections of the connector metadata against the schema of the
+/// connector, so that misconfigured metadata is rejected before it reaches the connector.
+/// Sections of connectors which do not define a schema are only required to be objects, absent
+/// sections are serialized as `null` and are skipped.
+pub fn validate_connector_metadata(
+    connector_metadata: &serde_json::Value,
+) -> Result<(), errors::ApiErrorResponse> {
+    let sections = connector_metadata.as_object().ok_or_else(|| {
+        errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "connector_metadata".to_string(),
+            expected_format: "object".to_string(),
+        }
+    })?;
 
-    #[test]
-    fn test_authenticate_client_secret_session_not_expired() {
-        let payment_intent = PaymentIntent {
-            payment_id: id_type::PaymentId::try_from(Cow::Borrowed("23")).unwrap(),
-            merchant_id: id_type::MerchantId::default(),
-            status: storage_enums::IntentStatus::RequiresCapture,
-            amount: MinorUnit::new(200),
-            currency: None,
-            amount_captured: None,
-            customer_id: None,
-            description: None,
-            return_url: None,
-            metadata: None,
-            connector_id: None,
-            shipping_address_id: None,
-            billing_address_id: None,
-            statement_descriptor_name: None,
-            statement_descriptor_suffix: None,
-            created_at: common_utils::date_time::now(),
-            modified_at: common_utils::date_time::now(),
-            last_synced: None,
-            setup_future_usage: None,
-            fingerprint_id: None,
-            off_session: None,
-            client_secret: Some("1".to_string()),
-            active_attempt: hyperswitch_domain_models::RemoteStorageObject::ForeignID(
-                "nopes".to_string(),
+    for (connector, section) in sections.iter().filter(|(_, section)| !section.is_null()) {
+        match connector.as_str() {
+            "apple_pay" => validate_connector_metadata_section::<
+                api_models::payments::ApplepayConnectorMetadataRequest,
+            >(connector, section),
+            "airwallex" => {
+                validate_connector_metadata_section::<api_models::payments::AirwallexData>(
+                    connector, section,
+                )
+            }
+            "noon" => validate_connector_metadata_section::<api_models::payments::NoonData>(
+                connector, section,
             ),
-            business_country: None,
-            business_label: None,
-            order_details: None,
-            allowed_payment_method_types: None,
-            connector_metadata: None,
-            feature_metadata: None,
-            attempt_count: 1,
-            payment_link_id: None,
-            profile_id: Some(common_utils::generate_profile_id_of_default_length()),
-            merchant_decision: None,
-            payment_confirm_source: None,
-            surcharge_applicable: None,
-            updated_by: storage_enums::MerchantStorageScheme::PostgresOnly.to_string(),
-            request_incremental_authorization: Some(
-                common_enums::RequestIncrementalAuthorization::default(),
+            _ => section.is_object().then_some(()).ok_or_else(|| {
+                errors::ApiErrorResponse::InvalidDataFormat {
+                    field_name: format!("connector_metadata.{connector}"),
+                    expected_format: "object".to_string(),
+                }
+            }),
+        }?;
+    }
+
+    Ok(())
+}
+
+fn validate_connector_metadata_section<T: serde::de::DeserializeOwned>(
+    connector: &str,
+    section: &serde_json::Value,
+) -> Result<(), errors::ApiErrorResponse> {
+    serde_path_to_error::deserialize::<_, T>(section)
+        .map(|_| ())
+        .map_err(|error| errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "Invalid connector_metadata at `{connector}.{}`: {}",
+                error.path(),
+                error.inner()
             ),
-            incremental_authorization_allowed: None,
-            authorization_count: None,
-            session_expiry: Some(
-                common_utils::date_time::now()
-                    .saturating_add(time::Duration::seconds(consts::DEFAULT_SESSION_EXPIRY)),
+        })
+}
+
+/// Validates that the connector metadata carries the fields required by the connector the payment
+/// is routed to. Connectors which do not require any metadata accept it unchanged.
+pub fn validate_connector_metadata_for_connector(
+    connector: api_enums::Connector,
+    connector_metadata: Option<&serde_json::Value>,
+    is_connector_mandate_payment: bool,
+) -> RouterResult<()> {
+    match connector {
+        // Noon requires the order category for every payment which is not made with a mandate
+        api_enums::Connector::Noon if !is_connector_mandate_payment => {
+            let order_category = connector_metadata
+                .map(|metadata| {
+                    metadata
+                        .clone()
+                        .parse_value::<api_models::payments::ConnectorMetadata>("ConnectorMetadata")
+                        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                            field_name: "connector_metadata",
+                        })
+                })
+                .transpose()?
+                .and_then(|metadata| metadata.noon)
+                .and_then(|noon| noon.order_category);
+
+            order_category.map(|_| ()).ok_or_else(|| {
+                report!(errors::ApiErrorResponse::MissingRequiredField {
+                    field_name: "connector_metadata.noon.order_category",
+                })
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validates that an authentication performed in a prior call can be linked to the payment.
+///
+/// The authentication has to be bound to the payment, which also binds it to the customer of the
+/// payment, since authentications do not record the customer. It has to be completed and not yet
+/// used by another authorization, so that it cannot be replayed across payments.
+#[cfg(feature = "v1")]
+pub fn validate_linked_authentication(
+    authentication: &storage::Authentication,
+    payment_intent: &PaymentIntent,
+) -> Result<(), errors::ApiErrorResponse> {
+    let belongs_to_payment = authentication.payment_id.as_ref() == Some(&payment_intent.payment_id)
+        && payment_intent.profile_id.as_ref() == Some(&authentication.profile_id);
+
+    if !belongs_to_payment {
+        return Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "authentication_id does not belong to this payment".to_string(),
+        });
+    }
+
+    if authentication.authentication_status != common_enums::AuthenticationStatus::Success {
+        return Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "authentication is not completed, current status is {}",
+                authentication.authentication_status
             ),
-            request_external_three_ds_authentication: None,
-            charges: None,
-            frm_metadata: None,
-            customer_details: None,
-            billing_details: None,
-            merchant_order_reference_id: None,
-            shipping_details: None,
-            is_payment_processor_token_flow: None,
-            organization_id: id_type::OrganizationId::default(),
-            shipping_cost: None,
-            tax_details: None,
-            skip_external_tax_calculation: None,
-        };
-        let req_cs = Some("1".to_string());
-        assert!(authenticate_client_secret(req_cs.as_ref(), &payment_intent).is_ok());
-        // Check if the result is an Ok variant
+        });
     }
 
-    #[test]
-    fn test_authenticate_client_secret_session_expired() {
-        let created_at =
-            common_utils::date_time::now().saturating_sub(time::Duration::seconds(20 * 60));
-        let payment_intent = PaymentIntent {
-            payment_id: id_type::PaymentId::try_from(Cow::Borrowed("23")).unwrap(),
-            merchant_id: id_type::MerchantId::default(),
-            status: storage_enums::IntentStatus::RequiresCapture,
-            amount: MinorUnit::new(200),
-            currency: None,
+    if authentication.authentication_lifecycle_status
+        != common_enums::AuthenticationLifecycleStatus::Unused
+    {
+        return Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "authentication cannot be used, its lifecycle status is {}",
+                authentication.authentication_lifecycle_status
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Marks a completed authentication as used by the authorization of the payment, so that it
+/// cannot be linked to another payment. Authentications that are not completed are returned as
+/// they are.
+#[cfg(feature = "v1")]
+pub async fn consume_authentication(
+    state: &SessionState,
+    authentication: storage::Authentication,
+) -> RouterResult<storage::Authentication> {
+    if authentication.authentication_status != common_enums::AuthenticationStatus::Success
+        || authentication.authentication_lifecycle_status
+            != common_enums::AuthenticationLifecycleStatus::Unused
+    {
+        return Ok(authentication);
+    }
+
+    state
+        .store
+        .update_authentication_by_merchant_id_authentication_id(
+            authentication,
+            storage::AuthenticationUpdate::PostAuthorizationUpdate {
+                authentication_lifecycle_status: common_enums::AuthenticationLifecycleStatus::Used,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to mark the authentication as used")
+}
+
+/// Returns whether the token reference of the card used for the payment should be returned. It is
+/// only returned to calls authenticated with the merchant's API key, so that it never reaches the
+/// client and the merchant's client side integration stays out of PCI scope.
+pub fn should_return_payment_method_token(
+    is_payment_method_token_response_enabled: Option<bool>,
+    auth_flow: services::AuthFlow,
+) -> bool {
+    is_payment_method_token_response_enabled.unwrap_or(false)
+        && auth_flow == services::AuthFlow::Merchant
+}
+
+/// Returns the token reference of the card used for the payment, if the business profile has
+/// enabled returning it. Only references are returned, the card number is never exposed.
+pub fn get_payment_method_token_reference(
+    should_return_payment_method_token: bool,
+    payment_method: Option<storage_enums::PaymentMethod>,
+    payment_method_id: Option<String>,
+    network_token_reference_id: Option<String>,
+) -> Option<api_models::payments::PaymentMethodTokenReference> {
+    let is_card_payment = payment_method == Some(storage_enums::PaymentMethod::Card);
+    payment_method_id
+        .filter(|_| should_return_payment_method_token && is_card_payment)
+        .map(
+            |payment_method_id| api_models::payments::PaymentMethodTokenReference {
+                payment_method_id,
+                network_token_reference_id,
+            },
+        )
+}
+
+/// Returns the routing rule that selected the connector, if the business profile has enabled
+/// returning it. This is present only for rule based routing algorithms.
+pub fn get_matched_routing_rule(
+    should_return_matched_routing_rule: bool,
+    routing_algorithm_id: Option<id_type::RoutingId>,
+    matched_rule: Option<api_models::routing::ast::Rule<api_models::routing::ConnectorSelection>>,
+) -> Option<api_models::payments::MatchedRoutingRule> {
+    matched_rule
+        .filter(|_| should_return_matched_routing_rule)
+        .map(|rule| api_models::payments::MatchedRoutingRule {
+            routing_algorithm_id,
+            rule_name: rule.name,
+            statements: rule.statements,
+        })
+}
+
+/// Normalizes a connector reported risk score to a 0-100 scale.
+///
+/// Scores reported without a scale are expected to already be on a 0-100 scale. Scores that
+/// cannot be mapped onto that range are dropped.
+pub fn normalize_connector_risk_score(score: f64, max_score: Option<f64>) -> Option<i16> {
+    let normalized_score = match max_score {
+        Some(max_score) if max_score > 0.0 => score / max_score * 100.0,
+        Some(_) => return None,
+        None => score,
+    };
+
+    #[allow(clippy::as_conversions)]
+    (0.0..=100.0)
+        .contains(&normalized_score)
+        .then(|| normalized_score.round() as i16)
+}
+
+/// Returns the normalized risk score and the risk decision reported by the connector
+pub fn get_connector_risk_details(
+    risk_data: Option<&ConnectorRiskData>,
+) -> (Option<i16>, Option<String>) {
+    risk_data
+        .map(|risk_data| {
+            (
+                risk_data
+                    .score
+                    .and_then(|score| normalize_connector_risk_score(score, risk_data.max_score)),
+                risk_data.decision.clone(),
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the fee amount and currency actually charged by the connector, if it reported one
+pub fn get_connector_fee_details(
+    fee_data: Option<&ConnectorFeeData>,
+) -> (Option<MinorUnit>, Option<storage_enums::Currency>) {
+    fee_data
+        .map(|fee_data| (Some(fee_data.amount), Some(fee_data.currency)))
+        .unwrap_or_default()
+}
+
+/// Returns the negotiated 3DS version (`major.minor`) and the full message version used to
+/// authenticate the payment, if it went through external 3DS authentication
+pub fn get_three_ds_version_details(
+    authentication: Option<&storage::Authentication>,
+) -> (Option<String>, Option<String>) {
+    authentication
+        .and_then(|authentication| authentication.message_version.as_ref())
+        .map(|message_version| {
+            (
+                Some(format!(
+                    "{}.{}",
+                    message_version.get_major(),
+                    message_version.get_minor()
+                )),
+                Some(message_version.to_string()),
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the time at which a payment created at `created_at` is cancelled if it is still
+/// unconfirmed
+pub fn get_auto_cancel_schedule_time(
+    created_at: time::PrimitiveDateTime,
+    auto_cancel_after: i64,
+) -> time::PrimitiveDateTime {
+    created_at.saturating_add(time::Duration::seconds(auto_cancel_after))
+}
+
+/// Returns the time in seconds after which a payment that is still processing is moved to failed,
+/// as configured for the merchant
+pub async fn get_processing_payment_expiry(
+    db: &dyn StorageInterface,
+    merchant_id: &id_type::MerchantId,
+) -> RouterResult<i64> {
+    let config = db
+        .find_config_by_key_unwrap_or(
+            &merchant_id.get_processing_payment_expiry_key(),
+            Some(consts::DEFAULT_PROCESSING_PAYMENT_EXPIRY.to_string()),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch processing payment expiry config")?;
+
+    config
+        .config
+        .parse::<i64>()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Invalid processing payment expiry config")
+}
+
+/// Whether a payment that was last modified at `modified_at` has been processing for longer than
+/// `processing_expiry` seconds at `current_time`
+pub fn should_expire_processing_payment(
+    intent_status: storage_enums::IntentStatus,
+    modified_at: time::PrimitiveDateTime,
+    processing_expiry: i64,
+    current_time: time::PrimitiveDateTime,
+) -> bool {
+    intent_status == storage_enums::IntentStatus::Processing
+        && current_time >= modified_at.saturating_add(time::Duration::seconds(processing_expiry))
+}
+
+/// Whether a payment can be auto cancelled for not being confirmed in time. Payments for which a
+/// confirm was attempted are exempt, even if they are back to requiring a payment method.
+pub fn should_auto_cancel_unconfirmed_payment(
+    intent_status: storage_enums::IntentStatus,
+    attempt_status: storage_enums::AttemptStatus,
+) -> bool {
+    matches!(
+        intent_status,
+        storage_enums::IntentStatus::RequiresPaymentMethod
+            | storage_enums::IntentStatus::RequiresConfirmation
+    ) && matches!(
+        attempt_status,
+        storage_enums::AttemptStatus::Started
+            | storage_enums::AttemptStatus::PaymentMethodAwaited
+            | storage_enums::AttemptStatus::ConfirmationAwaited
+    )
+}
+
+/// Records the status transition of a payment attempt in its status timeline, if the update
+/// changed the status. Failing to record the transition does not fail the payment.
+#[cfg(feature = "v1")]
+pub async fn record_payment_attempt_status_transition(
+    db: &dyn StorageInterface,
+    previous_status: storage_enums::AttemptStatus,
+    payment_attempt: &PaymentAttempt,
+) {
+    if previous_status == payment_attempt.status {
+        return;
+    }
+
+    let timeline_entry = storage::PaymentAttemptStatusTimelineNew {
+        merchant_id: payment_attempt.merchant_id.clone(),
+        payment_id: payment_attempt.payment_id.clone(),
+        attempt_id: payment_attempt.attempt_id.clone(),
+        previous_status,
+        status: payment_attempt.status,
+        created_at: common_utils::date_time::now(),
+    };
+    db.insert_payment_attempt_status_timeline_entry(timeline_entry)
+        .await
+        .map_err(|error| logger::error!(payment_attempt_status_timeline_error=?error))
+        .ok();
+}
+
+/// Sums up the amount captured from the customer in payments of the given currency that were
+/// captured in the last `window_in_secs` seconds
+#[cfg(feature = "v1")]
+pub async fn get_customer_captured_amount(
+    state: &SessionState,
+    merchant_account: &domain::MerchantAccount,
+    customer_id: &id_type::CustomerId,
+    currency: storage_enums::Currency,
+    window_in_secs: i64,
+) -> RouterResult<MinorUnit> {
+    let time_range = common_utils::types::TimeRange {
+        start_time: common_utils::date_time::now() - time::Duration::seconds(window_in_secs),
+        end_time: None,
+    };
+
+    state
+        .store
+        .get_customer_captured_amount(
+            merchant_account.get_id(),
+            customer_id,
+            currency,
+            vec![
+                storage_enums::IntentStatus::Succeeded,
+                storage_enums::IntentStatus::PartiallyCaptured,
+                storage_enums::IntentStatus::PartiallyCapturedAndCapturable,
+            ],
+            &time_range,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to sum up the amount captured from the customer")
+}
+
+/// Validates that confirming a payment of `amount` does not take the amount captured from the
+/// customer over the spending limit configured in the business profile
+pub fn validate_customer_spending_limit(
+    captured_amount: MinorUnit,
+    amount: MinorUnit,
+    spending_limit: MinorUnit,
+) -> Result<(), errors::ApiErrorResponse> {
+    if captured_amount + amount > spending_limit {
+        return Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "payment exceeds the customer spending limit of {spending_limit}, amount already captured is {captured_amount}"
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// The merchant connector account the payment is sent with is recorded on the attempt, so that
+/// the response carries the exact account used and not only the connector name. Connector
+/// account details passed in the request have no id, the id on the attempt is kept for those.
+pub fn get_merchant_connector_id_for_attempt(
+    merchant_connector_account: &MerchantConnectorAccountType,
+    attempt_merchant_connector_id: Option<id_type::MerchantConnectorAccountId>,
+) -> Option<id_type::MerchantConnectorAccountId> {
+    merchant_connector_account
+        .get_mca_id()
+        .or(attempt_merchant_connector_id)
+}
+
+/// Funding type of the card used for the payment, derived from the card type returned by the BIN
+/// lookup. Card types that are not recognized are reported as `unknown`.
+pub fn get_card_funding_type(
+    additional_payment_data: Option<&api_models::payments::AdditionalPaymentData>,
+) -> Option<storage_enums::CardFundingType> {
+    match additional_payment_data? {
+        api_models::payments::AdditionalPaymentData::Card(card_info) => Some(
+            match card_info
+                .card_type
+                .as_deref()
+                .map(|card_type| card_type.trim().to_uppercase())
+                .as_deref()
+            {
+                Some("CREDIT") => storage_enums::CardFundingType::Credit,
+                Some("DEBIT") => storage_enums::CardFundingType::Debit,
+                Some("PREPAID") => storage_enums::CardFundingType::Prepaid,
+                _ => storage_enums::CardFundingType::Unknown,
+            },
+        ),
+        _ => None,
+    }
+}
+
+/// Whether the liability for fraudulent chargebacks shifted to the issuer, derived from the ECI
+/// returned by the 3DS authentication. ECI values are defined per card network, only the value
+/// for a fully authenticated payment on that network (02 for Mastercard and Maestro, 05 for the
+/// others) shifts liability. Attempted authentications and authentications without a cryptogram
+/// do not. Networks without a known ECI convention are reported as `None`.
+pub fn get_liability_shift(
+    card_network: Option<&api_enums::CardNetwork>,
+    eci: Option<&str>,
+) -> Option<bool> {
+    let fully_authenticated_eci = match card_network? {
+        api_enums::CardNetwork::Mastercard | api_enums::CardNetwork::Maestro => 2,
+        api_enums::CardNetwork::Visa
+        | api_enums::CardNetwork::AmericanExpress
+        | api_enums::CardNetwork::JCB
+        | api_enums::CardNetwork::DinersClub
+        | api_enums::CardNetwork::Discover
+        | api_enums::CardNetwork::CartesBancaires
+        | api_enums::CardNetwork::UnionPay => 5,
+        api_enums::CardNetwork::Interac | api_enums::CardNetwork::RuPay => return None,
+    };
+    let eci = eci?.trim().parse::<u8>().ok()?;
+    Some(eci == fully_authenticated_eci)
+}
+
+#[cfg(feature = "v1")]
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_authenticate_client_secret_session_not_expired() {
+        let payment_intent = PaymentIntent {
+            payment_id: id_type::PaymentId::try_from(Cow::Borrowed("23")).unwrap(),
+            merchant_id: id_type::MerchantId::default(),
+            status: storage_enums::IntentStatus::RequiresCapture,
+            amount: MinorUnit::new(200),
+            currency: None,
+            amount_captured: None,
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            last_synced: None,
+            setup_future_usage: None,
+            fingerprint_id: None,
+            off_session: None,
+            client_secret: Some("1".to_string()),
+            active_attempt: hyperswitch_domain_models::RemoteStorageObject::ForeignID(
+                "nopes".to_string(),
+            ),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            payment_link_id: None,
+            profile_id: Some(common_utils::generate_profile_id_of_default_length()),
+            merchant_decision: None,
+            payment_confirm_source: None,
+            surcharge_applicable: None,
+            updated_by: storage_enums::MerchantStorageScheme::PostgresOnly.to_string(),
+            request_incremental_authorization: Some(
+                common_enums::RequestIncrementalAuthorization::default(),
+            ),
+            incremental_authorization_allowed: None,
+            authorization_count: None,
+            session_expiry: Some(
+                common_utils::date_time::now()
+                    .saturating_add(time::Duration::seconds(consts::DEFAULT_SESSION_EXPIRY)),
+            ),
+            request_external_three_ds_authentication: None,
+            charges: None,
+            frm_metadata: None,
+            customer_details: None,
+            billing_details: None,
+            merchant_order_reference_id: None,
+            shipping_details: None,
+            is_payment_processor_token_flow: None,
+            organization_id: id_type::OrganizationId::default(),
+            shipping_cost: None,
+            tax_details: None,
+            skip_external_tax_calculation: None,
+            skip_save_payment_method: None,
+        };
+        let req_cs = Some("1".to_string());
+        assert!(authenticate_client_secret(req_cs.as_ref(), &payment_intent).is_ok());
+        // Check if the result is an Ok variant
+    }
+
+    #[test]
+    fn test_authenticate_client_secret_session_expired() {
+        let created_at =
+            common_utils::date_time::now().saturating_sub(time::Duration::seconds(20 * 60));
+        let payment_intent = PaymentIntent {
+            payment_id: id_type::PaymentId::try_from(Cow::Borrowed("23")).unwrap(),
+            merchant_id: id_type::MerchantId::default(),
+            status: storage_enums::IntentStatus::RequiresCapture,
+            amount: MinorUnit::new(200),
+            currency: None,
             amount_captured: None,
             customer_id: None,
             description: None,
@@ -3362,6 +4231,7 @@ mod tests {
             shipping_cost: None,
             tax_details: None,
             skip_external_tax_calculation: None,
+            skip_save_payment_method: None,
         };
         let req_cs = Some("1".to_string());
         assert!(authenticate_client_secret(req_cs.as_ref(), &payment_intent,).is_err())
@@ -3429,61 +4299,1250 @@ mod tests {
             shipping_cost: None,
             tax_details: None,
             skip_external_tax_calculation: None,
+            skip_save_payment_method: None,
         };
         let req_cs = Some("1".to_string());
         assert!(authenticate_client_secret(req_cs.as_ref(), &payment_intent).is_err())
     }
-}
-
-// This function will be removed after moving this functionality to server_wrap and using cache instead of config
-#[instrument(skip_all)]
-pub async fn insert_merchant_connector_creds_to_config(
-    db: &dyn StorageInterface,
-    merchant_id: &id_type::MerchantId,
-    merchant_connector_details: admin::MerchantConnectorDetailsWrap,
-) -> RouterResult<()> {
-    if let Some(encoded_data) = merchant_connector_details.encoded_data {
-        let redis = &db
-            .get_redis_conn()
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed to get redis connection")?;
-
-        let key =
-            merchant_id.get_creds_identifier_key(&merchant_connector_details.creds_identifier);
-
-        redis
-            .serialize_and_set_key_with_expiry(
-                key.as_str(),
-                &encoded_data.peek(),
-                consts::CONNECTOR_CREDS_TOKEN_TTL,
-            )
-            .await
-            .map_or_else(
-                |e| {
-                    Err(e
-                        .change_context(errors::ApiErrorResponse::InternalServerError)
-                        .attach_printable("Failed to insert connector_creds to config"))
-                },
-                |_| Ok(()),
-            )
-    } else {
-        Ok(())
-    }
-}
-
-#[derive(Clone)]
-pub enum MerchantConnectorAccountType {
-    DbVal(domain::MerchantConnectorAccount),
-    CacheVal(api_models::admin::MerchantConnectorDetails),
-}
-
-impl MerchantConnectorAccountType {
-    pub fn get_metadata(&self) -> Option<masking::Secret<serde_json::Value>> {
-        match self {
-            Self::DbVal(val) => val.metadata.to_owned(),
-            Self::CacheVal(val) => val.metadata.to_owned(),
-        }
-    }
+
+    #[test]
+    fn test_validate_linked_authentication() {
+        let payment_intent = PaymentIntent {
+            payment_id: id_type::PaymentId::try_from(Cow::Borrowed("23")).unwrap(),
+            merchant_id: id_type::MerchantId::default(),
+            status: storage_enums::IntentStatus::RequiresCapture,
+            amount: MinorUnit::new(200),
+            currency: None,
+            amount_captured: None,
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            last_synced: None,
+            setup_future_usage: None,
+            fingerprint_id: None,
+            off_session: None,
+            client_secret: Some("1".to_string()),
+            active_attempt: hyperswitch_domain_models::RemoteStorageObject::ForeignID(
+                "nopes".to_string(),
+            ),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            payment_link_id: None,
+            profile_id: Some(common_utils::generate_profile_id_of_default_length()),
+            merchant_decision: None,
+            payment_confirm_source: None,
+            surcharge_applicable: None,
+            updated_by: storage_enums::MerchantStorageScheme::PostgresOnly.to_string(),
+            request_incremental_authorization: Some(
+                common_enums::RequestIncrementalAuthorization::default(),
+            ),
+            incremental_authorization_allowed: None,
+            authorization_count: None,
+            session_expiry: Some(
+                common_utils::date_time::now()
+                    .saturating_add(time::Duration::seconds(consts::DEFAULT_SESSION_EXPIRY)),
+            ),
+            request_external_three_ds_authentication: None,
+            charges: None,
+            frm_metadata: None,
+            customer_details: None,
+            billing_details: None,
+            merchant_order_reference_id: None,
+            shipping_details: None,
+            is_payment_processor_token_flow: None,
+            organization_id: id_type::OrganizationId::default(),
+            shipping_cost: None,
+            tax_details: None,
+            skip_external_tax_calculation: None,
+            skip_save_payment_method: None,
+        };
+        let authentication = storage::Authentication {
+            authentication_id: "authn_123".to_string(),
+            merchant_id: id_type::MerchantId::default(),
+            authentication_connector: "threedsecureio".to_string(),
+            connector_authentication_id: None,
+            authentication_data: None,
+            payment_method_id: "pm_123".to_string(),
+            authentication_type: None,
+            authentication_status: common_enums::AuthenticationStatus::Success,
+            authentication_lifecycle_status: common_enums::AuthenticationLifecycleStatus::Unused,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            error_message: None,
+            error_code: None,
+            connector_metadata: None,
+            maximum_supported_version: None,
+            threeds_server_transaction_id: None,
+            cavv: None,
+            authentication_flow_type: None,
+            message_version: None,
+            eci: None,
+            trans_status: None,
+            acquirer_bin: None,
+            acquirer_merchant_id: None,
+            three_ds_method_data: None,
+            three_ds_method_url: None,
+            acs_url: None,
+            challenge_request: None,
+            acs_reference_number: None,
+            acs_trans_id: None,
+            acs_signed_content: None,
+            profile_id: payment_intent.profile_id.clone().unwrap(),
+            payment_id: Some(payment_intent.payment_id.clone()),
+            merchant_connector_id: id_type::MerchantConnectorAccountId::wrap("mca_123".to_string())
+                .unwrap(),
+            ds_trans_id: None,
+            directory_server_id: None,
+            acquirer_country_code: None,
+        };
+        assert!(validate_linked_authentication(&authentication, &payment_intent).is_ok());
+
+        let incomplete_authentication = storage::Authentication {
+            authentication_status: common_enums::AuthenticationStatus::Pending,
+            ..authentication.clone()
+        };
+        assert!(matches!(
+            validate_linked_authentication(&incomplete_authentication, &payment_intent),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+
+        let used_authentication = storage::Authentication {
+            authentication_lifecycle_status: common_enums::AuthenticationLifecycleStatus::Used,
+            ..authentication.clone()
+        };
+        assert!(matches!(
+            validate_linked_authentication(&used_authentication, &payment_intent),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+
+        let unbound_authentication = storage::Authentication {
+            payment_id: None,
+            ..authentication.clone()
+        };
+        assert!(matches!(
+            validate_linked_authentication(&unbound_authentication, &payment_intent),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+
+        let other_payment_authentication = storage::Authentication {
+            payment_id: Some(id_type::PaymentId::try_from(Cow::Borrowed("24")).unwrap()),
+            ..authentication
+        };
+        assert!(matches!(
+            validate_linked_authentication(&other_payment_authentication, &payment_intent),
+            Err(errors::ApiErrorResponse::InvalidRequestData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_get_mandate_created() {
+        let mandate_id = "man_123".to_string();
+        assert_eq!(get_mandate_created(true, Some(&mandate_id)), Some(true));
+        assert_eq!(get_mandate_created(true, None), Some(false));
+        assert_eq!(get_mandate_created(false, None), None);
+    }
+
+    #[test]
+    fn test_validate_connector_metadata() {
+        let connector_metadata = serde_json::json!({
+            "noon": { "order_category": "pay" },
+            "some_connector": { "any_key": 1 }
+        });
+        assert!(validate_connector_metadata(&connector_metadata).is_ok());
+
+        let connector_metadata = serde_json::json!({ "noon": { "order_category": 5 } });
+        match validate_connector_metadata(&connector_metadata) {
+            Err(errors::ApiErrorResponse::InvalidRequestData { message }) => {
+                assert!(message.contains("`noon.order_category`"))
+            }
+            result => panic!("unexpected result {result:?}"),
+        }
+
+        let connector_metadata = serde_json::json!({ "some_connector": "not_an_object" });
+        assert!(validate_connector_metadata(&connector_metadata).is_err());
+    }
+
+    #[test]
+    fn test_validate_connector_metadata_with_absent_sections() {
+        let request = serde_json::from_value::<api_models::payments::ConnectorMetadata>(
+            serde_json::json!({ "noon": { "order_category": "pay" } }),
+        )
+        .unwrap();
+        let connector_metadata = serde_json::to_value(request).unwrap();
+        assert!(connector_metadata["apple_pay"].is_null());
+        assert!(connector_metadata["airwallex"].is_null());
+        assert!(validate_connector_metadata(&connector_metadata).is_ok());
+
+        let request = serde_json::from_value::<api_models::payments::ConnectorMetadata>(
+            serde_json::json!({ "airwallex": { "payload": "payload" } }),
+        )
+        .unwrap();
+        let connector_metadata = serde_json::to_value(request).unwrap();
+        assert!(validate_connector_metadata(&connector_metadata).is_ok());
+    }
+
+    #[test]
+    fn test_validate_connector_metadata_for_connector() {
+        let connector_metadata = serde_json::json!({ "noon": { "order_category": "pay" } });
+        assert!(validate_connector_metadata_for_connector(
+            api_enums::Connector::Noon,
+            Some(&connector_metadata),
+            false
+        )
+        .is_ok());
+
+        let connector_metadata = serde_json::json!({ "airwallex": { "payload": "payload" } });
+        match validate_connector_metadata_for_connector(
+            api_enums::Connector::Noon,
+            Some(&connector_metadata),
+            false,
+        ) {
+            Err(error) => assert!(matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::MissingRequiredField {
+                    field_name: "connector_metadata.noon.order_category",
+                }
+            )),
+            Ok(()) => panic!("expected missing order_category to be rejected"),
+        }
+        assert!(
+            validate_connector_metadata_for_connector(api_enums::Connector::Noon, None, false)
+                .is_err()
+        );
+
+        // mandate payments and connectors without required metadata pass through
+        assert!(
+            validate_connector_metadata_for_connector(api_enums::Connector::Noon, None, true)
+                .is_ok()
+        );
+        assert!(validate_connector_metadata_for_connector(
+            api_enums::Connector::Stripe,
+            Some(&connector_metadata),
+            false
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_amount_to_capture() {
+        // full capture
+        assert!(validate_amount_to_capture(1000, Some(1000)).is_ok());
+        // partial capture
+        assert!(validate_amount_to_capture(1000, Some(400)).is_ok());
+        // second partial capture against the remaining capturable amount
+        assert!(validate_amount_to_capture(600, Some(600)).is_ok());
+        assert!(validate_amount_to_capture(600, Some(700)).is_err());
+        // over-capture
+        match validate_amount_to_capture(1000, Some(1500)) {
+            Err(err) => assert!(matches!(
+                err.current_context(),
+                errors::ApiErrorResponse::InvalidRequestData { .. }
+            )),
+            Ok(()) => panic!("over-capture must be rejected"),
+        }
+    }
+
+    #[test]
+    fn test_validate_attempt_status_for_capture() {
+        assert!(validate_attempt_status_for_capture(
+            storage_enums::AttemptStatus::Authorized,
+            storage_enums::CaptureMethod::Manual
+        )
+        .is_ok());
+        assert!(validate_attempt_status_for_capture(
+            storage_enums::AttemptStatus::PartialChargedAndChargeable,
+            storage_enums::CaptureMethod::ManualMultiple
+        )
+        .is_ok());
+        assert!(validate_attempt_status_for_capture(
+            storage_enums::AttemptStatus::CaptureInitiated,
+            storage_enums::CaptureMethod::ManualMultiple
+        )
+        .is_ok());
+        assert!(validate_attempt_status_for_capture(
+            storage_enums::AttemptStatus::CaptureInitiated,
+            storage_enums::CaptureMethod::Manual
+        )
+        .is_err());
+        for attempt_status in [
+            storage_enums::AttemptStatus::Started,
+            storage_enums::AttemptStatus::Pending,
+            storage_enums::AttemptStatus::AuthorizationFailed,
+            storage_enums::AttemptStatus::Charged,
+            storage_enums::AttemptStatus::Voided,
+        ] {
+            assert!(validate_attempt_status_for_capture(
+                attempt_status,
+                storage_enums::CaptureMethod::Manual
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_customer_spending_limit() {
+        let spending_limit = MinorUnit::new(10000);
+        assert!(validate_customer_spending_limit(
+            MinorUnit::new(6000),
+            MinorUnit::new(4000),
+            spending_limit
+        )
+        .is_ok());
+        assert!(matches!(
+            validate_customer_spending_limit(
+                MinorUnit::new(6000),
+                MinorUnit::new(4001),
+                spending_limit
+            ),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_get_matched_routing_rule() {
+        use api_models::routing::ast;
+
+        let routing_algorithm_id = id_type::RoutingId::try_from(Cow::from("routing_123")).unwrap();
+        let statements = vec![ast::IfStatement {
+            condition: vec![ast::Comparison {
+                lhs: "payment_method".to_string(),
+                comparison: ast::ComparisonType::Equal,
+                value: ast::ValueType::EnumVariant("card".to_string()),
+                metadata: std::collections::HashMap::new(),
+            }],
+            nested: None,
+        }];
+        let matched_rule = ast::Rule {
+            name: "high_value_card_payments".to_string(),
+            connector_selection: api_models::routing::ConnectorSelection::Priority(vec![]),
+            statements: statements.clone(),
+        };
+        assert_eq!(
+            get_matched_routing_rule(
+                true,
+                Some(routing_algorithm_id.clone()),
+                Some(matched_rule.clone())
+            ),
+            Some(api_models::payments::MatchedRoutingRule {
+                routing_algorithm_id: Some(routing_algorithm_id.clone()),
+                rule_name: "high_value_card_payments".to_string(),
+                statements,
+            })
+        );
+        assert_eq!(
+            get_matched_routing_rule(
+                false,
+                Some(routing_algorithm_id.clone()),
+                Some(matched_rule)
+            ),
+            None
+        );
+        // default selection of the algorithm, no rule matched
+        assert_eq!(
+            get_matched_routing_rule(true, Some(routing_algorithm_id), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_connector_risk_score_normalized() {
+        let risk_data = ConnectorRiskData {
+            score: Some(450.0),
+            max_score: Some(999.0),
+            decision: Some("REVIEW".to_string()),
+        };
+        assert_eq!(
+            get_connector_risk_details(Some(&risk_data)),
+            (Some(45), Some("REVIEW".to_string()))
+        );
+        assert_eq!(normalize_connector_risk_score(72.0, None), Some(72));
+        assert_eq!(normalize_connector_risk_score(150.0, None), None);
+        assert_eq!(normalize_connector_risk_score(10.0, Some(0.0)), None);
+    }
+
+    #[test]
+    fn test_connector_risk_details_omitted_when_absent() {
+        let risk_data = ConnectorRiskData {
+            score: None,
+            max_score: Some(100.0),
+            decision: None,
+        };
+        assert_eq!(get_connector_risk_details(Some(&risk_data)), (None, None));
+        assert_eq!(get_connector_risk_details(None), (None, None));
+    }
+
+    #[test]
+    fn test_connector_fee_is_captured_when_reported() {
+        let fee_data = ConnectorFeeData {
+            amount: MinorUnit::new(59),
+            currency: storage_enums::Currency::USD,
+        };
+        assert_eq!(
+            get_connector_fee_details(Some(&fee_data)),
+            (Some(MinorUnit::new(59)), Some(storage_enums::Currency::USD))
+        );
+    }
+
+    #[test]
+    fn test_connector_fee_omitted_when_not_reported() {
+        assert_eq!(get_connector_fee_details(None), (None, None));
+    }
+
+    #[test]
+    fn test_valid_device_fingerprint_is_accepted() {
+        assert!(validate_device_fingerprint(None).is_ok());
+        assert!(validate_device_fingerprint(Some("d7f3a1c9-e2b8_4f6a9c0d")).is_ok());
+        assert!(validate_device_fingerprint(Some(
+            &"a".repeat(consts::MAX_DEVICE_FINGERPRINT_LENGTH)
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_invalid_device_fingerprint_is_rejected() {
+        let too_long = "a".repeat(consts::MAX_DEVICE_FINGERPRINT_LENGTH + 1);
+        for device_fingerprint in ["", "device fingerprint", "fp<script>", too_long.as_str()] {
+            assert!(matches!(
+                validate_device_fingerprint(Some(device_fingerprint))
+                    .unwrap_err()
+                    .current_context(),
+                errors::ApiErrorResponse::InvalidRequestData { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_statement_descriptor_prefix_is_prepended() {
+        assert_eq!(
+            get_statement_descriptor_with_prefix(Some("SUBMERCH"), Some("SHOES".to_string()))
+                .unwrap(),
+            Some("SUBMERCH* SHOES".to_string())
+        );
+        assert_eq!(
+            get_statement_descriptor_with_prefix(Some("SUBMERCH"), None).unwrap(),
+            Some("SUBMERCH".to_string())
+        );
+        assert_eq!(
+            get_statement_descriptor_with_prefix(None, Some("SHOES".to_string())).unwrap(),
+            Some("SHOES".to_string())
+        );
+    }
+
+    #[test]
+    fn test_over_length_statement_descriptor_with_prefix_is_rejected() {
+        let result = get_statement_descriptor_with_prefix(
+            Some("SUBMERCHANT"),
+            Some("SHOES PURCHASE".to_string()),
+        );
+
+        assert!(matches!(
+            result.unwrap_err().current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { .. }
+        ));
+    }
+
+    fn card_payment_data(card_type: Option<&str>) -> api_models::payments::AdditionalPaymentData {
+        api_models::payments::AdditionalPaymentData::Card(Box::new(
+            api_models::payments::AdditionalCardInfo {
+                card_type: card_type.map(ToString::to_string),
+                ..Default::default()
+            },
+        ))
+    }
+
+    #[test]
+    fn test_card_funding_type_is_derived_from_card_type() {
+        assert_eq!(
+            get_card_funding_type(Some(&card_payment_data(Some("CREDIT")))),
+            Some(storage_enums::CardFundingType::Credit)
+        );
+        assert_eq!(
+            get_card_funding_type(Some(&card_payment_data(Some("debit")))),
+            Some(storage_enums::CardFundingType::Debit)
+        );
+        assert_eq!(
+            get_card_funding_type(Some(&card_payment_data(Some(" Prepaid ")))),
+            Some(storage_enums::CardFundingType::Prepaid)
+        );
+        assert_eq!(get_card_funding_type(None), None);
+    }
+
+    #[test]
+    fn test_non_card_payment_has_no_card_funding_type() {
+        let pay_later = api_models::payments::AdditionalPaymentData::PayLater { klarna_sdk: None };
+        assert_eq!(get_card_funding_type(Some(&pay_later)), None);
+    }
+
+    #[test]
+    fn test_unrecognized_card_funding_type_is_unknown() {
+        for card_type in [Some("CHARGE CARD"), Some(""), None] {
+            let funding_type = get_card_funding_type(Some(&card_payment_data(card_type)));
+            assert_eq!(funding_type, Some(storage_enums::CardFundingType::Unknown));
+            assert_eq!(
+                funding_type.map(|funding_type| funding_type.to_string()),
+                Some("unknown".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_liability_shifts_for_authenticated_3ds_payment() {
+        let visa = Some(&api_enums::CardNetwork::Visa);
+        let mastercard = Some(&api_enums::CardNetwork::Mastercard);
+
+        assert_eq!(get_liability_shift(visa, Some("05")), Some(true));
+        assert_eq!(get_liability_shift(visa, Some("5")), Some(true));
+        assert_eq!(get_liability_shift(mastercard, Some("02")), Some(true));
+    }
+
+    #[test]
+    fn test_liability_does_not_shift_for_attempted_or_unauthenticated_3ds_payment() {
+        let visa = Some(&api_enums::CardNetwork::Visa);
+        let mastercard = Some(&api_enums::CardNetwork::Mastercard);
+
+        for eci in ["06", "07", "02"] {
+            assert_eq!(get_liability_shift(visa, Some(eci)), Some(false));
+        }
+        for eci in ["01", "00", "05"] {
+            assert_eq!(get_liability_shift(mastercard, Some(eci)), Some(false));
+        }
+        assert_eq!(get_liability_shift(visa, None), None);
+        assert_eq!(get_liability_shift(visa, Some("")), None);
+        assert_eq!(get_liability_shift(None, Some("05")), None);
+    }
+
+    #[test]
+    fn test_payment_attempt_of_same_merchant_is_returned() {
+        let merchant_id = id_type::MerchantId::try_from(Cow::from("merchant_1")).unwrap();
+        assert!(validate_payment_attempt_ownership(&merchant_id, &merchant_id.clone()).is_ok());
+    }
+
+    #[test]
+    fn test_payment_attempt_of_other_merchant_is_rejected() {
+        let merchant_id = id_type::MerchantId::try_from(Cow::from("merchant_1")).unwrap();
+        let attempt_merchant_id = id_type::MerchantId::try_from(Cow::from("merchant_2")).unwrap();
+
+        let result = validate_payment_attempt_ownership(&merchant_id, &attempt_merchant_id);
+        assert!(matches!(
+            result.unwrap_err().current_context(),
+            errors::ApiErrorResponse::PaymentNotFound
+        ));
+    }
+
+    fn get_routable_connector_choice(
+        connector: api_enums::RoutableConnectors,
+    ) -> api_models::routing::RoutableConnectorChoice {
+        api_models::routing::RoutableConnectorChoice {
+            choice_kind: api_models::routing::RoutableChoiceKind::FullStruct,
+            connector,
+            merchant_connector_id: None,
+        }
+    }
+
+    fn get_connector_amount_limits() -> ConnectorAmountLimits {
+        let limits = |minimum_amount, maximum_amount| {
+            vec![crate::configs::settings::AmountLimits {
+                currency: storage_enums::Currency::USD,
+                minimum_amount: Some(MinorUnit::new(minimum_amount)),
+                maximum_amount: Some(MinorUnit::new(maximum_amount)),
+            }]
+        };
+        ConnectorAmountLimits(std::collections::HashMap::from([
+            (api_enums::RoutableConnectors::Stripe, limits(500, 100_000)),
+            (api_enums::RoutableConnectors::Adyen, limits(100, 50_000)),
+        ]))
+    }
+
+    #[test]
+    fn test_amount_below_connector_minimum_routes_to_next_connector() {
+        let connectors = filter_connectors_by_amount_limits(
+            &get_connector_amount_limits(),
+            vec![
+                get_routable_connector_choice(api_enums::RoutableConnectors::Stripe),
+                get_routable_connector_choice(api_enums::RoutableConnectors::Adyen),
+            ],
+            MinorUnit::new(200),
+            Some(storage_enums::Currency::USD),
+        )
+        .unwrap();
+
+        assert_eq!(
+            connectors
+                .iter()
+                .map(|connector_choice| connector_choice.connector)
+                .collect::<Vec<_>>(),
+            vec![api_enums::RoutableConnectors::Adyen]
+        );
+    }
+
+    #[test]
+    fn test_amount_above_all_connector_maximums_is_rejected() {
+        let result = filter_connectors_by_amount_limits(
+            &get_connector_amount_limits(),
+            vec![
+                get_routable_connector_choice(api_enums::RoutableConnectors::Stripe),
+                get_routable_connector_choice(api_enums::RoutableConnectors::Adyen),
+            ],
+            MinorUnit::new(200_000),
+            Some(storage_enums::Currency::USD),
+        );
+
+        assert!(matches!(
+            result.unwrap_err().current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { .. }
+        ));
+    }
+
+    fn get_connector_amount_limits_error(amount: i64) -> Option<String> {
+        validate_connector_amount_limits(
+            &get_connector_amount_limits(),
+            api_enums::Connector::Stripe,
+            MinorUnit::new(amount),
+            Some(storage_enums::Currency::USD),
+        )
+        .err()
+        .map(|error| match error.current_context() {
+            errors::ApiErrorResponse::InvalidRequestData { message } => message.clone(),
+            error => error.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_amount_below_connector_minimum_is_rejected() {
+        assert_eq!(
+            get_connector_amount_limits_error(200).as_deref(),
+            Some("amount 200 USD is below the minimum amount 500 supported by stripe")
+        );
+    }
+
+    #[test]
+    fn test_amount_above_connector_maximum_is_rejected() {
+        assert_eq!(
+            get_connector_amount_limits_error(200_000).as_deref(),
+            Some("amount 200000 USD is above the maximum amount 100000 supported by stripe")
+        );
+    }
+
+    #[test]
+    fn test_amount_within_connector_limits_is_accepted() {
+        assert_eq!(get_connector_amount_limits_error(500), None);
+        assert_eq!(get_connector_amount_limits_error(100_000), None);
+        assert!(validate_connector_amount_limits(
+            &get_connector_amount_limits(),
+            api_enums::Connector::Checkout,
+            MinorUnit::new(1),
+            Some(storage_enums::Currency::USD),
+        )
+        .is_ok());
+    }
+
+    fn get_issuer_country_connector_preferences(
+    ) -> common_utils::types::IssuerCountryConnectorPreferences {
+        common_utils::types::IssuerCountryConnectorPreferences(std::collections::HashMap::from([(
+            common_enums::CountryAlpha2::GB,
+            vec![
+                api_enums::RoutableConnectors::Checkout,
+                api_enums::RoutableConnectors::Adyen,
+            ],
+        )]))
+    }
+
+    fn get_routed_connectors() -> Vec<api_models::routing::RoutableConnectorChoice> {
+        vec![
+            get_routable_connector_choice(api_enums::RoutableConnectors::Stripe),
+            get_routable_connector_choice(api_enums::RoutableConnectors::Adyen),
+            get_routable_connector_choice(api_enums::RoutableConnectors::Cybersource),
+        ]
+    }
+
+    #[test]
+    fn test_issuer_country_preference_reorders_routed_connectors() {
+        let connectors = reorder_connectors_by_issuer_country(
+            Some(&get_issuer_country_connector_preferences()),
+            Some(common_enums::CountryAlpha2::GB),
+            get_routed_connectors(),
+        );
+
+        assert_eq!(
+            connectors
+                .iter()
+                .map(|connector_choice| connector_choice.connector)
+                .collect::<Vec<_>>(),
+            vec![
+                api_enums::RoutableConnectors::Adyen,
+                api_enums::RoutableConnectors::Stripe,
+                api_enums::RoutableConnectors::Cybersource,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unmatched_issuer_country_keeps_routed_order() {
+        let connectors = reorder_connectors_by_issuer_country(
+            Some(&get_issuer_country_connector_preferences()),
+            Some(common_enums::CountryAlpha2::IN),
+            get_routed_connectors(),
+        );
+
+        assert_eq!(
+            connectors
+                .iter()
+                .map(|connector_choice| connector_choice.connector)
+                .collect::<Vec<_>>(),
+            vec![
+                api_enums::RoutableConnectors::Stripe,
+                api_enums::RoutableConnectors::Adyen,
+                api_enums::RoutableConnectors::Cybersource,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_issuer_country_is_resolved_to_its_alpha2_code() {
+        for issuer_country in [
+            "GB",
+            "gb",
+            "United Kingdom of Great Britain and Northern Ireland",
+        ] {
+            assert_eq!(
+                get_issuer_country_code(issuer_country),
+                Some(common_enums::CountryAlpha2::GB)
+            );
+        }
+        assert_eq!(
+            get_issuer_country_code("INDIA"),
+            Some(common_enums::CountryAlpha2::IN)
+        );
+        assert_eq!(get_issuer_country_code("ATLANTIS"), None);
+    }
+
+    fn get_card_payment_method_data_response(
+    ) -> api_models::payments::PaymentMethodDataResponseWithBilling {
+        api_models::payments::PaymentMethodDataResponseWithBilling {
+            payment_method_data: Some(api_models::payments::PaymentMethodDataResponse::Card(
+                Box::new(api_models::payments::CardResponse {
+                    last4: Some("4242".to_string()),
+                    card_type: Some("CREDIT".to_string()),
+                    card_network: Some(api_enums::CardNetwork::Visa),
+                    card_issuer: Some("Stripe Payments UK Limited".to_string()),
+                    card_issuing_country: Some("UNITEDKINGDOM".to_string()),
+                    card_isin: Some("424242".to_string()),
+                    card_extended_bin: None,
+                    card_exp_month: None,
+                    card_exp_year: None,
+                    card_holder_name: None,
+                    payment_checks: None,
+                    authentication_data: None,
+                }),
+            )),
+            billing: Some(api_models::payments::Address::default()),
+        }
+    }
+
+    #[test]
+    fn test_default_confirm_response_masking_policy_masks_fields() {
+        let (payment_method_data_response, billing) = apply_confirm_response_masking_policy(
+            common_utils::types::ConfirmResponseMaskingPolicy::default(),
+            Some(get_card_payment_method_data_response()),
+            Some(api_models::payments::Address::default()),
+        );
+        let payment_method_data_response = payment_method_data_response.unwrap();
+        let Some(api_models::payments::PaymentMethodDataResponse::Card(card)) =
+            payment_method_data_response.payment_method_data
+        else {
+            panic!("expected card payment method data")
+        };
+
+        assert!(card.last4.is_none());
+        assert!(card.card_issuer.is_none());
+        assert!(card.card_issuing_country.is_none());
+        assert!(payment_method_data_response.billing.is_none());
+        assert!(billing.is_none());
+    }
+
+    #[test]
+    fn test_permissive_confirm_response_masking_policy_reveals_allowed_fields() {
+        let masking_policy = common_utils::types::ConfirmResponseMaskingPolicy {
+            reveal_card_last4: true,
+            reveal_card_issuer: true,
+            reveal_billing: false,
+        };
+        let (payment_method_data_response, billing) = apply_confirm_response_masking_policy(
+            masking_policy,
+            Some(get_card_payment_method_data_response()),
+            Some(api_models::payments::Address::default()),
+        );
+        let payment_method_data_response = payment_method_data_response.unwrap();
+        let Some(api_models::payments::PaymentMethodDataResponse::Card(card)) =
+            payment_method_data_response.payment_method_data
+        else {
+            panic!("expected card payment method data")
+        };
+
+        assert_eq!(card.last4.as_deref(), Some("4242"));
+        assert_eq!(
+            card.card_issuer.as_deref(),
+            Some("Stripe Payments UK Limited")
+        );
+        assert_eq!(card.card_issuing_country.as_deref(), Some("UNITEDKINGDOM"));
+        // Billing is not part of the allowed fields, so it stays masked
+        assert!(payment_method_data_response.billing.is_none());
+        assert!(billing.is_none());
+    }
+
+    #[test]
+    fn test_payment_method_token_reference_returned_when_enabled() {
+        let token_reference = get_payment_method_token_reference(
+            true,
+            Some(storage_enums::PaymentMethod::Card),
+            Some("pm_123".to_string()),
+            Some("ntrr_456".to_string()),
+        )
+        .unwrap();
+        assert_eq!(token_reference.payment_method_id, "pm_123");
+        assert_eq!(
+            token_reference.network_token_reference_id.as_deref(),
+            Some("ntrr_456")
+        );
+
+        // Only the references are serialized, no card details are part of the response
+        let serialized = serde_json::to_value(&token_reference).unwrap();
+        let fields = serialized.as_object().unwrap();
+        assert_eq!(fields.len(), 2);
+        assert!(fields.contains_key("payment_method_id"));
+        assert!(fields.contains_key("network_token_reference_id"));
+    }
+
+    #[test]
+    fn test_payment_method_token_reference_omitted_when_disabled() {
+        assert!(get_payment_method_token_reference(
+            false,
+            Some(storage_enums::PaymentMethod::Card),
+            Some("pm_123".to_string()),
+            Some("ntrr_456".to_string()),
+        )
+        .is_none());
+        assert!(get_payment_method_token_reference(
+            true,
+            Some(storage_enums::PaymentMethod::Wallet),
+            Some("pm_123".to_string()),
+            None,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_payment_method_token_returned_only_to_merchant_authenticated_calls() {
+        assert!(should_return_payment_method_token(
+            Some(true),
+            services::AuthFlow::Merchant
+        ));
+        assert!(!should_return_payment_method_token(
+            Some(true),
+            services::AuthFlow::Client
+        ));
+        assert!(!should_return_payment_method_token(
+            Some(false),
+            services::AuthFlow::Merchant
+        ));
+        assert!(!should_return_payment_method_token(
+            None,
+            services::AuthFlow::Merchant
+        ));
+    }
+
+    #[test]
+    fn test_three_ds_version_details_recorded_from_authentication() {
+        let authentication = storage::Authentication {
+            authentication_id: "authn_123".to_string(),
+            merchant_id: id_type::MerchantId::default(),
+            authentication_connector: "threedsecureio".to_string(),
+            connector_authentication_id: None,
+            authentication_data: None,
+            payment_method_id: "pm_123".to_string(),
+            authentication_type: None,
+            authentication_status: common_enums::AuthenticationStatus::Success,
+            authentication_lifecycle_status: common_enums::AuthenticationLifecycleStatus::Unused,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            error_message: None,
+            error_code: None,
+            connector_metadata: None,
+            maximum_supported_version: Some(common_utils::types::SemanticVersion::new(2, 3, 1)),
+            threeds_server_transaction_id: None,
+            cavv: None,
+            authentication_flow_type: None,
+            message_version: Some(common_utils::types::SemanticVersion::new(2, 2, 0)),
+            eci: None,
+            trans_status: None,
+            acquirer_bin: None,
+            acquirer_merchant_id: None,
+            three_ds_method_data: None,
+            three_ds_method_url: None,
+            acs_url: None,
+            challenge_request: None,
+            acs_reference_number: None,
+            acs_trans_id: None,
+            acs_signed_content: None,
+            profile_id: common_utils::generate_profile_id_of_default_length(),
+            payment_id: None,
+            merchant_connector_id: id_type::MerchantConnectorAccountId::wrap("mca_123".to_string())
+                .unwrap(),
+            ds_trans_id: None,
+            directory_server_id: None,
+            acquirer_country_code: None,
+        };
+        assert_eq!(
+            get_three_ds_version_details(Some(&authentication)),
+            (Some("2.2".to_string()), Some("2.2.0".to_string()))
+        );
+
+        let pending_authentication = storage::Authentication {
+            message_version: None,
+            ..authentication
+        };
+        assert_eq!(
+            get_three_ds_version_details(Some(&pending_authentication)),
+            (None, None)
+        );
+        assert_eq!(get_three_ds_version_details(None), (None, None));
+    }
+
+    #[test]
+    fn test_unconfirmed_payment_auto_cancelled_after_window() {
+        let created_at = time::macros::datetime!(2024-10-14 10:00);
+        assert_eq!(
+            get_auto_cancel_schedule_time(created_at, 3600),
+            time::macros::datetime!(2024-10-14 11:00)
+        );
+        assert!(should_auto_cancel_unconfirmed_payment(
+            storage_enums::IntentStatus::RequiresPaymentMethod,
+            storage_enums::AttemptStatus::PaymentMethodAwaited,
+        ));
+        assert!(should_auto_cancel_unconfirmed_payment(
+            storage_enums::IntentStatus::RequiresConfirmation,
+            storage_enums::AttemptStatus::ConfirmationAwaited,
+        ));
+    }
+
+    #[test]
+    fn test_payment_with_attempt_exempt_from_auto_cancel() {
+        // A failed confirm moves the intent back to requiring a payment method
+        assert!(!should_auto_cancel_unconfirmed_payment(
+            storage_enums::IntentStatus::RequiresPaymentMethod,
+            storage_enums::AttemptStatus::Failure,
+        ));
+        assert!(!should_auto_cancel_unconfirmed_payment(
+            storage_enums::IntentStatus::RequiresCustomerAction,
+            storage_enums::AttemptStatus::AuthenticationPending,
+        ));
+        assert!(!should_auto_cancel_unconfirmed_payment(
+            storage_enums::IntentStatus::Succeeded,
+            storage_enums::AttemptStatus::Charged,
+        ));
+    }
+
+    #[test]
+    fn test_processing_payment_expired_after_expiry() {
+        let modified_at = time::macros::datetime!(2024-10-14 10:00);
+
+        assert!(!should_expire_processing_payment(
+            storage_enums::IntentStatus::Processing,
+            modified_at,
+            3600,
+            time::macros::datetime!(2024-10-14 10:59),
+        ));
+        assert!(should_expire_processing_payment(
+            storage_enums::IntentStatus::Processing,
+            modified_at,
+            3600,
+            time::macros::datetime!(2024-10-14 11:00),
+        ));
+        // Payments that completed processing are left as they are
+        assert!(!should_expire_processing_payment(
+            storage_enums::IntentStatus::Succeeded,
+            modified_at,
+            3600,
+            time::macros::datetime!(2024-10-15 10:00),
+        ));
+    }
+
+    #[cfg(feature = "v1")]
+    fn get_merchant_connector_account(merchant_connector_id: &str) -> MerchantConnectorAccountType {
+        let encryptable = || {
+            Encryptable::new(
+                masking::Secret::new(serde_json::Value::Null),
+                masking::Secret::new(Vec::new()),
+            )
+        };
+        MerchantConnectorAccountType::DbVal(domain::MerchantConnectorAccount {
+            merchant_id: id_type::MerchantId::default(),
+            connector_name: "stripe".to_string(),
+            connector_account_details: encryptable(),
+            test_mode: None,
+            disabled: None,
+            merchant_connector_id: id_type::MerchantConnectorAccountId::wrap(
+                merchant_connector_id.to_string(),
+            )
+            .unwrap(),
+            payment_methods_enabled: None,
+            connector_type: ConnectorType::PaymentProcessor,
+            metadata: None,
+            frm_configs: None,
+            connector_label: None,
+            business_country: None,
+            business_label: None,
+            business_sub_label: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            connector_webhook_details: None,
+            profile_id: id_type::ProfileId::try_from(Cow::Borrowed("pro_test")).unwrap(),
+            applepay_verified_domains: None,
+            pm_auth_config: None,
+            status: common_enums::ConnectorStatus::Active,
+            connector_wallets_details: None,
+            additional_merchant_data: None,
+            version: common_enums::ApiVersion::V1,
+        })
+    }
+
+    #[cfg(feature = "v1")]
+    #[test]
+    fn test_attempt_records_merchant_connector_id_of_chosen_connector() {
+        let chosen_connector_id =
+            id_type::MerchantConnectorAccountId::wrap("mca_chosen".to_string()).unwrap();
+        let previous_connector_id =
+            id_type::MerchantConnectorAccountId::wrap("mca_previous".to_string()).unwrap();
+
+        assert_eq!(
+            get_merchant_connector_id_for_attempt(
+                &get_merchant_connector_account("mca_chosen"),
+                Some(previous_connector_id),
+            ),
+            Some(chosen_connector_id.clone())
+        );
+        assert_eq!(
+            get_merchant_connector_id_for_attempt(
+                &get_merchant_connector_account("mca_chosen"),
+                None
+            ),
+            Some(chosen_connector_id)
+        );
+    }
+
+    #[test]
+    fn test_attempt_keeps_merchant_connector_id_for_request_connector_details() {
+        let merchant_connector_account =
+            MerchantConnectorAccountType::CacheVal(api_models::admin::MerchantConnectorDetails {
+                connector_account_details: masking::Secret::new(serde_json::Value::Null),
+                metadata: None,
+            });
+        let attempt_connector_id =
+            id_type::MerchantConnectorAccountId::wrap("mca_attempt".to_string()).unwrap();
+
+        assert_eq!(
+            get_merchant_connector_id_for_attempt(
+                &merchant_connector_account,
+                Some(attempt_connector_id.clone())
+            ),
+            Some(attempt_connector_id)
+        );
+        assert_eq!(
+            get_merchant_connector_id_for_attempt(&merchant_connector_account, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_saved_payment_method_billing_is_used_without_request_billing() {
+        let saved_billing = serde_json::json!({
+            "address": {
+                "line1": "1467 Harrison Street",
+                "city": "San Francisco",
+                "country": "US",
+                "zip": "94122"
+            }
+        });
+
+        let billing = get_saved_payment_method_billing(None, Some(saved_billing.clone()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            billing.address.and_then(|address| address.city),
+            Some("San Francisco".to_string())
+        );
+
+        let request_billing: api::Address = serde_json::from_value(serde_json::json!({
+            "address": {
+                "city": "Bangalore",
+                "country": "IN"
+            }
+        }))
+        .unwrap();
+        assert!(
+            get_saved_payment_method_billing(Some(&request_billing), Some(saved_billing))
+                .unwrap()
+                .is_none()
+        );
+        assert!(get_saved_payment_method_billing(None, None)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_setup_future_usage_can_only_be_set_when_unset() {
+        use storage_enums::FutureUsage;
+
+        assert!(validate_setup_future_usage_update(None, Some(FutureUsage::OffSession)).is_ok());
+        assert!(validate_setup_future_usage_update(None, Some(FutureUsage::OnSession)).is_ok());
+        assert!(validate_setup_future_usage_update(Some(FutureUsage::OffSession), None).is_ok());
+        assert!(validate_setup_future_usage_update(
+            Some(FutureUsage::OffSession),
+            Some(FutureUsage::OffSession)
+        )
+        .is_ok());
+        assert!(validate_setup_future_usage_update(
+            Some(FutureUsage::OffSession),
+            Some(FutureUsage::OnSession)
+        )
+        .is_err());
+        assert!(validate_setup_future_usage_update(
+            Some(FutureUsage::OnSession),
+            Some(FutureUsage::OffSession)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_skip_save_payment_method_prevents_saving() {
+        assert_eq!(
+            get_save_payment_method_action(false, false, false, false),
+            SavePaymentMethodAction::Save
+        );
+        assert_eq!(
+            get_save_payment_method_action(false, false, false, true),
+            SavePaymentMethodAction::Skip
+        );
+    }
+
+    #[test]
+    fn test_skip_save_payment_method_keeps_mandate_semantics() {
+        // Mandates created by the application need the saved payment method
+        assert_eq!(
+            get_save_payment_method_action(true, true, false, true),
+            SavePaymentMethodAction::LegacyMandate
+        );
+        // The connector mandate reference is still recorded when saving is skipped
+        assert_eq!(
+            get_save_payment_method_action(false, true, false, true),
+            SavePaymentMethodAction::ConnectorMandate { save: false }
+        );
+        assert_eq!(
+            get_save_payment_method_action(false, true, false, false),
+            SavePaymentMethodAction::ConnectorMandate { save: true }
+        );
+    }
+
+    #[test]
+    fn test_skip_save_payment_method_keeps_reused_payment_method() {
+        assert_eq!(
+            get_save_payment_method_action(false, false, true, true),
+            SavePaymentMethodAction::UseSavedPaymentMethod
+        );
+    }
+
+    #[test]
+    fn test_skip_save_payment_method_set_at_create_is_kept_on_confirm() {
+        assert_eq!(
+            get_skip_save_payment_method_on_confirm(Some(true), None),
+            Some(true)
+        );
+        assert_eq!(
+            get_skip_save_payment_method_on_confirm(Some(true), Some(false)),
+            Some(false)
+        );
+        assert_eq!(get_skip_save_payment_method_on_confirm(None, None), None);
+    }
+
+    #[test]
+    fn test_skip_save_payment_method_cannot_be_used_with_mandate_data() {
+        let request = api_models::payments::PaymentsRequest {
+            setup_future_usage: Some(storage_enums::FutureUsage::OffSession),
+            skip_save_payment_method: Some(true),
+            ..Default::default()
+        };
+        assert!(validate_skip_save_payment_method(&request).is_ok());
+
+        let request = api_models::payments::PaymentsRequest {
+            mandate_data: Some(api_models::payments::MandateData::default()),
+            ..request
+        };
+        assert!(validate_skip_save_payment_method(&request).is_err());
+    }
+}
+
+// This function will be removed after moving this functionality to server_wrap and using cache instead of config
+#[instrument(skip_all)]
+pub async fn insert_merchant_connector_creds_to_config(
+    db: &dyn StorageInterface,
+    merchant_id: &id_type::MerchantId,
+    merchant_connector_details: admin::MerchantConnectorDetailsWrap,
+) -> RouterResult<()> {
+    if let Some(encoded_data) = merchant_connector_details.encoded_data {
+        let redis = &db
+            .get_redis_conn()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to get redis connection")?;
+
+        let key =
+            merchant_id.get_creds_identifier_key(&merchant_connector_details.creds_identifier);
+
+        redis
+            .serialize_and_set_key_with_expiry(
+                key.as_str(),
+                &encoded_data.peek(),
+                consts::CONNECTOR_CREDS_TOKEN_TTL,
+            )
+            .await
+            .map_or_else(
+                |e| {
+                    Err(e
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("Failed to insert connector_creds to config"))
+                },
+                |_| Ok(()),
+            )
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub enum MerchantConnectorAccountType {
+    DbVal(domain::MerchantConnectorAccount),
+    CacheVal(api_models::admin::MerchantConnectorDetails),
+}
+
+impl MerchantConnectorAccountType {
+    pub fn get_metadata(&self) -> Option<masking::Secret<serde_json::Value>> {
+        match self {
+            Self::DbVal(val) => val.metadata.to_owned(),
+            Self::CacheVal(val) => val.metadata.to_owned(),
+        }
+    }
 
     pub fn get_connector_account_details(&self) -> serde_json::Value {
         match self {
@@ -3535,6 +5594,19 @@ impl MerchantConnectorAccountType {
         }
     }
 
+    /// Statement descriptor prefix configured under `statement_descriptor_prefix` in the
+    /// metadata of the merchant connector account
+    pub fn get_statement_descriptor_prefix(&self) -> Option<String> {
+        self.get_metadata().and_then(|metadata| {
+            metadata
+                .peek()
+                .get("statement_descriptor_prefix")
+                .and_then(serde_json::Value::as_str)
+                .filter(|prefix| !prefix.trim().is_empty())
+                .map(ToString::to_string)
+        })
+    }
+
     pub fn get_additional_merchant_data(
         &self,
     ) -> Option<Encryptable<masking::Secret<serde_json::Value>>> {
@@ -3958,6 +6030,18 @@ impl AttemptType {
             client_version: old_payment_attempt.client_version,
             customer_acceptance: old_payment_attempt.customer_acceptance,
             organization_id: old_payment_attempt.organization_id,
+            connector_risk_score: None,
+            connector_risk_decision: None,
+            connector_fee_amount: None,
+            connector_fee_currency: None,
+            device_fingerprint: old_payment_attempt.device_fingerprint,
+            three_ds_version: None,
+            three_ds_message_version: None,
+            request_challenge: old_payment_attempt.request_challenge,
+            tokenization_latency: old_payment_attempt.tokenization_latency,
+            card_funding_type: old_payment_attempt.card_funding_type,
+            liability_shift: old_payment_attempt.liability_shift,
+            connector_response_received_at: None,
             profile_id: old_payment_attempt.profile_id,
             shipping_cost: old_payment_attempt.shipping_cost,
             order_tax_amount: None,
@@ -4446,6 +6530,7 @@ pub async fn populate_bin_details_for_payment_method_create(
             card_holder_name: card_details.card_holder_name.clone(),
             card_number: card_details.card_number.clone(),
             nick_name: card_details.nick_name.clone(),
+            preferred_network: card_details.preferred_network.clone(),
         }
     } else {
         let card_info = card_isin
@@ -4468,6 +6553,7 @@ pub async fn populate_bin_details_for_payment_method_create(
                 card_holder_name: card_details.card_holder_name.clone(),
                 card_number: card_details.card_number.clone(),
                 nick_name: card_details.nick_name.clone(),
+                preferred_network: card_details.preferred_network.clone(),
             });
         card_info.unwrap_or_else(|| api::CardDetail {
             card_issuer: None,
@@ -4479,6 +6565,7 @@ pub async fn populate_bin_details_for_payment_method_create(
             card_holder_name: card_details.card_holder_name.clone(),
             card_number: card_details.card_number.clone(),
             nick_name: card_details.nick_name.clone(),
+            preferred_network: card_details.preferred_network.clone(),
         })
     }
 }
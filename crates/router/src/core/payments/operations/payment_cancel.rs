@@ -202,6 +202,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsCancelRequest>
             recurring_details: None,
             poll_config: None,
             tax_data: None,
+            should_return_payment_method_token: false,
+            matched_routing_rule: None,
+            confirm_response_masking_policy: None,
+            skip_save_payment_method: false,
         };
 
         let get_trackers_response = operations::GetTrackerResponse {
@@ -261,7 +265,8 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsCancelRequest> for
                 .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
         }
 
-        state
+        let previous_status = payment_data.payment_attempt.status;
+        let payment_attempt = state
             .store
             .update_payment_attempt_with_attempt_id(
                 payment_data.payment_attempt.clone(),
@@ -274,6 +279,12 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsCancelRequest> for
             )
             .await
             .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+        helpers::record_payment_attempt_status_transition(
+            &*state.store,
+            previous_status,
+            &payment_attempt,
+        )
+        .await;
         req_state
             .event_context
             .event(AuditEvent::new(AuditEventType::PaymentCancelled {
@@ -95,6 +95,8 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
 
         helpers::validate_status_with_capture_method(payment_intent.status, capture_method)?;
 
+        helpers::validate_attempt_status_for_capture(payment_attempt.status, capture_method)?;
+
         helpers::validate_amount_to_capture(
             payment_attempt.amount_capturable.get_amount_as_i64(),
             request
@@ -253,6 +255,10 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
             recurring_details: None,
             poll_config: None,
             tax_data: None,
+            should_return_payment_method_token: false,
+            matched_routing_rule: None,
+            confirm_response_masking_policy: None,
+            skip_save_payment_method: false,
         };
 
         let get_trackers_response = operations::GetTrackerResponse {
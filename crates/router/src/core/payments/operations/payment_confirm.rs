@@ -346,9 +346,15 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .attach_printable("Failed to convert order details to value")?
             .or(payment_intent.order_details);
 
-        payment_intent.setup_future_usage = request
-            .setup_future_usage
-            .or(payment_intent.setup_future_usage);
+        payment_intent.setup_future_usage = helpers::get_setup_future_usage_on_confirm(
+            payment_intent.setup_future_usage,
+            request.setup_future_usage,
+        )?;
+
+        payment_intent.skip_save_payment_method = helpers::get_skip_save_payment_method_on_confirm(
+            payment_intent.skip_save_payment_method,
+            request.skip_save_payment_method,
+        );
 
         let browser_info = request
             .browser_info
@@ -382,6 +388,13 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         )?;
 
         payment_attempt.browser_info = browser_info;
+        payment_attempt.device_fingerprint = request
+            .device_fingerprint
+            .clone()
+            .or(payment_attempt.device_fingerprint);
+        payment_attempt.request_challenge = request
+            .request_challenge
+            .or(payment_attempt.request_challenge);
 
         payment_attempt.payment_experience = request
             .payment_experience
@@ -410,6 +423,29 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 .or(customer_details.customer_id.as_ref()),
         )?;
 
+        if let Some((spending_limit, customer_id)) = business_profile.customer_spending_limit.zip(
+            payment_intent
+                .customer_id
+                .as_ref()
+                .or(customer_details.customer_id.as_ref()),
+        ) {
+            let captured_amount = helpers::get_customer_captured_amount(
+                state,
+                merchant_account,
+                customer_id,
+                currency,
+                business_profile
+                    .customer_spending_limit_window_in_secs
+                    .unwrap_or(crate::consts::DEFAULT_CUSTOMER_SPENDING_LIMIT_WINDOW),
+            )
+            .await?;
+            helpers::validate_customer_spending_limit(
+                captured_amount,
+                payment_attempt.get_total_amount(),
+                spending_limit,
+            )?;
+        }
+
         let creds_identifier = request
             .merchant_connector_details
             .as_ref()
@@ -615,6 +651,40 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             (None, payment_method_info)
         };
 
+        // Store the billing address saved with the payment method when the request does not
+        // carry one and the attempt does not have one yet
+        let payment_method_billing = match payment_method_billing {
+            Some(payment_method_billing) => Some(payment_method_billing),
+            None => {
+                let saved_payment_method_billing = helpers::get_saved_payment_method_billing(
+                    request
+                        .payment_method_data
+                        .as_ref()
+                        .and_then(|pmd| pmd.billing.as_ref()),
+                    payment_method_info.as_ref().and_then(|payment_method| {
+                        payment_method
+                            .payment_method_billing_address
+                            .clone()
+                            .map(|billing| billing.into_inner().expose())
+                    }),
+                )?;
+                helpers::create_or_update_address_for_payment_by_request(
+                    state,
+                    saved_payment_method_billing.as_ref(),
+                    None,
+                    merchant_id,
+                    payment_intent
+                        .customer_id
+                        .as_ref()
+                        .or(customer_details.customer_id.as_ref()),
+                    key_store,
+                    &payment_intent.payment_id,
+                    storage_scheme,
+                )
+                .await?
+            }
+        };
+
         // The operation merges mandate data from both request and payment_attempt
         let setup_mandate = mandate_data.map(|mut sm| {
             sm.mandate_type = payment_attempt.mandate_details.clone().or(sm.mandate_type);
@@ -695,6 +765,32 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 _ => None,
             });
 
+        // If an authentication was performed in a prior call, link it to this payment
+        let authentication = request
+            .authentication_id
+            .clone()
+            .async_map(|authentication_id| async move {
+                state
+                    .store
+                    .find_authentication_by_merchant_id_authentication_id(
+                        merchant_id,
+                        authentication_id.clone(),
+                    )
+                    .await
+                    .to_not_found_response(errors::ApiErrorResponse::AuthenticationNotFound {
+                        id: authentication_id,
+                    })
+            })
+            .await
+            .transpose()?;
+
+        if let Some(authentication) = authentication.as_ref() {
+            helpers::validate_linked_authentication(authentication, &payment_intent)?;
+            payment_attempt.authentication_id = Some(authentication.authentication_id.clone());
+        }
+
+        let skip_save_payment_method = payment_intent.skip_save_payment_method.unwrap_or(false);
+
         let payment_data = PaymentData {
             flow: PhantomData,
             payment_intent,
@@ -730,10 +826,17 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             payment_link_data: None,
             incremental_authorization_details: None,
             authorizations: vec![],
-            authentication: None,
+            authentication,
             recurring_details,
             poll_config: None,
             tax_data: None,
+            should_return_payment_method_token: helpers::should_return_payment_method_token(
+                business_profile.is_payment_method_token_response_enabled,
+                auth_flow,
+            ),
+            matched_routing_rule: None,
+            confirm_response_masking_policy: business_profile.confirm_response_masking_policy,
+            skip_save_payment_method,
         };
 
         let get_trackers_response = operations::GetTrackerResponse {
@@ -871,6 +974,10 @@ impl<F: Clone + Send> Domain<F, api::PaymentsRequest, PaymentData<F>> for Paymen
         key_store: &domain::MerchantKeyStore,
         mandate_type: Option<api_models::payments::MandateTransactionType>,
     ) -> CustomResult<(), errors::ApiErrorResponse> {
+        // Authentication linked from a prior call has already been completed
+        if payment_data.authentication.is_some() {
+            return Ok(());
+        }
         let external_authentication_flow =
             helpers::get_payment_external_authentication_flow_during_confirm(
                 state,
@@ -1084,6 +1191,13 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
         let frm_message = payment_data.frm_message.clone();
         let capture_method = payment_data.payment_attempt.capture_method;
 
+        // A completed authentication is consumed by the authorization of this payment
+        payment_data.authentication = payment_data
+            .authentication
+            .async_map(|authentication| helpers::consume_authentication(state, authentication))
+            .await
+            .transpose()?;
+
         let default_status_result = (
             storage_enums::IntentStatus::Processing,
             storage_enums::AttemptStatus::Pending,
@@ -1171,6 +1285,26 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
             .transpose()?
             .flatten();
 
+        // A retry with a payment method that is not a card has no funding type, the one of the
+        // previous card is only kept when no payment method data is passed in this confirm
+        if additional_pm_data.is_some() {
+            payment_data.payment_attempt.card_funding_type =
+                helpers::get_card_funding_type(additional_pm_data.as_ref());
+        }
+        payment_data.payment_attempt.liability_shift = payment_data
+            .authentication
+            .as_ref()
+            .and_then(|authentication| {
+                let card_network = match additional_pm_data.as_ref() {
+                    Some(api_models::payments::AdditionalPaymentData::Card(card_info)) => {
+                        card_info.card_network.as_ref()
+                    }
+                    _ => None,
+                };
+                helpers::get_liability_shift(card_network, authentication.eci.as_deref())
+            })
+            .or(payment_data.payment_attempt.liability_shift);
+
         let encoded_additional_pm_data = additional_pm_data
             .as_ref()
             .map(Encode::encode_to_value)
@@ -1268,6 +1402,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
         let m_error_code = error_code.clone();
         let m_error_message = error_message.clone();
         let m_fingerprint_id = payment_data.payment_attempt.fingerprint_id.clone();
+        let m_device_fingerprint = payment_data.payment_attempt.device_fingerprint.clone();
         let m_db = state.clone().store;
         let surcharge_amount = payment_data
             .surcharge_details
@@ -1290,6 +1425,8 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
             ),
             None => (None, None, None),
         };
+        let (three_ds_version, three_ds_message_version) =
+            helpers::get_three_ds_version_details(payment_data.authentication.as_ref());
 
         let shipping_cost = payment_data.payment_intent.shipping_cost;
 
@@ -1318,46 +1455,62 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
 
         let payment_attempt_fut = tokio::spawn(
             async move {
-                m_db.update_payment_attempt_with_attempt_id(
-                    m_payment_data_payment_attempt,
-                    storage::PaymentAttemptUpdate::ConfirmUpdate {
-                        amount: payment_data.payment_attempt.amount,
-                        currency: payment_data.currency,
-                        status: attempt_status,
-                        payment_method,
-                        authentication_type,
-                        capture_method: m_capture_method,
-                        browser_info: m_browser_info,
-                        connector: m_connector,
-                        payment_token: m_payment_token,
-                        payment_method_data: m_additional_pm_data,
-                        payment_method_type,
-                        payment_experience,
-                        business_sub_label: m_business_sub_label,
-                        straight_through_algorithm: m_straight_through_algorithm,
-                        error_code: m_error_code,
-                        error_message: m_error_message,
-                        amount_capturable: Some(authorized_amount),
-                        updated_by: storage_scheme.to_string(),
-                        merchant_connector_id,
-                        surcharge_amount,
-                        tax_amount,
-                        external_three_ds_authentication_attempted,
-                        authentication_connector,
-                        authentication_id,
-                        payment_method_billing_address_id,
-                        fingerprint_id: m_fingerprint_id,
-                        payment_method_id: m_payment_method_id,
-                        client_source,
-                        client_version,
-                        customer_acceptance: payment_data.payment_attempt.customer_acceptance,
-                        shipping_cost,
-                        order_tax_amount,
-                    },
-                    storage_scheme,
+                let previous_status = m_payment_data_payment_attempt.status;
+                let payment_attempt = m_db
+                    .update_payment_attempt_with_attempt_id(
+                        m_payment_data_payment_attempt,
+                        storage::PaymentAttemptUpdate::ConfirmUpdate {
+                            amount: payment_data.payment_attempt.amount,
+                            currency: payment_data.currency,
+                            status: attempt_status,
+                            payment_method,
+                            authentication_type,
+                            capture_method: m_capture_method,
+                            browser_info: m_browser_info,
+                            connector: m_connector,
+                            payment_token: m_payment_token,
+                            payment_method_data: m_additional_pm_data,
+                            payment_method_type,
+                            payment_experience,
+                            business_sub_label: m_business_sub_label,
+                            straight_through_algorithm: m_straight_through_algorithm,
+                            error_code: m_error_code,
+                            error_message: m_error_message,
+                            amount_capturable: Some(authorized_amount),
+                            updated_by: storage_scheme.to_string(),
+                            merchant_connector_id,
+                            surcharge_amount,
+                            tax_amount,
+                            external_three_ds_authentication_attempted,
+                            authentication_connector,
+                            authentication_id,
+                            payment_method_billing_address_id,
+                            fingerprint_id: m_fingerprint_id,
+                            payment_method_id: m_payment_method_id,
+                            client_source,
+                            client_version,
+                            customer_acceptance: payment_data.payment_attempt.customer_acceptance,
+                            shipping_cost,
+                            order_tax_amount,
+                            device_fingerprint: m_device_fingerprint,
+                            three_ds_version,
+                            three_ds_message_version,
+                            request_challenge: payment_data.payment_attempt.request_challenge,
+                            tokenization_latency: payment_data.payment_attempt.tokenization_latency,
+                            card_funding_type: payment_data.payment_attempt.card_funding_type,
+                            liability_shift: payment_data.payment_attempt.liability_shift,
+                        },
+                        storage_scheme,
+                    )
+                    .await
+                    .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+                helpers::record_payment_attempt_status_transition(
+                    &*m_db,
+                    previous_status,
+                    &payment_attempt,
                 )
-                .map(|x| x.to_not_found_response(errors::ApiErrorResponse::PaymentNotFound))
-                .await
+                .await;
+                Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(payment_attempt)
             }
             .in_current_span(),
         );
@@ -1397,6 +1550,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
         let key_manager_state = state.into();
         let is_payment_processor_token_flow =
             payment_data.payment_intent.is_payment_processor_token_flow;
+        let skip_save_payment_method = payment_data.payment_intent.skip_save_payment_method;
 
         let payment_intent_fut = tokio::spawn(
             async move {
@@ -1431,6 +1585,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                         shipping_details,
                         is_payment_processor_token_flow,
                         tax_details: None,
+                        skip_save_payment_method,
                     })),
                     &m_key_store,
                     storage_scheme,
@@ -1511,6 +1666,10 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest, PaymentData<F>> f
     ) -> RouterResult<(PaymentConfirmOperation<'b, F>, operations::ValidateResult)> {
         helpers::validate_customer_information(request)?;
 
+        helpers::validate_device_fingerprint(request.device_fingerprint.as_deref())?;
+
+        helpers::validate_skip_save_payment_method(request)?;
+
         if let Some(amount) = request.amount {
             helpers::validate_max_amount(amount)?;
         }
@@ -1524,6 +1683,14 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest, PaymentData<F>> f
 
         helpers::validate_payment_method_fields_present(request)?;
 
+        if let Some(connector_metadata) = request
+            .get_connector_metadata_as_value()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Error converting connector_metadata to Value")?
+        {
+            helpers::validate_connector_metadata(&connector_metadata)?;
+        }
+
         let _mandate_type =
             helpers::validate_mandate(request, payments::is_operation_confirm(self))?;
 
@@ -1562,3 +1729,251 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest, PaymentData<F>> f
         ))
     }
 }
+
+#[cfg(all(test, feature = "v1"))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{borrow::Cow, sync::Arc};
+
+    use hyperswitch_domain_models::payments::{
+        payment_intent::PaymentIntentInterface, PaymentIntent,
+    };
+    use masking::Secret;
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::{configs::settings::Settings, db::StorageImpl, routes};
+
+    async fn get_session_state() -> SessionState {
+        let conf = Settings::new().unwrap();
+        let tx: oneshot::Sender<()> = oneshot::channel().0;
+        let app_state = Box::pin(routes::AppState::with_storage(
+            conf,
+            StorageImpl::Mock,
+            tx,
+            Box::new(services::MockApiClient),
+        ))
+        .await;
+        Arc::new(app_state)
+            .get_session_state("public", || {})
+            .unwrap()
+    }
+
+    fn get_payment_intent(
+        payment_id: &common_utils::id_type::PaymentId,
+        merchant_id: &common_utils::id_type::MerchantId,
+        setup_future_usage: Option<storage_enums::FutureUsage>,
+    ) -> PaymentIntent {
+        PaymentIntent {
+            payment_id: payment_id.clone(),
+            merchant_id: merchant_id.clone(),
+            status: storage_enums::IntentStatus::RequiresConfirmation,
+            amount: common_utils::types::MinorUnit::new(200),
+            currency: Some(storage_enums::Currency::USD),
+            amount_captured: None,
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            last_synced: None,
+            setup_future_usage,
+            fingerprint_id: None,
+            off_session: None,
+            client_secret: Some("1".to_string()),
+            active_attempt: hyperswitch_domain_models::RemoteStorageObject::ForeignID(
+                "nopes".to_string(),
+            ),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            payment_link_id: None,
+            profile_id: Some(common_utils::generate_profile_id_of_default_length()),
+            merchant_decision: None,
+            payment_confirm_source: None,
+            surcharge_applicable: None,
+            updated_by: storage_enums::MerchantStorageScheme::PostgresOnly.to_string(),
+            request_incremental_authorization: Some(
+                common_enums::RequestIncrementalAuthorization::default(),
+            ),
+            incremental_authorization_allowed: None,
+            authorization_count: None,
+            session_expiry: Some(common_utils::date_time::now().saturating_add(
+                time::Duration::seconds(crate::consts::DEFAULT_SESSION_EXPIRY),
+            )),
+            request_external_three_ds_authentication: None,
+            charges: None,
+            frm_metadata: None,
+            customer_details: None,
+            billing_details: None,
+            merchant_order_reference_id: None,
+            shipping_details: None,
+            is_payment_processor_token_flow: None,
+            organization_id: common_utils::id_type::OrganizationId::default(),
+            shipping_cost: None,
+            tax_details: None,
+            skip_external_tax_calculation: None,
+            skip_save_payment_method: None,
+        }
+    }
+
+    /// Confirms the stored intent with the requested `setup_future_usage` the way
+    /// `PaymentConfirm` does and reads the intent back from the store.
+    async fn confirm_and_find_payment_intent(
+        state: &SessionState,
+        current: Option<storage_enums::FutureUsage>,
+        requested: Option<storage_enums::FutureUsage>,
+    ) -> RouterResult<PaymentIntent> {
+        let merchant_id =
+            common_utils::id_type::MerchantId::try_from(Cow::from("merchant_1")).unwrap();
+        let payment_id =
+            common_utils::id_type::PaymentId::try_from(Cow::from("pay_future_usage")).unwrap();
+        let key_store = domain::MerchantKeyStore {
+            merchant_id: merchant_id.clone(),
+            key: common_utils::crypto::Encryptable::new(
+                Secret::new(services::generate_aes256_key().unwrap().to_vec()),
+                Secret::new(Vec::new()),
+            ),
+            created_at: common_utils::date_time::now(),
+        };
+        let key_manager_state = &state.into();
+        let storage_scheme = storage_enums::MerchantStorageScheme::PostgresOnly;
+
+        let mut payment_intent = state
+            .store
+            .insert_payment_intent(
+                key_manager_state,
+                get_payment_intent(&payment_id, &merchant_id, current),
+                &key_store,
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+
+        payment_intent.setup_future_usage = helpers::get_setup_future_usage_on_confirm(
+            payment_intent.setup_future_usage,
+            requested,
+        )?;
+
+        state
+            .store
+            .update_payment_intent(
+                key_manager_state,
+                payment_intent.clone(),
+                storage::PaymentIntentUpdate::Update(Box::new(PaymentIntentUpdateFields {
+                    amount: payment_intent.amount,
+                    currency: storage_enums::Currency::USD,
+                    setup_future_usage: payment_intent.setup_future_usage,
+                    status: storage_enums::IntentStatus::Processing,
+                    customer_id: None,
+                    shipping_address_id: None,
+                    billing_address_id: None,
+                    return_url: None,
+                    business_country: None,
+                    business_label: None,
+                    description: None,
+                    statement_descriptor_name: None,
+                    statement_descriptor_suffix: None,
+                    order_details: None,
+                    metadata: None,
+                    payment_confirm_source: None,
+                    updated_by: storage_scheme.to_string(),
+                    fingerprint_id: None,
+                    session_expiry: payment_intent.session_expiry,
+                    request_external_three_ds_authentication: None,
+                    frm_metadata: None,
+                    customer_details: None,
+                    merchant_order_reference_id: None,
+                    billing_details: None,
+                    shipping_details: None,
+                    is_payment_processor_token_flow: None,
+                    tax_details: None,
+                    skip_save_payment_method: None,
+                })),
+                &key_store,
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+
+        Ok(state
+            .store
+            .find_payment_intent_by_payment_id_merchant_id(
+                key_manager_state,
+                &payment_id,
+                &merchant_id,
+                &key_store,
+                storage_scheme,
+            )
+            .await
+            .unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_setup_future_usage_set_on_confirm_is_persisted() {
+        let state = get_session_state().await;
+
+        let payment_intent = confirm_and_find_payment_intent(
+            &state,
+            None,
+            Some(storage_enums::FutureUsage::OffSession),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            payment_intent.setup_future_usage,
+            Some(storage_enums::FutureUsage::OffSession)
+        );
+        assert_eq!(
+            payment_intent.status,
+            storage_enums::IntentStatus::Processing
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_future_usage_on_the_intent_is_kept_on_confirm() {
+        let state = get_session_state().await;
+
+        let payment_intent = confirm_and_find_payment_intent(
+            &state,
+            Some(storage_enums::FutureUsage::OnSession),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            payment_intent.setup_future_usage,
+            Some(storage_enums::FutureUsage::OnSession)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_future_usage_on_the_intent_cannot_be_changed_on_confirm() {
+        let state = get_session_state().await;
+
+        let result = confirm_and_find_payment_intent(
+            &state,
+            Some(storage_enums::FutureUsage::OffSession),
+            Some(storage_enums::FutureUsage::OnSession),
+        )
+        .await;
+
+        assert!(matches!(
+            result.unwrap_err().current_context(),
+            errors::ApiErrorResponse::PreconditionFailed { .. }
+        ));
+    }
+}
@@ -75,7 +75,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         request: &api::PaymentsRequest,
         merchant_account: &domain::MerchantAccount,
         merchant_key_store: &domain::MerchantKeyStore,
-        _auth_flow: services::AuthFlow,
+        auth_flow: services::AuthFlow,
         header_payload: &api::HeaderPayload,
     ) -> RouterResult<operations::GetTrackerResponse<'a, F, api::PaymentsRequest, PaymentData<F>>>
     {
@@ -323,6 +323,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             merchant_key_store,
             profile_id,
             &customer_acceptance,
+            header_payload,
         )
         .await?;
 
@@ -354,6 +355,21 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 payment_id: payment_id.clone(),
             })?;
 
+        #[cfg(feature = "v1")]
+        if let Some(auto_cancel_after) = business_profile
+            .auto_cancel_unconfirmed_after
+            .filter(|_| request.confirm != Some(true))
+        {
+            payments::add_auto_cancel_unconfirmed_payment_task(
+                db,
+                &payment_intent,
+                auto_cancel_after,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while adding auto cancel task to process tracker")?;
+        }
+
         #[cfg(feature = "v2")]
         let payment_attempt = db
             .insert_payment_attempt(
@@ -562,6 +578,18 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             recurring_details,
             poll_config: None,
             tax_data: None,
+            should_return_payment_method_token: request.confirm == Some(true)
+                && helpers::should_return_payment_method_token(
+                    business_profile.is_payment_method_token_response_enabled,
+                    auth_flow,
+                ),
+            matched_routing_rule: None,
+            confirm_response_masking_policy: request
+                .confirm
+                .unwrap_or(false)
+                .then_some(business_profile.confirm_response_masking_policy)
+                .flatten(),
+            skip_save_payment_method: request.skip_save_payment_method.unwrap_or(false),
         };
 
         let get_trackers_response = operations::GetTrackerResponse {
@@ -806,6 +834,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
             .clone();
         let authorized_amount = payment_data.payment_attempt.amount;
         let merchant_connector_id = payment_data.payment_attempt.merchant_connector_id.clone();
+        let tokenization_latency = payment_data.payment_attempt.tokenization_latency;
 
         let surcharge_amount = payment_data
             .surcharge_details
@@ -832,6 +861,7 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     tax_amount,
                     updated_by: storage_scheme.to_string(),
                     merchant_connector_id,
+                    tokenization_latency,
                 },
                 storage_scheme,
             )
@@ -894,6 +924,10 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest, PaymentData<F>> f
     ) -> RouterResult<(PaymentCreateOperation<'b, F>, operations::ValidateResult)> {
         helpers::validate_customer_information(request)?;
 
+        helpers::validate_device_fingerprint(request.device_fingerprint.as_deref())?;
+
+        helpers::validate_skip_save_payment_method(request)?;
+
         if let Some(amount) = request.amount {
             helpers::validate_max_amount(amount)?;
         }
@@ -1011,6 +1045,7 @@ impl PaymentCreate {
         _key_store: &domain::MerchantKeyStore,
         profile_id: common_utils::id_type::ProfileId,
         customer_acceptance: &Option<payments::CustomerAcceptance>,
+        header_payload: &api::HeaderPayload,
     ) -> RouterResult<(
         storage::PaymentAttemptNew,
         Option<api_models::payments::AdditionalPaymentData>,
@@ -1039,6 +1074,7 @@ impl PaymentCreate {
         _key_store: &domain::MerchantKeyStore,
         profile_id: common_utils::id_type::ProfileId,
         customer_acceptance: &Option<payments::CustomerAcceptance>,
+        header_payload: &api::HeaderPayload,
     ) -> RouterResult<(
         storage::PaymentAttemptNew,
         Option<api_models::payments::AdditionalPaymentData>,
@@ -1204,8 +1240,8 @@ impl PaymentCreate {
                 authentication_connector: None,
                 authentication_id: None,
                 charge_id: None,
-                client_source: None,
-                client_version: None,
+                client_source: header_payload.client_source.clone(),
+                client_version: header_payload.client_version.clone(),
                 customer_acceptance: customer_acceptance
                     .clone()
                     .map(|customer_acceptance| customer_acceptance.encode_to_value())
@@ -1214,6 +1250,19 @@ impl PaymentCreate {
                     .attach_printable("Failed to serialize customer_acceptance")?
                     .map(Secret::new),
                 organization_id: organization_id.clone(),
+                connector_risk_score: None,
+                connector_risk_decision: None,
+                connector_fee_amount: None,
+                connector_fee_currency: None,
+                device_fingerprint: request.device_fingerprint.clone(),
+                three_ds_version: None,
+                three_ds_message_version: None,
+                request_challenge: request.request_challenge,
+                tokenization_latency: None,
+                card_funding_type: helpers::get_card_funding_type(additional_pm_data.as_ref()),
+                liability_shift: None,
+                connector_payment_method_token: None,
+                connector_response_received_at: None,
                 profile_id,
                 shipping_cost: request.shipping_cost,
                 order_tax_amount: None,
@@ -1421,6 +1470,7 @@ impl PaymentCreate {
             shipping_cost: request.shipping_cost,
             tax_details: None,
             skip_external_tax_calculation,
+            skip_save_payment_method: request.skip_save_payment_method,
         })
     }
 
@@ -1529,3 +1579,121 @@ async fn create_payment_link(
         payment_link_id: payment_link_db.payment_link_id,
     }))
 }
+
+#[cfg(all(test, feature = "v1", not(feature = "payment_methods_v2")))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::{borrow::Cow, sync::Arc};
+
+    use hyperswitch_domain_models::payments::payment_attempt::PaymentAttemptInterface;
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::{configs::settings::Settings, db::StorageImpl, routes};
+
+    async fn get_session_state() -> SessionState {
+        let conf = Settings::new().unwrap();
+        let tx: oneshot::Sender<()> = oneshot::channel().0;
+        let app_state = Box::pin(routes::AppState::with_storage(
+            conf,
+            StorageImpl::Mock,
+            tx,
+            Box::new(services::MockApiClient),
+        ))
+        .await;
+        Arc::new(app_state)
+            .get_session_state("public", || {})
+            .unwrap()
+    }
+
+    async fn create_and_find_payment_attempt(
+        state: &SessionState,
+        payment_id: &str,
+        header_payload: &api::HeaderPayload,
+    ) -> PaymentAttempt {
+        let merchant_id =
+            common_utils::id_type::MerchantId::try_from(Cow::from("merchant_1")).unwrap();
+        let payment_id =
+            common_utils::id_type::PaymentId::try_from(Cow::from(payment_id.to_string())).unwrap();
+        let key_store = domain::MerchantKeyStore {
+            merchant_id: merchant_id.clone(),
+            key: common_utils::crypto::Encryptable::new(
+                Secret::new(services::generate_aes256_key().unwrap().to_vec()),
+                Secret::new(Vec::new()),
+            ),
+            created_at: common_utils::date_time::now(),
+        };
+
+        let (payment_attempt_new, _) = PaymentCreate::make_payment_attempt(
+            &payment_id,
+            &merchant_id,
+            &common_utils::id_type::OrganizationId::default(),
+            (api::Amount::from(MinorUnit::new(100)), enums::Currency::USD),
+            None,
+            None,
+            &api::PaymentsRequest::default(),
+            None,
+            state,
+            None,
+            &None,
+            &key_store,
+            common_utils::generate_profile_id_of_default_length(),
+            &None,
+            header_payload,
+        )
+        .await
+        .unwrap();
+
+        let payment_attempt = state
+            .store
+            .insert_payment_attempt(
+                payment_attempt_new,
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        state
+            .store
+            .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+                &payment_id,
+                &merchant_id,
+                &payment_attempt.attempt_id,
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_client_headers_are_persisted_on_the_created_attempt() {
+        let state = get_session_state().await;
+        let header_payload = api::HeaderPayload {
+            client_source: Some("ios-sdk".to_string()),
+            client_version: Some("0.1.24".to_string()),
+            ..Default::default()
+        };
+
+        let payment_attempt =
+            create_and_find_payment_attempt(&state, "pay_client_headers", &header_payload).await;
+
+        assert_eq!(payment_attempt.client_source.as_deref(), Some("ios-sdk"));
+        assert_eq!(payment_attempt.client_version.as_deref(), Some("0.1.24"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_client_headers_are_persisted_as_none() {
+        let state = get_session_state().await;
+
+        let payment_attempt = create_and_find_payment_attempt(
+            &state,
+            "pay_no_client_headers",
+            &api::HeaderPayload::default(),
+        )
+        .await;
+
+        assert_eq!(payment_attempt.client_source, None);
+        assert_eq!(payment_attempt.client_version, None);
+    }
+}
@@ -188,6 +188,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, PaymentsCancelRequest> for P
             recurring_details: None,
             poll_config: None,
             tax_data: None,
+            should_return_payment_method_token: false,
+            matched_routing_rule: None,
+            confirm_response_masking_policy: None,
+            skip_save_payment_method: false,
         };
 
         let get_trackers_response = operations::GetTrackerResponse {
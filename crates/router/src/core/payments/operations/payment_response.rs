@@ -208,130 +208,170 @@ impl<F: Send + Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsAuthor
                 Some(enums::FutureUsage::OffSession)
             );
 
-        if is_legacy_mandate {
-            // Mandate is created on the application side and at the connector.
-            let tokenization::SavePaymentMethodDataResponse {
-                payment_method_id, ..
-            } = save_payment_call_future.await?;
-
-            let mandate_id = mandate::mandate_procedure(
-                state,
-                resp,
-                &customer_id.clone(),
-                payment_method_id.clone(),
-                merchant_connector_id.clone(),
-                merchant_account.storage_scheme,
-                payment_data.payment_intent.get_id(),
-            )
-            .await?;
-            payment_data.payment_attempt.payment_method_id = payment_method_id;
-            payment_data.payment_attempt.mandate_id = mandate_id;
-
-            Ok(())
-        } else if is_connector_mandate {
-            // The mandate is created on connector's end.
-            let tokenization::SavePaymentMethodDataResponse {
-                payment_method_id,
-                mandate_reference_id,
-                ..
-            } = save_payment_call_future.await?;
-
-            payment_data.payment_attempt.payment_method_id = payment_method_id;
-
-            payment_data.set_mandate_id(api_models::payments::MandateIds {
-                mandate_id: None,
-                mandate_reference_id,
-            });
-            Ok(())
-        } else if should_avoid_saving {
-            if let Some(pm_info) = &payment_data.payment_method_info {
-                payment_data.payment_attempt.payment_method_id = Some(pm_info.get_id().clone());
-            };
-            Ok(())
-        } else {
-            // Save card flow
-            let save_payment_data = tokenization::SavePaymentMethodData::from(resp);
-            let merchant_account = merchant_account.clone();
-            let key_store = key_store.clone();
-            let state = state.clone();
-            let customer_id = payment_data.payment_intent.customer_id.clone();
-
-            let merchant_connector_id = payment_data.payment_attempt.merchant_connector_id.clone();
-            let payment_attempt = payment_data.payment_attempt.clone();
-
-            let business_profile = business_profile.clone();
-
-            let amount = resp.request.amount;
-            let currency = resp.request.currency;
-            let payment_method_type = resp.request.payment_method_type;
-            let storage_scheme = merchant_account.clone().storage_scheme;
-            let payment_method_billing_address = payment_method_billing_address.cloned();
-
-            logger::info!("Call to save_payment_method in locker");
-            let _task_handle = tokio::spawn(
-                async move {
-                    logger::info!("Starting async call to save_payment_method in locker");
-
-                    let result = Box::pin(tokenization::save_payment_method(
-                        &state,
-                        connector_name,
-                        merchant_connector_id,
-                        save_payment_data,
-                        customer_id,
-                        &merchant_account,
-                        payment_method_type,
-                        &key_store,
-                        Some(amount),
-                        Some(currency),
-                        billing_name,
-                        payment_method_billing_address.as_ref(),
-                        &business_profile,
-                    ))
-                    .await;
+        match payments_helpers::get_save_payment_method_action(
+            is_legacy_mandate,
+            is_connector_mandate,
+            should_avoid_saving,
+            payment_data.skip_save_payment_method,
+        ) {
+            payments_helpers::SavePaymentMethodAction::LegacyMandate => {
+                // Mandate is created on the application side and at the connector.
+                let tokenization::SavePaymentMethodDataResponse {
+                    payment_method_id, ..
+                } = save_payment_call_future.await?;
+
+                let mandate_id = mandate::mandate_procedure(
+                    state,
+                    resp,
+                    &customer_id.clone(),
+                    payment_method_id.clone(),
+                    merchant_connector_id.clone(),
+                    merchant_account.storage_scheme,
+                    payment_data.payment_intent.get_id(),
+                )
+                .await?;
+                payment_data.payment_attempt.payment_method_id = payment_method_id;
+                payment_data.payment_attempt.mandate_id = mandate_id;
 
-                    if let Err(err) = result {
-                        logger::error!("Asynchronously saving card in locker failed : {:?}", err);
-                    } else if let Ok(tokenization::SavePaymentMethodDataResponse {
+                Ok(())
+            }
+            payments_helpers::SavePaymentMethodAction::ConnectorMandate { save } => {
+                // The mandate is created on connector's end.
+                let mandate_reference_id = if save {
+                    let tokenization::SavePaymentMethodDataResponse {
                         payment_method_id,
+                        mandate_reference_id,
                         ..
-                    }) = result
-                    {
-                        let payment_attempt_update =
-                            storage::PaymentAttemptUpdate::PaymentMethodDetailsUpdate {
-                                payment_method_id,
-                                updated_by: storage_scheme.clone().to_string(),
-                            };
+                    } = save_payment_call_future.await?;
 
-                        #[cfg(feature = "v1")]
-                        let respond = state
-                            .store
-                            .update_payment_attempt_with_attempt_id(
-                                payment_attempt,
-                                payment_attempt_update,
-                                storage_scheme,
+                    payment_data.payment_attempt.payment_method_id = payment_method_id;
+                    mandate_reference_id
+                } else {
+                    logger::info!(
+                        "Skipping saving the payment method as requested by the merchant"
+                    );
+                    match &resp.response {
+                        Ok(response) => {
+                            let (connector_mandate_id, mandate_metadata) =
+                                tokenization::get_connector_mandate_reference(response);
+                            tokenization::get_mandate_reference_id(
+                                state,
+                                merchant_account,
+                                connector_mandate_id,
+                                mandate_metadata,
+                                None,
                             )
-                            .await;
+                            .await
+                        }
+                        Err(_) => None,
+                    }
+                };
 
-                        #[cfg(feature = "v2")]
-                        let respond = state
-                            .store
-                            .update_payment_attempt_with_attempt_id(
-                                &(&state).into(),
-                                &key_store,
-                                payment_attempt,
-                                payment_attempt_update,
-                                storage_scheme,
-                            )
-                            .await;
+                payment_data.set_mandate_id(api_models::payments::MandateIds {
+                    mandate_id: None,
+                    mandate_reference_id,
+                });
+                Ok(())
+            }
+            payments_helpers::SavePaymentMethodAction::UseSavedPaymentMethod => {
+                if let Some(pm_info) = &payment_data.payment_method_info {
+                    payment_data.payment_attempt.payment_method_id = Some(pm_info.get_id().clone());
+                };
+                Ok(())
+            }
+            payments_helpers::SavePaymentMethodAction::Skip => {
+                logger::info!("Skipping saving the payment method as requested by the merchant");
+                Ok(())
+            }
+            payments_helpers::SavePaymentMethodAction::Save => {
+                // Save card flow
+                let save_payment_data = tokenization::SavePaymentMethodData::from(resp);
+                let merchant_account = merchant_account.clone();
+                let key_store = key_store.clone();
+                let state = state.clone();
+                let customer_id = payment_data.payment_intent.customer_id.clone();
+
+                let merchant_connector_id =
+                    payment_data.payment_attempt.merchant_connector_id.clone();
+                let payment_attempt = payment_data.payment_attempt.clone();
+
+                let business_profile = business_profile.clone();
+
+                let amount = resp.request.amount;
+                let currency = resp.request.currency;
+                let payment_method_type = resp.request.payment_method_type;
+                let storage_scheme = merchant_account.clone().storage_scheme;
+                let payment_method_billing_address = payment_method_billing_address.cloned();
+
+                logger::info!("Call to save_payment_method in locker");
+                let _task_handle = tokio::spawn(
+                    async move {
+                        logger::info!("Starting async call to save_payment_method in locker");
+
+                        let result = Box::pin(tokenization::save_payment_method(
+                            &state,
+                            connector_name,
+                            merchant_connector_id,
+                            save_payment_data,
+                            customer_id,
+                            &merchant_account,
+                            payment_method_type,
+                            &key_store,
+                            Some(amount),
+                            Some(currency),
+                            billing_name,
+                            payment_method_billing_address.as_ref(),
+                            &business_profile,
+                        ))
+                        .await;
+
+                        if let Err(err) = result {
+                            logger::error!(
+                                "Asynchronously saving card in locker failed : {:?}",
+                                err
+                            );
+                        } else if let Ok(tokenization::SavePaymentMethodDataResponse {
+                            payment_method_id,
+                            ..
+                        }) = result
+                        {
+                            let payment_attempt_update =
+                                storage::PaymentAttemptUpdate::PaymentMethodDetailsUpdate {
+                                    payment_method_id,
+                                    updated_by: storage_scheme.clone().to_string(),
+                                };
 
-                        if let Err(err) = respond {
-                            logger::error!("Error updating payment attempt: {:?}", err);
-                        };
+                            #[cfg(feature = "v1")]
+                            let respond = state
+                                .store
+                                .update_payment_attempt_with_attempt_id(
+                                    payment_attempt,
+                                    payment_attempt_update,
+                                    storage_scheme,
+                                )
+                                .await;
+
+                            #[cfg(feature = "v2")]
+                            let respond = state
+                                .store
+                                .update_payment_attempt_with_attempt_id(
+                                    &(&state).into(),
+                                    &key_store,
+                                    payment_attempt,
+                                    payment_attempt_update,
+                                    storage_scheme,
+                                )
+                                .await;
+
+                            if let Err(err) = respond {
+                                logger::error!("Error updating payment attempt: {:?}", err);
+                            };
+                        }
                     }
-                }
-                .in_current_span(),
-            );
-            Ok(())
+                    .in_current_span(),
+                );
+                Ok(())
+            }
         }
     }
 }
@@ -1094,12 +1134,31 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
         None => None,
     };
 
+    let (connector_risk_score, connector_risk_decision) =
+        payments_helpers::get_connector_risk_details(
+            router_data
+                .connector_response
+                .as_ref()
+                .and_then(|connector_response| connector_response.risk_data.as_ref()),
+        );
+    let (connector_fee_amount, connector_fee_currency) =
+        payments_helpers::get_connector_fee_details(
+            router_data
+                .connector_response
+                .as_ref()
+                .and_then(|connector_response| connector_response.fee_data.as_ref()),
+        );
+
     router_data.payment_method_status.and_then(|status| {
         payment_data
             .payment_method_info
             .as_mut()
             .map(|info| info.status = status)
     });
+    let connector_payment_method_token =
+        tokenization::get_connector_payment_method_token_for_attempt(
+            router_data.payment_method_token.as_ref(),
+        );
     let (capture_update, mut payment_attempt_update) = match router_data.response.clone() {
         Err(err) => {
             let auth_update = if Some(router_data.auth_type)
@@ -1219,6 +1278,11 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                             connector_transaction_id: err.connector_transaction_id,
                             payment_method_data: additional_payment_method_data,
                             authentication_type: auth_update,
+                            connector_response_received_at: payment_data
+                                .payment_attempt
+                                .connector_response_received_at
+                                .is_none()
+                                .then(common_utils::date_time::now),
                         }),
                     )
                 }
@@ -1256,6 +1320,11 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                             connector_transaction_id,
                             payment_method_data: None,
                             authentication_type: auth_update,
+                            connector_response_received_at: payment_data
+                                .payment_attempt
+                                .connector_response_received_at
+                                .is_none()
+                                .then(common_utils::date_time::now),
                         }),
                     )
                 }
@@ -1430,6 +1499,16 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                                         encoded_data,
                                         payment_method_data: additional_payment_method_data,
                                         charge_id,
+                                        connector_risk_score,
+                                        connector_risk_decision,
+                                        connector_fee_amount,
+                                        connector_fee_currency,
+                                        connector_response_received_at: payment_data
+                                            .payment_attempt
+                                            .connector_response_received_at
+                                            .is_none()
+                                            .then(common_utils::date_time::now),
+                                        connector_payment_method_token,
                                     }),
                                 ),
                             };
@@ -1542,14 +1621,24 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
             Box::pin(async move {
                 Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(
                     match m_payment_attempt_update {
-                        Some(payment_attempt_update) => m_db
-                            .update_payment_attempt_with_attempt_id(
-                                m_payment_attempt,
-                                payment_attempt_update,
-                                storage_scheme,
+                        Some(payment_attempt_update) => {
+                            let previous_status = m_payment_attempt.status;
+                            let updated_payment_attempt = m_db
+                                .update_payment_attempt_with_attempt_id(
+                                    m_payment_attempt,
+                                    payment_attempt_update,
+                                    storage_scheme,
+                                )
+                                .await
+                                .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+                            payments_helpers::record_payment_attempt_status_transition(
+                                &*m_db,
+                                previous_status,
+                                &updated_payment_attempt,
                             )
-                            .await
-                            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?,
+                            .await;
+                            updated_payment_attempt
+                        }
                         None => m_payment_attempt,
                     },
                 )
@@ -1711,6 +1800,32 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
         utils::flatten_join_error(payment_attempt_fut)
     )?;
 
+    // Schedule moving the payment to failed if it does not complete processing in time. Failing to
+    // schedule it must not fail the payment.
+    if payment_intent.status == enums::IntentStatus::Processing
+        && payment_data.payment_intent.status != enums::IntentStatus::Processing
+    {
+        let _ = async {
+            let processing_expiry = payments_helpers::get_processing_payment_expiry(
+                &*state.store,
+                &payment_attempt.merchant_id,
+            )
+            .await?;
+            let schedule_time = common_utils::date_time::now()
+                .saturating_add(time::Duration::seconds(processing_expiry));
+            crate::core::payments::add_processing_payment_expiry_task(
+                &*state.store,
+                &payment_attempt,
+                schedule_time,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while adding processing expiry task to process tracker")
+        }
+        .await
+        .map_err(|error| logger::error!(processing_payment_expiry_error=?error));
+    }
+
     #[cfg(all(feature = "v1", feature = "dynamic_routing"))]
     {
         if let Some(dynamic_routing_algorithm) = business_profile.dynamic_routing_algorithm.clone()
@@ -505,6 +505,10 @@ async fn get_tracker_for_sync<
         recurring_details: None,
         poll_config: None,
         tax_data: None,
+        should_return_payment_method_token: false,
+        matched_routing_rule: None,
+        confirm_response_masking_policy: business_profile.confirm_response_masking_policy,
+        skip_save_payment_method: false,
     };
 
     let get_trackers_response = operations::GetTrackerResponse {
@@ -0,0 +1,365 @@
+use api_models::payments::{
+    PaymentAttemptReconciliationExportConstraints, PaymentAttemptReconciliationRecord,
+};
+use common_utils::ext_traits::Encode;
+use error_stack::ResultExt;
+use router_env::{instrument, tracing};
+
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult},
+    routes::SessionState,
+    services::ApplicationResponse,
+    types::{
+        domain,
+        storage::{self, enums as storage_enums},
+    },
+};
+
+/// Statuses of the payment attempts that are included in the reconciliation export, which are
+/// the confirmed attempts whose amount was authorized or captured
+pub const RECONCILIATION_EXPORT_ATTEMPT_STATUSES: [storage_enums::AttemptStatus; 4] = [
+    storage_enums::AttemptStatus::Authorized,
+    storage_enums::AttemptStatus::Charged,
+    storage_enums::AttemptStatus::PartialCharged,
+    storage_enums::AttemptStatus::PartialChargedAndChargeable,
+];
+
+/// The maximum number of payment attempts exported in a single page
+pub const RECONCILIATION_EXPORT_MAX_LIMIT: u32 = 1000;
+
+/// Exports a page of the confirmed payment attempts created in the time range as newline
+/// delimited JSON, ordered by their creation time. The following pages are fetched by increasing
+/// the offset by the limit, until a page has fewer attempts than the limit.
+#[instrument(skip_all)]
+pub async fn export_payment_attempts(
+    state: SessionState,
+    merchant_account: domain::MerchantAccount,
+    constraints: PaymentAttemptReconciliationExportConstraints,
+) -> RouterResponse<serde_json::Value> {
+    if constraints.limit == 0 || constraints.limit > RECONCILIATION_EXPORT_MAX_LIMIT {
+        return Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("limit should be between 1 and {RECONCILIATION_EXPORT_MAX_LIMIT}"),
+        }
+        .into());
+    }
+
+    let time_range = common_utils::types::TimeRange {
+        start_time: constraints.start_time,
+        end_time: constraints.end_time,
+    };
+    let payment_attempts = state
+        .store
+        .filter_payment_attempts_by_statuses_time_range(
+            merchant_account.get_id(),
+            &RECONCILIATION_EXPORT_ATTEMPT_STATUSES,
+            &time_range,
+            i64::from(constraints.limit),
+            i64::from(constraints.offset),
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch payment attempts for the reconciliation export")?;
+
+    let records = payment_attempts
+        .into_iter()
+        .map(get_reconciliation_record)
+        .collect::<Vec<_>>();
+
+    Ok(ApplicationResponse::FileData((
+        encode_reconciliation_records(&records)?,
+        "application/x-ndjson"
+            .parse::<mime::Mime>()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to parse the content type of the reconciliation export")?,
+    )))
+}
+
+fn get_reconciliation_record(
+    payment_attempt: storage::PaymentAttempt,
+) -> PaymentAttemptReconciliationRecord {
+    PaymentAttemptReconciliationRecord {
+        payment_id: payment_attempt.payment_id,
+        attempt_id: payment_attempt.attempt_id,
+        merchant_id: payment_attempt.merchant_id,
+        profile_id: payment_attempt.profile_id,
+        status: payment_attempt.status,
+        amount: payment_attempt.amount,
+        net_amount: payment_attempt.net_amount,
+        amount_to_capture: payment_attempt.amount_to_capture,
+        currency: payment_attempt.currency,
+        connector_fee_amount: payment_attempt.connector_fee_amount,
+        connector_fee_currency: payment_attempt.connector_fee_currency,
+        connector: payment_attempt.connector,
+        merchant_connector_id: payment_attempt.merchant_connector_id,
+        connector_transaction_id: payment_attempt.connector_transaction_id,
+        connector_response_reference_id: payment_attempt.connector_response_reference_id,
+        payment_method: payment_attempt.payment_method,
+        payment_method_type: payment_attempt.payment_method_type,
+        capture_method: payment_attempt.capture_method,
+        created_at: payment_attempt.created_at,
+        modified_at: payment_attempt.modified_at,
+    }
+}
+
+/// Encodes the records as newline delimited JSON, with one record per line
+fn encode_reconciliation_records(
+    records: &[PaymentAttemptReconciliationRecord],
+) -> RouterResult<Vec<u8>> {
+    let mut ndjson = Vec::new();
+    for record in records {
+        ndjson.extend(
+            record
+                .encode_to_vec()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to encode the payment attempt for reconciliation")?,
+        );
+        ndjson.push(b'\n');
+    }
+    Ok(ndjson)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::borrow::Cow;
+
+    use common_utils::types::MinorUnit;
+    use hyperswitch_domain_models::payments::payment_attempt::PaymentAttemptInterface;
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::db::MockDb;
+
+    fn get_payment_attempt_new(
+        merchant_id: &common_utils::id_type::MerchantId,
+        attempt_id: &str,
+        status: storage_enums::AttemptStatus,
+        created_at: time::PrimitiveDateTime,
+    ) -> storage::PaymentAttemptNew {
+        storage::PaymentAttemptNew {
+            payment_id: common_utils::id_type::PaymentId::try_from(Cow::Owned(format!(
+                "pay_{attempt_id}"
+            )))
+            .unwrap(),
+            merchant_id: merchant_id.clone(),
+            attempt_id: attempt_id.to_string(),
+            status,
+            amount: MinorUnit::new(10_000),
+            net_amount: MinorUnit::new(10_500),
+            currency: Some(storage_enums::Currency::USD),
+            save_to_locker: None,
+            connector: Some("stripe".to_string()),
+            error_message: None,
+            offer_amount: None,
+            surcharge_amount: Some(MinorUnit::new(500)),
+            tax_amount: None,
+            payment_method_id: Some("pm_4242".to_string()),
+            payment_method: Some(storage_enums::PaymentMethod::Card),
+            capture_method: Some(storage_enums::CaptureMethod::Automatic),
+            capture_on: None,
+            confirm: true,
+            authentication_type: None,
+            created_at: Some(created_at),
+            modified_at: Some(created_at),
+            last_synced: None,
+            cancellation_reason: None,
+            amount_to_capture: Some(MinorUnit::new(10_500)),
+            mandate_id: None,
+            browser_info: None,
+            payment_token: Some("token_4242".to_string()),
+            error_code: None,
+            connector_metadata: None,
+            payment_experience: None,
+            payment_method_type: Some(storage_enums::PaymentMethodType::Credit),
+            payment_method_data: Some(serde_json::json!({"card": {"last4": "4242"}})),
+            business_sub_label: None,
+            straight_through_algorithm: None,
+            preprocessing_step_id: None,
+            mandate_details: None,
+            error_reason: None,
+            connector_response_reference_id: None,
+            multiple_capture_count: None,
+            amount_capturable: MinorUnit::new(0),
+            updated_by: storage_enums::MerchantStorageScheme::PostgresOnly.to_string(),
+            authentication_data: None,
+            encoded_data: None,
+            merchant_connector_id: None,
+            unified_code: None,
+            unified_message: None,
+            external_three_ds_authentication_attempted: None,
+            authentication_connector: None,
+            authentication_id: None,
+            mandate_data: None,
+            payment_method_billing_address_id: None,
+            fingerprint_id: Some("fingerprint_4242".to_string()),
+            charge_id: None,
+            client_source: None,
+            client_version: None,
+            customer_acceptance: None,
+            profile_id: common_utils::generate_profile_id_of_default_length(),
+            organization_id: common_utils::id_type::OrganizationId::default(),
+            connector_risk_score: None,
+            connector_risk_decision: None,
+            connector_fee_amount: Some(MinorUnit::new(320)),
+            connector_fee_currency: Some(storage_enums::Currency::USD),
+            device_fingerprint: Some("device_4242".to_string()),
+            three_ds_version: None,
+            three_ds_message_version: None,
+            request_challenge: None,
+            tokenization_latency: None,
+            card_funding_type: None,
+            liability_shift: None,
+            connector_payment_method_token: None,
+            shipping_cost: None,
+            order_tax_amount: None,
+        }
+    }
+
+    async fn get_mockdb_with_payment_attempts(
+        merchant_id: &common_utils::id_type::MerchantId,
+    ) -> MockDb {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+
+        for (attempt_id, status, created_at) in [
+            (
+                "attempt_3",
+                storage_enums::AttemptStatus::Charged,
+                datetime!(2024-10-13 12:00),
+            ),
+            (
+                "attempt_1",
+                storage_enums::AttemptStatus::Charged,
+                datetime!(2024-10-13 10:00),
+            ),
+            (
+                "attempt_failed",
+                storage_enums::AttemptStatus::Failure,
+                datetime!(2024-10-13 10:30),
+            ),
+            (
+                "attempt_2",
+                storage_enums::AttemptStatus::Authorized,
+                datetime!(2024-10-13 11:00),
+            ),
+            (
+                "attempt_next_day",
+                storage_enums::AttemptStatus::Charged,
+                datetime!(2024-10-14 09:00),
+            ),
+        ] {
+            mockdb
+                .insert_payment_attempt(
+                    get_payment_attempt_new(merchant_id, attempt_id, status, created_at),
+                    storage_enums::MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .unwrap();
+        }
+        mockdb
+    }
+
+    async fn get_exported_attempt_ids(
+        mockdb: &MockDb,
+        merchant_id: &common_utils::id_type::MerchantId,
+        limit: i64,
+        offset: i64,
+    ) -> Vec<String> {
+        mockdb
+            .filter_payment_attempts_by_statuses_time_range(
+                merchant_id,
+                &RECONCILIATION_EXPORT_ATTEMPT_STATUSES,
+                &common_utils::types::TimeRange {
+                    start_time: datetime!(2024-10-13 00:00),
+                    end_time: Some(datetime!(2024-10-13 23:59:59)),
+                },
+                limit,
+                offset,
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|payment_attempt| payment_attempt.attempt_id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_export_paginates_confirmed_attempts() {
+        let merchant_id =
+            common_utils::id_type::MerchantId::try_from(Cow::from("merchant_recon")).unwrap();
+        let mockdb = get_mockdb_with_payment_attempts(&merchant_id).await;
+
+        assert_eq!(
+            get_exported_attempt_ids(&mockdb, &merchant_id, 2, 0).await,
+            vec!["attempt_1", "attempt_2"]
+        );
+        assert_eq!(
+            get_exported_attempt_ids(&mockdb, &merchant_id, 2, 2).await,
+            vec!["attempt_3"]
+        );
+        assert!(get_exported_attempt_ids(&mockdb, &merchant_id, 2, 4)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_export_contains_expected_fields() {
+        let merchant_id =
+            common_utils::id_type::MerchantId::try_from(Cow::from("merchant_recon")).unwrap();
+        let mockdb = get_mockdb_with_payment_attempts(&merchant_id).await;
+        let payment_attempts = mockdb
+            .filter_payment_attempts_by_statuses_time_range(
+                &merchant_id,
+                &RECONCILIATION_EXPORT_ATTEMPT_STATUSES,
+                &common_utils::types::TimeRange {
+                    start_time: datetime!(2024-10-13 00:00),
+                    end_time: Some(datetime!(2024-10-13 23:59:59)),
+                },
+                10,
+                0,
+                storage_enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        let records = payment_attempts
+            .into_iter()
+            .map(|payment_attempt| {
+                get_reconciliation_record(storage::PaymentAttempt {
+                    connector_transaction_id: Some(format!("ch_{}", payment_attempt.attempt_id)),
+                    ..payment_attempt
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let ndjson = String::from_utf8(encode_reconciliation_records(&records).unwrap()).unwrap();
+        let lines = ndjson.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+
+        let record = serde_json::from_str::<serde_json::Value>(lines[0]).unwrap();
+        assert_eq!(record["payment_id"], "pay_attempt_1");
+        assert_eq!(record["attempt_id"], "attempt_1");
+        assert_eq!(record["status"], "charged");
+        assert_eq!(record["amount"], 10_000);
+        assert_eq!(record["net_amount"], 10_500);
+        assert_eq!(record["currency"], "USD");
+        assert_eq!(record["connector_fee_amount"], 320);
+        assert_eq!(record["connector_fee_currency"], "USD");
+        assert_eq!(record["connector"], "stripe");
+        assert_eq!(record["connector_transaction_id"], "ch_attempt_1");
+        assert_eq!(record["created_at"], "2024-10-13T10:00:00.000Z");
+
+        for sensitive_field in [
+            "payment_method_data",
+            "payment_method_id",
+            "payment_token",
+            "fingerprint_id",
+            "device_fingerprint",
+        ] {
+            assert!(record.get(sensitive_field).is_none());
+        }
+    }
+}
@@ -1,10 +1,11 @@
-use std::{str::FromStr, vec::IntoIter};
+use std::{collections::HashMap, str::FromStr, vec::IntoIter};
 
 use common_utils::{ext_traits::Encode, types::MinorUnit};
 use diesel_models::enums as storage_enums;
 use error_stack::{report, ResultExt};
 use router_env::{
     logger,
+    metrics::add_attributes,
     tracing::{self, instrument},
 };
 
@@ -98,7 +99,7 @@ where
             router_data,
             validate_result,
             schedule_time,
-            true,
+            Some(storage_enums::AuthenticationType::ThreeDs),
             frm_suggestion,
             business_profile,
         )
@@ -106,6 +107,9 @@ where
     }
     // Step up is not applicable so proceed with auto retries flow
     else {
+        let same_connector_retry_decline_codes =
+            get_same_connector_retry_decline_codes(state, merchant_account.get_id()).await;
+        let mut current_connector_data = original_connector_data;
         loop {
             // Use initial_gsm for first time alone
             let gsm = match initial_gsm.as_ref() {
@@ -113,25 +117,77 @@ where
                 None => get_gsm(state, &router_data).await?,
             };
 
-            match get_gsm_decision(gsm) {
-                api_models::gsm::GsmDecision::Retry => {
-                    retries =
-                        get_retries(state, retries, merchant_account.get_id(), business_profile)
-                            .await;
+            let gsm_decision = get_gsm_decision(gsm);
+            let soft_three_ds_failure_policy = get_soft_three_ds_failure_policy(
+                gsm_decision,
+                payment_data.get_payment_attempt().authentication_type,
+                business_profile.soft_three_ds_failure_policy,
+            );
+            if let Some(policy) = soft_three_ds_failure_policy {
+                logger::info!(soft_three_ds_failure_policy=?policy, "soft 3DS failure received from connector");
+                metrics::AUTO_RETRY_SOFT_THREE_DS_FAILURE_COUNT.add(
+                    &metrics::CONTEXT,
+                    1,
+                    &add_attributes([("policy", policy.to_string())]),
+                );
+            }
 
-                    if retries.is_none() || retries == Some(0) {
-                        metrics::AUTO_RETRY_EXHAUSTED_COUNT.add(&metrics::CONTEXT, 1, &[]);
-                        logger::info!("retries exhausted for auto_retry payment");
-                        break;
-                    }
+            let step = AutoRetryStep::new(gsm_decision, soft_three_ds_failure_policy);
+            if step.is_retry() {
+                retries =
+                    get_retries(state, retries, merchant_account.get_id(), business_profile).await;
+
+                if !take_retry(&mut retries) {
+                    metrics::AUTO_RETRY_EXHAUSTED_COUNT.add(&metrics::CONTEXT, 1, &[]);
+                    logger::info!("retries exhausted for auto_retry payment");
+                    break;
+                }
+            }
+
+            match step {
+                AutoRetryStep::RetryFrictionless => {
+                    router_data = do_retry(
+                        &state.clone(),
+                        req_state.clone(),
+                        current_connector_data.clone(),
+                        operation,
+                        customer,
+                        merchant_account,
+                        key_store,
+                        payment_data,
+                        router_data,
+                        validate_result,
+                        schedule_time,
+                        Some(storage_enums::AuthenticationType::NoThreeDs),
+                        frm_suggestion,
+                        business_profile,
+                    )
+                    .await?;
+                }
+                AutoRetryStep::Retry => {
+                    let is_same_connector_retry = is_same_connector_retry_eligible(
+                        &same_connector_retry_decline_codes,
+                        current_connector_data.connector_name,
+                        router_data
+                            .response
+                            .as_ref()
+                            .err()
+                            .map(|error_response| error_response.code.as_str()),
+                    );
 
-                    if connectors.len() == 0 {
+                    if !is_same_connector_retry && connectors.len() == 0 {
                         logger::info!("connectors exhausted for auto_retry payment");
                         metrics::AUTO_RETRY_EXHAUSTED_COUNT.add(&metrics::CONTEXT, 1, &[]);
                         break;
                     }
 
-                    let connector = super::get_connector_data(&mut connectors)?;
+                    let connector = if is_same_connector_retry {
+                        logger::info!("retrying the decline on the same connector");
+                        current_connector_data.clone()
+                    } else {
+                        super::get_connector_data(&mut connectors)?
+                    };
+                    current_connector_data = connector.clone();
 
                     router_data = do_retry(
                         &state.clone(),
@@ -146,22 +202,18 @@ where
                         validate_result,
                         schedule_time,
                         //this is an auto retry payment, but not step-up
-                        false,
+                        None,
                         frm_suggestion,
                         business_profile,
                     )
                     .await?;
-
-                    retries = retries.map(|i| i - 1);
-                }
-                api_models::gsm::GsmDecision::Requeue => {
-                    Err(report!(errors::ApiErrorResponse::NotImplemented {
-                        message: errors::NotImplementedMessage::Reason(
-                            "Requeue not implemented".to_string(),
-                        ),
-                    }))?
                 }
-                api_models::gsm::GsmDecision::DoDefault => break,
+                AutoRetryStep::Requeue => Err(report!(errors::ApiErrorResponse::NotImplemented {
+                    message: errors::NotImplementedMessage::Reason(
+                        "Requeue not implemented".to_string(),
+                    ),
+                }))?,
+                AutoRetryStep::Stop => break,
             }
             initial_gsm = None;
         }
@@ -193,6 +245,44 @@ pub async fn is_step_up_enabled_for_merchant_connector(
         .unwrap_or(false)
 }
 
+/// Decline codes of each connector that the merchant has configured to be retried on the same
+/// connector, instead of moving on to the next eligible connector
+#[instrument(skip_all)]
+pub async fn get_same_connector_retry_decline_codes(
+    state: &app::SessionState,
+    merchant_id: &common_utils::id_type::MerchantId,
+) -> HashMap<types::Connector, Vec<String>> {
+    let key = merchant_id.get_same_connector_retry_decline_codes_key();
+    let db = &*state.store;
+    db.find_config_by_key_unwrap_or(key.as_str(), Some("{}".to_string()))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .and_then(|retry_config| {
+            serde_json::from_str::<HashMap<types::Connector, Vec<String>>>(&retry_config.config)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Same connector retry config parsing failed")
+        })
+        .map_err(|err| {
+            logger::error!(same_connector_retry_config_error=?err);
+        })
+        .unwrap_or_default()
+}
+
+pub fn is_same_connector_retry_eligible(
+    same_connector_retry_decline_codes: &HashMap<types::Connector, Vec<String>>,
+    connector_name: types::Connector,
+    decline_code: Option<&str>,
+) -> bool {
+    decline_code
+        .zip(same_connector_retry_decline_codes.get(&connector_name))
+        .map(|(decline_code, eligible_decline_codes)| {
+            eligible_decline_codes
+                .iter()
+                .any(|eligible_decline_code| eligible_decline_code == decline_code)
+        })
+        .unwrap_or(false)
+}
+
 #[cfg(feature = "v1")]
 pub async fn get_merchant_max_auto_retries_enabled(
     db: &dyn StorageInterface,
@@ -271,6 +361,70 @@ pub fn get_gsm_decision(
     option_gsm_decision.unwrap_or_default()
 }
 
+/// Returns the soft 3DS failure policy of the profile if the connector has failed a 3DS payment
+/// with a soft decline, `None` otherwise
+pub fn get_soft_three_ds_failure_policy(
+    gsm_decision: api_models::gsm::GsmDecision,
+    authentication_type: Option<storage_enums::AuthenticationType>,
+    profile_policy: Option<storage_enums::SoftThreeDsFailurePolicy>,
+) -> Option<storage_enums::SoftThreeDsFailurePolicy> {
+    let is_soft_three_ds_failure = gsm_decision == api_models::gsm::GsmDecision::SoftThreeDsFailure
+        && authentication_type == Some(storage_enums::AuthenticationType::ThreeDs);
+    profile_policy.filter(|_| is_soft_three_ds_failure)
+}
+
+/// Next step of the auto retries flow after an attempt has failed
+#[cfg(feature = "v1")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AutoRetryStep {
+    /// Retry once more on the same connector without 3DS
+    RetryFrictionless,
+    /// Retry on the same connector or on the next eligible one
+    Retry,
+    Requeue,
+    /// Leave the attempt as it is
+    Stop,
+}
+
+#[cfg(feature = "v1")]
+impl AutoRetryStep {
+    fn new(
+        gsm_decision: api_models::gsm::GsmDecision,
+        soft_three_ds_failure_policy: Option<storage_enums::SoftThreeDsFailurePolicy>,
+    ) -> Self {
+        match (gsm_decision, soft_three_ds_failure_policy) {
+            (_, Some(storage_enums::SoftThreeDsFailurePolicy::RetryFrictionless)) => {
+                Self::RetryFrictionless
+            }
+            (api_models::gsm::GsmDecision::Retry, _)
+            | (_, Some(storage_enums::SoftThreeDsFailurePolicy::FallThrough)) => Self::Retry,
+            (api_models::gsm::GsmDecision::Requeue, _) => Self::Requeue,
+            (
+                api_models::gsm::GsmDecision::DoDefault
+                | api_models::gsm::GsmDecision::SoftThreeDsFailure,
+                _,
+            ) => Self::Stop,
+        }
+    }
+
+    /// Whether the step makes a new attempt, which counts against the retry budget
+    fn is_retry(self) -> bool {
+        matches!(self, Self::RetryFrictionless | Self::Retry)
+    }
+}
+
+/// Takes a retry from the remaining retries, returns `false` if there are none left
+#[cfg(feature = "v1")]
+fn take_retry(retries: &mut Option<i32>) -> bool {
+    match retries {
+        Some(remaining) if *remaining > 0 => {
+            *remaining -= 1;
+            true
+        }
+        _ => false,
+    }
+}
+
 #[inline]
 fn get_flow_name<F>() -> RouterResult<String> {
     Ok(std::any::type_name::<F>()
@@ -296,7 +450,7 @@ pub async fn do_retry<F, ApiRequest, FData, D>(
     router_data: types::RouterData<F, FData, types::PaymentsResponseData>,
     validate_result: &operations::ValidateResult,
     schedule_time: Option<time::PrimitiveDateTime>,
-    is_step_up: bool,
+    authentication_type: Option<storage_enums::AuthenticationType>,
     frm_suggestion: Option<storage_enums::FrmSuggestion>,
     business_profile: &domain::Profile,
 ) -> RouterResult<types::RouterData<F, FData, types::PaymentsResponseData>>
@@ -322,7 +476,7 @@ where
         key_store,
         merchant_account.storage_scheme,
         router_data,
-        is_step_up,
+        authentication_type,
     )
     .await?;
 
@@ -341,7 +495,6 @@ where
         api::HeaderPayload::default(),
         frm_suggestion,
         business_profile,
-        true,
     )
     .await?;
 
@@ -357,7 +510,7 @@ pub async fn modify_trackers<F, FData, D>(
     key_store: &domain::MerchantKeyStore,
     storage_scheme: storage_enums::MerchantStorageScheme,
     router_data: types::RouterData<F, FData, types::PaymentsResponseData>,
-    is_step_up: bool,
+    authentication_type: Option<storage_enums::AuthenticationType>,
 ) -> RouterResult<()>
 where
     F: Clone + Send,
@@ -376,7 +529,7 @@ pub async fn modify_trackers<F, FData, D>(
     key_store: &domain::MerchantKeyStore,
     storage_scheme: storage_enums::MerchantStorageScheme,
     router_data: types::RouterData<F, FData, types::PaymentsResponseData>,
-    is_step_up: bool,
+    authentication_type: Option<storage_enums::AuthenticationType>,
 ) -> RouterResult<()>
 where
     F: Clone + Send,
@@ -388,7 +541,7 @@ where
         connector,
         payment_data.get_payment_attempt().clone(),
         new_attempt_count,
-        is_step_up,
+        authentication_type,
     );
 
     let db = &*state.store;
@@ -404,6 +557,25 @@ where
                 .clone()
                 .and_then(|connector_response| connector_response.additional_payment_method_data),
         )?;
+    let (connector_risk_score, connector_risk_decision) =
+        payments::helpers::get_connector_risk_details(
+            router_data
+                .connector_response
+                .as_ref()
+                .and_then(|connector_response| connector_response.risk_data.as_ref()),
+        );
+    let (connector_fee_amount, connector_fee_currency) =
+        payments::helpers::get_connector_fee_details(
+            router_data
+                .connector_response
+                .as_ref()
+                .and_then(|connector_response| connector_response.fee_data.as_ref()),
+        );
+
+    let connector_payment_method_token =
+        payments::tokenization::get_connector_payment_method_token_for_attempt(
+            router_data.payment_method_token.as_ref(),
+        );
 
     match router_data.response {
         Ok(types::PaymentsResponseData::TransactionResponse {
@@ -456,6 +628,12 @@ where
                 unified_message: None,
                 payment_method_data: additional_payment_method_data,
                 charge_id,
+                connector_risk_score,
+                connector_risk_decision,
+                connector_fee_amount,
+                connector_fee_currency,
+                connector_response_received_at: Some(common_utils::date_time::now()),
+                connector_payment_method_token,
             };
 
             #[cfg(feature = "v1")]
@@ -505,6 +683,7 @@ where
                 connector_transaction_id: error_response.connector_transaction_id.clone(),
                 payment_method_data: additional_payment_method_data,
                 authentication_type: auth_update,
+                connector_response_received_at: Some(common_utils::date_time::now()),
             };
 
             #[cfg(feature = "v1")]
@@ -577,7 +756,7 @@ pub fn make_new_payment_attempt(
     connector: String,
     old_payment_attempt: storage::PaymentAttempt,
     new_attempt_count: i16,
-    is_step_up: bool,
+    authentication_type: Option<storage_enums::AuthenticationType>,
 ) -> storage::PaymentAttemptNew {
     let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
     storage::PaymentAttemptNew {
@@ -600,11 +779,7 @@ pub fn make_new_payment_attempt(
         capture_method: old_payment_attempt.capture_method,
         capture_on: old_payment_attempt.capture_on,
         confirm: old_payment_attempt.confirm,
-        authentication_type: if is_step_up {
-            Some(storage_enums::AuthenticationType::ThreeDs)
-        } else {
-            old_payment_attempt.authentication_type
-        },
+        authentication_type: authentication_type.or(old_payment_attempt.authentication_type),
         amount_to_capture: old_payment_attempt.amount_to_capture,
         mandate_id: old_payment_attempt.mandate_id,
         browser_info: old_payment_attempt.browser_info,
@@ -616,6 +791,19 @@ pub fn make_new_payment_attempt(
         last_synced,
         profile_id: old_payment_attempt.profile_id,
         organization_id: old_payment_attempt.organization_id,
+        connector_risk_score: None,
+        connector_risk_decision: None,
+        connector_fee_amount: None,
+        connector_fee_currency: None,
+        device_fingerprint: old_payment_attempt.device_fingerprint,
+        three_ds_version: Default::default(),
+        three_ds_message_version: Default::default(),
+        request_challenge: old_payment_attempt.request_challenge,
+        tokenization_latency: old_payment_attempt.tokenization_latency,
+        card_funding_type: old_payment_attempt.card_funding_type,
+        liability_shift: old_payment_attempt.liability_shift,
+        connector_payment_method_token: None,
+        connector_response_received_at: None,
         shipping_cost: old_payment_attempt.shipping_cost,
         net_amount: Default::default(),
         error_message: Default::default(),
@@ -656,7 +844,7 @@ pub fn make_new_payment_attempt(
     _connector: String,
     _old_payment_attempt: storage::PaymentAttempt,
     _new_attempt_count: i16,
-    _is_step_up: bool,
+    _authentication_type: Option<storage_enums::AuthenticationType>,
 ) -> storage::PaymentAttempt {
     todo!()
 }
@@ -736,3 +924,170 @@ impl<F: Send + Clone + Sync, FData: Send + Sync>
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soft_three_ds_failure_retried_frictionless_with_retry_frictionless_policy() {
+        assert_eq!(
+            get_soft_three_ds_failure_policy(
+                api_models::gsm::GsmDecision::SoftThreeDsFailure,
+                Some(storage_enums::AuthenticationType::ThreeDs),
+                Some(storage_enums::SoftThreeDsFailurePolicy::RetryFrictionless),
+            ),
+            Some(storage_enums::SoftThreeDsFailurePolicy::RetryFrictionless)
+        );
+    }
+
+    #[test]
+    fn test_soft_three_ds_failure_falls_through_with_fall_through_policy() {
+        assert_eq!(
+            get_soft_three_ds_failure_policy(
+                api_models::gsm::GsmDecision::SoftThreeDsFailure,
+                Some(storage_enums::AuthenticationType::ThreeDs),
+                Some(storage_enums::SoftThreeDsFailurePolicy::FallThrough),
+            ),
+            Some(storage_enums::SoftThreeDsFailurePolicy::FallThrough)
+        );
+        // A frictionless payment or any other decline is not handled as a soft 3DS failure
+        assert_eq!(
+            get_soft_three_ds_failure_policy(
+                api_models::gsm::GsmDecision::SoftThreeDsFailure,
+                Some(storage_enums::AuthenticationType::NoThreeDs),
+                Some(storage_enums::SoftThreeDsFailurePolicy::FallThrough),
+            ),
+            None
+        );
+        assert_eq!(
+            get_soft_three_ds_failure_policy(
+                api_models::gsm::GsmDecision::Retry,
+                Some(storage_enums::AuthenticationType::ThreeDs),
+                Some(storage_enums::SoftThreeDsFailurePolicy::FallThrough),
+            ),
+            None
+        );
+    }
+
+    /// Runs the auto retries flow over attempts that all fail with `gsm_decision`, returning the
+    /// steps taken
+    #[cfg(feature = "v1")]
+    fn run_auto_retries(
+        gsm_decision: api_models::gsm::GsmDecision,
+        authentication_type: storage_enums::AuthenticationType,
+        profile_policy: Option<storage_enums::SoftThreeDsFailurePolicy>,
+        mut retries: Option<i32>,
+    ) -> Vec<AutoRetryStep> {
+        let mut steps = Vec::new();
+        loop {
+            let step = AutoRetryStep::new(
+                gsm_decision,
+                get_soft_three_ds_failure_policy(
+                    gsm_decision,
+                    Some(authentication_type),
+                    profile_policy,
+                ),
+            );
+            if !step.is_retry() || !take_retry(&mut retries) {
+                break;
+            }
+            steps.push(step);
+        }
+        steps
+    }
+
+    #[cfg(feature = "v1")]
+    #[test]
+    fn test_soft_three_ds_failures_retried_frictionless_count_against_retry_budget() {
+        assert_eq!(
+            run_auto_retries(
+                api_models::gsm::GsmDecision::SoftThreeDsFailure,
+                storage_enums::AuthenticationType::ThreeDs,
+                Some(storage_enums::SoftThreeDsFailurePolicy::RetryFrictionless),
+                Some(2),
+            ),
+            vec![AutoRetryStep::RetryFrictionless; 2]
+        );
+        assert!(run_auto_retries(
+            api_models::gsm::GsmDecision::SoftThreeDsFailure,
+            storage_enums::AuthenticationType::ThreeDs,
+            Some(storage_enums::SoftThreeDsFailurePolicy::RetryFrictionless),
+            None,
+        )
+        .is_empty());
+    }
+
+    #[cfg(feature = "v1")]
+    #[test]
+    fn test_soft_three_ds_failures_falling_through_are_retried_like_other_declines() {
+        assert_eq!(
+            run_auto_retries(
+                api_models::gsm::GsmDecision::SoftThreeDsFailure,
+                storage_enums::AuthenticationType::ThreeDs,
+                Some(storage_enums::SoftThreeDsFailurePolicy::FallThrough),
+                Some(3),
+            ),
+            vec![AutoRetryStep::Retry; 3]
+        );
+        assert_eq!(
+            run_auto_retries(
+                api_models::gsm::GsmDecision::Retry,
+                storage_enums::AuthenticationType::NoThreeDs,
+                None,
+                Some(3),
+            ),
+            vec![AutoRetryStep::Retry; 3]
+        );
+    }
+
+    #[cfg(feature = "v1")]
+    #[test]
+    fn test_soft_three_ds_failures_are_not_retried_without_policy() {
+        assert!(run_auto_retries(
+            api_models::gsm::GsmDecision::SoftThreeDsFailure,
+            storage_enums::AuthenticationType::ThreeDs,
+            None,
+            Some(3),
+        )
+        .is_empty());
+    }
+
+    fn get_same_connector_retry_decline_codes() -> HashMap<types::Connector, Vec<String>> {
+        HashMap::from([(
+            types::Connector::Stripe,
+            vec!["do_not_honor".to_string(), "try_again_later".to_string()],
+        )])
+    }
+
+    #[test]
+    fn test_eligible_decline_code_is_retried_on_same_connector() {
+        assert!(is_same_connector_retry_eligible(
+            &get_same_connector_retry_decline_codes(),
+            types::Connector::Stripe,
+            Some("do_not_honor"),
+        ));
+    }
+
+    #[test]
+    fn test_ineligible_decline_code_is_not_retried_on_same_connector() {
+        let decline_codes = get_same_connector_retry_decline_codes();
+
+        assert!(!is_same_connector_retry_eligible(
+            &decline_codes,
+            types::Connector::Stripe,
+            Some("insufficient_funds"),
+        ));
+        // Codes are configured per connector
+        assert!(!is_same_connector_retry_eligible(
+            &decline_codes,
+            types::Connector::Adyen,
+            Some("do_not_honor"),
+        ));
+        assert!(!is_same_connector_retry_eligible(
+            &decline_codes,
+            types::Connector::Stripe,
+            None,
+        ));
+    }
+}
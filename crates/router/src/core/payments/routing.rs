@@ -57,7 +57,10 @@ pub enum CachedAlgorithm {
     Single(Box<routing_types::RoutableConnectorChoice>),
     Priority(Vec<routing_types::RoutableConnectorChoice>),
     VolumeSplit(Vec<routing_types::ConnectorVolumeSplit>),
-    Advanced(backend::VirInterpreterBackend<ConnectorSelection>),
+    Advanced(
+        backend::VirInterpreterBackend<ConnectorSelection>,
+        Vec<ast::Rule<ConnectorSelection>>,
+    ),
 }
 
 pub struct SessionFlowRoutingInput<'a> {
@@ -279,13 +282,18 @@ pub fn make_dsl_input(
     })
 }
 
+/// Returns the connectors selected by the routing algorithm of the profile, along with the matched
+/// rule if the algorithm is rule based
 pub async fn perform_static_routing_v1(
     state: &SessionState,
     merchant_id: &common_utils::id_type::MerchantId,
     algorithm_id: Option<&common_utils::id_type::RoutingId>,
     business_profile: &domain::Profile,
     transaction_data: &routing::TransactionData<'_>,
-) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
+) -> RoutingResult<(
+    Vec<routing_types::RoutableConnectorChoice>,
+    Option<ast::Rule<ConnectorSelection>>,
+)> {
     let algorithm_id = if let Some(id) = algorithm_id {
         id
     } else {
@@ -302,7 +310,7 @@ pub async fn perform_static_routing_v1(
             .get_default_fallback_list_of_connector_under_profile()
             .change_context(errors::RoutingError::FallbackConfigFetchFailed)?;
 
-        return Ok(fallback_config);
+        return Ok((fallback_config, None));
     };
     let cached_algorithm = ensure_algorithm_cached_v1(
         state,
@@ -314,14 +322,17 @@ pub async fn perform_static_routing_v1(
     .await?;
 
     Ok(match cached_algorithm.as_ref() {
-        CachedAlgorithm::Single(conn) => vec![(**conn).clone()],
+        CachedAlgorithm::Single(conn) => (vec![(**conn).clone()], None),
 
-        CachedAlgorithm::Priority(plist) => plist.clone(),
+        CachedAlgorithm::Priority(plist) => (plist.clone(), None),
 
-        CachedAlgorithm::VolumeSplit(splits) => perform_volume_split(splits.to_vec(), None)
-            .change_context(errors::RoutingError::ConnectorSelectionFailed)?,
+        CachedAlgorithm::VolumeSplit(splits) => (
+            perform_volume_split(splits.to_vec(), None)
+                .change_context(errors::RoutingError::ConnectorSelectionFailed)?,
+            None,
+        ),
 
-        CachedAlgorithm::Advanced(interpreter) => {
+        CachedAlgorithm::Advanced(interpreter, rules) => {
             let backend_input = match transaction_data {
                 routing::TransactionData::Payment(payment_data) => make_dsl_input(payment_data)?,
                 #[cfg(feature = "payouts")]
@@ -330,7 +341,7 @@ pub async fn perform_static_routing_v1(
                 }
             };
 
-            execute_dsl_and_get_connector_v1(backend_input, interpreter)?
+            execute_dsl_and_get_connector_with_rule_v1(backend_input, interpreter, rules)?
         }
     })
 }
@@ -404,12 +415,27 @@ fn execute_dsl_and_get_connector_v1(
     backend_input: dsl_inputs::BackendInput,
     interpreter: &backend::VirInterpreterBackend<ConnectorSelection>,
 ) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
-    let routing_output: routing_types::RoutingAlgorithm = interpreter
+    execute_dsl_and_get_connector_with_rule_v1(backend_input, interpreter, &[])
+        .map(|(connectors, _matched_rule)| connectors)
+}
+
+/// Executes the rule based routing algorithm, returning the selected connectors along with the
+/// rule of `rules` that matched
+fn execute_dsl_and_get_connector_with_rule_v1(
+    backend_input: dsl_inputs::BackendInput,
+    interpreter: &backend::VirInterpreterBackend<ConnectorSelection>,
+    rules: &[ast::Rule<ConnectorSelection>],
+) -> RoutingResult<(
+    Vec<routing_types::RoutableConnectorChoice>,
+    Option<ast::Rule<ConnectorSelection>>,
+)> {
+    let backend_output = interpreter
         .execute(backend_input)
-        .map(|out| out.connector_selection.foreign_into())
         .change_context(errors::RoutingError::DslExecutionError)?;
+    let routing_output: routing_types::RoutingAlgorithm =
+        backend_output.connector_selection.foreign_into();
 
-    Ok(match routing_output {
+    let connectors = match routing_output {
         routing_types::RoutingAlgorithm::Priority(plist) => plist,
 
         routing_types::RoutingAlgorithm::VolumeSplit(splits) => perform_volume_split(splits, None)
@@ -417,7 +443,13 @@ fn execute_dsl_and_get_connector_v1(
 
         _ => Err(errors::RoutingError::DslIncorrectSelectionAlgorithm)
             .attach_printable("Unsupported algorithm received as a result of static routing")?,
-    })
+    };
+
+    let matched_rule = backend_output
+        .rule_name
+        .and_then(|rule_name| rules.iter().find(|rule| rule.name == rule_name).cloned());
+
+    Ok((connectors, matched_rule))
 }
 
 pub async fn refresh_routing_cache_v1(
@@ -446,11 +478,12 @@ pub async fn refresh_routing_cache_v1(
             CachedAlgorithm::VolumeSplit(splits)
         }
         routing_types::RoutingAlgorithm::Advanced(program) => {
+            let rules = program.rules.clone();
             let interpreter = backend::VirInterpreterBackend::with_program(program)
                 .change_context(errors::RoutingError::DslBackendInitError)
                 .attach_printable("Error initializing DSL interpreter backend")?;
 
-            CachedAlgorithm::Advanced(interpreter)
+            CachedAlgorithm::Advanced(interpreter, rules)
         }
     };
 
@@ -1009,7 +1042,7 @@ async fn perform_session_routing_for_pm_type(
                 perform_volume_split(splits.to_vec(), Some(session_pm_input.attempt_id))
                     .change_context(errors::RoutingError::ConnectorSelectionFailed)?
             }
-            CachedAlgorithm::Advanced(interpreter) => execute_dsl_and_get_connector_v1(
+            CachedAlgorithm::Advanced(interpreter, _) => execute_dsl_and_get_connector_v1(
                 session_pm_input.backend_input.clone(),
                 interpreter,
             )?,
@@ -1091,7 +1124,7 @@ async fn perform_session_routing_for_pm_type(
                 perform_volume_split(splits.to_vec(), Some(session_pm_input.attempt_id))
                     .change_context(errors::RoutingError::ConnectorSelectionFailed)?
             }
-            CachedAlgorithm::Advanced(interpreter) => execute_dsl_and_get_connector_v1(
+            CachedAlgorithm::Advanced(interpreter, _) => execute_dsl_and_get_connector_v1(
                 session_pm_input.backend_input.clone(),
                 interpreter,
             )?,
@@ -1202,3 +1235,95 @@ pub fn make_dsl_input_for_surcharge(
     };
     Ok(backend_input)
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use common_utils::types::MinorUnit;
+
+    use super::*;
+
+    fn get_priority_selection(connector: api_enums::RoutableConnectors) -> ConnectorSelection {
+        ConnectorSelection::Priority(vec![routing_types::RoutableConnectorChoice {
+            choice_kind: routing_types::RoutableChoiceKind::FullStruct,
+            connector,
+            merchant_connector_id: None,
+        }])
+    }
+
+    fn get_backend_input(amount: i64) -> dsl_inputs::BackendInput {
+        dsl_inputs::BackendInput {
+            metadata: None,
+            payment: dsl_inputs::PaymentInput {
+                amount: MinorUnit::new(amount),
+                currency: api_enums::Currency::USD,
+                authentication_type: None,
+                card_bin: None,
+                capture_method: None,
+                business_country: None,
+                billing_country: None,
+                business_label: None,
+                setup_future_usage: None,
+            },
+            payment_method: dsl_inputs::PaymentMethodInput {
+                payment_method: None,
+                payment_method_type: None,
+                card_network: None,
+            },
+            mandate: dsl_inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_rule_based_routing_returns_the_matched_rule() {
+        let high_value_rule = ast::Rule {
+            name: "high_value_payments".to_string(),
+            connector_selection: get_priority_selection(api_enums::RoutableConnectors::Adyen),
+            statements: vec![ast::IfStatement {
+                condition: vec![ast::Comparison {
+                    lhs: "amount".to_string(),
+                    comparison: ast::ComparisonType::GreaterThan,
+                    value: ast::ValueType::Number(MinorUnit::new(100000)),
+                    metadata: HashMap::new(),
+                }],
+                nested: None,
+            }],
+        };
+        let program = ast::Program {
+            default_selection: get_priority_selection(api_enums::RoutableConnectors::Stripe),
+            rules: vec![high_value_rule.clone()],
+            metadata: HashMap::new(),
+        };
+        let interpreter = backend::VirInterpreterBackend::with_program(program.clone()).unwrap();
+
+        let (connectors, matched_rule) = execute_dsl_and_get_connector_with_rule_v1(
+            get_backend_input(150000),
+            &interpreter,
+            &program.rules,
+        )
+        .unwrap();
+        assert_eq!(
+            connectors
+                .iter()
+                .map(|connector_choice| connector_choice.connector)
+                .collect::<Vec<_>>(),
+            vec![api_enums::RoutableConnectors::Adyen]
+        );
+        let matched_rule = matched_rule.unwrap();
+        assert_eq!(matched_rule.name, high_value_rule.name);
+        assert_eq!(matched_rule.statements, high_value_rule.statements);
+
+        // default selection of the algorithm, no rule matched
+        let (_, matched_rule) = execute_dsl_and_get_connector_with_rule_v1(
+            get_backend_input(500),
+            &interpreter,
+            &program.rules,
+        )
+        .unwrap();
+        assert!(matched_rule.is_none());
+    }
+}
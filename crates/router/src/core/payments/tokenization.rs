@@ -158,22 +158,8 @@ where
                 .change_context(errors::ApiErrorResponse::InternalServerError)
                 .attach_printable("Unable to serialize customer acceptance to value")?;
 
-            let (connector_mandate_id, mandate_metadata) = match responses {
-                types::PaymentsResponseData::TransactionResponse {
-                    ref mandate_reference,
-                    ..
-                } => {
-                    if let Some(mandate_ref) = mandate_reference {
-                        (
-                            mandate_ref.connector_mandate_id.clone(),
-                            mandate_ref.mandate_metadata.clone(),
-                        )
-                    } else {
-                        (None, None)
-                    }
-                }
-                _ => (None, None),
-            };
+            let (connector_mandate_id, mandate_metadata) =
+                get_connector_mandate_reference(&responses);
             let check_for_mit_mandates = save_payment_method_data
                 .request
                 .get_setup_mandate_details()
@@ -747,24 +733,14 @@ where
             } else {
                 None
             };
-            let cmid_config = db
-                .find_config_by_key_unwrap_or(
-                    format!("{}_should_show_connector_mandate_id_in_payments_response", merchant_account.get_id().get_string_repr().to_owned()).as_str(),
-                    Some("false".to_string()),
-                )
-                .await.map_err(|err| services::logger::error!(message="Failed to fetch the config", connector_mandate_details_population=?err)).ok();
-
-            let mandate_reference_id = match cmid_config {
-                Some(config) if config.config == "true" => Some(
-                    MandateReferenceId::ConnectorMandateId(ConnectorMandateReferenceId {
-                        connector_mandate_id: connector_mandate_id.clone(),
-                        payment_method_id: pm_id.clone(),
-                        update_history: None,
-                        mandate_metadata: mandate_metadata.clone(),
-                    }),
-                ),
-                _ => None,
-            };
+            let mandate_reference_id = get_mandate_reference_id(
+                state,
+                merchant_account,
+                connector_mandate_id,
+                mandate_metadata,
+                pm_id.clone(),
+            )
+            .await;
 
             Ok(SavePaymentMethodDataResponse {
                 payment_method_id: pm_id,
@@ -780,6 +756,58 @@ where
     }
 }
 
+/// Extracts the connector mandate id and mandate metadata from the connector response
+pub fn get_connector_mandate_reference(
+    response: &types::PaymentsResponseData,
+) -> (Option<String>, Option<serde_json::Value>) {
+    match response {
+        types::PaymentsResponseData::TransactionResponse {
+            mandate_reference: Some(mandate_ref),
+            ..
+        } => (
+            mandate_ref.connector_mandate_id.clone(),
+            mandate_ref.mandate_metadata.clone(),
+        ),
+        _ => (None, None),
+    }
+}
+
+/// Builds the connector mandate reference that is shown in the payments response,
+/// if the merchant has enabled it through config
+pub async fn get_mandate_reference_id(
+    state: &SessionState,
+    merchant_account: &domain::MerchantAccount,
+    connector_mandate_id: Option<String>,
+    mandate_metadata: Option<serde_json::Value>,
+    payment_method_id: Option<String>,
+) -> Option<MandateReferenceId> {
+    let cmid_config = state
+        .store
+        .find_config_by_key_unwrap_or(
+            format!(
+                "{}_should_show_connector_mandate_id_in_payments_response",
+                merchant_account.get_id().get_string_repr()
+            )
+            .as_str(),
+            Some("false".to_string()),
+        )
+        .await
+        .map_err(|err| services::logger::error!(message="Failed to fetch the config", connector_mandate_details_population=?err))
+        .ok();
+
+    match cmid_config {
+        Some(config) if config.config == "true" => Some(MandateReferenceId::ConnectorMandateId(
+            ConnectorMandateReferenceId {
+                connector_mandate_id,
+                payment_method_id,
+                update_history: None,
+                mandate_metadata,
+            },
+        )),
+        _ => None,
+    }
+}
+
 // check in review
 #[cfg(all(feature = "v2", feature = "payment_methods_v2"))]
 #[instrument(skip_all)]
@@ -1036,6 +1064,7 @@ pub async fn save_network_token_in_locker(
                     card_network: Some(token_response.card_brand.clone()),
                     card_issuer: None,
                     card_type: None,
+                    preferred_network: None,
                 };
 
                 let (res, dc) = Box::pin(payment_methods::cards::add_card_to_locker(
@@ -1168,32 +1197,58 @@ pub async fn add_payment_method_token<F: Clone, T: types::Tokenizable + Clone>(
 pub fn update_router_data_with_payment_method_token_result<F: Clone, T>(
     payment_method_token_result: types::PaymentMethodTokenResult,
     router_data: &mut types::RouterData<F, T, types::PaymentsResponseData>,
-    is_retry_payment: bool,
     should_continue_further: bool,
 ) -> bool {
-    if payment_method_token_result.is_payment_method_tokenization_performed {
-        match payment_method_token_result.payment_method_token_result {
-            Ok(pm_token_result) => {
-                router_data.payment_method_token = pm_token_result.map(|pm_token| {
-                    hyperswitch_domain_models::router_data::PaymentMethodToken::Token(Secret::new(
-                        pm_token,
-                    ))
-                });
+    match get_connector_payment_method_token(payment_method_token_result) {
+        Some(Ok(payment_method_token)) => {
+            router_data.payment_method_token = payment_method_token;
+            true
+        }
+        Some(Err(err)) => {
+            // The payment is still authorized, without the connector token
+            logger::error!(payment_method_tokenization_error=?err);
+            true
+        }
+        None => should_continue_further,
+    }
+}
 
-                true
-            }
-            Err(err) => {
-                if is_retry_payment {
-                    router_data.response = Err(err);
-                    false
-                } else {
-                    logger::debug!(payment_method_tokenization_error=?err);
-                    true
-                }
-            }
+/// Connector token to be sent in the authorize request, `None` if the connector does not
+/// require tokenization and it was skipped
+fn get_connector_payment_method_token(
+    payment_method_token_result: types::PaymentMethodTokenResult,
+) -> Option<
+    Result<
+        Option<hyperswitch_domain_models::router_data::PaymentMethodToken>,
+        types::ErrorResponse,
+    >,
+> {
+    payment_method_token_result
+        .is_payment_method_tokenization_performed
+        .then(|| {
+            payment_method_token_result
+                .payment_method_token_result
+                .map(|pm_token_result| {
+                    pm_token_result.map(|pm_token| {
+                        hyperswitch_domain_models::router_data::PaymentMethodToken::Token(
+                            Secret::new(pm_token),
+                        )
+                    })
+                })
+        })
+}
+
+/// Connector token of the payment method to be stored on the payment attempt. Decrypted wallet
+/// data is not a connector token and is not stored.
+pub fn get_connector_payment_method_token_for_attempt(
+    payment_method_token: Option<&hyperswitch_domain_models::router_data::PaymentMethodToken>,
+) -> Option<String> {
+    match payment_method_token? {
+        hyperswitch_domain_models::router_data::PaymentMethodToken::Token(token) => {
+            Some(token.clone().expose())
         }
-    } else {
-        should_continue_further
+        hyperswitch_domain_models::router_data::PaymentMethodToken::ApplePayDecrypt(_)
+        | hyperswitch_domain_models::router_data::PaymentMethodToken::PazeDecrypt(_) => None,
     }
 }
 
@@ -1290,3 +1345,62 @@ pub fn update_connector_mandate_details_in_payment_method(
 
     Ok(connector_mandate_details)
 }
+
+#[cfg(test)]
+mod tests {
+    use masking::PeekInterface;
+
+    use super::*;
+
+    #[test]
+    fn test_connector_token_used_when_tokenization_required() {
+        let payment_method_token_result = types::PaymentMethodTokenResult {
+            payment_method_token_result: Ok(Some("tok_123".to_string())),
+            is_payment_method_tokenization_performed: true,
+        };
+        match get_connector_payment_method_token(payment_method_token_result) {
+            Some(Ok(Some(hyperswitch_domain_models::router_data::PaymentMethodToken::Token(
+                token,
+            )))) => assert_eq!(token.peek(), "tok_123"),
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_connector_tokenization_skipped_when_not_required() {
+        let payment_method_token_result = types::PaymentMethodTokenResult {
+            payment_method_token_result: Ok(None),
+            is_payment_method_tokenization_performed: false,
+        };
+        assert!(get_connector_payment_method_token(payment_method_token_result).is_none());
+    }
+
+    #[test]
+    fn test_connector_token_stored_on_attempt() {
+        let payment_method_token =
+            hyperswitch_domain_models::router_data::PaymentMethodToken::Token(Secret::new(
+                "tok_123".to_string(),
+            ));
+        assert_eq!(
+            get_connector_payment_method_token_for_attempt(Some(&payment_method_token)),
+            Some("tok_123".to_string())
+        );
+        assert_eq!(get_connector_payment_method_token_for_attempt(None), None);
+    }
+
+    #[test]
+    fn test_connector_tokenization_failure_is_returned() {
+        let payment_method_token_result = types::PaymentMethodTokenResult {
+            payment_method_token_result: Err(types::ErrorResponse {
+                code: "invalid_card".to_string(),
+                message: "Card could not be tokenized".to_string(),
+                ..Default::default()
+            }),
+            is_payment_method_tokenization_performed: true,
+        };
+        match get_connector_payment_method_token(payment_method_token_result) {
+            Some(Err(err)) => assert_eq!(err.code, "invalid_card"),
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+}
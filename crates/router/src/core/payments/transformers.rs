@@ -74,6 +74,7 @@ where
         payment_data: payment_data.clone(),
         state,
         customer_data: customer,
+        statement_descriptor_prefix: merchant_connector_account.get_statement_descriptor_prefix(),
     };
 
     let router_data = types::RouterData {
@@ -229,6 +230,7 @@ where
         payment_data: payment_data.clone(),
         state,
         customer_data: customer,
+        statement_descriptor_prefix: merchant_connector_account.get_statement_descriptor_prefix(),
     };
 
     let customer_id = customer.to_owned().map(|customer| customer.customer_id);
@@ -982,7 +984,7 @@ where
                                                 &payment_attempt,
                                                 payment_connector_name,
                                             ),
-                                            three_ds_method_details: authentication.three_ds_method_url.as_ref().zip(authentication.three_ds_method_data.as_ref()).map(|(three_ds_method_url,three_ds_method_data )|{
+                                            three_ds_method_details: authentication.three_ds_method_url.as_ref().zip(authentication.three_ds_method_data.as_ref()).filter(|_| payment_attempt.authentication_type == Some(enums::AuthenticationType::ThreeDs)).map(|(three_ds_method_url,three_ds_method_data )|{
                                                 api_models::payments::ThreeDsMethodData::AcsThreeDsMethodData {
                                                     three_ds_method_data_submission: true,
                                                     three_ds_method_data: Some(three_ds_method_data.clone()),
@@ -1120,6 +1122,31 @@ where
                 })
         });
 
+        let billing = payment_data.get_address().get_payment_billing().cloned();
+        let (payment_method_data_response, billing) =
+            match payment_data.get_confirm_response_masking_policy() {
+                Some(masking_policy) => helpers::apply_confirm_response_masking_policy(
+                    masking_policy,
+                    payment_method_data_response,
+                    billing,
+                ),
+                None => (payment_method_data_response, billing),
+            };
+
+        let payment_method_token_reference = helpers::get_payment_method_token_reference(
+            payment_data.get_should_return_payment_method_token(),
+            payment_attempt.payment_method,
+            payment_attempt.payment_method_id.clone(),
+            payment_data
+                .get_payment_method_info()
+                .and_then(|pm_info| pm_info.network_token_requestor_reference_id.clone()),
+        );
+
+        let mandate_created = helpers::get_mandate_created(
+            payment_data.get_setup_mandate().is_some(),
+            mandate_id.as_ref(),
+        );
+
         let payments_response = api::PaymentsResponse {
             payment_id: payment_intent.payment_id,
             merchant_id: payment_intent.merchant_id,
@@ -1141,6 +1168,7 @@ where
             captures: captures_response,
             mandate_id,
             mandate_data,
+            mandate_created,
             setup_future_usage: payment_intent.setup_future_usage,
             off_session: payment_intent.off_session,
             capture_on: None,
@@ -1149,7 +1177,7 @@ where
             payment_method_data: payment_method_data_response,
             payment_token: payment_attempt.payment_token,
             shipping: payment_data.get_address().get_shipping().cloned(),
-            billing: payment_data.get_address().get_payment_billing().cloned(),
+            billing,
             order_details: payment_intent.order_details,
             email: customer
                 .as_ref()
@@ -1198,6 +1226,7 @@ where
             profile_id: payment_intent.profile_id,
             surcharge_details,
             attempt_count: payment_intent.attempt_count,
+            attempt_id: payment_attempt.attempt_id.clone(),
             merchant_decision,
             merchant_connector_id: payment_attempt.merchant_connector_id,
             incremental_authorization_allowed: payment_intent.incremental_authorization_allowed,
@@ -1219,6 +1248,14 @@ where
             merchant_order_reference_id: payment_intent.merchant_order_reference_id,
             order_tax_amount,
             connector_mandate_id,
+            payment_method_token_reference,
+            matched_routing_rule: payment_data.get_matched_routing_rule().cloned(),
+            connector_risk_score: payment_attempt.connector_risk_score,
+            connector_risk_decision: payment_attempt.connector_risk_decision,
+            connector_fee_amount: payment_attempt.connector_fee_amount,
+            connector_fee_currency: payment_attempt.connector_fee_currency,
+            card_funding_type: payment_attempt.card_funding_type,
+            liability_shift: payment_attempt.liability_shift,
         };
 
         services::ApplicationResponse::JsonWithHeaders((payments_response, headers))
@@ -1377,6 +1414,7 @@ impl ForeignFrom<(storage::PaymentIntent, storage::PaymentAttempt)> for api::Pay
             authentication_type: pa.authentication_type,
             connector_transaction_id: pa.connector_transaction_id,
             attempt_count: pi.attempt_count,
+            attempt_id: pa.attempt_id,
             profile_id: pi.profile_id,
             merchant_connector_id: pa.merchant_connector_id,
             payment_method_data: pa.payment_method_data.and_then(|data| {
@@ -1432,6 +1470,7 @@ impl ForeignFrom<(storage::PaymentIntent, storage::PaymentAttempt)> for api::Pay
             captures: None,
             mandate_id: None,
             mandate_data: None,
+            mandate_created: None,
             off_session: None,
             capture_on: None,
             payment_token: None,
@@ -1474,6 +1513,14 @@ impl ForeignFrom<(storage::PaymentIntent, storage::PaymentAttempt)> for api::Pay
             frm_metadata: None,
             order_tax_amount: None,
             connector_mandate_id:None,
+            payment_method_token_reference: None,
+            matched_routing_rule: None,
+            connector_risk_score: None,
+            connector_risk_decision: None,
+            connector_fee_amount: None,
+            connector_fee_currency: None,
+            card_funding_type: None,
+            liability_shift: None,
         }
     }
 }
@@ -1498,19 +1545,7 @@ pub fn bank_transfer_next_steps_check(
     {
         if payment_attempt.payment_method_type != Some(diesel_models::enums::PaymentMethodType::Pix)
         {
-            let bank_transfer_next_steps: Option<api_models::payments::BankTransferNextStepsData> =
-                payment_attempt
-                    .connector_metadata
-                    .map(|metadata| {
-                        metadata
-                            .parse_value("NextStepsRequirements")
-                            .change_context(errors::ApiErrorResponse::InternalServerError)
-                            .attach_printable(
-                                "Failed to parse the Value to NextRequirements struct",
-                            )
-                    })
-                    .transpose()?;
-            bank_transfer_next_steps
+            parse_payment_instructions(payment_attempt.connector_metadata)?
         } else {
             None
         }
@@ -1527,22 +1562,28 @@ pub fn voucher_next_steps_check(
     let voucher_next_step = if let Some(diesel_models::enums::PaymentMethod::Voucher) =
         payment_attempt.payment_method
     {
-        let voucher_next_steps: Option<api_models::payments::VoucherNextStepData> = payment_attempt
-            .connector_metadata
-            .map(|metadata| {
-                metadata
-                    .parse_value("NextStepsRequirements")
-                    .change_context(errors::ApiErrorResponse::InternalServerError)
-                    .attach_printable("Failed to parse the Value to NextRequirements struct")
-            })
-            .transpose()?;
-        voucher_next_steps
+        parse_payment_instructions(payment_attempt.connector_metadata)?
     } else {
         None
     };
     Ok(voucher_next_step)
 }
 
+/// Parses the payment instructions returned by the connector for an asynchronous payment method,
+/// such as the voucher reference or the account to transfer to, along with their expiry
+pub fn parse_payment_instructions<T: serde::de::DeserializeOwned>(
+    connector_metadata: Option<serde_json::Value>,
+) -> RouterResult<Option<T>> {
+    connector_metadata
+        .map(|metadata| {
+            metadata
+                .parse_value("NextStepsRequirements")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to parse the Value to NextRequirements struct")
+        })
+        .transpose()
+}
+
 pub fn change_order_details_to_new_type(
     order_amount: i64,
     order_details: api_models::payments::OrderDetails,
@@ -1604,6 +1645,7 @@ where
     payment_data: PaymentData<F>,
     state: &'a SessionState,
     customer_data: &'a Option<domain::Customer>,
+    statement_descriptor_prefix: Option<String>,
 }
 
 #[cfg(all(feature = "v2", feature = "customer_v2"))]
@@ -1726,6 +1768,11 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             .merchant_order_reference_id
             .clone();
 
+        let statement_descriptor = helpers::get_statement_descriptor_with_prefix(
+            additional_data.statement_descriptor_prefix.as_deref(),
+            payment_data.payment_intent.statement_descriptor_name,
+        )?;
+
         Ok(Self {
             payment_method_data: (payment_method_data.get_required_value("payment_method_data")?),
             setup_future_usage: payment_data.payment_intent.setup_future_usage,
@@ -1734,7 +1781,7 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             setup_mandate_details: payment_data.setup_mandate.clone(),
             confirm: payment_data.payment_attempt.confirm,
             statement_descriptor_suffix: payment_data.payment_intent.statement_descriptor_suffix,
-            statement_descriptor: payment_data.payment_intent.statement_descriptor_name,
+            statement_descriptor,
             capture_method: payment_data.payment_attempt.capture_method,
             amount: amount.get_amount_as_i64(),
             minor_amount: amount,
@@ -2431,3 +2478,305 @@ impl ForeignFrom<CustomerDetails> for router_request_types::CustomerDetails {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::connector::adyen::transformers as adyen;
+
+    fn get_payment_instructions(response: serde_json::Value) -> Option<serde_json::Value> {
+        let response: adyen::PresentToShopperResponse = serde_json::from_value(response).unwrap();
+        adyen::get_present_to_shopper_metadata(&response).unwrap()
+    }
+
+    #[test]
+    fn test_voucher_next_action_from_connector_response() {
+        let connector_metadata = get_payment_instructions(serde_json::json!({
+            "resultCode": "PresentToShopper",
+            "pspReference": "ZVWN7D3WSMK2WN82",
+            "action": {
+                "type": "voucher",
+                "paymentMethodType": "boletobancario",
+                "reference": "03399.33335 33823.303087 13185.201017 1 99990000010000",
+                "expiresAt": "2030-10-20T00:00:00",
+                "downloadUrl": "https://test.adyen.com/hpp/generationBoleto.shtml?data=BQABAQ"
+            }
+        }));
+
+        let voucher_details: api_models::payments::VoucherNextStepData =
+            parse_payment_instructions(connector_metadata)
+                .unwrap()
+                .unwrap();
+        assert_eq!(
+            voucher_details.reference,
+            "03399.33335 33823.303087 13185.201017 1 99990000010000"
+        );
+        assert_eq!(voucher_details.expires_at, Some(1_918_684_800_000));
+        assert!(voucher_details.download_url.is_some());
+
+        let next_action = serde_json::to_value(
+            api_models::payments::NextActionData::DisplayVoucherInformation { voucher_details },
+        )
+        .unwrap();
+        assert_eq!(next_action["type"], "display_voucher_information");
+        assert_eq!(
+            next_action["voucher_details"]["expires_at"],
+            1_918_684_800_000_i64
+        );
+    }
+
+    #[test]
+    fn test_bank_transfer_next_action_from_connector_response() {
+        let connector_metadata = get_payment_instructions(serde_json::json!({
+            "resultCode": "PresentToShopper",
+            "action": {
+                "type": "voucher",
+                "paymentMethodType": "doku_permata_lite_atm",
+                "reference": "8315018000000001",
+                "expiresAt": "2030-10-20T00:00:00"
+            }
+        }));
+
+        let bank_transfer_steps_and_charges_details: api_models::payments::BankTransferNextStepsData =
+            parse_payment_instructions(connector_metadata)
+                .unwrap()
+                .unwrap();
+        match &bank_transfer_steps_and_charges_details.bank_transfer_instructions {
+            api_models::payments::BankTransferInstructions::DokuBankTransferInstructions(
+                instructions,
+            ) => assert_eq!(instructions.expires_at, Some(1_918_684_800_000)),
+            _ => panic!("expected doku bank transfer instructions"),
+        }
+
+        let next_action = serde_json::to_value(
+            api_models::payments::NextActionData::DisplayBankTransferInformation {
+                bank_transfer_steps_and_charges_details,
+            },
+        )
+        .unwrap();
+        assert_eq!(next_action["type"], "display_bank_transfer_information");
+    }
+
+    #[test]
+    fn test_payment_instructions_omitted_without_connector_metadata() {
+        assert!(
+            parse_payment_instructions::<api_models::payments::VoucherNextStepData>(None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    /// Payment waiting on the customer for an external 3DS authentication whose ACS returned
+    /// the 3DS method url and data
+    #[cfg(feature = "v1")]
+    fn get_three_ds_invoke_payment_data(
+        authentication_type: enums::AuthenticationType,
+    ) -> PaymentData<api::Authorize> {
+        let payment_id = common_utils::id_type::PaymentId::try_from(std::borrow::Cow::from(
+            "pay_three_ds_method",
+        ))
+        .unwrap();
+        let merchant_id =
+            common_utils::id_type::MerchantId::try_from(std::borrow::Cow::from("merchant_1"))
+                .unwrap();
+        let payment_intent = storage::PaymentIntent {
+            payment_id,
+            merchant_id,
+            status: enums::IntentStatus::RequiresCustomerAction,
+            amount: MinorUnit::new(200),
+            currency: Some(Currency::USD),
+            amount_captured: None,
+            customer_id: None,
+            description: None,
+            return_url: None,
+            metadata: None,
+            connector_id: None,
+            shipping_address_id: None,
+            billing_address_id: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            created_at: common_utils::date_time::now(),
+            modified_at: common_utils::date_time::now(),
+            last_synced: None,
+            setup_future_usage: None,
+            fingerprint_id: None,
+            off_session: None,
+            client_secret: None,
+            active_attempt: hyperswitch_domain_models::RemoteStorageObject::ForeignID(
+                "pay_three_ds_method_1".to_string(),
+            ),
+            business_country: None,
+            business_label: None,
+            order_details: None,
+            allowed_payment_method_types: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            attempt_count: 1,
+            payment_link_id: None,
+            profile_id: None,
+            merchant_decision: None,
+            payment_confirm_source: None,
+            surcharge_applicable: None,
+            updated_by: enums::MerchantStorageScheme::PostgresOnly.to_string(),
+            request_incremental_authorization: None,
+            incremental_authorization_allowed: None,
+            authorization_count: None,
+            session_expiry: None,
+            request_external_three_ds_authentication: Some(true),
+            charges: None,
+            frm_metadata: None,
+            customer_details: None,
+            billing_details: None,
+            merchant_order_reference_id: None,
+            shipping_details: None,
+            is_payment_processor_token_flow: None,
+            organization_id: common_utils::id_type::OrganizationId::default(),
+            shipping_cost: None,
+            tax_details: None,
+            skip_external_tax_calculation: None,
+            skip_save_payment_method: None,
+        };
+        let payment_attempt: storage::PaymentAttempt = serde_json::from_value(serde_json::json!({
+            "payment_id": "pay_three_ds_method",
+            "merchant_id": "merchant_1",
+            "attempt_id": "pay_three_ds_method_1",
+            "status": "authentication_pending",
+            "amount": 200,
+            "net_amount": 200,
+            "currency": "USD",
+            "connector": "cybersource",
+            "authentication_type": authentication_type,
+            "confirm": true,
+            "created_at": "2024-10-17T12:00:00Z",
+            "modified_at": "2024-10-17T12:00:00Z",
+            "amount_capturable": 200,
+            "updated_by": "postgres_only",
+            "profile_id": "pro_three_ds_method",
+            "organization_id": "org_three_ds_method",
+        }))
+        .unwrap();
+        let mut authentication: storage::Authentication =
+            serde_json::from_value(serde_json::json!({
+                "authentication_id": "authn_three_ds_method",
+                "merchant_id": "merchant_1",
+                "authentication_connector": "threedsecureio",
+                "payment_method_id": "pm_three_ds_method",
+                "authentication_status": "pending",
+                "authentication_lifecycle_status": "unused",
+                "created_at": "2024-10-17T12:00:00Z",
+                "modified_at": "2024-10-17T12:00:00Z",
+                "three_ds_method_url": "https://acs.example.com/3ds-method",
+                "three_ds_method_data": "eyJ0aHJlZURTU2VydmVyVHJhbnNJRCI6IjEyMyJ9",
+                "profile_id": "pro_three_ds_method",
+                "payment_id": "pay_three_ds_method",
+                "merchant_connector_id": "mca_three_ds_method",
+            }))
+            .unwrap();
+        authentication.maximum_supported_version =
+            Some(common_utils::types::SemanticVersion::new(2, 2, 0));
+
+        PaymentData {
+            flow: PhantomData,
+            payment_intent,
+            payment_attempt,
+            multiple_capture_data: None,
+            amount: api::Amount::Zero,
+            mandate_id: None,
+            mandate_connector: None,
+            currency: Currency::USD,
+            setup_mandate: None,
+            customer_acceptance: None,
+            address: hyperswitch_domain_models::payment_address::PaymentAddress::default(),
+            token: None,
+            token_data: None,
+            confirm: Some(true),
+            force_sync: None,
+            payment_method_data: None,
+            payment_method_info: None,
+            refunds: vec![],
+            disputes: vec![],
+            attempts: None,
+            sessions_token: vec![],
+            card_cvc: None,
+            email: None,
+            creds_identifier: None,
+            pm_token: None,
+            connector_customer_id: None,
+            recurring_mandate_payment_data: None,
+            ephemeral_key: None,
+            redirect_response: None,
+            surcharge_details: None,
+            frm_message: None,
+            payment_link_data: None,
+            incremental_authorization_details: None,
+            authorizations: vec![],
+            authentication: Some(authentication),
+            recurring_details: None,
+            poll_config: None,
+            tax_data: None,
+            should_return_payment_method_token: false,
+            matched_routing_rule: None,
+            confirm_response_masking_policy: None,
+            skip_save_payment_method: false,
+        }
+    }
+
+    #[cfg(feature = "v1")]
+    fn get_confirm_three_ds_method_details(
+        authentication_type: enums::AuthenticationType,
+    ) -> api_models::payments::ThreeDsMethodData {
+        let response = payments_to_payments_response(
+            get_three_ds_invoke_payment_data(authentication_type),
+            None,
+            None,
+            services::AuthFlow::Merchant,
+            "https://sandbox.hyperswitch.io",
+            &payments::PaymentConfirm,
+            &ConnectorRequestReferenceIdConfig::default(),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        match response {
+            services::ApplicationResponse::JsonWithHeaders((payments_response, _)) => {
+                match payments_response.next_action {
+                    Some(api_models::payments::NextActionData::ThreeDsInvoke { three_ds_data }) => {
+                        three_ds_data.three_ds_method_details
+                    }
+                    _ => panic!("expected a three_ds_invoke next action"),
+                }
+            }
+            _ => panic!("expected a json payments response"),
+        }
+    }
+
+    #[cfg(feature = "v1")]
+    #[test]
+    fn test_three_ds_confirm_returns_three_ds_method_details() {
+        assert_eq!(
+            get_confirm_three_ds_method_details(enums::AuthenticationType::ThreeDs),
+            api_models::payments::ThreeDsMethodData::AcsThreeDsMethodData {
+                three_ds_method_data_submission: true,
+                three_ds_method_data: Some("eyJ0aHJlZURTU2VydmVyVHJhbnNJRCI6IjEyMyJ9".to_string()),
+                three_ds_method_url: Some("https://acs.example.com/3ds-method".to_string()),
+            }
+        );
+    }
+
+    #[cfg(feature = "v1")]
+    #[test]
+    fn test_non_three_ds_confirm_omits_three_ds_method_details() {
+        assert_eq!(
+            get_confirm_three_ds_method_details(enums::AuthenticationType::NoThreeDs),
+            api_models::payments::ThreeDsMethodData::AcsThreeDsMethodData {
+                three_ds_method_data_submission: false,
+                three_ds_method_data: None,
+                three_ds_method_url: None,
+            }
+        );
+    }
+}
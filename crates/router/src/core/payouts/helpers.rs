@@ -221,6 +221,7 @@ pub async fn save_payout_data_to_locker(
                     card_network: None,
                     card_issuer: None,
                     card_type: None,
+                    preferred_network: None,
                 };
                 let payload = StoreLockerReq::LockerCard(StoreCardReq {
                     merchant_id: merchant_account.get_id().clone(),
@@ -460,6 +461,8 @@ pub async fn save_payout_data_to_locker(
                             card_network: card_info.card_network,
                             card_type: card_info.card_type,
                             saved_to_locker: true,
+                            card_networks: None,
+                            preferred_network: None,
                         },
                     )
                 })
@@ -480,6 +483,8 @@ pub async fn save_payout_data_to_locker(
                             card_network: None,
                             card_type: None,
                             saved_to_locker: true,
+                            card_networks: None,
+                            preferred_network: None,
                         },
                     )
                 });
@@ -80,7 +80,8 @@ pub async fn do_gsm_multiple_connector_actions(
                     ),
                 }))?
             }
-            api_models::gsm::GsmDecision::DoDefault => break,
+            api_models::gsm::GsmDecision::DoDefault
+            | api_models::gsm::GsmDecision::SoftThreeDsFailure => break,
         }
     }
     Ok(())
@@ -144,7 +145,8 @@ pub async fn do_gsm_single_connector_actions(
                     ),
                 }))?
             }
-            api_models::gsm::GsmDecision::DoDefault => break,
+            api_models::gsm::GsmDecision::DoDefault
+            | api_models::gsm::GsmDecision::SoftThreeDsFailure => break,
         }
     }
     Ok(())
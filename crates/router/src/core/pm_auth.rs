@@ -354,6 +354,8 @@ async fn store_bank_details_in_payment_methods(
             &customer_id,
             merchant_account.get_id(),
             common_enums::enums::PaymentMethodStatus::Active,
+            &Default::default(),
+            None,
             None,
             merchant_account.storage_scheme,
         )
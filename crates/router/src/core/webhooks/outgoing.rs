@@ -62,8 +62,11 @@ pub(crate) async fn create_event_and_trigger_outgoing_webhook(
     primary_object_created_at: Option<time::PrimitiveDateTime>,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
     let delivery_attempt = enums::WebhookDeliveryAttempt::InitialAttempt;
-    let idempotent_event_id =
-        utils::get_idempotent_event_id(&primary_object_id, event_type, delivery_attempt);
+    let idempotent_event_id = utils::get_idempotent_event_id(
+        &utils::get_idempotent_object_id(&primary_object_id, &content),
+        event_type,
+        delivery_attempt,
+    );
     let webhook_url_result = get_webhook_url_from_business_profile(&business_profile);
 
     if !state.conf.webhooks.outgoing_enabled
@@ -631,8 +634,12 @@ pub(crate) fn get_outgoing_webhook_request(
                     .map(|(key, value)| (key.clone(), value.clone().into_masked())),
             );
         };
+        let status_mapping = business_profile
+            .webhook_details
+            .as_ref()
+            .and_then(|webhook_details| webhook_details.status_mapping.as_ref());
         let outgoing_webhooks_signature = transformed_outgoing_webhook
-            .get_outgoing_webhooks_signature(payment_response_hash_key)?;
+            .get_outgoing_webhooks_signature(payment_response_hash_key, status_mapping)?;
 
         if let Some(signature) = outgoing_webhooks_signature.signature {
             WebhookType::add_webhook_header(&mut headers, signature)
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use api_models::webhooks;
 use common_utils::{crypto::SignMessage, ext_traits::Encode};
 use error_stack::ResultExt;
@@ -14,9 +16,12 @@ pub struct OutgoingWebhookPayloadWithSignature {
 pub trait OutgoingWebhookType:
     Serialize + From<webhooks::OutgoingWebhook> + Sync + Send + std::fmt::Debug + 'static
 {
+    /// Serialize the webhook and sign it with the payment response hash key. The status mapping
+    /// of the profile is applied to the serialized payload, if the webhook format supports it.
     fn get_outgoing_webhooks_signature(
         &self,
         payment_response_hash_key: Option<impl AsRef<[u8]>>,
+        status_mapping: Option<&HashMap<String, String>>,
     ) -> errors::CustomResult<OutgoingWebhookPayloadWithSignature, errors::WebhooksFlowError>;
 
     fn add_webhook_header(header: &mut Vec<(String, Maskable<String>)>, signature: String);
@@ -26,9 +31,19 @@ impl OutgoingWebhookType for webhooks::OutgoingWebhook {
     fn get_outgoing_webhooks_signature(
         &self,
         payment_response_hash_key: Option<impl AsRef<[u8]>>,
+        status_mapping: Option<&HashMap<String, String>>,
     ) -> errors::CustomResult<OutgoingWebhookPayloadWithSignature, errors::WebhooksFlowError> {
-        let webhook_signature_payload = self
-            .encode_to_string_of_json()
+        let webhook_signature_payload =
+            match status_mapping.filter(|status_mapping| !status_mapping.is_empty()) {
+                Some(status_mapping) => self
+                    .encode_to_value()
+                    .map(|mut payload| {
+                        apply_status_mapping(&mut payload, status_mapping);
+                        payload
+                    })
+                    .and_then(|payload| payload.encode_to_string_of_json()),
+                None => self.encode_to_string_of_json(),
+            }
             .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
             .attach_printable("failed encoding outgoing webhook payload")?;
 
@@ -56,6 +71,27 @@ impl OutgoingWebhookType for webhooks::OutgoingWebhook {
     }
 }
 
+/// Replace the status in a payment webhook payload with the name configured for it in the status
+/// mapping. Statuses that are not mapped, and webhooks for other objects, are left unchanged.
+fn apply_status_mapping(payload: &mut serde_json::Value, status_mapping: &HashMap<String, String>) {
+    if payload
+        .pointer("/content/type")
+        .and_then(serde_json::Value::as_str)
+        != Some("payment_details")
+    {
+        return;
+    }
+
+    if let Some(status) = payload.pointer_mut("/content/object/status") {
+        if let Some(mapped_status) = status
+            .as_str()
+            .and_then(|status| status_mapping.get(status))
+        {
+            *status = serde_json::Value::String(mapped_status.clone());
+        }
+    }
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub(crate) struct OutgoingWebhookTrackingData {
     pub(crate) merchant_id: common_utils::id_type::MerchantId,
@@ -66,3 +102,51 @@ pub(crate) struct OutgoingWebhookTrackingData {
     pub(crate) primary_object_type: enums::EventObjectType,
     pub(crate) initial_attempt_id: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_mapping() -> HashMap<String, String> {
+        HashMap::from([
+            ("succeeded".to_string(), "PAID".to_string()),
+            ("failed".to_string(), "DECLINED".to_string()),
+        ])
+    }
+
+    fn webhook_payload(content_type: &str, status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "event_type": "payment_succeeded",
+            "content": {
+                "type": content_type,
+                "object": {
+                    "payment_id": "pay_mbabizu24mvu3mela5njyhpit4",
+                    "status": status,
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn test_mapped_payment_status_is_translated() {
+        let mut payload = webhook_payload("payment_details", "succeeded");
+
+        apply_status_mapping(&mut payload, &status_mapping());
+
+        assert_eq!(payload, webhook_payload("payment_details", "PAID"));
+    }
+
+    #[test]
+    fn test_unmapped_status_is_sent_as_is() {
+        let mut payload = webhook_payload("payment_details", "requires_capture");
+        apply_status_mapping(&mut payload, &status_mapping());
+        assert_eq!(
+            payload,
+            webhook_payload("payment_details", "requires_capture")
+        );
+
+        let mut payload = webhook_payload("refund_details", "succeeded");
+        apply_status_mapping(&mut payload, &status_mapping());
+        assert_eq!(payload, webhook_payload("refund_details", "succeeded"));
+    }
+}
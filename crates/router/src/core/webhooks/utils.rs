@@ -145,7 +145,212 @@ pub(crate) fn get_idempotent_event_id(
     }
 }
 
+/// Returns the object that the initial delivery of an outgoing webhook is deduplicated on.
+///
+/// A payment can go through multiple attempts, each of which notifies the merchant of its outcome,
+/// so payment events are deduplicated on the payment attempt they describe instead of the payment.
+#[inline]
+pub(crate) fn get_idempotent_object_id(
+    primary_object_id: &str,
+    content: &api::OutgoingWebhookContent,
+) -> String {
+    match content {
+        api::OutgoingWebhookContent::PaymentDetails(payments_response) => {
+            payments_response.attempt_id.clone()
+        }
+        _ => primary_object_id.to_owned(),
+    }
+}
+
 #[inline]
 pub(crate) fn generate_event_id() -> String {
     common_utils::generate_time_ordered_id("evt")
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::types::storage::enums::{EventType, WebhookDeliveryAttempt};
+
+    #[test]
+    fn test_initial_delivery_deduplicated_per_payment_attempt() {
+        let payment_id = common_utils::id_type::PaymentId::try_from(std::borrow::Cow::Borrowed(
+            "pay_mbabizu24mvu3mela5njyhpit4",
+        ))
+        .unwrap();
+        let first_attempt_event_id = get_idempotent_event_id(
+            &payment_id.get_attempt_id(1),
+            EventType::PaymentFailed,
+            WebhookDeliveryAttempt::InitialAttempt,
+        );
+
+        assert_eq!(
+            first_attempt_event_id,
+            get_idempotent_event_id(
+                &payment_id.get_attempt_id(1),
+                EventType::PaymentFailed,
+                WebhookDeliveryAttempt::InitialAttempt,
+            )
+        );
+        assert_ne!(
+            first_attempt_event_id,
+            get_idempotent_event_id(
+                &payment_id.get_attempt_id(2),
+                EventType::PaymentFailed,
+                WebhookDeliveryAttempt::InitialAttempt,
+            )
+        );
+    }
+
+    fn get_payment_details_content(
+        payment_id: &common_utils::id_type::PaymentId,
+        attempt_id: String,
+    ) -> api::OutgoingWebhookContent {
+        api::OutgoingWebhookContent::PaymentDetails(api_models::payments::PaymentsResponse {
+            payment_id: payment_id.clone(),
+            merchant_id: common_utils::id_type::MerchantId::try_from(std::borrow::Cow::Borrowed(
+                "merchant_1",
+            ))
+            .unwrap(),
+            status: common_enums::IntentStatus::Failed,
+            amount: common_utils::types::MinorUnit::new(6540),
+            net_amount: common_utils::types::MinorUnit::new(6540),
+            amount_capturable: common_utils::types::MinorUnit::new(0),
+            amount_received: None,
+            connector: None,
+            client_secret: None,
+            created: None,
+            currency: "USD".to_string(),
+            customer_id: None,
+            customer: None,
+            description: None,
+            refunds: None,
+            disputes: None,
+            attempts: None,
+            captures: None,
+            mandate_id: None,
+            mandate_data: None,
+            mandate_created: None,
+            setup_future_usage: None,
+            off_session: None,
+            capture_on: None,
+            capture_method: None,
+            payment_method: None,
+            payment_method_data: None,
+            payment_token: None,
+            shipping: None,
+            billing: None,
+            order_details: None,
+            email: None,
+            name: None,
+            phone: None,
+            return_url: None,
+            authentication_type: None,
+            statement_descriptor_name: None,
+            statement_descriptor_suffix: None,
+            next_action: None,
+            cancellation_reason: None,
+            error_code: None,
+            error_message: None,
+            unified_code: None,
+            unified_message: None,
+            payment_experience: None,
+            payment_method_type: None,
+            connector_label: None,
+            business_country: None,
+            business_label: None,
+            business_sub_label: None,
+            allowed_payment_method_types: None,
+            ephemeral_key: None,
+            manual_retry_allowed: None,
+            connector_transaction_id: None,
+            frm_message: None,
+            metadata: None,
+            connector_metadata: None,
+            feature_metadata: None,
+            reference_id: None,
+            payment_link: None,
+            profile_id: None,
+            surcharge_details: None,
+            attempt_count: 2,
+            attempt_id,
+            merchant_decision: None,
+            merchant_connector_id: None,
+            incremental_authorization_allowed: None,
+            authorization_count: None,
+            incremental_authorizations: None,
+            external_authentication_details: None,
+            external_3ds_authentication_attempted: None,
+            expires_on: None,
+            fingerprint: None,
+            browser_info: None,
+            payment_method_id: None,
+            payment_method_status: None,
+            updated: None,
+            charges: None,
+            frm_metadata: None,
+            merchant_order_reference_id: None,
+            order_tax_amount: None,
+            connector_mandate_id: None,
+            payment_method_token_reference: None,
+            matched_routing_rule: None,
+            connector_risk_score: None,
+            connector_risk_decision: None,
+            connector_fee_amount: None,
+            connector_fee_currency: None,
+            card_funding_type: None,
+            liability_shift: None,
+        })
+    }
+
+    #[test]
+    fn test_payment_webhooks_deduplicated_on_payment_attempt() {
+        let payment_id = common_utils::id_type::PaymentId::try_from(std::borrow::Cow::Borrowed(
+            "pay_mbabizu24mvu3mela5njyhpit4",
+        ))
+        .unwrap();
+        let primary_object_id = payment_id.get_string_repr();
+
+        // The payment has two attempts, the event of each one is keyed on that attempt
+        assert_eq!(
+            get_idempotent_object_id(
+                primary_object_id,
+                &get_payment_details_content(&payment_id, payment_id.get_attempt_id(1))
+            ),
+            "pay_mbabizu24mvu3mela5njyhpit4_1"
+        );
+        assert_eq!(
+            get_idempotent_object_id(
+                primary_object_id,
+                &get_payment_details_content(&payment_id, payment_id.get_attempt_id(2))
+            ),
+            "pay_mbabizu24mvu3mela5njyhpit4_2"
+        );
+        assert_eq!(
+            get_idempotent_object_id(
+                "man_mbabizu24mvu3mela5njyhpit4",
+                &api::OutgoingWebhookContent::MandateDetails(Box::default())
+            ),
+            "man_mbabizu24mvu3mela5njyhpit4"
+        );
+    }
+
+    #[test]
+    fn test_retried_delivery_gets_new_idempotent_event_id() {
+        let initial_event_id = get_idempotent_event_id(
+            "pay_mbabizu24mvu3mela5njyhpit4_1",
+            EventType::PaymentFailed,
+            WebhookDeliveryAttempt::InitialAttempt,
+        );
+        let retry_event_id = get_idempotent_event_id(
+            "pay_mbabizu24mvu3mela5njyhpit4_1",
+            EventType::PaymentFailed,
+            WebhookDeliveryAttempt::AutomaticRetry,
+        );
+
+        assert_ne!(initial_event_id, retry_event_id);
+        assert!(retry_event_id.starts_with(&initial_event_id));
+    }
+}
@@ -26,6 +26,7 @@ pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
 pub mod organization;
+pub mod payment_attempt_status_timeline;
 pub mod payment_link;
 pub mod payment_method;
 pub mod refund;
@@ -103,6 +104,7 @@ pub trait StorageInterface:
     + merchant_connector_account::ConnectorAccessToken
     + merchant_connector_account::MerchantConnectorAccountInterface
     + PaymentAttemptInterface
+    + payment_attempt_status_timeline::PaymentAttemptStatusTimelineInterface
     + PaymentIntentInterface
     + payment_method::PaymentMethodInterface
     + blocklist::BlocklistInterface
@@ -1630,6 +1630,28 @@ impl PaymentAttemptInterface for KafkaStore {
             .find_attempts_by_merchant_id_payment_id(merchant_id, payment_id, storage_scheme)
             .await
     }
+
+    #[cfg(all(feature = "v1", feature = "olap"))]
+    async fn filter_payment_attempts_by_statuses_time_range(
+        &self,
+        merchant_id: &id_type::MerchantId,
+        statuses: &[common_enums::AttemptStatus],
+        time_range: &common_utils::types::TimeRange,
+        limit: i64,
+        offset: i64,
+        storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<Vec<storage::PaymentAttempt>, errors::DataStorageError> {
+        self.diesel_store
+            .filter_payment_attempts_by_statuses_time_range(
+                merchant_id,
+                statuses,
+                time_range,
+                limit,
+                offset,
+                storage_scheme,
+            )
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -1721,6 +1743,28 @@ impl PaymentIntentInterface for KafkaStore {
             .await
     }
 
+    #[cfg(feature = "v1")]
+    async fn get_customer_captured_amount(
+        &self,
+        merchant_id: &id_type::MerchantId,
+        customer_id: &id_type::CustomerId,
+        currency: common_enums::Currency,
+        statuses: Vec<common_enums::IntentStatus>,
+        time_range: &common_utils::types::TimeRange,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<common_utils::types::MinorUnit, errors::DataStorageError> {
+        self.diesel_store
+            .get_customer_captured_amount(
+                merchant_id,
+                customer_id,
+                currency,
+                statuses,
+                time_range,
+                storage_scheme,
+            )
+            .await
+    }
+
     #[cfg(all(feature = "olap", feature = "v1"))]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -1890,7 +1934,9 @@ impl PaymentMethodInterface for KafkaStore {
         customer_id: &id_type::CustomerId,
         merchant_id: &id_type::MerchantId,
         status: common_enums::PaymentMethodStatus,
+        filters: &diesel_models::payment_method::PaymentMethodListFilters,
         limit: Option<i64>,
+        offset: Option<i64>,
         storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<domain::PaymentMethod>, errors::StorageError> {
         self.diesel_store
@@ -1900,7 +1946,9 @@ impl PaymentMethodInterface for KafkaStore {
                 customer_id,
                 merchant_id,
                 status,
+                filters,
                 limit,
+                offset,
                 storage_scheme,
             )
             .await
@@ -0,0 +1,203 @@
+use error_stack::{report, ResultExt};
+use router_env::{instrument, tracing};
+use storage_impl::MockDb;
+
+use super::Store;
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    db::kafka_store::KafkaStore,
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait PaymentAttemptStatusTimelineInterface {
+    async fn insert_payment_attempt_status_timeline_entry(
+        &self,
+        timeline_entry: storage::PaymentAttemptStatusTimelineNew,
+    ) -> CustomResult<storage::PaymentAttemptStatusTimeline, errors::StorageError>;
+
+    async fn find_payment_attempt_status_timeline_by_merchant_id_attempt_id(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        attempt_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentAttemptStatusTimeline>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl PaymentAttemptStatusTimelineInterface for Store {
+    #[instrument(skip_all)]
+    async fn insert_payment_attempt_status_timeline_entry(
+        &self,
+        timeline_entry: storage::PaymentAttemptStatusTimelineNew,
+    ) -> CustomResult<storage::PaymentAttemptStatusTimeline, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        timeline_entry
+            .insert(&conn)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    #[instrument(skip_all)]
+    async fn find_payment_attempt_status_timeline_by_merchant_id_attempt_id(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        attempt_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentAttemptStatusTimeline>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::PaymentAttemptStatusTimeline::find_all_by_merchant_id_attempt_id(
+            &conn,
+            merchant_id,
+            attempt_id,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentAttemptStatusTimelineInterface for MockDb {
+    async fn insert_payment_attempt_status_timeline_entry(
+        &self,
+        timeline_entry: storage::PaymentAttemptStatusTimelineNew,
+    ) -> CustomResult<storage::PaymentAttemptStatusTimeline, errors::StorageError> {
+        let mut timeline = self.payment_attempt_status_timeline.lock().await;
+        let timeline_entry = storage::PaymentAttemptStatusTimeline {
+            id: i32::try_from(timeline.len()).change_context(errors::StorageError::MockDbError)?,
+            merchant_id: timeline_entry.merchant_id,
+            payment_id: timeline_entry.payment_id,
+            attempt_id: timeline_entry.attempt_id,
+            previous_status: timeline_entry.previous_status,
+            status: timeline_entry.status,
+            created_at: timeline_entry.created_at,
+        };
+        timeline.push(timeline_entry.clone());
+        Ok(timeline_entry)
+    }
+
+    async fn find_payment_attempt_status_timeline_by_merchant_id_attempt_id(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        attempt_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentAttemptStatusTimeline>, errors::StorageError> {
+        let mut timeline: Vec<_> = self
+            .payment_attempt_status_timeline
+            .lock()
+            .await
+            .iter()
+            .filter(|timeline_entry| {
+                timeline_entry.merchant_id == *merchant_id
+                    && timeline_entry.attempt_id == attempt_id
+            })
+            .cloned()
+            .collect();
+        timeline.sort_by_key(|timeline_entry| timeline_entry.created_at);
+        Ok(timeline)
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentAttemptStatusTimelineInterface for KafkaStore {
+    #[instrument(skip_all)]
+    async fn insert_payment_attempt_status_timeline_entry(
+        &self,
+        timeline_entry: storage::PaymentAttemptStatusTimelineNew,
+    ) -> CustomResult<storage::PaymentAttemptStatusTimeline, errors::StorageError> {
+        self.diesel_store
+            .insert_payment_attempt_status_timeline_entry(timeline_entry)
+            .await
+    }
+
+    #[instrument(skip_all)]
+    async fn find_payment_attempt_status_timeline_by_merchant_id_attempt_id(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        attempt_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentAttemptStatusTimeline>, errors::StorageError> {
+        self.diesel_store
+            .find_payment_attempt_status_timeline_by_merchant_id_attempt_id(merchant_id, attempt_id)
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "v1"))]
+mod tests {
+    use diesel_models::enums;
+
+    use crate::{
+        core::payments::helpers::record_payment_attempt_status_transition,
+        db::{
+            payment_attempt_status_timeline::PaymentAttemptStatusTimelineInterface, MockDb,
+            PaymentAttemptInterface,
+        },
+        types::storage,
+    };
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_status_transitions_of_payment_attempt_are_recorded() {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let storage_scheme = enums::MerchantStorageScheme::PostgresOnly;
+
+        let payment_attempt: storage::PaymentAttemptNew =
+            serde_json::from_value(serde_json::json!({
+                "payment_id": "pay_timeline",
+                "merchant_id": "merchant1",
+                "attempt_id": "pay_timeline_1",
+                "status": "authorized",
+                "amount": 100,
+                "net_amount": 100,
+                "confirm": true,
+                "amount_capturable": 100,
+                "updated_by": "postgres_only",
+                "profile_id": "pro_timeline",
+                "organization_id": "org_timeline",
+            }))
+            .unwrap();
+        let payment_attempt = mockdb
+            .insert_payment_attempt(payment_attempt, storage_scheme)
+            .await
+            .unwrap();
+
+        // Void of the authorized payment, recorded the way the cancel operation does
+        let previous_status = payment_attempt.status;
+        let payment_attempt = mockdb
+            .update_payment_attempt_with_attempt_id(
+                payment_attempt,
+                storage::PaymentAttemptUpdate::VoidUpdate {
+                    status: enums::AttemptStatus::Voided,
+                    cancellation_reason: None,
+                    updated_by: storage_scheme.to_string(),
+                },
+                storage_scheme,
+            )
+            .await
+            .unwrap();
+        record_payment_attempt_status_transition(&mockdb, previous_status, &payment_attempt).await;
+        // Updates that leave the status unchanged are not recorded
+        record_payment_attempt_status_transition(&mockdb, payment_attempt.status, &payment_attempt)
+            .await;
+
+        let timeline = mockdb
+            .find_payment_attempt_status_timeline_by_merchant_id_attempt_id(
+                &payment_attempt.merchant_id,
+                &payment_attempt.attempt_id,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            timeline
+                .iter()
+                .map(|entry| (entry.previous_status, entry.status))
+                .collect::<Vec<_>>(),
+            vec![(
+                enums::AttemptStatus::Authorized,
+                enums::AttemptStatus::Voided
+            )]
+        );
+    }
+}
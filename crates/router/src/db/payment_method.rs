@@ -12,6 +12,31 @@ use crate::{
     },
 };
 
+/// Orders the payment methods the way the database query does, most recently used first, and
+/// takes the requested page out of them.
+fn paginate_payment_methods(
+    mut payment_methods: Vec<storage_types::PaymentMethod>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Vec<storage_types::PaymentMethod> {
+    payment_methods.sort_by(|a, b| {
+        (b.last_used_at, &b.payment_method_id).cmp(&(a.last_used_at, &a.payment_method_id))
+    });
+    payment_methods
+        .into_iter()
+        .skip(
+            offset
+                .and_then(|offset| usize::try_from(offset).ok())
+                .unwrap_or(0),
+        )
+        .take(
+            limit
+                .and_then(|limit| usize::try_from(limit).ok())
+                .unwrap_or(usize::MAX),
+        )
+        .collect()
+}
+
 #[async_trait::async_trait]
 pub trait PaymentMethodInterface {
     #[cfg(all(
@@ -78,7 +103,9 @@ pub trait PaymentMethodInterface {
         customer_id: &id_type::CustomerId,
         merchant_id: &id_type::MerchantId,
         status: common_enums::PaymentMethodStatus,
+        filters: &diesel_models::payment_method::PaymentMethodListFilters,
         limit: Option<i64>,
+        offset: Option<i64>,
         storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<domain::PaymentMethod>, errors::StorageError>;
 
@@ -716,17 +743,29 @@ mod storage {
             customer_id: &id_type::CustomerId,
             merchant_id: &id_type::MerchantId,
             status: common_enums::PaymentMethodStatus,
+            filters: &diesel_models::payment_method::PaymentMethodListFilters,
             limit: Option<i64>,
+            offset: Option<i64>,
             storage_scheme: MerchantStorageScheme,
         ) -> CustomResult<Vec<domain::PaymentMethod>, errors::StorageError> {
             let conn = connection::pg_connection_read(self).await?;
+            // Rows in redis have to be merged with the rows in the database before a page can be
+            // taken, so the database is read from the start when KV is enabled
+            let (db_limit, db_offset) = match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => (limit, offset),
+                MerchantStorageScheme::RedisKv => {
+                    (limit.map(|limit| limit + offset.unwrap_or(0)), None)
+                }
+            };
             let database_call = || async {
                 storage_types::PaymentMethod::find_by_customer_id_merchant_id_status(
                     &conn,
                     customer_id,
                     merchant_id,
                     status,
-                    limit,
+                    filters,
+                    db_limit,
+                    db_offset,
                 )
                 .await
                 .map_err(|error| report!(errors::StorageError::from(error)))
@@ -753,7 +792,11 @@ mod storage {
                         kv_result.map(|payment_methods| {
                             payment_methods
                                 .into_iter()
-                                .filter(|pm| pm.status == status)
+                                .filter(|pm| {
+                                    pm.status == status
+                                        && filters
+                                            .matches(pm.payment_method, pm.payment_method_type)
+                                })
                                 .collect()
                         })
                     };
@@ -761,9 +804,12 @@ mod storage {
                     Box::pin(db_utils::find_all_combined_kv_database(
                         redis_fut,
                         database_call,
-                        limit,
+                        None,
                     ))
                     .await
+                    .map(|payment_methods| {
+                        super::paginate_payment_methods(payment_methods, limit, offset)
+                    })
                 }
             }?;
 
@@ -1133,7 +1179,9 @@ mod storage {
             customer_id: &id_type::CustomerId,
             merchant_id: &id_type::MerchantId,
             status: common_enums::PaymentMethodStatus,
+            filters: &diesel_models::payment_method::PaymentMethodListFilters,
             limit: Option<i64>,
+            offset: Option<i64>,
             _storage_scheme: MerchantStorageScheme,
         ) -> CustomResult<Vec<domain::PaymentMethod>, errors::StorageError> {
             let conn = connection::pg_connection_read(self).await?;
@@ -1143,7 +1191,9 @@ mod storage {
                     customer_id,
                     merchant_id,
                     status,
+                    filters,
                     limit,
+                    offset,
                 )
                 .await
                 .map_err(|error| report!(errors::StorageError::from(error)))?;
@@ -1443,19 +1493,26 @@ impl PaymentMethodInterface for MockDb {
         customer_id: &id_type::CustomerId,
         merchant_id: &id_type::MerchantId,
         status: common_enums::PaymentMethodStatus,
-        _limit: Option<i64>,
+        filters: &diesel_models::payment_method::PaymentMethodListFilters,
+        limit: Option<i64>,
+        offset: Option<i64>,
         _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<domain::PaymentMethod>, errors::StorageError> {
         let payment_methods = self.payment_methods.lock().await;
-        let payment_methods_found: Vec<storage_types::PaymentMethod> = payment_methods
-            .iter()
-            .filter(|pm| {
-                pm.customer_id == *customer_id
-                    && pm.merchant_id == *merchant_id
-                    && pm.status == status
-            })
-            .cloned()
-            .collect();
+        let payment_methods_found = paginate_payment_methods(
+            payment_methods
+                .iter()
+                .filter(|pm| {
+                    pm.customer_id == *customer_id
+                        && pm.merchant_id == *merchant_id
+                        && pm.status == status
+                        && filters.matches(pm.payment_method, pm.payment_method_type)
+                })
+                .cloned()
+                .collect(),
+            limit,
+            offset,
+        );
 
         if payment_methods_found.is_empty() {
             Err(
@@ -1624,3 +1681,94 @@ impl PaymentMethodInterface for MockDb {
         }
     }
 }
+
+#[cfg(all(
+    test,
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::borrow::Cow;
+
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn payment_method(
+        payment_method_id: &str,
+        last_used_at: time::PrimitiveDateTime,
+    ) -> storage_types::PaymentMethod {
+        storage_types::PaymentMethod {
+            customer_id: id_type::CustomerId::try_from(Cow::from("cus_1")).unwrap(),
+            merchant_id: id_type::MerchantId::try_from(Cow::from("merchant_1")).unwrap(),
+            payment_method_id: payment_method_id.to_string(),
+            accepted_currency: None,
+            scheme: None,
+            token: None,
+            cardholder_name: None,
+            issuer_name: None,
+            issuer_country: None,
+            payer_country: None,
+            is_stored: None,
+            swift_code: None,
+            direct_debit_token: None,
+            created_at: last_used_at,
+            last_modified: last_used_at,
+            payment_method: None,
+            payment_method_type: None,
+            payment_method_issuer: None,
+            payment_method_issuer_code: None,
+            metadata: None,
+            payment_method_data: None,
+            locker_id: None,
+            last_used_at,
+            connector_mandate_details: None,
+            customer_acceptance: None,
+            status: common_enums::PaymentMethodStatus::Active,
+            network_transaction_id: None,
+            client_secret: None,
+            payment_method_billing_address: None,
+            updated_by: None,
+            version: common_enums::ApiVersion::V1,
+            network_token_requestor_reference_id: None,
+            network_token_locker_id: None,
+            network_token_payment_method_data: None,
+        }
+    }
+
+    #[test]
+    fn test_payment_methods_are_paged_most_recently_used_first() {
+        let payment_methods = vec![
+            payment_method("pm_a", datetime!(2024-01-01 00:00)),
+            payment_method("pm_b", datetime!(2024-03-01 00:00)),
+            payment_method("pm_c", datetime!(2024-01-01 00:00)),
+            payment_method("pm_d", datetime!(2024-02-01 00:00)),
+        ];
+        let ids = |payment_methods: Vec<storage_types::PaymentMethod>| {
+            payment_methods
+                .into_iter()
+                .map(|pm| pm.payment_method_id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ids(paginate_payment_methods(
+                payment_methods.clone(),
+                None,
+                None
+            )),
+            vec!["pm_b", "pm_d", "pm_c", "pm_a"]
+        );
+        assert_eq!(
+            ids(paginate_payment_methods(
+                payment_methods.clone(),
+                Some(2),
+                Some(1)
+            )),
+            vec!["pm_d", "pm_c"]
+        );
+        assert!(paginate_payment_methods(payment_methods, Some(2), Some(4)).is_empty());
+    }
+}
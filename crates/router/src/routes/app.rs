@@ -552,6 +552,10 @@ impl Payments {
                 .service(web::resource("/filter").route(web::post().to(get_filters_for_payments)))
                 .service(web::resource("/v2/filter").route(web::get().to(get_payment_filters)))
                 .service(web::resource("/aggregate").route(web::get().to(get_payments_aggregates)))
+                .service(
+                    web::resource("/reconciliation/export")
+                        .route(web::get().to(export_payment_attempts_for_reconciliation)),
+                )
                 .service(
                     web::resource("/profile/aggregate")
                         .route(web::get().to(get_payments_aggregates_profile)),
@@ -635,6 +639,9 @@ impl Payments {
                 .service(
                     web::resource("/{payment_id}/extended_card_info").route(web::get().to(retrieve_extended_card_info)),
                 )
+                .service(
+                    web::resource("/{payment_id}/attempts/{attempt_id}/timeline").route(web::get().to(retrieve_payment_attempt_status_timeline)),
+                )
                 .service(
                 web::resource("{payment_id}/calculate_tax")
                     .route(web::post().to(payments_dynamic_tax_calculation)),
@@ -1127,6 +1134,10 @@ impl PaymentMethods {
                 .service(
                     web::resource("/migrate-batch").route(web::post().to(migrate_payment_methods)),
                 )
+                .service(
+                    web::resource("/migrate-bulk")
+                        .route(web::post().to(migrate_payment_methods_bulk_api)),
+                )
                 .service(
                     web::resource("/collect").route(web::post().to(initiate_pm_collect_link_flow)),
                 )
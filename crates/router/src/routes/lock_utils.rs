@@ -134,6 +134,8 @@ impl From<Flow> for ApiIdentifier {
             | Flow::PaymentsExternalAuthentication
             | Flow::PaymentsAuthorize
             | Flow::GetExtendedCardInfo
+            | Flow::PaymentAttemptStatusTimeline
+            | Flow::PaymentAttemptsReconciliationExport
             | Flow::PaymentsCompleteAuthorize
             | Flow::PaymentsManualUpdate
             | Flow::SessionUpdateTaxCalculation => Self::Payments,
@@ -58,13 +58,15 @@ pub async fn create_payment_method_api(
         &req,
         json_payload.into_inner(),
         |state, auth, req, _| async move {
-            Box::pin(cards::get_client_secret_or_add_payment_method(
+            let result = Box::pin(cards::get_client_secret_or_add_payment_method(
                 &state,
                 req,
                 &auth.merchant_account,
                 &auth.key_store,
             ))
-            .await
+            .await;
+            payment_methods_routes::record_payment_method_creation_outcome("create", &result);
+            result
         },
         &auth::HeaderAuth(auth::ApiKeyAuth),
         api_locking::LockAction::NotApplicable,
@@ -87,13 +89,15 @@ pub async fn create_payment_method_api(
         &req,
         json_payload.into_inner(),
         |state, auth, req, _| async move {
-            Box::pin(create_payment_method(
+            let result = Box::pin(create_payment_method(
                 &state,
                 req,
                 &auth.merchant_account,
                 &auth.key_store,
             ))
-            .await
+            .await;
+            payment_methods_routes::record_payment_method_creation_outcome("create", &result);
+            result
         },
         &auth::HeaderAuth(auth::ApiKeyAuth),
         api_locking::LockAction::NotApplicable,
@@ -255,14 +259,63 @@ pub async fn migrate_payment_method_api(
         |state, _, req, _| async move {
             let merchant_id = req.merchant_id.clone();
             let (key_store, merchant_account) = get_merchant_account(&state, &merchant_id).await?;
-            Box::pin(cards::migrate_payment_method(
+            let result = Box::pin(cards::migrate_payment_method(
                 state,
                 req,
                 &merchant_id,
                 &merchant_account,
                 &key_store,
             ))
-            .await
+            .await;
+            payment_methods_routes::record_payment_method_creation_outcome("migrate", &result);
+            result
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(any(feature = "payment_methods_v2", feature = "customer_v2"))
+))]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodsMigrate))]
+pub async fn migrate_payment_methods_bulk_api(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<Vec<payment_methods::PaymentMethodMigrate>>,
+) -> HttpResponse {
+    let flow = Flow::PaymentMethodsMigrate;
+    let payment_methods = json_payload.into_inner();
+    let merchant_id = match migration::get_batch_merchant_id(
+        payment_methods
+            .iter()
+            .map(|payment_method| &payment_method.merchant_id),
+    ) {
+        Ok(merchant_id) => merchant_id,
+        Err(e) => return api::log_and_return_error_response(e.into()),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payment_methods,
+        |state, _, req, _| {
+            let merchant_id = merchant_id.clone();
+            async move {
+                let (key_store, merchant_account) =
+                    get_merchant_account(&state, &merchant_id).await?;
+                Box::pin(migration::migrate_payment_methods_batch(
+                    state,
+                    req,
+                    &merchant_id,
+                    &merchant_account,
+                    &key_store,
+                ))
+                .await
+            }
         },
         &auth::AdminApiAuth,
         api_locking::LockAction::NotApplicable,
@@ -400,17 +400,23 @@ pub async fn payments_confirm(
         &req,
         payload,
         |state, auth, req, req_state| {
-            authorize_verify_select::<_>(
-                payments::PaymentConfirm,
-                state,
-                req_state,
-                auth.merchant_account,
-                auth.profile_id,
-                auth.key_store,
-                header_payload.clone(),
-                req,
-                auth_flow,
-            )
+            let header_payload = header_payload.clone();
+            async move {
+                let locale = header_payload.locale.clone();
+                authorize_verify_select::<_>(
+                    payments::PaymentConfirm,
+                    state,
+                    req_state,
+                    auth.merchant_account,
+                    auth.profile_id,
+                    auth.key_store,
+                    header_payload,
+                    req,
+                    auth_flow,
+                )
+                .await
+                .map_err(|error| errors::LocalizedApiErrorResponse::from_report(error, locale))
+            }
         },
         &*auth_type,
         locking_action,
@@ -1096,6 +1102,36 @@ pub async fn get_payments_aggregates(
     .await
 }
 
+#[instrument(skip_all, fields(flow = ?Flow::PaymentAttemptsReconciliationExport))]
+#[cfg(all(feature = "olap", feature = "v1"))]
+pub async fn export_payment_attempts_for_reconciliation(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    payload: web::Query<payment_types::PaymentAttemptReconciliationExportConstraints>,
+) -> impl Responder {
+    let flow = Flow::PaymentAttemptsReconciliationExport;
+    let payload = payload.into_inner();
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth: auth::AuthenticationData, req, _| {
+            payments::reconciliation::export_payment_attempts(state, auth.merchant_account, req)
+        },
+        auth::auth_type(
+            &auth::HeaderAuth(auth::ApiKeyAuth),
+            &auth::JWTAuth {
+                permission: Permission::PaymentRead,
+                minimum_entity_level: EntityType::Merchant,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
 #[cfg(all(feature = "oltp", feature = "v1"))]
 #[instrument(skip_all, fields(flow = ?Flow::PaymentsApprove, payment_id))]
 pub async fn payments_approve(
@@ -1503,6 +1539,37 @@ pub async fn retrieve_extended_card_info(
     .await
 }
 
+#[cfg(feature = "v1")]
+/// Retrieve endpoint for merchant to fetch the status transitions of a payment attempt
+#[instrument(skip_all, fields(flow = ?Flow::PaymentAttemptStatusTimeline, payment_id))]
+pub async fn retrieve_payment_attempt_status_timeline(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<(common_utils::id_type::PaymentId, String)>,
+) -> impl Responder {
+    let flow = Flow::PaymentAttemptStatusTimeline;
+    let (payment_id, attempt_id) = path.into_inner();
+    tracing::Span::current().record("payment_id", payment_id.get_string_repr());
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        (payment_id, attempt_id),
+        |state, auth, (payment_id, attempt_id), _| {
+            payments::get_payment_attempt_status_timeline(
+                state,
+                auth.merchant_account.get_id().to_owned(),
+                payment_id,
+                attempt_id,
+            )
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
 #[cfg(feature = "v1")]
 pub fn get_or_generate_payment_id(
     payload: &mut payment_types::PaymentsRequest,
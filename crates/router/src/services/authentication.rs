@@ -368,6 +368,7 @@ where
         }
 
         let api_key = api_keys::PlaintextApiKey::from(api_key);
+
         let hash_key = {
             let config = state.conf();
             config.api_keys.get_inner().get_hash_key()?
@@ -392,6 +393,8 @@ where
                 .attach_printable("API key has expired");
         }
 
+        api_key.validate_environment(router_env::env::prefix_for_env())?;
+
         let key_manager_state = &(&state.session_state()).into();
 
         let key_store = state
@@ -57,6 +57,36 @@ pub struct KafkaPaymentAttempt<'a> {
     pub profile_id: &'a id_type::ProfileId,
     pub organization_id: &'a id_type::OrganizationId,
     pub card_network: Option<String>,
+    pub device_fingerprint: Option<&'a String>,
+    pub three_ds_version: Option<&'a String>,
+    pub three_ds_message_version: Option<&'a String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub card_bin: Option<String>,
+    pub liability_shift: Option<bool>,
+    #[serde(default, with = "time::serde::timestamp::option")]
+    pub connector_response_received_at: Option<OffsetDateTime>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
+}
+
+/// BIN of the card of the attempt, from the extended BIN or the ISIN in the additional card
+/// details of the attempt. At most the first 8 digits are kept, so that the card number is never
+/// sent to analytics even if the details hold more.
+#[cfg(feature = "v1")]
+pub(super) fn get_card_bin(payment_method_data: Option<&serde_json::Value>) -> Option<String> {
+    let card = payment_method_data?.get("card")?;
+    ["card_extended_bin", "card_isin"]
+        .into_iter()
+        .filter_map(|key| card.get(key).and_then(|bin| bin.as_str()))
+        .map(|bin| {
+            bin.chars()
+                .filter(char::is_ascii_digit)
+                .take(8)
+                .collect::<String>()
+        })
+        .find(|bin| bin.len() >= 6)
 }
 
 #[cfg(feature = "v1")]
@@ -115,6 +145,19 @@ impl<'a> KafkaPaymentAttempt<'a> {
                 .and_then(|card| card.get("card_network"))
                 .and_then(|network| network.as_str())
                 .map(|network| network.to_string()),
+            device_fingerprint: attempt.device_fingerprint.as_ref(),
+            three_ds_version: attempt.three_ds_version.as_ref(),
+            three_ds_message_version: attempt.three_ds_message_version.as_ref(),
+            request_challenge: attempt.request_challenge,
+            tokenization_latency: attempt.tokenization_latency,
+            card_funding_type: attempt.card_funding_type,
+            card_bin: get_card_bin(attempt.payment_method_data.as_ref()),
+            liability_shift: attempt.liability_shift,
+            connector_response_received_at: attempt
+                .connector_response_received_at
+                .map(|i| i.assume_utc()),
+            connector_fee_amount: attempt.connector_fee_amount,
+            connector_fee_currency: attempt.connector_fee_currency,
         }
     }
 }
@@ -194,3 +237,43 @@ impl<'a> super::KafkaMessage for KafkaPaymentAttempt<'a> {
         crate::events::EventType::PaymentAttempt
     }
 }
+
+#[cfg(all(test, feature = "v1"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_bin_is_truncated_to_eight_digits() {
+        let payment_method_data = serde_json::json!({
+            "card": {
+                "card_isin": "424242",
+                "card_extended_bin": "4242424242424242",
+            }
+        });
+
+        assert_eq!(
+            get_card_bin(Some(&payment_method_data)),
+            Some("42424242".to_string())
+        );
+    }
+
+    #[test]
+    fn test_card_bin_falls_back_to_card_isin() {
+        let payment_method_data = serde_json::json!({
+            "card": {
+                "card_isin": "424242",
+                "card_extended_bin": null,
+            }
+        });
+
+        assert_eq!(
+            get_card_bin(Some(&payment_method_data)),
+            Some("424242".to_string())
+        );
+        assert_eq!(
+            get_card_bin(Some(&serde_json::json!({ "wallet": {} }))),
+            None
+        );
+        assert_eq!(get_card_bin(None), None);
+    }
+}
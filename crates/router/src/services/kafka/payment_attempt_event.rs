@@ -58,6 +58,18 @@ pub struct KafkaPaymentAttemptEvent<'a> {
     pub profile_id: &'a id_type::ProfileId,
     pub organization_id: &'a id_type::OrganizationId,
     pub card_network: Option<String>,
+    pub device_fingerprint: Option<&'a String>,
+    pub three_ds_version: Option<&'a String>,
+    pub three_ds_message_version: Option<&'a String>,
+    pub request_challenge: Option<bool>,
+    pub tokenization_latency: Option<i64>,
+    pub card_funding_type: Option<storage_enums::CardFundingType>,
+    pub card_bin: Option<String>,
+    pub liability_shift: Option<bool>,
+    #[serde(default, with = "time::serde::timestamp::milliseconds::option")]
+    pub connector_response_received_at: Option<OffsetDateTime>,
+    pub connector_fee_amount: Option<MinorUnit>,
+    pub connector_fee_currency: Option<storage_enums::Currency>,
 }
 
 #[cfg(feature = "v1")]
@@ -116,6 +128,19 @@ impl<'a> KafkaPaymentAttemptEvent<'a> {
                 .and_then(|card| card.get("card_network"))
                 .and_then(|network| network.as_str())
                 .map(|network| network.to_string()),
+            device_fingerprint: attempt.device_fingerprint.as_ref(),
+            three_ds_version: attempt.three_ds_version.as_ref(),
+            three_ds_message_version: attempt.three_ds_message_version.as_ref(),
+            request_challenge: attempt.request_challenge,
+            tokenization_latency: attempt.tokenization_latency,
+            card_funding_type: attempt.card_funding_type,
+            card_bin: super::payment_attempt::get_card_bin(attempt.payment_method_data.as_ref()),
+            liability_shift: attempt.liability_shift,
+            connector_response_received_at: attempt
+                .connector_response_received_at
+                .map(|i| i.assume_utc()),
+            connector_fee_amount: attempt.connector_fee_amount,
+            connector_fee_currency: attempt.connector_fee_currency,
         }
     }
 }
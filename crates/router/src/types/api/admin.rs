@@ -170,6 +170,15 @@ impl ForeignTryFrom<domain::Profile> for ProfileResponse {
             is_network_tokenization_enabled: item.is_network_tokenization_enabled,
             is_auto_retries_enabled: item.is_auto_retries_enabled,
             max_auto_retries_enabled: item.max_auto_retries_enabled,
+            is_payment_method_token_response_enabled: item.is_payment_method_token_response_enabled,
+            customer_spending_limit: item.customer_spending_limit,
+            customer_spending_limit_window_in_secs: item.customer_spending_limit_window_in_secs,
+            is_matched_routing_rule_response_enabled: item.is_matched_routing_rule_response_enabled,
+            confirm_response_masking_policy: item.confirm_response_masking_policy,
+            soft_three_ds_failure_policy: item.soft_three_ds_failure_policy,
+            pan_storage_policy: item.pan_storage_policy,
+            auto_cancel_unconfirmed_after: item.auto_cancel_unconfirmed_after,
+            issuer_country_connector_preferences: item.issuer_country_connector_preferences,
         })
     }
 }
@@ -357,5 +366,16 @@ pub async fn create_profile_from_merchant_account(
         is_network_tokenization_enabled: request.is_network_tokenization_enabled,
         is_auto_retries_enabled: request.is_auto_retries_enabled.unwrap_or_default(),
         max_auto_retries_enabled: request.max_auto_retries_enabled.map(i16::from),
+        is_payment_method_token_response_enabled: request.is_payment_method_token_response_enabled,
+        customer_spending_limit: request.customer_spending_limit,
+        customer_spending_limit_window_in_secs: request
+            .customer_spending_limit_window_in_secs
+            .map(i64::from),
+        is_matched_routing_rule_response_enabled: request.is_matched_routing_rule_response_enabled,
+        confirm_response_masking_policy: request.confirm_response_masking_policy,
+        soft_three_ds_failure_policy: request.soft_three_ds_failure_policy,
+        pan_storage_policy: request.pan_storage_policy,
+        auto_cancel_unconfirmed_after: request.auto_cancel_unconfirmed_after.map(i64::from),
+        issuer_country_connector_preferences: request.issuer_country_connector_preferences,
     }))
 }
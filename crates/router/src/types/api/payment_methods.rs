@@ -27,18 +27,154 @@ pub use api_models::payment_methods::{
     TokenizedWalletValue2,
 };
 use error_stack::report;
+use masking::PeekInterface;
 
-use crate::core::{
-    errors::{self, RouterResult},
-    payments::helpers::validate_payment_method_type_against_payment_method,
-};
 #[cfg(all(feature = "v2", feature = "payment_methods_v2"))]
 use crate::utils;
+use crate::{
+    connector::utils::{get_card_issuer, CardIssuer},
+    core::{
+        errors::{self, RouterResult},
+        payments::helpers::validate_payment_method_type_against_payment_method,
+    },
+    types::api::enums as api_enums,
+};
 
 pub(crate) trait PaymentMethodCreateExt {
     fn validate(&self) -> RouterResult<()>;
 }
 
+/// Card network of the card number, for the networks that can be told apart by the card number
+/// alone
+pub(crate) fn get_card_network_from_card_number(
+    card_number: &str,
+) -> Option<api_enums::CardNetwork> {
+    match get_card_issuer(card_number).ok()? {
+        CardIssuer::AmericanExpress => Some(api_enums::CardNetwork::AmericanExpress),
+        CardIssuer::Master => Some(api_enums::CardNetwork::Mastercard),
+        CardIssuer::Maestro => Some(api_enums::CardNetwork::Maestro),
+        CardIssuer::Visa => Some(api_enums::CardNetwork::Visa),
+        CardIssuer::Discover => Some(api_enums::CardNetwork::Discover),
+        CardIssuer::DinersClub => Some(api_enums::CardNetwork::DinersClub),
+        CardIssuer::JCB => Some(api_enums::CardNetwork::JCB),
+        CardIssuer::CarteBlanche => None,
+    }
+}
+
+/// Networks of the card, given the network provided for it. The network that can be told apart by
+/// the card number is detected from it, and a provided network that cannot, such as the domestic
+/// network of a co-badged card, is added to it.
+pub(crate) fn get_card_networks(
+    card: &CardDetail,
+    card_network: Option<&api_enums::CardNetwork>,
+) -> Vec<api_enums::CardNetwork> {
+    let mut card_networks = get_card_network_from_card_number(card.card_number.peek())
+        .into_iter()
+        .collect::<Vec<_>>();
+    if let Some(card_network) = card_network {
+        if !card_networks.contains(card_network) {
+            card_networks.push(card_network.clone());
+        }
+    }
+    card_networks
+}
+
+/// Network provided for the card of the payment method, either on the card or on the payment
+/// method
+#[cfg(all(
+    any(feature = "v2", feature = "v1"),
+    not(feature = "payment_methods_v2")
+))]
+pub(crate) fn get_provided_card_network(
+    req: &PaymentMethodCreate,
+) -> Option<api_enums::CardNetwork> {
+    req.card
+        .as_ref()
+        .and_then(|card| card.card_network.clone())
+        .or_else(|| {
+            req.card_network
+                .as_deref()
+                .and_then(|card_network| card_network.parse().ok())
+        })
+}
+
+fn invalid_card_data(message: &str) -> error_stack::Report<errors::ApiErrorResponse> {
+    report!(errors::ApiErrorResponse::InvalidRequestData {
+        message: message.to_string()
+    })
+}
+
+/// Validate that the card has not expired and that the card number belongs to the card network
+/// provided, if any. Networks that are co-badged with another network, such as Cartes
+/// Bancaires, cannot be detected from the card number and are not checked. The preferred network
+/// of the card, if any, must be one of the networks of the card.
+fn validate_card_detail(
+    card: &CardDetail,
+    card_network: Option<&api_enums::CardNetwork>,
+) -> RouterResult<()> {
+    let card_exp_month = card
+        .card_exp_month
+        .peek()
+        .trim()
+        .parse::<u8>()
+        .ok()
+        .and_then(|month| ::cards::CardExpirationMonth::try_from(month).ok())
+        .ok_or_else(|| invalid_card_data("Invalid card expiry month"))?;
+
+    let card_exp_year = card.card_exp_year.peek().trim();
+    let card_exp_year = match card_exp_year.len() {
+        2 => format!("20{card_exp_year}"),
+        _ => card_exp_year.to_string(),
+    }
+    .parse::<u16>()
+    .map_err(|_| invalid_card_data("Invalid card expiry year"))?;
+    let current_year = u16::try_from(common_utils::date_time::now().year()).unwrap_or(u16::MAX);
+    if card_exp_year < current_year {
+        return Err(invalid_card_data("Card expired"));
+    }
+    let card_exp_year = ::cards::CardExpirationYear::try_from(card_exp_year)
+        .map_err(|_| invalid_card_data("Invalid card expiry year"))?;
+
+    let is_expired = ::cards::CardExpiration {
+        month: card_exp_month,
+        year: card_exp_year,
+    }
+    .is_expired()
+    .map_err(|_| invalid_card_data("Invalid card expiry"))?;
+    if is_expired {
+        return Err(invalid_card_data("Card expired"));
+    }
+
+    let detected_card_network = get_card_network_from_card_number(card.card_number.peek());
+    if let (Some(card_network), Some(detected_card_network)) = (card_network, detected_card_network)
+    {
+        let is_detectable_network = matches!(
+            card_network,
+            api_enums::CardNetwork::AmericanExpress
+                | api_enums::CardNetwork::Mastercard
+                | api_enums::CardNetwork::Maestro
+                | api_enums::CardNetwork::Visa
+                | api_enums::CardNetwork::Discover
+                | api_enums::CardNetwork::DinersClub
+                | api_enums::CardNetwork::JCB
+        );
+        if is_detectable_network && *card_network != detected_card_network {
+            return Err(invalid_card_data(
+                "Card number does not belong to the card network provided",
+            ));
+        }
+    }
+
+    if let Some(preferred_network) = card.preferred_network.as_ref() {
+        if !get_card_networks(card, card_network).contains(preferred_network) {
+            return Err(invalid_card_data(
+                "Preferred network is not one of the networks of the card",
+            ));
+        }
+    }
+    Ok(())
+}
+
 // convert self.payment_method_type to payment_method and compare it against self.payment_method
 #[cfg(all(
     any(feature = "v2", feature = "v1"),
@@ -56,6 +192,10 @@ impl PaymentMethodCreateExt for PaymentMethodCreate {
                 }
             }
         }
+
+        if let Some(card) = self.card.as_ref() {
+            validate_card_detail(card, get_provided_card_network(self).as_ref())?;
+        }
         Ok(())
     }
 }
@@ -88,6 +228,12 @@ impl PaymentMethodCreateExt for PaymentMethodCreate {
                 .attach_printable("Invalid payment method data"))
             },
         )?;
+
+        match &self.payment_method_data {
+            PaymentMethodCreateData::Card(card) => {
+                validate_card_detail(card, card.card_network.as_ref())?
+            }
+        }
         Ok(())
     }
 }
@@ -120,6 +266,182 @@ impl PaymentMethodCreateExt for PaymentMethodIntentConfirm {
                 .attach_printable("Invalid payment method data"))
             },
         )?;
+
+        match &self.payment_method_data {
+            PaymentMethodCreateData::Card(card) => {
+                validate_card_detail(card, card.card_network.as_ref())?
+            }
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(
+        card_number: &str,
+        card_exp_month: &str,
+        card_exp_year: &str,
+        card_network: Option<&str>,
+    ) -> CardDetail {
+        serde_json::from_value(serde_json::json!({
+            "card_number": card_number,
+            "card_exp_month": card_exp_month,
+            "card_exp_year": card_exp_year,
+            "card_network": card_network,
+        }))
+        .unwrap()
+    }
+
+    fn next_year() -> String {
+        (common_utils::date_time::now().year() + 1).to_string()
+    }
+
+    fn error_message(result: RouterResult<()>) -> String {
+        match result.unwrap_err().current_context() {
+            errors::ApiErrorResponse::InvalidRequestData { message } => message.clone(),
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+
+    #[test]
+    fn test_valid_card_is_accepted() {
+        let card = card("4111111111111111", "12", &next_year(), Some("Visa"));
+
+        assert!(validate_card_detail(&card, card.card_network.as_ref()).is_ok());
+    }
+
+    #[test]
+    fn test_expired_card_is_rejected() {
+        let card = card("4111111111111111", "01", "2020", None);
+
+        assert_eq!(
+            error_message(validate_card_detail(&card, None)),
+            "Card expired"
+        );
+    }
+
+    #[test]
+    fn test_malformed_expiry_is_rejected() {
+        let invalid_month = card("4111111111111111", "13", &next_year(), None);
+        let invalid_year = card("4111111111111111", "12", "2o3o", None);
+
+        assert_eq!(
+            error_message(validate_card_detail(&invalid_month, None)),
+            "Invalid card expiry month"
+        );
+        assert_eq!(
+            error_message(validate_card_detail(&invalid_year, None)),
+            "Invalid card expiry year"
+        );
+    }
+
+    #[test]
+    fn test_card_network_mismatch_is_rejected() {
+        let card = card("5555555555554444", "12", &next_year(), Some("Visa"));
+
+        assert_eq!(
+            error_message(validate_card_detail(&card, card.card_network.as_ref())),
+            "Card number does not belong to the card network provided"
+        );
+    }
+
+    #[test]
+    fn test_co_badged_card_network_is_not_checked() {
+        let card = card(
+            "4111111111111111",
+            "12",
+            &next_year(),
+            Some("CartesBancaires"),
+        );
+
+        assert!(validate_card_detail(&card, card.card_network.as_ref()).is_ok());
+    }
+
+    #[test]
+    fn test_co_badged_card_networks_include_provided_network() {
+        let card = card(
+            "4111111111111111",
+            "12",
+            &next_year(),
+            Some("CartesBancaires"),
+        );
+
+        assert_eq!(
+            get_card_networks(&card, card.card_network.as_ref()),
+            vec![
+                api_enums::CardNetwork::Visa,
+                api_enums::CardNetwork::CartesBancaires
+            ]
+        );
+        assert_eq!(
+            get_card_networks(&card, Some(&api_enums::CardNetwork::Visa)),
+            vec![api_enums::CardNetwork::Visa]
+        );
+    }
+
+    #[test]
+    fn test_preferred_network_of_card_is_accepted() {
+        for preferred_network in [
+            api_enums::CardNetwork::Visa,
+            api_enums::CardNetwork::CartesBancaires,
+        ] {
+            let card = CardDetail {
+                preferred_network: Some(preferred_network),
+                ..card(
+                    "4111111111111111",
+                    "12",
+                    &next_year(),
+                    Some("CartesBancaires"),
+                )
+            };
+
+            assert!(validate_card_detail(&card, card.card_network.as_ref()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_preferred_network_not_of_card_is_rejected() {
+        let card = CardDetail {
+            preferred_network: Some(api_enums::CardNetwork::Mastercard),
+            ..card(
+                "4111111111111111",
+                "12",
+                &next_year(),
+                Some("CartesBancaires"),
+            )
+        };
+
+        assert_eq!(
+            error_message(validate_card_detail(&card, card.card_network.as_ref())),
+            "Preferred network is not one of the networks of the card"
+        );
+    }
+
+    #[test]
+    fn test_card_numbers_are_luhn_checked_when_parsed() {
+        let card_numbers = [
+            ("4222222222222", "4222222222223"),
+            ("30569309025904", "30569309025905"),
+            ("378282246310005", "378282246310006"),
+            ("4111111111111111", "4111111111111112"),
+            ("42222222222222220", "42222222222222221"),
+            ("422222222222222224", "422222222222222225"),
+            ("4222222222222222224", "4222222222222222225"),
+        ];
+
+        for (valid_card_number, invalid_card_number) in card_numbers {
+            let valid_card = card(valid_card_number, "12", &next_year(), None);
+            let invalid_card = serde_json::from_value::<CardDetail>(serde_json::json!({
+                "card_number": invalid_card_number,
+                "card_exp_month": "12",
+                "card_exp_year": next_year(),
+            }));
+
+            assert!(validate_card_detail(&valid_card, None).is_ok());
+            assert!(invalid_card.is_err(), "{invalid_card_number} was accepted");
+        }
+    }
+}
@@ -27,6 +27,7 @@ pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
 pub mod payment_attempt;
+pub mod payment_attempt_status_timeline;
 pub mod payment_link;
 pub mod payment_method;
 pub mod payout_attempt;
@@ -63,10 +64,10 @@ pub use self::{
     blocklist_fingerprint::*, blocklist_lookup::*, business_profile::*, capture::*, cards_info::*,
     configs::*, customers::*, dashboard_metadata::*, dispute::*, ephemeral_key::*, events::*,
     file::*, fraud_check::*, generic_link::*, gsm::*, locker_mock_up::*, mandate::*,
-    merchant_account::*, merchant_connector_account::*, merchant_key_store::*, payment_link::*,
-    payment_method::*, process_tracker::*, refund::*, reverse_lookup::*, role::*,
-    routing_algorithm::*, unified_translations::*, user::*, user_authentication_method::*,
-    user_role::*,
+    merchant_account::*, merchant_connector_account::*, merchant_key_store::*,
+    payment_attempt_status_timeline::*, payment_link::*, payment_method::*, process_tracker::*,
+    refund::*, reverse_lookup::*, role::*, routing_algorithm::*, unified_translations::*, user::*,
+    user_authentication_method::*, user_role::*,
 };
 use crate::types::api::routing;
 
@@ -132,3 +133,16 @@ impl From<PaymentRoutingInfo> for PaymentRoutingInfoSerde {
         }))
     }
 }
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PaymentIntentAutoCancelTrackingData {
+    pub payment_id: common_utils::id_type::PaymentId,
+    pub merchant_id: common_utils::id_type::MerchantId,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PaymentIntentProcessingExpiryTrackingData {
+    pub payment_id: common_utils::id_type::PaymentId,
+    pub merchant_id: common_utils::id_type::MerchantId,
+    pub attempt_id: String,
+}
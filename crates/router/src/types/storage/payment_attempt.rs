@@ -218,6 +218,19 @@ mod tests {
             customer_acceptance: Default::default(),
             profile_id: common_utils::generate_profile_id_of_default_length(),
             organization_id: Default::default(),
+            connector_risk_score: Default::default(),
+            connector_risk_decision: Default::default(),
+            connector_fee_amount: Default::default(),
+            connector_fee_currency: Default::default(),
+            device_fingerprint: Default::default(),
+            three_ds_version: Default::default(),
+            three_ds_message_version: Default::default(),
+            request_challenge: Default::default(),
+            tokenization_latency: Default::default(),
+            card_funding_type: Default::default(),
+            liability_shift: Default::default(),
+            connector_payment_method_token: Default::default(),
+            connector_response_received_at: Default::default(),
             shipping_cost: Default::default(),
             order_tax_amount: Default::default(),
         };
@@ -306,6 +319,19 @@ mod tests {
             customer_acceptance: Default::default(),
             profile_id: common_utils::generate_profile_id_of_default_length(),
             organization_id: Default::default(),
+            connector_risk_score: Default::default(),
+            connector_risk_decision: Default::default(),
+            connector_fee_amount: Default::default(),
+            connector_fee_currency: Default::default(),
+            device_fingerprint: Default::default(),
+            three_ds_version: Default::default(),
+            three_ds_message_version: Default::default(),
+            request_challenge: Default::default(),
+            tokenization_latency: Default::default(),
+            card_funding_type: Default::default(),
+            liability_shift: Default::default(),
+            connector_payment_method_token: Default::default(),
+            connector_response_received_at: Default::default(),
             shipping_cost: Default::default(),
             order_tax_amount: Default::default(),
         };
@@ -407,6 +433,19 @@ mod tests {
             customer_acceptance: Default::default(),
             profile_id: common_utils::generate_profile_id_of_default_length(),
             organization_id: Default::default(),
+            connector_risk_score: Default::default(),
+            connector_risk_decision: Default::default(),
+            connector_fee_amount: Default::default(),
+            connector_fee_currency: Default::default(),
+            device_fingerprint: Default::default(),
+            three_ds_version: Default::default(),
+            three_ds_message_version: Default::default(),
+            request_challenge: Default::default(),
+            tokenization_latency: Default::default(),
+            card_funding_type: Default::default(),
+            liability_shift: Default::default(),
+            connector_payment_method_token: Default::default(),
+            connector_response_received_at: Default::default(),
             shipping_cost: Default::default(),
             order_tax_amount: Default::default(),
         };
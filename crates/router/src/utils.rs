@@ -1122,13 +1122,7 @@ where
                 .collect()
         });
 
-    if matches!(
-        status,
-        enums::IntentStatus::Succeeded
-            | enums::IntentStatus::Failed
-            | enums::IntentStatus::PartiallyCaptured
-            | enums::IntentStatus::RequiresMerchantAction
-    ) {
+    if should_trigger_payments_webhook(status) {
         let payments_response = crate::core::payments::transformers::payments_to_payments_response(
             payment_data,
             captures,
@@ -1186,6 +1180,21 @@ where
     Ok(())
 }
 
+/// Intent statuses for which the merchant is notified with an outgoing webhook.
+/// Payments authorized for manual capture are notified with the `payment_authorized` event, so
+/// that the merchant can trigger the capture once the payment response carries the capturable amount
+#[cfg(feature = "v1")]
+fn should_trigger_payments_webhook(status: enums::IntentStatus) -> bool {
+    matches!(
+        status,
+        enums::IntentStatus::Succeeded
+            | enums::IntentStatus::Failed
+            | enums::IntentStatus::PartiallyCaptured
+            | enums::IntentStatus::RequiresMerchantAction
+            | enums::IntentStatus::RequiresCapture
+    )
+}
+
 type Handle<T> = tokio::task::JoinHandle<RouterResult<T>>;
 
 pub async fn flatten_join_error<T>(handle: Handle<T>) -> RouterResult<T> {
@@ -1197,3 +1206,27 @@ pub async fn flatten_join_error<T>(handle: Handle<T>) -> RouterResult<T> {
             .attach_printable("Join Error"),
     }
 }
+
+#[cfg(all(test, feature = "v1"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_capture_authorization_triggers_payment_authorized_event() {
+        let status = enums::IntentStatus::RequiresCapture;
+        let event_type: Option<diesel_models::enums::EventType> = ForeignFrom::foreign_from(status);
+
+        assert!(should_trigger_payments_webhook(status));
+        assert_eq!(
+            event_type,
+            Some(diesel_models::enums::EventType::PaymentAuthorized)
+        );
+    }
+
+    #[test]
+    fn test_processing_payment_does_not_trigger_webhook() {
+        assert!(!should_trigger_payments_webhook(
+            enums::IntentStatus::Processing
+        ));
+    }
+}
@@ -104,6 +104,9 @@ impl FxExchangeRatesCacheEntry {
     fn is_expired(&self, call_delay: i64) -> bool {
         self.timestamp + call_delay < date_time::now_unix_timestamp()
     }
+    pub fn get_exchange_rates(&self) -> Arc<ExchangeRates> {
+        self.data.clone()
+    }
 }
 
 async fn retrieve_forex_from_local() -> Option<FxExchangeRatesCacheEntry> {
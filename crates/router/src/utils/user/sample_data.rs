@@ -252,6 +252,7 @@ pub async fn generate_sample_data(
             shipping_cost: None,
             tax_details: None,
             skip_external_tax_calculation: None,
+            skip_save_payment_method: None,
         };
         let payment_attempt = PaymentAttemptBatchNew {
             attempt_id: attempt_id.clone(),
@@ -331,6 +332,19 @@ pub async fn generate_sample_data(
             customer_acceptance: None,
             profile_id: profile_id.clone(),
             organization_id: org_id.clone(),
+            connector_risk_score: None,
+            connector_risk_decision: None,
+            connector_fee_amount: None,
+            connector_fee_currency: None,
+            device_fingerprint: None,
+            three_ds_version: None,
+            three_ds_message_version: None,
+            request_challenge: None,
+            tokenization_latency: None,
+            card_funding_type: None,
+            liability_shift: None,
+            connector_payment_method_token: None,
+            connector_response_received_at: None,
             shipping_cost: None,
             order_tax_amount: None,
         };
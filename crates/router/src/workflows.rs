@@ -5,6 +5,10 @@ pub mod attach_payout_account_workflow;
 #[cfg(feature = "v1")]
 pub mod outgoing_webhook_retry;
 #[cfg(feature = "v1")]
+pub mod payment_intent_auto_cancel;
+#[cfg(feature = "v1")]
+pub mod payment_intent_processing_expiry;
+#[cfg(feature = "v1")]
 pub mod payment_method_status_update;
 pub mod payment_sync;
 #[cfg(feature = "v1")]
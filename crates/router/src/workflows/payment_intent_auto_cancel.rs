@@ -0,0 +1,126 @@
+use common_utils::ext_traits::ValueExt;
+use diesel_models::process_tracker::business_status;
+use router_env::logger;
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    errors as sch_errors,
+};
+
+use crate::{
+    consts,
+    core::payments::{self as payment_flows, helpers, operations},
+    db::StorageInterface,
+    errors,
+    routes::SessionState,
+    services,
+    types::{api, storage},
+};
+
+pub struct PaymentIntentAutoCancelWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<SessionState> for PaymentIntentAutoCancelWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: storage::PaymentIntentAutoCancelTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentIntentAutoCancelTrackingData")?;
+        let key_manager_state = &state.into();
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                key_manager_state,
+                &tracking_data.merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(
+                key_manager_state,
+                &tracking_data.merchant_id,
+                &key_store,
+            )
+            .await?;
+
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                key_manager_state,
+                &tracking_data.payment_id,
+                &tracking_data.merchant_id,
+                &key_store,
+                merchant_account.storage_scheme,
+            )
+            .await?;
+
+        let payment_attempt = db
+            .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+                &tracking_data.payment_id,
+                &tracking_data.merchant_id,
+                &payment_intent.active_attempt.get_id(),
+                merchant_account.storage_scheme,
+            )
+            .await?;
+
+        if !helpers::should_auto_cancel_unconfirmed_payment(
+            payment_intent.status,
+            payment_attempt.status,
+        ) {
+            logger::info!(
+                payment_id = ?tracking_data.payment_id,
+                "Payment was confirmed before the auto cancel window elapsed"
+            );
+            return db
+                .as_scheduler()
+                .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+                .await
+                .map_err(Into::into);
+        }
+
+        // The cancel operation does not call the connector for unconfirmed payments, it only
+        // updates the trackers and emits the payment cancelled event
+        let cancel_request = api::PaymentsCancelRequest {
+            payment_id: tracking_data.payment_id,
+            cancellation_reason: Some(consts::AUTO_CANCEL_UNCONFIRMED_PAYMENT_REASON.to_string()),
+            merchant_connector_details: None,
+        };
+        Box::pin(payment_flows::payments_operation_core::<
+            api::Void,
+            _,
+            _,
+            _,
+            payment_flows::PaymentData<api::Void>,
+        >(
+            state,
+            state.get_req_state(),
+            merchant_account,
+            None,
+            key_store,
+            operations::PaymentCancel,
+            cancel_request,
+            payment_flows::CallConnectorAction::Trigger,
+            services::AuthFlow::Merchant,
+            None,
+            api::HeaderPayload::default(),
+        ))
+        .await?;
+
+        db.as_scheduler()
+            .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
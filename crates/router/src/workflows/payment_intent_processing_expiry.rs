@@ -0,0 +1,276 @@
+use common_utils::ext_traits::{OptionExt, ValueExt};
+use diesel_models::process_tracker::business_status;
+use error_stack::ResultExt;
+use router_env::logger;
+use scheduler::{
+    consumer::{self, workflows::ProcessTrackerWorkflow},
+    errors as sch_errors,
+};
+
+use crate::{
+    consts,
+    core::{
+        errors::StorageErrorExt,
+        payments::{self as payment_flows, helpers, operations},
+    },
+    db::StorageInterface,
+    errors,
+    routes::SessionState,
+    services,
+    types::{api, storage},
+    utils,
+};
+
+pub struct PaymentIntentProcessingExpiryWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<SessionState> for PaymentIntentProcessingExpiryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: storage::PaymentIntentProcessingExpiryTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentIntentProcessingExpiryTrackingData")?;
+        let key_manager_state = &state.into();
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                key_manager_state,
+                &tracking_data.merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(
+                key_manager_state,
+                &tracking_data.merchant_id,
+                &key_store,
+            )
+            .await?;
+
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                key_manager_state,
+                &tracking_data.payment_id,
+                &tracking_data.merchant_id,
+                &key_store,
+                merchant_account.storage_scheme,
+            )
+            .await?;
+
+        // The payment completed processing, or was retried with another attempt which schedules
+        // its own expiry, since the task was added
+        if !is_processing_attempt(
+            payment_intent.status,
+            &payment_intent.active_attempt.get_id(),
+            &tracking_data.attempt_id,
+        ) {
+            logger::info!(
+                payment_id = ?tracking_data.payment_id,
+                "Payment completed processing before the processing expiry elapsed"
+            );
+            return db
+                .as_scheduler()
+                .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+                .await
+                .map_err(Into::into);
+        }
+
+        // The payment may have been updated while processing, in which case the expiry is counted
+        // from the last update
+        let processing_expiry =
+            helpers::get_processing_payment_expiry(db, &tracking_data.merchant_id).await?;
+        let current_time = common_utils::date_time::now();
+        if !helpers::should_expire_processing_payment(
+            payment_intent.status,
+            payment_intent.modified_at,
+            processing_expiry,
+            current_time,
+        ) {
+            let schedule_time = payment_intent
+                .modified_at
+                .saturating_add(time::Duration::seconds(processing_expiry));
+            return db
+                .as_scheduler()
+                .reset_process(process, schedule_time)
+                .await
+                .map_err(Into::into);
+        }
+
+        let retrieve_request = api::PaymentsRetrieveRequest {
+            resource_id: api::PaymentIdType::PaymentIntentId(tracking_data.payment_id),
+            merchant_id: Some(tracking_data.merchant_id),
+            force_sync: false,
+            connector: None,
+            param: None,
+            merchant_connector_details: None,
+            client_secret: None,
+            expand_attempts: None,
+            expand_captures: None,
+        };
+        let (mut payment_data, _, customer, _, _) =
+            Box::pin(payment_flows::payments_operation_core::<
+                api::PSync,
+                _,
+                _,
+                _,
+                payment_flows::PaymentData<api::PSync>,
+            >(
+                state,
+                state.get_req_state(),
+                merchant_account.clone(),
+                None,
+                key_store.clone(),
+                operations::PaymentStatus,
+                retrieve_request,
+                payment_flows::CallConnectorAction::Avoid,
+                services::AuthFlow::Merchant,
+                None,
+                api::HeaderPayload::default(),
+            ))
+            .await?;
+
+        // The payment may have completed processing or been retried while it was retrieved, in
+        // which case it must not be failed
+        if !is_processing_attempt(
+            payment_data.payment_intent.status,
+            &payment_data.payment_attempt.attempt_id,
+            &tracking_data.attempt_id,
+        ) {
+            logger::info!(
+                payment_id = ?payment_data.payment_intent.payment_id,
+                "Payment completed processing before the processing expiry elapsed"
+            );
+            return db
+                .as_scheduler()
+                .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+                .await
+                .map_err(Into::into);
+        }
+
+        let payment_intent_update =
+            hyperswitch_domain_models::payments::payment_intent::PaymentIntentUpdate::PGStatusUpdate {
+                status: api_models::enums::IntentStatus::Failed,
+                updated_by: merchant_account.storage_scheme.to_string(),
+                incremental_authorization_allowed: Some(false),
+            };
+        let payment_attempt_update =
+            hyperswitch_domain_models::payments::payment_attempt::PaymentAttemptUpdate::ErrorUpdate {
+                connector: None,
+                status: api_models::enums::AttemptStatus::Failure,
+                error_code: None,
+                error_message: None,
+                error_reason: Some(Some(consts::PROCESSING_PAYMENT_EXPIRED_REASON.to_string())),
+                amount_capturable: Some(common_utils::types::MinorUnit::new(0)),
+                updated_by: merchant_account.storage_scheme.to_string(),
+                unified_code: None,
+                unified_message: None,
+                connector_transaction_id: None,
+                payment_method_data: None,
+                authentication_type: None,
+                connector_response_received_at: None,
+            };
+
+        payment_data.payment_attempt = db
+            .update_payment_attempt_with_attempt_id(
+                payment_data.payment_attempt,
+                payment_attempt_update,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+        payment_data.payment_intent = db
+            .update_payment_intent(
+                key_manager_state,
+                payment_data.payment_intent,
+                payment_intent_update,
+                &key_store,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+        let profile_id = payment_data
+            .payment_intent
+            .profile_id
+            .as_ref()
+            .get_required_value("profile_id")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Could not find profile_id in payment intent")?;
+
+        let business_profile = db
+            .find_business_profile_by_profile_id(key_manager_state, &key_store, profile_id)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::ProfileNotFound {
+                id: profile_id.get_string_repr().to_owned(),
+            })?;
+
+        // Trigger the outgoing webhook to notify the merchant about the failed payment
+        Box::pin(utils::trigger_payments_webhook(
+            merchant_account,
+            business_profile,
+            &key_store,
+            payment_data,
+            customer,
+            state,
+            operations::PaymentStatus,
+        ))
+        .await
+        .map_err(|error| logger::warn!(payments_outgoing_webhook_error=?error))
+        .ok();
+
+        db.as_scheduler()
+            .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
+
+/// Whether the payment is still processing the attempt that the expiry was scheduled for
+fn is_processing_attempt(
+    status: storage::enums::IntentStatus,
+    active_attempt_id: &str,
+    scheduled_attempt_id: &str,
+) -> bool {
+    status == storage::enums::IntentStatus::Processing && active_attempt_id == scheduled_attempt_id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_the_processing_scheduled_attempt_is_expired() {
+        let scheduled_attempt_id = "pay_mbabizu24mvu3mela5njyhpit4_1";
+
+        assert!(is_processing_attempt(
+            storage::enums::IntentStatus::Processing,
+            scheduled_attempt_id,
+            scheduled_attempt_id,
+        ));
+        assert!(!is_processing_attempt(
+            storage::enums::IntentStatus::Succeeded,
+            scheduled_attempt_id,
+            scheduled_attempt_id,
+        ));
+        assert!(!is_processing_attempt(
+            storage::enums::IntentStatus::Processing,
+            "pay_mbabizu24mvu3mela5njyhpit4_2",
+            scheduled_attempt_id,
+        ));
+    }
+}
@@ -386,6 +386,7 @@ async fn payments_create_core() {
         attempts: None,
         captures: None,
         mandate_data: None,
+        mandate_created: None,
         setup_future_usage: None,
         off_session: None,
         capture_on: None,
@@ -446,6 +447,14 @@ async fn payments_create_core() {
         merchant_order_reference_id: None,
         order_tax_amount: None,
         connector_mandate_id: None,
+        payment_method_token_reference: None,
+        matched_routing_rule: None,
+        connector_risk_score: None,
+        connector_risk_decision: None,
+        connector_fee_amount: None,
+        connector_fee_currency: None,
+        card_funding_type: None,
+        liability_shift: None,
     };
     let expected_response =
         services::ApplicationResponse::JsonWithHeaders((expected_response, vec![]));
@@ -644,6 +653,7 @@ async fn payments_create_core_adyen_no_redirect() {
             attempts: None,
             captures: None,
             mandate_data: None,
+            mandate_created: None,
             setup_future_usage: None,
             off_session: None,
             capture_on: None,
@@ -704,6 +714,14 @@ async fn payments_create_core_adyen_no_redirect() {
             merchant_order_reference_id: None,
             order_tax_amount: None,
             connector_mandate_id: None,
+            payment_method_token_reference: None,
+            matched_routing_rule: None,
+            connector_risk_score: None,
+            connector_risk_decision: None,
+            connector_fee_amount: None,
+            connector_fee_currency: None,
+            card_funding_type: None,
+            liability_shift: None,
         },
         vec![],
     ));
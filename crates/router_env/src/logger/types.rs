@@ -492,6 +492,10 @@ pub enum Flow {
     ToggleConnectorAgnosticMit,
     /// Get the extended card info associated to a payment_id
     GetExtendedCardInfo,
+    /// Get the status transitions of a payment attempt
+    PaymentAttemptStatusTimeline,
+    /// Export the confirmed payment attempts for reconciliation
+    PaymentAttemptsReconciliationExport,
     /// Manually update the refund details like status, error code, error message etc.
     RefundsManualUpdate,
     /// Manually update the payment details like status, error code, error message etc.
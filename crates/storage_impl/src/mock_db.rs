@@ -28,6 +28,8 @@ pub struct MockDb {
     pub merchant_accounts: Arc<Mutex<Vec<store::MerchantAccount>>>,
     pub merchant_connector_accounts: Arc<Mutex<Vec<store::MerchantConnectorAccount>>>,
     pub payment_attempts: Arc<Mutex<Vec<PaymentAttempt>>>,
+    pub payment_attempt_status_timeline:
+        Arc<Mutex<Vec<store::payment_attempt_status_timeline::PaymentAttemptStatusTimeline>>>,
     pub payment_intents: Arc<Mutex<Vec<PaymentIntent>>>,
     pub payment_methods: Arc<Mutex<Vec<store::PaymentMethod>>>,
     pub customers: Arc<Mutex<Vec<store::Customer>>>,
@@ -70,6 +72,7 @@ impl MockDb {
             merchant_accounts: Default::default(),
             merchant_connector_accounts: Default::default(),
             payment_attempts: Default::default(),
+            payment_attempt_status_timeline: Default::default(),
             payment_intents: Default::default(),
             payment_methods: Default::default(),
             customers: Default::default(),
@@ -20,13 +20,25 @@ impl PaymentAttemptInterface for MockDb {
     #[cfg(feature = "v1")]
     async fn find_payment_attempt_by_payment_id_merchant_id_attempt_id(
         &self,
-        _payment_id: &common_utils::id_type::PaymentId,
-        _merchant_id: &common_utils::id_type::MerchantId,
-        _attempt_id: &str,
+        payment_id: &common_utils::id_type::PaymentId,
+        merchant_id: &common_utils::id_type::MerchantId,
+        attempt_id: &str,
         _storage_scheme: storage_enums::MerchantStorageScheme,
     ) -> CustomResult<PaymentAttempt, StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(StorageError::MockDbError)?
+        let payment_attempts = self.payment_attempts.lock().await;
+
+        let payment_attempt = payment_attempts
+            .iter()
+            .find(|payment_attempt| {
+                payment_attempt.payment_id == *payment_id
+                    && payment_attempt.merchant_id.eq(merchant_id)
+                    && payment_attempt.attempt_id == attempt_id
+            })
+            .ok_or(StorageError::ValueNotFound(
+                "PaymentAttempt not found".to_string(),
+            ))?;
+
+        Ok(payment_attempt.clone())
     }
 
     #[cfg(all(feature = "v1", feature = "olap"))]
@@ -58,6 +70,42 @@ impl PaymentAttemptInterface for MockDb {
         Err(StorageError::MockDbError)?
     }
 
+    #[cfg(all(feature = "v1", feature = "olap"))]
+    async fn filter_payment_attempts_by_statuses_time_range(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        statuses: &[storage_enums::AttemptStatus],
+        time_range: &common_utils::types::TimeRange,
+        limit: i64,
+        offset: i64,
+        _storage_scheme: storage_enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<PaymentAttempt>, StorageError> {
+        let payment_attempts = self.payment_attempts.lock().await;
+        let mut payment_attempts = payment_attempts
+            .iter()
+            .filter(|payment_attempt| {
+                payment_attempt.merchant_id == *merchant_id
+                    && statuses.contains(&payment_attempt.status)
+                    && payment_attempt.created_at >= time_range.start_time
+                    && time_range
+                        .end_time
+                        .map_or(true, |end_time| payment_attempt.created_at <= end_time)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        payment_attempts.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.attempt_id.cmp(&b.attempt_id))
+        });
+
+        Ok(payment_attempts
+            .into_iter()
+            .skip(usize::try_from(offset).unwrap_or_default())
+            .take(usize::try_from(limit).unwrap_or_default())
+            .collect())
+    }
+
     #[cfg(feature = "v1")]
     async fn find_payment_attempt_by_attempt_id_merchant_id(
         &self,
@@ -184,6 +232,19 @@ impl PaymentAttemptInterface for MockDb {
             client_version: payment_attempt.client_version,
             customer_acceptance: payment_attempt.customer_acceptance,
             organization_id: payment_attempt.organization_id,
+            connector_risk_score: payment_attempt.connector_risk_score,
+            connector_risk_decision: payment_attempt.connector_risk_decision,
+            connector_fee_amount: payment_attempt.connector_fee_amount,
+            connector_fee_currency: payment_attempt.connector_fee_currency,
+            device_fingerprint: payment_attempt.device_fingerprint,
+            three_ds_version: payment_attempt.three_ds_version,
+            three_ds_message_version: payment_attempt.three_ds_message_version,
+            request_challenge: payment_attempt.request_challenge,
+            tokenization_latency: payment_attempt.tokenization_latency,
+            card_funding_type: payment_attempt.card_funding_type,
+            liability_shift: payment_attempt.liability_shift,
+            connector_payment_method_token: payment_attempt.connector_payment_method_token,
+            connector_response_received_at: payment_attempt.connector_response_received_at,
             profile_id: payment_attempt.profile_id,
             shipping_cost: payment_attempt.shipping_cost,
             order_tax_amount: payment_attempt.order_tax_amount,
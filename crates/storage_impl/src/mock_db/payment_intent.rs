@@ -15,6 +15,37 @@ use super::MockDb;
 
 #[async_trait::async_trait]
 impl PaymentIntentInterface for MockDb {
+    #[cfg(feature = "v1")]
+    async fn get_customer_captured_amount(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        customer_id: &common_utils::id_type::CustomerId,
+        currency: storage_enums::Currency,
+        statuses: Vec<storage_enums::IntentStatus>,
+        time_range: &common_utils::types::TimeRange,
+        _storage_scheme: storage_enums::MerchantStorageScheme,
+    ) -> CustomResult<common_utils::types::MinorUnit, StorageError> {
+        let payment_intents = self.payment_intents.lock().await;
+
+        Ok(payment_intents
+            .iter()
+            .filter(|payment_intent| {
+                payment_intent.merchant_id == *merchant_id
+                    && payment_intent.customer_id.as_ref() == Some(customer_id)
+                    && payment_intent.currency == Some(currency)
+                    && statuses.contains(&payment_intent.status)
+                    && payment_intent.modified_at >= time_range.start_time
+                    && time_range
+                        .end_time
+                        .map_or(true, |end_time| payment_intent.modified_at <= end_time)
+            })
+            .filter_map(|payment_intent| payment_intent.amount_captured)
+            .fold(
+                common_utils::types::MinorUnit::new(0),
+                |total, amount_captured| total + amount_captured,
+            ))
+    }
+
     #[cfg(all(feature = "v1", feature = "olap"))]
     async fn filter_payment_intent_by_constraints(
         &self,
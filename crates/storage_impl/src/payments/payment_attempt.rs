@@ -435,6 +435,44 @@ impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
             er.change_context(new_err)
         })
     }
+
+    #[cfg(all(feature = "v1", feature = "olap"))]
+    #[instrument(skip_all)]
+    async fn filter_payment_attempts_by_statuses_time_range(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        statuses: &[common_enums::AttemptStatus],
+        time_range: &common_utils::types::TimeRange,
+        limit: i64,
+        offset: i64,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<Vec<PaymentAttempt>, errors::StorageError> {
+        let conn = self
+            .db_store
+            .get_replica_pool()
+            .get()
+            .await
+            .change_context(errors::StorageError::DatabaseConnectionError)?;
+        DieselPaymentAttempt::filter_by_merchant_id_statuses_time_range(
+            &conn,
+            merchant_id,
+            statuses.to_vec(),
+            time_range,
+            limit,
+            offset,
+        )
+        .await
+        .map_err(|er| {
+            let new_err = diesel_error_to_data_error(er.current_context());
+            er.change_context(new_err)
+        })
+        .map(|attempts| {
+            attempts
+                .into_iter()
+                .map(PaymentAttempt::from_storage_model)
+                .collect()
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -533,6 +571,19 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     client_version: payment_attempt.client_version.clone(),
                     customer_acceptance: payment_attempt.customer_acceptance.clone(),
                     organization_id: payment_attempt.organization_id.clone(),
+                    connector_risk_score: payment_attempt.connector_risk_score,
+                    connector_risk_decision: payment_attempt.connector_risk_decision.clone(),
+                    connector_fee_amount: payment_attempt.connector_fee_amount,
+                    connector_fee_currency: payment_attempt.connector_fee_currency,
+                    device_fingerprint: payment_attempt.device_fingerprint.clone(),
+                    three_ds_version: payment_attempt.three_ds_version.clone(),
+                    three_ds_message_version: payment_attempt.three_ds_message_version.clone(),
+                    request_challenge: payment_attempt.request_challenge,
+                    tokenization_latency: payment_attempt.tokenization_latency,
+                    card_funding_type: payment_attempt.card_funding_type,
+                    liability_shift: payment_attempt.liability_shift,
+                    connector_payment_method_token: payment_attempt.connector_payment_method_token,
+                    connector_response_received_at: payment_attempt.connector_response_received_at,
                     profile_id: payment_attempt.profile_id.clone(),
                     shipping_cost: payment_attempt.shipping_cost,
                     order_tax_amount: payment_attempt.order_tax_amount,
@@ -1304,6 +1355,29 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
             )
             .await
     }
+
+    #[cfg(all(feature = "v1", feature = "olap"))]
+    #[instrument(skip_all)]
+    async fn filter_payment_attempts_by_statuses_time_range(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        statuses: &[common_enums::AttemptStatus],
+        time_range: &common_utils::types::TimeRange,
+        limit: i64,
+        offset: i64,
+        storage_scheme: MerchantStorageScheme,
+    ) -> CustomResult<Vec<PaymentAttempt>, errors::StorageError> {
+        self.router_store
+            .filter_payment_attempts_by_statuses_time_range(
+                merchant_id,
+                statuses,
+                time_range,
+                limit,
+                offset,
+                storage_scheme,
+            )
+            .await
+    }
 }
 
 impl DataModelExt for MandateAmountData {
@@ -1443,6 +1517,19 @@ impl DataModelExt for PaymentAttempt {
             client_version: self.client_version,
             customer_acceptance: self.customer_acceptance,
             organization_id: self.organization_id,
+            connector_risk_score: self.connector_risk_score,
+            connector_risk_decision: self.connector_risk_decision,
+            connector_fee_amount: self.connector_fee_amount,
+            connector_fee_currency: self.connector_fee_currency,
+            device_fingerprint: self.device_fingerprint,
+            three_ds_version: self.three_ds_version,
+            three_ds_message_version: self.three_ds_message_version,
+            request_challenge: self.request_challenge,
+            tokenization_latency: self.tokenization_latency,
+            card_funding_type: self.card_funding_type,
+            liability_shift: self.liability_shift,
+            connector_payment_method_token: self.connector_payment_method_token,
+            connector_response_received_at: self.connector_response_received_at,
             profile_id: self.profile_id,
             shipping_cost: self.shipping_cost,
             order_tax_amount: self.order_tax_amount,
@@ -1514,6 +1601,19 @@ impl DataModelExt for PaymentAttempt {
             client_version: storage_model.client_version,
             customer_acceptance: storage_model.customer_acceptance,
             organization_id: storage_model.organization_id,
+            connector_risk_score: storage_model.connector_risk_score,
+            connector_risk_decision: storage_model.connector_risk_decision,
+            connector_fee_amount: storage_model.connector_fee_amount,
+            connector_fee_currency: storage_model.connector_fee_currency,
+            device_fingerprint: storage_model.device_fingerprint,
+            three_ds_version: storage_model.three_ds_version,
+            three_ds_message_version: storage_model.three_ds_message_version,
+            request_challenge: storage_model.request_challenge,
+            tokenization_latency: storage_model.tokenization_latency,
+            card_funding_type: storage_model.card_funding_type,
+            liability_shift: storage_model.liability_shift,
+            connector_payment_method_token: storage_model.connector_payment_method_token,
+            connector_response_received_at: storage_model.connector_response_received_at,
             profile_id: storage_model.profile_id,
             shipping_cost: storage_model.shipping_cost,
             order_tax_amount: storage_model.order_tax_amount,
@@ -1596,6 +1696,19 @@ impl DataModelExt for PaymentAttemptNew {
             client_version: self.client_version,
             customer_acceptance: self.customer_acceptance,
             organization_id: self.organization_id,
+            connector_risk_score: self.connector_risk_score,
+            connector_risk_decision: self.connector_risk_decision,
+            connector_fee_amount: self.connector_fee_amount,
+            connector_fee_currency: self.connector_fee_currency,
+            device_fingerprint: self.device_fingerprint,
+            three_ds_version: self.three_ds_version,
+            three_ds_message_version: self.three_ds_message_version,
+            request_challenge: self.request_challenge,
+            tokenization_latency: self.tokenization_latency,
+            card_funding_type: self.card_funding_type,
+            liability_shift: self.liability_shift,
+            connector_payment_method_token: self.connector_payment_method_token,
+            connector_response_received_at: self.connector_response_received_at,
             profile_id: self.profile_id,
             shipping_cost: self.shipping_cost,
             order_tax_amount: self.order_tax_amount,
@@ -1666,6 +1779,19 @@ impl DataModelExt for PaymentAttemptNew {
             client_version: storage_model.client_version,
             customer_acceptance: storage_model.customer_acceptance,
             organization_id: storage_model.organization_id,
+            connector_risk_score: storage_model.connector_risk_score,
+            connector_risk_decision: storage_model.connector_risk_decision,
+            connector_fee_amount: storage_model.connector_fee_amount,
+            connector_fee_currency: storage_model.connector_fee_currency,
+            device_fingerprint: storage_model.device_fingerprint,
+            three_ds_version: storage_model.three_ds_version,
+            three_ds_message_version: storage_model.three_ds_message_version,
+            request_challenge: storage_model.request_challenge,
+            tokenization_latency: storage_model.tokenization_latency,
+            card_funding_type: storage_model.card_funding_type,
+            liability_shift: storage_model.liability_shift,
+            connector_payment_method_token: storage_model.connector_payment_method_token,
+            connector_response_received_at: storage_model.connector_response_received_at,
             profile_id: storage_model.profile_id,
             shipping_cost: storage_model.shipping_cost,
             order_tax_amount: storage_model.order_tax_amount,
@@ -1725,6 +1851,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 surcharge_amount,
                 tax_amount,
                 merchant_connector_id,
+                tokenization_latency,
             } => DieselPaymentAttemptUpdate::UpdateTrackers {
                 payment_token,
                 connector,
@@ -1734,6 +1861,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 tax_amount,
                 updated_by,
                 merchant_connector_id,
+                tokenization_latency,
             },
             Self::AuthenticationTypeUpdate {
                 authentication_type,
@@ -1793,6 +1921,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 customer_acceptance,
                 shipping_cost,
                 order_tax_amount,
+                device_fingerprint,
+                three_ds_version,
+                three_ds_message_version,
+                request_challenge,
+                tokenization_latency,
+                card_funding_type,
+                liability_shift,
             } => DieselPaymentAttemptUpdate::ConfirmUpdate {
                 amount,
                 currency,
@@ -1826,6 +1961,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 customer_acceptance,
                 shipping_cost,
                 order_tax_amount,
+                device_fingerprint,
+                three_ds_version,
+                three_ds_message_version,
+                request_challenge,
+                tokenization_latency,
+                card_funding_type,
+                liability_shift,
             },
             Self::VoidUpdate {
                 status,
@@ -1857,6 +1999,12 @@ impl DataModelExt for PaymentAttemptUpdate {
                 unified_message,
                 payment_method_data,
                 charge_id,
+                connector_risk_score,
+                connector_risk_decision,
+                connector_fee_amount,
+                connector_fee_currency,
+                connector_response_received_at,
+                connector_payment_method_token,
             } => DieselPaymentAttemptUpdate::ResponseUpdate {
                 status,
                 connector,
@@ -1878,6 +2026,12 @@ impl DataModelExt for PaymentAttemptUpdate {
                 unified_message,
                 payment_method_data,
                 charge_id,
+                connector_risk_score,
+                connector_risk_decision,
+                connector_fee_amount,
+                connector_fee_currency,
+                connector_response_received_at,
+                connector_payment_method_token,
             },
             Self::UnresolvedResponseUpdate {
                 status,
@@ -1916,6 +2070,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 connector_transaction_id,
                 payment_method_data,
                 authentication_type,
+                connector_response_received_at,
             } => DieselPaymentAttemptUpdate::ErrorUpdate {
                 connector,
                 status,
@@ -1929,6 +2084,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 connector_transaction_id,
                 payment_method_data,
                 authentication_type,
+                connector_response_received_at,
             },
             Self::CaptureUpdate {
                 multiple_capture_count,
@@ -2081,6 +2237,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 surcharge_amount,
                 tax_amount,
                 merchant_connector_id: connector_id,
+                tokenization_latency,
             } => Self::UpdateTrackers {
                 payment_token,
                 connector,
@@ -2090,6 +2247,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 tax_amount,
                 updated_by,
                 merchant_connector_id: connector_id,
+                tokenization_latency,
             },
             DieselPaymentAttemptUpdate::AuthenticationTypeUpdate {
                 authentication_type,
@@ -2131,6 +2289,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 customer_acceptance,
                 shipping_cost,
                 order_tax_amount,
+                device_fingerprint,
+                three_ds_version,
+                three_ds_message_version,
+                request_challenge,
+                tokenization_latency,
+                card_funding_type,
+                liability_shift,
             } => Self::ConfirmUpdate {
                 amount,
                 currency,
@@ -2164,6 +2329,13 @@ impl DataModelExt for PaymentAttemptUpdate {
                 customer_acceptance,
                 shipping_cost,
                 order_tax_amount,
+                device_fingerprint,
+                three_ds_version,
+                three_ds_message_version,
+                request_challenge,
+                tokenization_latency,
+                card_funding_type,
+                liability_shift,
             },
             DieselPaymentAttemptUpdate::VoidUpdate {
                 status,
@@ -2213,6 +2385,12 @@ impl DataModelExt for PaymentAttemptUpdate {
                 unified_message,
                 payment_method_data,
                 charge_id,
+                connector_risk_score,
+                connector_risk_decision,
+                connector_fee_amount,
+                connector_fee_currency,
+                connector_response_received_at,
+                connector_payment_method_token,
             } => Self::ResponseUpdate {
                 status,
                 connector,
@@ -2234,6 +2412,12 @@ impl DataModelExt for PaymentAttemptUpdate {
                 unified_message,
                 payment_method_data,
                 charge_id,
+                connector_risk_score,
+                connector_risk_decision,
+                connector_fee_amount,
+                connector_fee_currency,
+                connector_response_received_at,
+                connector_payment_method_token,
             },
             DieselPaymentAttemptUpdate::UnresolvedResponseUpdate {
                 status,
@@ -2272,6 +2456,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 connector_transaction_id,
                 payment_method_data,
                 authentication_type,
+                connector_response_received_at,
             } => Self::ErrorUpdate {
                 connector,
                 status,
@@ -2285,6 +2470,7 @@ impl DataModelExt for PaymentAttemptUpdate {
                 connector_transaction_id,
                 payment_method_data,
                 authentication_type,
+                connector_response_received_at,
             },
             DieselPaymentAttemptUpdate::CaptureUpdate {
                 amount_to_capture,
@@ -372,6 +372,28 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
         .change_context(StorageError::DecryptionError)
     }
 
+    #[cfg(feature = "v1")]
+    async fn get_customer_captured_amount(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        customer_id: &common_utils::id_type::CustomerId,
+        currency: common_enums::Currency,
+        statuses: Vec<common_enums::IntentStatus>,
+        time_range: &common_utils::types::TimeRange,
+        storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<common_utils::types::MinorUnit, StorageError> {
+        self.router_store
+            .get_customer_captured_amount(
+                merchant_id,
+                customer_id,
+                currency,
+                statuses,
+                time_range,
+                storage_scheme,
+            )
+            .await
+    }
+
     #[cfg(all(feature = "v1", feature = "olap"))]
     async fn filter_payment_intent_by_constraints(
         &self,
@@ -586,6 +608,35 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
         .change_context(StorageError::DecryptionError)
     }
 
+    #[cfg(feature = "v1")]
+    #[instrument(skip_all)]
+    async fn get_customer_captured_amount(
+        &self,
+        merchant_id: &common_utils::id_type::MerchantId,
+        customer_id: &common_utils::id_type::CustomerId,
+        currency: common_enums::Currency,
+        statuses: Vec<common_enums::IntentStatus>,
+        time_range: &common_utils::types::TimeRange,
+        _storage_scheme: MerchantStorageScheme,
+    ) -> error_stack::Result<common_utils::types::MinorUnit, StorageError> {
+        let conn = pg_connection_read(self).await?;
+
+        DieselPaymentIntent::get_customer_captured_amount(
+            &conn,
+            merchant_id,
+            customer_id,
+            currency,
+            statuses,
+            time_range,
+        )
+        .await
+        .map(|captured_amount| common_utils::types::MinorUnit::new(captured_amount.unwrap_or(0)))
+        .map_err(|er| {
+            let new_err = diesel_error_to_data_error(er.current_context());
+            er.change_context(new_err)
+        })
+    }
+
     #[cfg(all(feature = "v1", feature = "olap"))]
     #[instrument(skip_all)]
     async fn filter_payment_intent_by_constraints(
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+-- Drop is_payment_method_token_response_enabled column from business_profile table
+ALTER TABLE business_profile DROP COLUMN IF EXISTS is_payment_method_token_response_enabled;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- Add is_payment_method_token_response_enabled column in business_profile table
+ALTER TABLE business_profile ADD COLUMN IF NOT EXISTS is_payment_method_token_response_enabled BOOLEAN;
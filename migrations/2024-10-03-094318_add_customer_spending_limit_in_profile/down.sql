@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+-- Drop customer spending limit columns from business_profile table
+ALTER TABLE business_profile
+DROP COLUMN IF EXISTS customer_spending_limit,
+DROP COLUMN IF EXISTS customer_spending_limit_window_in_secs;
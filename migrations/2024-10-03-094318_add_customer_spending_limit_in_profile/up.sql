@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- Add customer spending limit columns in business_profile table
+ALTER TABLE business_profile
+ADD COLUMN IF NOT EXISTS customer_spending_limit BIGINT,
+ADD COLUMN IF NOT EXISTS customer_spending_limit_window_in_secs BIGINT;
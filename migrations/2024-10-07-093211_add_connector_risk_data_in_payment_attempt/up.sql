@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt
+ADD COLUMN IF NOT EXISTS connector_risk_score SMALLINT,
+ADD COLUMN IF NOT EXISTS connector_risk_decision VARCHAR(64);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+-- Drop confirm_response_masking_policy column from business_profile table
+ALTER TABLE business_profile DROP COLUMN IF EXISTS confirm_response_masking_policy;
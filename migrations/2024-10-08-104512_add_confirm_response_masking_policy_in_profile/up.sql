@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- Add confirm_response_masking_policy column in business_profile table
+ALTER TABLE business_profile ADD COLUMN IF NOT EXISTS confirm_response_masking_policy JSONB;
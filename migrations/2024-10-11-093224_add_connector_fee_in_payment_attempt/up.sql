@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt ADD COLUMN IF NOT EXISTS connector_fee_amount BIGINT;
+ALTER TABLE payment_attempt ADD COLUMN IF NOT EXISTS connector_fee_currency "Currency";
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS payment_attempt_status_timeline (
+    id SERIAL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    attempt_id VARCHAR(64) NOT NULL,
+    previous_status "AttemptStatus" NOT NULL,
+    status "AttemptStatus" NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS payment_attempt_status_timeline_merchant_id_attempt_id_index ON payment_attempt_status_timeline (merchant_id, attempt_id);
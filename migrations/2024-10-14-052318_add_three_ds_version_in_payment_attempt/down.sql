@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_attempt DROP COLUMN IF EXISTS three_ds_version;
+
+ALTER TABLE payment_attempt DROP COLUMN IF EXISTS three_ds_message_version;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE payment_attempt ADD COLUMN IF NOT EXISTS three_ds_version VARCHAR(16);
+
+ALTER TABLE payment_attempt ADD COLUMN IF NOT EXISTS three_ds_message_version VARCHAR(16);